@@ -578,6 +578,7 @@ export declare enum BuiltinPluginName {
   InferAsyncModulesPlugin = 'InferAsyncModulesPlugin',
   JavascriptModulesPlugin = 'JavascriptModulesPlugin',
   AsyncWebAssemblyModulesPlugin = 'AsyncWebAssemblyModulesPlugin',
+  SyncWebAssemblyModulesPlugin = 'SyncWebAssemblyModulesPlugin',
   AssetModulesPlugin = 'AssetModulesPlugin',
   SourceMapDevToolPlugin = 'SourceMapDevToolPlugin',
   EvalSourceMapDevToolPlugin = 'EvalSourceMapDevToolPlugin',
@@ -589,6 +590,7 @@ export declare enum BuiltinPluginName {
   MangleExportsPlugin = 'MangleExportsPlugin',
   ModuleConcatenationPlugin = 'ModuleConcatenationPlugin',
   CssModulesPlugin = 'CssModulesPlugin',
+  HtmlModulesPlugin = 'HtmlModulesPlugin',
   APIPlugin = 'APIPlugin',
   RuntimeChunkPlugin = 'RuntimeChunkPlugin',
   SizeLimitsPlugin = 'SizeLimitsPlugin',
@@ -1795,6 +1797,24 @@ export interface NativeWatcherOptions {
   followSymlinks?: boolean
   pollInterval?: number
   aggregateTimeout?: number
+  /**
+   * Wait until no event arrived for `aggregateTimeout` before reporting
+   * changes. A number caps the total wait in milliseconds.
+   */
+  aggregateSettle?: boolean | number
+  /**
+   * Report a path that is removed and created again within one aggregation
+   * as a single change, e.g. for editors that save through an atomic rename.
+   */
+  coalesceRenames?: boolean
+  /**
+   * Poll every watch root (`true`) or the watch roots inside the given paths
+   * instead of using native file system events.
+   * Watch roots that can't be watched natively are always polled.
+   */
+  polling?: boolean | string[]
+  /** The maximum number of paths to stat per poll interval. */
+  pollMaxStats?: number
   /**
    * The ignored paths for the watcher.
    * It can be a single path, an array of paths, or a regular expression.
@@ -1946,11 +1966,17 @@ export interface RawCircularCheckRspackPluginOptions {
   onDetected?: (module: Module, paths: string[]) => void
 }
 
+export interface RawCircularDependencyComponentsOptions {
+  baseline?: string
+  filename?: string
+}
+
 /** Deprecated. Use `RawCircularCheckRspackPluginOptions` instead. */
 export interface RawCircularDependencyRspackPluginOptions {
   failOnError?: boolean
   exclude?: RegExp
   ignoredConnections?: Array<[string | RegExp, string | RegExp]>
+  components?: RawCircularDependencyComponentsOptions
   onDetected?: (entrypoint: Module, modules: string[]) => void
   onIgnored?: (entrypoint: Module, modules: string[]) => void
   onStart?: () => void
@@ -2120,6 +2146,13 @@ export interface RawCopyPattern {
    * @default undefined
    */
   transform?: { transformer: (input: Buffer, absoluteFilename: string) => string | Buffer | Promise<string> | Promise<Buffer>  } | ((input: Buffer, absoluteFilename: string) => string | Buffer | Promise<string> | Promise<Buffer>)
+  /**
+   * Emits a JSON asset with this name, mapping the source path of every copied
+   * file, relative to `context`, to its emitted filename. Useful with content
+   * hash templates in `to`.
+   * @default undefined
+   */
+  manifest?: string
 }
 
 export interface RawCopyRspackPluginOptions {
@@ -2147,6 +2180,7 @@ export interface RawCssChunkingPluginOptions {
   minSize?: number
   maxSize?: number
   exclude?: RegExp
+  report?: string
 }
 
 export interface RawCssExtractPluginOption {
@@ -2417,6 +2451,8 @@ export interface RawHtmlRspackPluginOptions {
   meta?: Record<string, Record<string, string>>
   hash?: boolean
   base?: RawHtmlRspackPluginBaseOptions
+  strict?: boolean
+  criticalCss?: boolean
   uid?: number
 }
 
@@ -2429,6 +2465,8 @@ export interface RawHttpUriPluginOptions {
   lockfileLocation?: string
   cacheLocation?: string
   upgrade: boolean
+  frozen?: boolean
+  pruneLockfile?: boolean
   httpClient: (url: string, headers: Record<string, string>) => Promise<JsHttpResponseRaw>
 }
 
@@ -2888,6 +2926,7 @@ export interface RawProgressPluginOptions {
   template?: string
   tick?: string | Array<string>
   progressChars?: string
+  json?: boolean | string
   handler?: (percent: number, msg: string, info: RawProgressPluginHandlerInfo) => void
 }
 
@@ -2929,6 +2968,7 @@ export interface RawResolveOptions {
   fallback?: Array<RawAliasOptionItem> | false
   symlinks?: boolean
   tsconfig?: RawResolveTsconfigOptions
+  importMap?: string
   modules?: Array<string>
   byDependency?: Record<string, RawResolveOptions>
   fullySpecified?: boolean
@@ -2954,6 +2994,7 @@ export interface RawResolveOptionsWithDependencyType {
   fallback?: Array<RawAliasOptionItem> | false
   symlinks?: boolean
   tsconfig?: RawResolveTsconfigOptions
+  importMap?: string
   modules?: Array<string>
   byDependency?: Record<string, RawResolveOptions>
   fullySpecified?: boolean
@@ -3073,11 +3114,23 @@ export interface RawSharedUsedExportsOptimizerPluginOptions {
   statsFileName?: string
 }
 
+export interface RawSizeBudget {
+  type: "asset" | "chunk" | "entrypoint"
+  /** glob of asset filenames, chunk names or entrypoint names */
+  test: string
+  measure?: "raw" | "gzip" | "brotli"
+  warning?: number
+  error?: number
+}
+
 export interface RawSizeLimitsPluginOptions {
   assetFilter?: (assetFilename: string) => boolean
   hints?: "error" | "warning"
   maxAssetSize?: number
   maxEntrypointSize?: number
+  budgets?: Array<RawSizeBudget>
+  reportFilename?: string
+  baseline?: string
 }
 
 export interface RawSnapshotOptions {
@@ -3123,17 +3176,21 @@ export interface RawStatsBuildInfo {
 
 export interface RawStatsOptions {
   colors: boolean
+  errorDetails: boolean
 }
 
 export interface RawStorageOptions {
-  type: "filesystem"
+  type: "filesystem" | "remote"
   directory: string
+  /** Base url of the content-addressed HTTP store, required by the "remote" type */
+  url?: string
 }
 
 export interface RawSubresourceIntegrityPluginOptions {
   integrityCallback?: (data: RawIntegrityData) => void
   hashFuncNames: Array<string>
   htmlPlugin: "JavaScript" | "Native" | "Disabled"
+  manifest?: string
 }
 
 export interface RawSwcEmitDtsOptions {
@@ -3353,6 +3410,7 @@ export interface SourceMapDevToolPluginOptions {
   include?: string | RegExp | (string | RegExp)[]
   exclude?: string | RegExp | (string | RegExp)[]
   debugIds?: boolean
+  indexMap?: boolean
 }
 
 export interface SourcePosition {
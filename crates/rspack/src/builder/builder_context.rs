@@ -40,7 +40,9 @@ pub(super) enum BuiltinPluginOptions {
   JsonModulesPlugin,
   AssetModulesPlugin,
   AsyncWebAssemblyModulesPlugin,
+  SyncWebAssemblyModulesPlugin,
   CssModulesPlugin,
+  HtmlModulesPlugin,
 
   // Entry and runtime plugins
   EntryPlugin(Box<(String /* entry request */, EntryOptions)>),
@@ -221,9 +223,15 @@ impl BuilderContext {
       BuiltinPluginOptions::AsyncWebAssemblyModulesPlugin => {
         plugins.push(rspack_plugin_wasm::AsyncWasmPlugin::default().boxed());
       }
+      BuiltinPluginOptions::SyncWebAssemblyModulesPlugin => {
+        plugins.push(rspack_plugin_wasm::SyncWasmPlugin::default().boxed());
+      }
       BuiltinPluginOptions::CssModulesPlugin => {
         plugins.push(rspack_plugin_css::CssPlugin::default().boxed());
       }
+      BuiltinPluginOptions::HtmlModulesPlugin => {
+        plugins.push(rspack_plugin_html::HtmlModulesPlugin::default().boxed());
+      }
 
       // Entry and runtime plugins
       BuiltinPluginOptions::EntryPlugin(entry_options) => {
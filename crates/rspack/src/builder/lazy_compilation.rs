@@ -0,0 +1,85 @@
+use rspack_core::{
+  BoxPlugin, CompilationId, CompilerId, ExternalItem, ExternalItemValue, Module, PluginExt,
+};
+use rspack_plugin_lazy_compilation::{
+  LazyCompilationPlugin, LazyCompilationTest, LazyCompilationTestCheck, LocalBackend,
+};
+use rspack_regex::RspackRegex;
+
+/// Options for [`super::CompilerBuilder::lazy_compilation`].
+#[derive(Debug)]
+pub struct LazyCompilationOptions {
+  /// Compile entries lazily.
+  pub entries: bool,
+  /// Compile dynamic imports lazily.
+  pub imports: bool,
+  /// Only compile matching modules lazily.
+  pub test: Option<RspackRegex>,
+  /// Path of the client module that activates the lazy modules at runtime,
+  /// e.g. `@rspack/core/hot/lazy-compilation-web.js`.
+  pub client: String,
+}
+
+impl LazyCompilationOptions {
+  pub fn new(client: impl Into<String>) -> Self {
+    Self {
+      entries: true,
+      imports: true,
+      test: None,
+      client: client.into(),
+    }
+  }
+}
+
+/// `test` is always a regex when the plugin is created from Rust, so this type has no value.
+#[derive(Debug)]
+#[expect(clippy::empty_enums)]
+pub(super) enum NoTestFn {}
+
+impl LazyCompilationTestCheck for NoTestFn {
+  async fn test(
+    &self,
+    _compiler_id: CompilerId,
+    _compilation_id: CompilationId,
+    _module: &dyn Module,
+  ) -> bool {
+    match *self {}
+  }
+}
+
+/// Statically-declared external requests, reserved by inactive proxies.
+fn collect_reserved_externals(externals: &[ExternalItem]) -> Vec<String> {
+  let mut requests = vec![];
+  for item in externals {
+    match item {
+      ExternalItem::String(request) => requests.push(request.clone()),
+      ExternalItem::Object(object) => requests.extend(
+        object
+          .iter()
+          .filter(|(_, value)| !matches!(value, ExternalItemValue::Bool(false)))
+          .map(|(request, _)| request.clone()),
+      ),
+      ExternalItem::RegExp(_) | ExternalItem::Fn(_) => {}
+    }
+  }
+  requests.sort();
+  requests.dedup();
+  requests
+}
+
+pub(super) fn create_plugin(
+  backend: LocalBackend,
+  options: LazyCompilationOptions,
+  externals: &[ExternalItem],
+) -> BoxPlugin {
+  let client = backend.client_request(&options.client);
+  LazyCompilationPlugin::<LocalBackend, NoTestFn>::new(
+    backend,
+    options.test.map(LazyCompilationTest::Regex),
+    options.entries,
+    options.imports,
+    client,
+    collect_reserved_externals(externals),
+  )
+  .boxed()
+}
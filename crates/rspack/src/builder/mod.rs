@@ -4,10 +4,12 @@ mod browserslist_target;
 mod builder_context;
 mod devtool;
 mod externals;
+mod lazy_compilation;
 mod target;
 
 pub use builder_context::BuilderContext;
 pub use devtool::Devtool;
+pub use lazy_compilation::LazyCompilationOptions;
 use rspack_tasks::CURRENT_COMPILER_CONTEXT;
 use rspack_util::{fx_hash::FxIndexMap, json_stringify_str};
 pub use target::Targets;
@@ -61,6 +63,7 @@ use rspack_error::{Error, Result};
 use rspack_fs::{IntermediateFileSystem, ReadableFileSystem, WritableFileSystem};
 use rspack_hash::{HashDigest, HashFunction, HashSalt};
 use rspack_paths::{AssertUtf8, Utf8PathBuf};
+use rspack_plugin_lazy_compilation::LocalBackend;
 use rspack_regex::RspackRegex;
 use rustc_hash::{FxHashMap as HashMap, FxHashSet as HashSet};
 use serde_json::json;
@@ -111,6 +114,7 @@ pub struct CompilerBuilder {
   input_filesystem: Option<Arc<dyn ReadableFileSystem>>,
   intermediate_filesystem: Option<Arc<dyn IntermediateFileSystem>>,
   output_filesystem: Option<Arc<dyn WritableFileSystem>>,
+  lazy_compilation: Option<(LocalBackend, LazyCompilationOptions)>,
 }
 
 impl CompilerBuilder {
@@ -125,6 +129,7 @@ impl CompilerBuilder {
       input_filesystem: None,
       intermediate_filesystem: None,
       output_filesystem: None,
+      lazy_compilation: None,
     }
   }
 }
@@ -446,13 +451,56 @@ impl CompilerBuilder {
     self
   }
 
+  /// Compile entries and dynamic imports on demand.
+  ///
+  /// Modules are activated through `backend`, either by the client module
+  /// over HTTP or directly with [`LocalBackend::activate`]. Call
+  /// [`Compiler::rebuild`] once [`LocalBackend::changed`] resolves.
+  ///
+  /// # Examples
+  ///
+  /// ```rust,no_run
+  /// use rspack::builder::{Builder as _, LazyCompilationOptions};
+  /// use rspack_core::Compiler;
+  /// use rspack_plugin_lazy_compilation::{LocalBackend, LocalBackendOptions};
+  ///
+  /// # async fn run() -> rspack_error::Result<()> {
+  /// let backend = LocalBackend::start(LocalBackendOptions::default()).await?;
+  /// let compiler = Compiler::builder().lazy_compilation(
+  ///   backend.clone(),
+  ///   LazyCompilationOptions::new("./lazy-compilation-client.js"),
+  /// );
+  /// # Ok(())
+  /// # }
+  /// ```
+  pub fn lazy_compilation(
+    &mut self,
+    backend: LocalBackend,
+    options: LazyCompilationOptions,
+  ) -> &mut Self {
+    self.lazy_compilation = Some((backend, options));
+    self
+  }
+
   /// Build [`Compiler`] from options and plugins.
   pub fn build(&mut self) -> Result<Compiler> {
+    let lazy_compilation_plugin = self.lazy_compilation.take().map(|(backend, options)| {
+      lazy_compilation::create_plugin(
+        backend,
+        options,
+        self
+          .options_builder
+          .externals
+          .as_deref()
+          .unwrap_or_default(),
+      )
+    });
     let mut builder_context = BuilderContext::default();
     let compiler_options = self.options_builder.build(&mut builder_context)?;
     let mut plugins = builder_context.take_plugins(&compiler_options);
     let platform = builder_context.take_platform();
     plugins.append(&mut self.plugins);
+    plugins.extend(lazy_compilation_plugin);
 
     let input_filesystem = self.input_filesystem.take();
     let intermediate_filesystem = self.intermediate_filesystem.take();
@@ -961,6 +1009,18 @@ impl CompilerOptionsBuilder {
         .plugins
         .push(BuiltinPluginOptions::AsyncWebAssemblyModulesPlugin);
     }
+    let sync_web_assembly = expect!(experiments_builder.sync_web_assembly);
+    if sync_web_assembly {
+      builder_context
+        .plugins
+        .push(BuiltinPluginOptions::SyncWebAssemblyModulesPlugin);
+    }
+    let html = expect!(experiments_builder.html);
+    if html {
+      builder_context
+        .plugins
+        .push(BuiltinPluginOptions::HtmlModulesPlugin);
+    }
     let css = expect!(experiments_builder.css);
     if css {
       builder_context
@@ -973,7 +1033,9 @@ impl CompilerOptionsBuilder {
     let module = f!(self.module.take(), ModuleOptions::builder).build(
       builder_context,
       async_web_assembly,
+      sync_web_assembly,
       css,
+      html,
       &target_properties,
       &mode,
     )?;
@@ -1023,6 +1085,7 @@ impl CompilerOptionsBuilder {
         include: None,
         exclude: None,
         debug_ids: false,
+        index_map: false,
       };
 
       if eval_wrapped {
@@ -1271,6 +1334,7 @@ impl CompilerOptionsBuilder {
         let default_stats_colors = supports_color::on(Stream::Stdout).is_some();
         StatsOptions {
           colors: default_stats_colors,
+          error_details: false,
         }
       }
     };
@@ -1697,7 +1761,9 @@ impl ModuleOptionsBuilder {
     &mut self,
     _builder_context: &mut BuilderContext,
     async_web_assembly: bool,
+    sync_web_assembly: bool,
     css: bool,
+    html: bool,
     target_properties: &TargetProperties,
     mode: &Mode,
   ) -> Result<ModuleOptions> {
@@ -1830,7 +1896,7 @@ impl ModuleOptionsBuilder {
       );
     }
 
-    let default_rules = default_rules(async_web_assembly, css);
+    let default_rules = default_rules(async_web_assembly, sync_web_assembly, css, html);
 
     let mut module_options = ModuleOptions {
       rules: vec![
@@ -1859,7 +1925,12 @@ fn extension_rule(extension: &str) -> RuleSetCondition {
   )
 }
 
-fn default_rules(async_web_assembly: bool, css: bool) -> Vec<ModuleRule> {
+fn default_rules(
+  async_web_assembly: bool,
+  sync_web_assembly: bool,
+  css: bool,
+  html: bool,
+) -> Vec<ModuleRule> {
   let mut rules = vec![
     // application/node
     ModuleRule {
@@ -1983,13 +2054,20 @@ fn default_rules(async_web_assembly: bool, css: bool) -> Vec<ModuleRule> {
     },
   ];
 
-  // Add WebAssembly rules if enabled
-  if async_web_assembly {
+  // Add WebAssembly rules if enabled, async modules take precedence
+  let wasm_module_type = if async_web_assembly {
+    Some(ModuleType::WasmAsync)
+  } else if sync_web_assembly {
+    Some(ModuleType::WasmSync)
+  } else {
+    None
+  };
+  if let Some(wasm_module_type) = wasm_module_type {
     rules.extend(vec![
       ModuleRule {
         test: Some(extension_rule(".wasm")),
         effect: ModuleRuleEffect {
-          r#type: Some(ModuleType::WasmAsync),
+          r#type: Some(wasm_module_type),
           ..Default::default()
         },
         rules: Some(vec![ModuleRule {
@@ -2011,7 +2089,7 @@ fn default_rules(async_web_assembly: bool, css: bool) -> Vec<ModuleRule> {
       ModuleRule {
         mimetype: Some(RuleSetCondition::String("application/wasm".into()).into()),
         effect: ModuleRuleEffect {
-          r#type: Some(ModuleType::WasmAsync),
+          r#type: Some(wasm_module_type),
           ..Default::default()
         },
         rules: Some(vec![ModuleRule {
@@ -2072,6 +2150,28 @@ fn default_rules(async_web_assembly: bool, css: bool) -> Vec<ModuleRule> {
     ]);
   }
 
+  // Add HTML rules if enabled
+  if html {
+    rules.extend(vec![
+      ModuleRule {
+        test: Some(extension_rule(".html")),
+        effect: ModuleRuleEffect {
+          r#type: Some(ModuleType::Html),
+          ..Default::default()
+        },
+        ..Default::default()
+      },
+      ModuleRule {
+        mimetype: Some(RuleSetCondition::String("text/html".into()).into()),
+        effect: ModuleRuleEffect {
+          r#type: Some(ModuleType::Html),
+          ..Default::default()
+        },
+        ..Default::default()
+      },
+    ]);
+  }
+
   // Add URL dependency rules
   rules.extend(vec![
     ModuleRule {
@@ -3685,6 +3785,10 @@ pub struct ExperimentsBuilder {
   new_cache: Option<NewCacheOptions>,
   /// Whether to enable async web assembly.
   async_web_assembly: Option<bool>,
+  /// Whether to enable sync web assembly.
+  sync_web_assembly: Option<bool>,
+  /// Whether to enable html modules.
+  html: Option<bool>,
   /// Whether to enable defer import.
   defer_import: Option<bool>,
   /// Whether to enable source import.
@@ -3701,6 +3805,8 @@ impl From<Experiments> for ExperimentsBuilder {
       css: Some(value.css),
       new_cache: Some(value.new_cache),
       async_web_assembly: None,
+      sync_web_assembly: None,
+      html: None,
       defer_import: Some(value.defer_import),
       source_import: Some(value.source_import),
       pure_functions: Some(value.pure_functions),
@@ -3716,6 +3822,8 @@ impl From<&mut ExperimentsBuilder> for ExperimentsBuilder {
       css: value.css.take(),
       new_cache: value.new_cache.take(),
       async_web_assembly: value.async_web_assembly.take(),
+      sync_web_assembly: value.sync_web_assembly.take(),
+      html: value.html.take(),
       defer_import: value.defer_import.take(),
       source_import: value.source_import.take(),
       pure_functions: value.pure_functions.take(),
@@ -3753,6 +3861,18 @@ impl ExperimentsBuilder {
     self
   }
 
+  /// Set whether to enable sync web assembly.
+  pub fn sync_web_assembly(&mut self, sync_web_assembly: bool) -> &mut Self {
+    self.sync_web_assembly = Some(sync_web_assembly);
+    self
+  }
+
+  /// Set whether to enable html modules.
+  pub fn html(&mut self, html: bool) -> &mut Self {
+    self.html = Some(html);
+    self
+  }
+
   /// Set whether to enable defer import.
   pub fn defer_import(&mut self, defer_import: bool) -> &mut Self {
     self.defer_import = Some(defer_import);
@@ -3778,6 +3898,8 @@ impl ExperimentsBuilder {
     let future_defaults = w!(self.future_defaults, false);
     w!(self.css, *future_defaults);
     w!(self.async_web_assembly, true);
+    w!(self.sync_web_assembly, false);
+    w!(self.html, false);
 
     Ok(Experiments {
       css: d!(self.css, false),
@@ -0,0 +1,3 @@
+exports.activate = function () {
+	return function () {};
+};
@@ -0,0 +1 @@
+import("./lazy").then(({ value }) => console.log(value));
@@ -0,0 +1,117 @@
+#[cfg(test)]
+mod tests {
+  use rspack::builder::{Builder as _, LazyCompilationOptions};
+  use rspack_core::Compiler;
+  use rspack_paths::Utf8Path;
+  use rspack_plugin_lazy_compilation::{LocalBackend, LocalBackendOptions};
+
+  fn contains_lazy_module(compiler: &Compiler) -> bool {
+    compiler.compilation.assets().values().any(|asset| {
+      asset.source.as_ref().is_some_and(|source| {
+        source
+          .source()
+          .into_string_lossy()
+          .contains("\"lazy module\"")
+      })
+    })
+  }
+
+  #[tokio::test(flavor = "multi_thread")]
+  async fn activate_and_dispose() {
+    use rspack_tasks::within_compiler_context_for_testing;
+    within_compiler_context_for_testing(async move {
+      let backend = LocalBackend::start(LocalBackendOptions {
+        listen: None,
+        ..Default::default()
+      })
+      .await
+      .unwrap();
+      let mut compiler = Compiler::builder()
+        .context(Utf8Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/lazy-compilation"))
+        .entry("main", "./src/index.js")
+        .lazy_compilation(
+          backend.clone(),
+          LazyCompilationOptions {
+            entries: false,
+            ..LazyCompilationOptions::new("./src/client.js")
+          },
+        )
+        .build()
+        .unwrap();
+
+      compiler.build().await.unwrap();
+      assert!(compiler.compilation.get_errors().next().is_none());
+      assert!(!contains_lazy_module(&compiler));
+
+      let proxy = compiler
+        .compilation
+        .get_module_graph()
+        .modules_keys()
+        .find(|identifier| identifier.starts_with("lazy-compilation-proxy|"))
+        .copied()
+        .expect("should create a proxy for the dynamic import");
+
+      backend.activate(proxy);
+      compiler
+        .rebuild(Default::default(), Default::default())
+        .await
+        .unwrap();
+      assert!(contains_lazy_module(&compiler));
+
+      backend.deactivate(proxy);
+      assert!(backend.active_modules().contains(&proxy));
+      backend.dispose(proxy);
+      compiler
+        .rebuild(Default::default(), Default::default())
+        .await
+        .unwrap();
+      assert!(!contains_lazy_module(&compiler));
+    })
+    .await;
+  }
+
+  async fn post(url: &str, body: &str) {
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    let (addr, path) = url
+      .strip_prefix("http://")
+      .and_then(|url| url.split_once('/'))
+      .expect("should be an http url");
+    let mut stream = tokio::net::TcpStream::connect(addr).await.unwrap();
+    let request = format!(
+      "POST /{path} HTTP/1.1\r\nHost: {addr}\r\nContent-Type: text/plain\r\nContent-Length: {}\r\n\r\n{body}",
+      body.len()
+    );
+    stream.write_all(request.as_bytes()).await.unwrap();
+    let mut response = String::new();
+    stream.read_to_string(&mut response).await.unwrap();
+    assert!(response.starts_with("HTTP/1.1 200"), "{response}");
+  }
+
+  fn active_modules(backend: &LocalBackend) -> Vec<String> {
+    let mut active = backend
+      .active_modules()
+      .into_iter()
+      .map(|module| module.to_string())
+      .collect::<Vec<_>>();
+    active.sort();
+    active
+  }
+
+  #[tokio::test]
+  async fn requests_only_add_modules() {
+    let backend = LocalBackend::start(Default::default()).await.unwrap();
+    let url = backend.url().expect("should listen");
+
+    // the bundled clients only post the modules that are still compiling
+    post(&url, "a").await;
+    post(&url, "b").await;
+    post(&url, "a\nb").await;
+    assert_eq!(active_modules(&backend), ["a", "b"]);
+
+    backend.dispose("a");
+    assert_eq!(active_modules(&backend), ["b"]);
+    post(&url, "a").await;
+    assert_eq!(active_modules(&backend), ["a", "b"]);
+  }
+}
@@ -9,7 +9,10 @@ use napi::bindgen_prelude::*;
 use napi_derive::*;
 use rspack_paths::ArcPath;
 use rspack_regex::RspackRegex;
-use rspack_watcher::{FsEventKind, FsWatcher, FsWatcherIgnored, FsWatcherOptions};
+use rspack_watcher::{
+  FsEventKind, FsWatcher, FsWatcherAggregateMode, FsWatcherIgnored, FsWatcherOptions,
+  FsWatcherPolling,
+};
 
 type JsWatcherIgnored = Either3<String, Vec<String>, RspackRegex>;
 
@@ -33,6 +36,24 @@ pub struct NativeWatcherOptions {
 
   pub aggregate_timeout: Option<u32>,
 
+  #[napi(ts_type = "boolean | number")]
+  /// Wait until no event arrived for `aggregateTimeout` before reporting
+  /// changes. A number caps the total wait in milliseconds.
+  pub aggregate_settle: Option<Either<bool, u32>>,
+
+  /// Report a path that is removed and created again within one aggregation
+  /// as a single change, e.g. for editors that save through an atomic rename.
+  pub coalesce_renames: Option<bool>,
+
+  #[napi(ts_type = "boolean | string[]")]
+  /// Poll every watch root (`true`) or the watch roots inside the given paths
+  /// instead of using native file system events.
+  /// Watch roots that can't be watched natively are always polled.
+  pub polling: Option<Either<bool, Vec<String>>>,
+
+  /// The maximum number of paths to stat per poll interval.
+  pub poll_max_stats: Option<u32>,
+
   #[napi(ts_type = "string | string[] | RegExp")]
   /// The ignored paths for the watcher.
   /// It can be a single path, an array of paths, or a regular expression.
@@ -73,6 +94,25 @@ impl NativeWatcher {
         follow_symlinks: options.follow_symlinks.unwrap_or(false),
         poll_interval: options.poll_interval,
         aggregate_timeout: options.aggregate_timeout,
+        aggregate_mode: match options.aggregate_settle {
+          Some(Either::A(true)) => FsWatcherAggregateMode::Settle { max_wait: None },
+          Some(Either::B(max_wait)) => FsWatcherAggregateMode::Settle {
+            max_wait: Some(max_wait),
+          },
+          Some(Either::A(false)) | None => FsWatcherAggregateMode::Fixed,
+        },
+        coalesce_renames: options.coalesce_renames.unwrap_or(false),
+        polling: match options.polling {
+          Some(Either::A(true)) => FsWatcherPolling::All,
+          Some(Either::B(paths)) => FsWatcherPolling::Paths(
+            paths
+              .into_iter()
+              .map(|path| ArcPath::from(path.as_str()))
+              .collect(),
+          ),
+          Some(Either::A(false)) | None => FsWatcherPolling::Fallback,
+        },
+        poll_max_stats: options.poll_max_stats.map(|max| max as usize),
       },
       to_fs_watcher_ignored(options.ignored),
     );
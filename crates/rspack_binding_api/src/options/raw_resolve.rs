@@ -42,6 +42,7 @@ pub struct RawResolveOptions {
   pub fallback: Option<Either<Vec<RawAliasOptionItem>, bool>>,
   pub symlinks: Option<bool>,
   pub tsconfig: Option<RawResolveTsconfigOptions>,
+  pub import_map: Option<String>,
   pub modules: Option<Vec<String>>,
   pub by_dependency: Option<HashMap<String, RawResolveOptions>>,
   pub fully_specified: Option<bool>,
@@ -120,6 +121,7 @@ impl TryFrom<RawResolveOptions> for Resolve {
       Some(config) => Some(TsconfigOptions::try_from(config)?),
       None => None,
     };
+    let import_map = value.import_map.map(Into::into);
     let by_dependency = value
       .by_dependency
       .map(|i| {
@@ -162,6 +164,7 @@ impl TryFrom<RawResolveOptions> for Resolve {
       alias,
       symlinks,
       tsconfig,
+      import_map,
       fallback,
       by_dependency,
       fully_specified,
@@ -220,6 +223,7 @@ pub struct RawResolveOptionsWithDependencyType {
   pub fallback: Option<Either<Vec<RawAliasOptionItem>, bool>>,
   pub symlinks: Option<bool>,
   pub tsconfig: Option<RawResolveTsconfigOptions>,
+  pub import_map: Option<String>,
   pub modules: Option<Vec<String>>,
   pub by_dependency: Option<HashMap<String, RawResolveOptions>>,
   pub fully_specified: Option<bool>,
@@ -293,6 +297,7 @@ pub fn normalize_raw_resolve_options_with_dependency_type(
         main_fields: raw.main_fields,
         condition_names: raw.condition_names,
         tsconfig,
+        import_map: raw.import_map.map(Into::into),
         pnp: raw.pnp,
         modules: raw.modules,
         fallback: normalize_alias(raw.fallback)?,
@@ -74,7 +74,7 @@ use rspack_plugin_externals::{
   http_externals_rspack_plugin, node_target_plugin,
 };
 use rspack_plugin_hmr::HotModuleReplacementPlugin;
-use rspack_plugin_html::HtmlRspackPlugin;
+use rspack_plugin_html::{HtmlModulesPlugin, HtmlRspackPlugin};
 use rspack_plugin_ignore::IgnorePlugin;
 use rspack_plugin_javascript::{
   FlagDependencyExportsPlugin, FlagDependencyUsagePlugin, InferAsyncModulesPlugin,
@@ -110,7 +110,7 @@ use rspack_plugin_size_limits::SizeLimitsPlugin;
 use rspack_plugin_sri::{SubresourceIntegrityPlugin, SubresourceIntegrityPluginOptions};
 use rspack_plugin_swc_js_minimizer::SwcJsMinimizerRspackPlugin;
 use rspack_plugin_wasm::{
-  AsyncWasmPlugin, FetchCompileAsyncWasmPlugin, enable_wasm_loading_plugin,
+  AsyncWasmPlugin, FetchCompileAsyncWasmPlugin, SyncWasmPlugin, enable_wasm_loading_plugin,
 };
 use rspack_plugin_worker::WorkerPlugin;
 use rustc_hash::FxHashMap as HashMap;
@@ -216,6 +216,7 @@ pub enum BuiltinPluginName {
   InferAsyncModulesPlugin,
   JavascriptModulesPlugin,
   AsyncWebAssemblyModulesPlugin,
+  SyncWebAssemblyModulesPlugin,
   AssetModulesPlugin,
   SourceMapDevToolPlugin,
   EvalSourceMapDevToolPlugin,
@@ -227,6 +228,7 @@ pub enum BuiltinPluginName {
   MangleExportsPlugin,
   ModuleConcatenationPlugin,
   CssModulesPlugin,
+  HtmlModulesPlugin,
   APIPlugin,
   RuntimeChunkPlugin,
   SizeLimitsPlugin,
@@ -664,6 +666,9 @@ impl<'a> BuiltinPlugin<'a> {
       BuiltinPluginName::AsyncWebAssemblyModulesPlugin => {
         plugins.push(AsyncWasmPlugin::default().boxed())
       }
+      BuiltinPluginName::SyncWebAssemblyModulesPlugin => {
+        plugins.push(SyncWasmPlugin::default().boxed())
+      }
       BuiltinPluginName::AssetModulesPlugin => plugins.push(AssetPlugin::default().boxed()),
       BuiltinPluginName::SourceMapDevToolPlugin => {
         let options: rspack_plugin_devtool::SourceMapDevToolPluginOptions =
@@ -734,6 +739,7 @@ impl<'a> BuiltinPlugin<'a> {
         plugins.push(ModuleConcatenationPlugin::default().boxed())
       }
       BuiltinPluginName::CssModulesPlugin => plugins.push(CssPlugin::default().boxed()),
+      BuiltinPluginName::HtmlModulesPlugin => plugins.push(HtmlModulesPlugin::default().boxed()),
       BuiltinPluginName::APIPlugin => plugins.push(APIPlugin::default().boxed()),
       BuiltinPluginName::RuntimeChunkPlugin => plugins.push(
         RuntimeChunkPlugin::new(
@@ -747,7 +753,8 @@ impl<'a> BuiltinPlugin<'a> {
         let plugin = SizeLimitsPlugin::new(
           downcast_into::<RawSizeLimitsPluginOptions>(self.options)
             .map_err(|report| napi::Error::from_reason(report.to_string()))?
-            .into(),
+            .try_into()
+            .map_err(|report: rspack_error::Error| napi::Error::from_reason(report.to_string()))?,
         )
         .boxed();
         plugins.push(plugin)
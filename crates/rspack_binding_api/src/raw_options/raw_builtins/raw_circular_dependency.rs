@@ -6,9 +6,9 @@ use napi::{Either, bindgen_prelude::FnArgs};
 use napi_derive::napi;
 use rspack_core::{CompilerId, Module};
 use rspack_plugin_circular_dependencies::{
-  CircularCheckHandlerFn, CircularCheckRspackPluginOptions, CircularDependencyIgnoredConnection,
-  CircularDependencyIgnoredConnectionEntry, CircularDependencyRspackPluginOptions,
-  CompilationHookFn, CycleHandlerFn,
+  CircularCheckHandlerFn, CircularCheckRspackPluginOptions, CircularDependencyComponentsOptions,
+  CircularDependencyIgnoredConnection, CircularDependencyIgnoredConnectionEntry,
+  CircularDependencyRspackPluginOptions, CompilationHookFn, CycleHandlerFn,
 };
 use rspack_regex::RspackRegex;
 
@@ -25,6 +25,22 @@ fn ignore_pattern_to_entry(
   }
 }
 
+#[derive(Debug)]
+#[napi(object, object_to_js = false)]
+pub struct RawCircularDependencyComponentsOptions {
+  pub baseline: Option<String>,
+  pub filename: Option<String>,
+}
+
+impl From<RawCircularDependencyComponentsOptions> for CircularDependencyComponentsOptions {
+  fn from(value: RawCircularDependencyComponentsOptions) -> Self {
+    Self {
+      baseline: value.baseline,
+      filename: value.filename,
+    }
+  }
+}
+
 type ConnectionPattern = Either<String, RspackRegex>;
 type CycleHookParams = (String, Vec<String>);
 
@@ -37,6 +53,7 @@ pub struct RawCircularDependencyRspackPluginOptions {
   pub exclude: Option<RspackRegex>,
   #[napi(ts_type = "Array<[string | RegExp, string | RegExp]>")]
   pub ignored_connections: Option<Vec<(ConnectionPattern, ConnectionPattern)>>,
+  pub components: Option<RawCircularDependencyComponentsOptions>,
   #[debug(skip)]
   #[napi(ts_type = "(entrypoint: Module, modules: string[]) => void")]
   pub on_detected: Option<ThreadsafeFunction<FnArgs<CycleHookParams>, ()>>,
@@ -121,6 +138,7 @@ impl From<RawCircularDependencyRspackPluginOptions> for CircularDependencyRspack
           })
           .collect()
       }),
+      components: value.components.map(Into::into),
       on_detected,
       on_ignored,
       on_start,
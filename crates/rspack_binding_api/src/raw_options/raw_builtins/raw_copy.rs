@@ -97,6 +97,11 @@ pub struct RawCopyPattern {
     ts_type = "{ transformer: (input: Buffer, absoluteFilename: string) => string | Buffer | Promise<string> | Promise<Buffer>  } | ((input: Buffer, absoluteFilename: string) => string | Buffer | Promise<string> | Promise<Buffer>)"
   )]
   pub transform: Option<RawTransformer>,
+  /// Emits a JSON asset with this name, mapping the source path of every copied
+  /// file, relative to `context`, to its emitted filename. Useful with content
+  /// hash templates in `to`.
+  /// @default undefined
+  pub manifest: Option<String>,
 }
 
 #[derive(Debug, Clone)]
@@ -155,6 +160,7 @@ impl From<RawCopyPattern> for CopyPattern {
       info,
       copy_permissions,
       transform,
+      manifest,
     } = value;
 
     Self {
@@ -210,6 +216,7 @@ impl From<RawCopyPattern> for CopyPattern {
         })
       }),
       cache: None,
+      manifest,
     }
   }
 }
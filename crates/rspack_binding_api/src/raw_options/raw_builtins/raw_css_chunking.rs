@@ -7,6 +7,7 @@ pub struct RawCssChunkingPluginOptions {
   pub max_size: Option<f64>,
   #[napi(ts_type = "RegExp")]
   pub exclude: Option<RspackRegex>,
+  pub report: Option<String>,
 }
 
 impl From<RawCssChunkingPluginOptions> for rspack_plugin_css_chunking::CssChunkingPluginOptions {
@@ -16,6 +17,7 @@ impl From<RawCssChunkingPluginOptions> for rspack_plugin_css_chunking::CssChunki
       min_size: options.min_size,
       max_size: options.max_size,
       exclude: options.exclude,
+      report: options.report,
     }
   }
 }
@@ -54,6 +54,8 @@ pub struct RawHtmlRspackPluginOptions {
   pub meta: Option<HashMap<String, HashMap<String, String>>>,
   pub hash: Option<bool>,
   pub base: Option<RawHtmlRspackPluginBaseOptions>,
+  pub strict: Option<bool>,
+  pub critical_css: Option<bool>,
   pub uid: Option<u32>,
 }
 
@@ -110,6 +112,8 @@ impl From<RawHtmlRspackPluginOptions> for HtmlRspackPluginOptions {
       meta: value.meta,
       hash: value.hash,
       base: value.base.map(|v| v.into()),
+      strict: value.strict,
+      critical_css: value.critical_css,
       uid: value.uid,
     }
   }
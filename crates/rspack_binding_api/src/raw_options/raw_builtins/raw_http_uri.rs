@@ -25,7 +25,8 @@ pub struct RawHttpUriPluginOptions {
   pub cache_location: Option<String>,
   pub upgrade: bool,
   // pub proxy: Option<String>,
-  // pub frozen: Option<bool>,
+  pub frozen: Option<bool>,
+  pub prune_lockfile: Option<bool>,
   #[napi(ts_type = "(url: string, headers: Record<string, string>) => Promise<JsHttpResponseRaw>")]
   pub http_client: HttpClientRequest,
 }
@@ -94,7 +95,8 @@ fn create_http_uri_plugin_options(
     cache_location: options.cache_location,
     upgrade: options.upgrade,
     // proxy: options.proxy,
-    // frozen: options.frozen,
+    frozen: options.frozen.unwrap_or(false),
+    prune_lockfile: options.prune_lockfile.unwrap_or(false),
     http_client,
     filesystem,
   }
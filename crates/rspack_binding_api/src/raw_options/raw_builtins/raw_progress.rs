@@ -4,7 +4,8 @@ use derive_more::Debug;
 use napi::{Either, bindgen_prelude::FnArgs};
 use napi_derive::napi;
 use rspack_plugin_progress::{
-  ProgressPluginDisplayOptions, ProgressPluginHandlerInfo, ProgressPluginOptions,
+  ProgressJsonOutput, ProgressPluginDisplayOptions, ProgressPluginHandlerInfo,
+  ProgressPluginJsonOptions, ProgressPluginOptions,
 };
 
 use crate::compiler_scoped_tsfn::CompilerScopedTsFnHandle as ThreadsafeFunction;
@@ -41,6 +42,9 @@ pub struct RawProgressPluginOptions {
   pub tick: Option<Either<String, Vec<String>>>,
   // the progress characters
   pub progress_chars: Option<String>,
+  // writes newline-delimited JSON events to stderr (`true`) or to the given file
+  #[napi(ts_type = "boolean | string")]
+  pub json: Option<Either<bool, String>>,
   // the handler for progress event
   #[debug(skip)]
   #[napi(ts_type = "(percent: number, msg: string, info: RawProgressPluginHandlerInfo) => void")]
@@ -54,6 +58,15 @@ impl From<RawProgressPluginOptions> for ProgressPluginOptions {
         let f = f.clone();
         Box::pin(async move { f.call_with_sync((percent, msg, info.into()).into()).await })
       }))
+    } else if let Some(output) = value.json.and_then(|json| match json {
+      Either::A(true) => Some(ProgressJsonOutput::Stderr),
+      Either::A(false) => None,
+      Either::B(path) => Some(ProgressJsonOutput::File(path.into())),
+    }) {
+      Self::Json(ProgressPluginJsonOptions {
+        output,
+        profile: value.profile.unwrap_or_default(),
+      })
     } else {
       Self::Default(ProgressPluginDisplayOptions {
         prefix: value.prefix.unwrap_or_default(),
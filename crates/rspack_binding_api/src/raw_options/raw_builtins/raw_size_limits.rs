@@ -1,6 +1,8 @@
 use derive_more::Debug;
 use napi_derive::napi;
-use rspack_plugin_size_limits::{AssetFilterFn, SizeLimitsPluginOptions};
+use rspack_plugin_size_limits::{
+  AssetFilterFn, SizeBudget, SizeBudgetTarget, SizeLimitsPluginOptions, SizeMeasure,
+};
 
 use crate::compiler_scoped_tsfn::CompilerScopedTsFnHandle as ThreadsafeFunction;
 
@@ -14,11 +16,47 @@ pub struct RawSizeLimitsPluginOptions {
   pub hints: Option<String>,
   pub max_asset_size: Option<f64>,
   pub max_entrypoint_size: Option<f64>,
+  pub budgets: Option<Vec<RawSizeBudget>>,
+  pub report_filename: Option<String>,
+  pub baseline: Option<String>,
 }
 
-impl From<RawSizeLimitsPluginOptions> for SizeLimitsPluginOptions {
-  fn from(value: RawSizeLimitsPluginOptions) -> Self {
-    SizeLimitsPluginOptions {
+#[derive(Debug)]
+#[napi(object, object_to_js = false)]
+pub struct RawSizeBudget {
+  #[napi(ts_type = "\"asset\" | \"chunk\" | \"entrypoint\"")]
+  pub r#type: String,
+  /// glob of asset filenames, chunk names or entrypoint names
+  pub test: String,
+  #[napi(ts_type = "\"raw\" | \"gzip\" | \"brotli\"")]
+  pub measure: Option<String>,
+  pub warning: Option<f64>,
+  pub error: Option<f64>,
+}
+
+impl TryFrom<RawSizeBudget> for SizeBudget {
+  type Error = rspack_error::Error;
+
+  fn try_from(value: RawSizeBudget) -> Result<Self, rspack_error::Error> {
+    Ok(SizeBudget {
+      target: SizeBudgetTarget::try_from(value.r#type.as_str())?,
+      test: value.test,
+      measure: value
+        .measure
+        .map(|measure| SizeMeasure::try_from(measure.as_str()))
+        .transpose()?
+        .unwrap_or_default(),
+      warning: value.warning,
+      error: value.error,
+    })
+  }
+}
+
+impl TryFrom<RawSizeLimitsPluginOptions> for SizeLimitsPluginOptions {
+  type Error = rspack_error::Error;
+
+  fn try_from(value: RawSizeLimitsPluginOptions) -> Result<Self, rspack_error::Error> {
+    Ok(SizeLimitsPluginOptions {
       asset_filter: value.asset_filter.map(|asset_filter| {
         let asset_filter_fn: AssetFilterFn = Box::new(move |name| {
           let f = asset_filter.clone();
@@ -30,6 +68,14 @@ impl From<RawSizeLimitsPluginOptions> for SizeLimitsPluginOptions {
       hints: value.hints,
       max_asset_size: value.max_asset_size,
       max_entrypoint_size: value.max_entrypoint_size,
-    }
+      budgets: value
+        .budgets
+        .unwrap_or_default()
+        .into_iter()
+        .map(SizeBudget::try_from)
+        .collect::<Result<_, rspack_error::Error>>()?,
+      report_filename: value.report_filename,
+      baseline: value.baseline.map(Into::into),
+    })
   }
 }
@@ -4,9 +4,11 @@ use rspack_core::cache::persistent::storage::StorageOptions;
 #[derive(Debug, Default)]
 #[napi(object)]
 pub struct RawStorageOptions {
-  #[napi(ts_type = r#""filesystem""#)]
+  #[napi(ts_type = r#""filesystem" | "remote""#)]
   pub r#type: String,
   pub directory: String,
+  /// Base url of the content-addressed HTTP store, required by the "remote" type
+  pub url: Option<String>,
 }
 
 impl RawStorageOptions {
@@ -15,6 +17,19 @@ impl RawStorageOptions {
       "filesystem" => Ok(StorageOptions::FileSystem {
         directory: self.directory.into(),
       }),
+      "remote" => {
+        let Some(url) = self.url else {
+          return Err(rspack_error::error!("storage type remote requires a url"));
+        };
+        let options = StorageOptions::Remote {
+          directory: self.directory.into(),
+          url,
+        };
+        options
+          .validate()
+          .map_err(|err| rspack_error::error!("invalid cache.storage.url: {err}"))?;
+        Ok(options)
+      }
       storage_type => Err(rspack_error::error!(
         "unsupported storage type {storage_type}"
       )),
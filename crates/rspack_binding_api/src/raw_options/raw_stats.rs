@@ -5,12 +5,14 @@ use rspack_core::StatsOptions;
 #[napi(object)]
 pub struct RawStatsOptions {
   pub colors: bool,
+  pub error_details: bool,
 }
 
 impl From<RawStatsOptions> for StatsOptions {
   fn from(value: RawStatsOptions) -> Self {
     Self {
       colors: value.colors,
+      error_details: value.error_details,
     }
   }
 }
@@ -75,11 +75,13 @@ impl PersistentCache {
       compiler_path.hash(&mut hasher);
       CacheDirectory::new(hex::encode(hasher.finish().to_ne_bytes()))
     };
+    let logger = CompilationLogger::new(LOGGER_NAME.to_string(), compilation_logging);
     let storage = create_storage(
       option.storage.clone(),
       cache_directory,
       option.max_age,
       intermediate_filesystem,
+      logger.clone(),
     );
     let snapshot = Arc::new(Snapshot::new(
       option.snapshot.clone(),
@@ -89,11 +91,7 @@ impl PersistentCache {
 
     Self {
       initialized: false,
-      ctx: CacheContext::new(
-        storage,
-        option.readonly,
-        CompilationLogger::new(LOGGER_NAME.to_string(), compilation_logging),
-      ),
+      ctx: CacheContext::new(storage, option.readonly, logger),
       validation: CacheValidation::new(
         codec.clone(),
         format!("{}|{}", rspack_pkg_version!(), option.version),
@@ -17,15 +17,16 @@ use crate::{AssetInfo, CompilationAsset, RayonConsumer};
 
 pub const SCOPE: &str = "occasion_source_map_dev_tool_plugin";
 
+/// Value stored in [SCOPE] for an asset.
 #[cacheable]
-struct Entry {
+pub struct Entry {
   #[cacheable(with=AsVec<AsPreset>)]
   pub append: Vec<BoxSource>,
   pub source_map: Option<SourceMapAssetEntry>,
 }
 
 #[cacheable]
-struct SourceMapAssetEntry {
+pub struct SourceMapAssetEntry {
   pub filename: String,
   #[cacheable(with=AsPreset)]
   pub source: BoxSource,
@@ -46,9 +47,9 @@ struct SourceMapAssetEntry {
 /// a cache version.
 #[cacheable]
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
-struct CacheKey {
-  filename: String,
-  version: String,
+pub struct CacheKey {
+  pub filename: String,
+  pub version: String,
 }
 
 impl CacheKey {
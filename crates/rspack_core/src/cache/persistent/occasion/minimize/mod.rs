@@ -14,15 +14,16 @@ use crate::RayonConsumer;
 
 pub const SCOPE: &str = "occasion_minimize";
 
+/// Value stored in [SCOPE] for a minimized asset.
 #[cacheable]
-struct Entry {
+pub struct Entry {
   #[cacheable(with=AsPreset)]
   pub source: BoxSource,
   pub extracted_comments: Option<ExtractedCommentsEntry>,
 }
 
 #[cacheable]
-struct ExtractedCommentsEntry {
+pub struct ExtractedCommentsEntry {
   #[cacheable(with=AsPreset)]
   pub source: BoxSource,
   pub comments_file_name: String,
@@ -4,7 +4,12 @@ use rspack_cacheable::{cacheable, utils::PortablePath, with::As};
 use rspack_fs::IntermediateFileSystem;
 use rspack_paths::Utf8PathBuf;
 pub use rspack_storage::{BoxStorage, CacheDirectory, MemoryStorage, Storage};
-use rspack_storage::{FileSystemOptions, FileSystemStorage};
+use rspack_storage::{
+  FileSystemOptions, FileSystemStorage, HttpRemoteClient, RemoteOptions, RemoteReporter,
+  RemoteStorage, Result,
+};
+
+use crate::{CompilationLogger, Logger};
 
 /// Storage Options
 ///
@@ -16,24 +21,77 @@ pub enum StorageOptions {
     #[cacheable(with=As<PortablePath>)]
     directory: Utf8PathBuf,
   },
+  /// Filesystem storage in `directory` that is shared with other machines
+  /// through the content-addressed HTTP store at `url`.
+  Remote {
+    #[cacheable(with=As<PortablePath>)]
+    directory: Utf8PathBuf,
+    url: String,
+  },
+}
+
+impl StorageOptions {
+  /// The local directory that holds the cache files
+  pub fn directory(&self) -> &Utf8PathBuf {
+    match self {
+      Self::FileSystem { directory } | Self::Remote { directory, .. } => directory,
+    }
+  }
+
+  /// Checks that the storage can be created, e.g. that the remote url is valid
+  pub fn validate(&self) -> Result<()> {
+    match self {
+      Self::FileSystem { .. } => Ok(()),
+      Self::Remote { url, .. } => HttpRemoteClient::new(url).map(|_| ()),
+    }
+  }
 }
 
+impl RemoteReporter for CompilationLogger {
+  fn report(&self, message: String) {
+    self.warn(message);
+  }
+}
+
+/// Creates the storage of `options`, remote failures are reported to `logger`.
+///
+/// # Panics
+///
+/// Panics when the remote url of [`StorageOptions::Remote`] is invalid, the options
+/// should have been checked with [`StorageOptions::validate`].
 pub fn create_storage(
   options: StorageOptions,
   cache_directory: CacheDirectory,
   max_age: u64,
   fs: Arc<dyn IntermediateFileSystem>,
+  logger: CompilationLogger,
 ) -> BoxStorage {
   match options {
-    StorageOptions::FileSystem { directory } => {
-      let option = FileSystemOptions {
-        directory,
-        cache_directory,
-        max_pack_size: 500 * 1024,
-        expire: max_age,
-        fs,
-      };
-      Box::new(FileSystemStorage::new(option))
+    StorageOptions::FileSystem { directory } => Box::new(FileSystemStorage::new(
+      filesystem_options(directory, cache_directory, max_age, fs),
+    )),
+    StorageOptions::Remote { directory, url } => {
+      let client = HttpRemoteClient::new(&url).expect("remote url should be checked by validate");
+      Box::new(RemoteStorage::new(RemoteOptions {
+        local: filesystem_options(directory, cache_directory, max_age, fs),
+        client: Arc::new(client),
+        reporter: Arc::new(logger),
+      }))
     }
   }
 }
+
+fn filesystem_options(
+  directory: Utf8PathBuf,
+  cache_directory: CacheDirectory,
+  max_age: u64,
+  fs: Arc<dyn IntermediateFileSystem>,
+) -> FileSystemOptions {
+  FileSystemOptions {
+    directory,
+    cache_directory,
+    max_pack_size: 500 * 1024,
+    expire: max_age,
+    fs,
+  }
+}
@@ -334,6 +334,7 @@ impl Compilation {
   pub const PROCESS_ASSETS_STAGE_OPTIMIZE_INLINE: i32 = 700;
   pub const PROCESS_ASSETS_STAGE_OPTIMIZE_HASH: i32 = 2500;
   pub const PROCESS_ASSETS_STAGE_AFTER_OPTIMIZE_HASH: i32 = 2600;
+  pub const PROCESS_ASSETS_STAGE_REPORT: i32 = 5000;
 
   #[allow(clippy::too_many_arguments)]
   pub fn new(
@@ -288,11 +288,15 @@ impl Compiler {
 
   #[instrument("Compiler:build",target=TRACING_BENCH_TARGET, skip_all)]
   async fn build_inner(&mut self) -> Result<()> {
-    // TODO: clear the outdated cache entries in resolver,
-    // TODO: maybe it's better to use external entries.
+    // Start with a cold resolver cache, it is kept after the build so that the rebuilds of
+    // watch mode only invalidate the entries of the changed files.
+    self.resolver_factory.clear_cache();
+    self.loader_resolver_factory.clear_cache();
     let plugin_driver_clone = self.plugin_driver.clone();
     let compilation_id = self.compilation.id();
-    let _guard = scopeguard::guard((), move |_| plugin_driver_clone.clear_cache(compilation_id));
+    let _guard = scopeguard::guard((), move |_| {
+      plugin_driver_clone.clear_plugins_cache(compilation_id)
+    });
     let compilation_logging = self.compilation.get_logging().clone();
     compilation_logging.clear();
     self.incremental_artifacts.reset();
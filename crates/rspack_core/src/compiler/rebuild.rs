@@ -71,7 +71,18 @@ impl Compiler {
       let mut all_files = modified_files.clone();
       all_files.extend(removed_files.clone());
 
-      self.plugin_driver.clear_cache(self.compilation.id());
+      if all_files.is_empty() {
+        // nothing is known about what changed, e.g. on a manual invalidation
+        self.plugin_driver.clear_cache(self.compilation.id());
+        self.loader_resolver_factory.clear_cache();
+      } else {
+        self
+          .plugin_driver
+          .invalidate_cache(self.compilation.id(), &all_files);
+        self
+          .loader_resolver_factory
+          .invalidate(all_files.iter().map(|file| file.as_path()));
+      }
       let compilation_logging = self.compilation.get_logging().clone();
       compilation_logging.clear();
 
@@ -73,6 +73,8 @@ pub enum DependencyType {
   CssLocalIdent,
   // css modules self reference
   CssSelfReferenceLocalIdent,
+  // html <script src>, <link href> and <img src>
+  HtmlSource,
   // context element
   ContextElement(ContextTypePrefix),
   // import context
@@ -187,6 +189,7 @@ impl DependencyType {
       DependencyType::CssIcssSymbol => "css icss symbol",
       DependencyType::CssLocalIdent => "css local ident",
       DependencyType::CssSelfReferenceLocalIdent => "css self reference local ident",
+      DependencyType::HtmlSource => "html source",
       DependencyType::ContextElement(type_prefix) => match type_prefix {
         ContextTypePrefix::Import => "import() context element",
         ContextTypePrefix::Normal => "context element",
@@ -139,6 +139,7 @@ pub enum SourceType {
   Unknown,
   CssImport,
   Runtime,
+  Html,
 }
 
 impl std::fmt::Display for SourceType {
@@ -170,6 +171,7 @@ impl SourceType {
       SourceType::CssImport => "css-import",
       SourceType::Custom(source_type) => source_type,
       SourceType::Runtime => "runtime",
+      SourceType::Html => "html",
     }
   }
 }
@@ -188,6 +190,7 @@ impl From<&str> for SourceType {
       "share-container-shared" => Self::ShareContainerShared,
       "unknown" => Self::Unknown,
       "css-import" => Self::CssImport,
+      "html" => Self::Html,
       other => SourceType::Custom(other.into()),
     }
   }
@@ -204,6 +207,7 @@ impl From<&ModuleType> for SourceType {
       ModuleType::Asset | ModuleType::AssetInline | ModuleType::AssetResource => Self::Asset,
       ModuleType::ConsumeShared => Self::ConsumeShared,
       ModuleType::ShareContainerShared => Self::ShareContainerShared,
+      ModuleType::Html => Self::Html,
       _ => Self::Unknown,
     }
   }
@@ -227,6 +231,7 @@ pub enum ModuleType {
   AssetSource,
   AssetBytes,
   Asset,
+  Html,
   Runtime,
   Remote,
   Fallback,
@@ -294,6 +299,7 @@ impl ModuleType {
       ModuleType::AssetResource => "asset/resource",
       ModuleType::AssetInline => "asset/inline",
       ModuleType::AssetBytes => "asset/bytes",
+      ModuleType::Html => "html",
       ModuleType::Runtime => "runtime",
       ModuleType::Remote => "remote-module",
       ModuleType::Fallback => "fallback-module",
@@ -338,6 +344,8 @@ impl From<&str> for ModuleType {
       "asset/inline" => Self::AssetInline,
       "asset/bytes" => Self::AssetBytes,
 
+      "html" => Self::Html,
+
       custom => Self::Custom(custom.into()),
     }
   }
@@ -147,6 +147,9 @@ pub struct CssModuleRenderCondition {
   #[cacheable(with=AsOption<AsPreset>)]
   pub supports: Option<SmolStr>,
   pub layer: Option<CssLayer>,
+  /// The `@import` that adds the condition, the at-rules wrapping the imported module are mapped
+  /// to it.
+  pub loc: Option<CssModuleRenderConditionLoc>,
 }
 
 impl CssModuleRenderCondition {
@@ -155,14 +158,30 @@ impl CssModuleRenderCondition {
       media,
       supports,
       layer,
+      loc: None,
     }
   }
 
+  pub fn with_loc(mut self, loc: CssModuleRenderConditionLoc) -> Self {
+    self.loc = Some(loc);
+    self
+  }
+
   pub fn is_empty(&self) -> bool {
     self.media.is_none() && self.supports.is_none() && self.layer.is_none()
   }
 }
 
+/// The position of an `@import` in the importing module, with a 1-based line and a UTF-16 column
+/// as in source maps.
+#[cacheable]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct CssModuleRenderConditionLoc {
+  pub module: ModuleIdentifier,
+  pub line: u32,
+  pub column: u32,
+}
+
 pub fn iter_css_module_render_conditions<'a>(
   inherited_render_conditions: &'a [CssModuleRenderCondition],
   render_condition: &'a CssModuleRenderCondition,
@@ -59,13 +59,13 @@ pub fn create_cache(
     input_filesystem,
     CompilationLogger::new("rspack.newCache".to_string(), compilation_logging),
   );
-  let (base_path, database_path) = match &options.storage {
-    crate::cache::persistent::storage::StorageOptions::FileSystem { directory } => {
-      let base_path = directory.parent().unwrap_or_else(|| {
-        panic!("Persistent cache directory must have a parent directory: {directory}")
-      });
-      (base_path.to_path_buf(), directory.clone())
-    }
+  // The remote store is not supported here, only its local directory is used.
+  let (base_path, database_path) = {
+    let directory = options.storage.directory();
+    let base_path = directory.parent().unwrap_or_else(|| {
+      panic!("Persistent cache directory must have a parent directory: {directory}")
+    });
+    (base_path.to_path_buf(), directory.clone())
   };
   let strategy = match FileCacheStrategy::new(
     (base_path, database_path),
@@ -2,7 +2,7 @@ use rspack_util::fx_hash::FxLinkedHashMap;
 
 use super::{
   Alias, AliasFields, ByDependency, ConditionNames, DependencyCategoryStr, DescriptionFiles,
-  EnforceExtension, ExportsFields, ExtensionAlias, Extensions, Fallback, FullySpecified,
+  EnforceExtension, ExportsFields, ExtensionAlias, Extensions, Fallback, FullySpecified, ImportMap,
   ImportsFields, MainFields, MainFiles, Modules, PreferAbsolute, PreferRelative, Resolve,
   Restrictions, Roots, Symlink, TsconfigOptions,
   value_type::{GetValueType, ValueType},
@@ -39,6 +39,7 @@ fn is_empty(resolve: &Resolve) -> bool {
     && is_none!(restrictions)
     && is_none!(roots)
     && is_none!(tsconfig)
+    && is_none!(import_map)
     && is_none!(by_dependency)
 }
 
@@ -61,6 +62,7 @@ struct ResolveWithEntry {
   modules: Entry<Modules>,
   fallback: Entry<Fallback>,
   tsconfig: Entry<TsconfigOptions>,
+  import_map: Entry<ImportMap>,
   fully_specified: Entry<FullySpecified>,
   exports_fields: Entry<ExportsFields>,
   imports_fields: Entry<ImportsFields>,
@@ -94,6 +96,7 @@ fn parse_resolve(resolve: Resolve) -> ResolveWithEntry {
     modules: entry!(modules),
     fallback: entry!(fallback),
     tsconfig: entry!(tsconfig),
+    import_map: entry!(import_map),
     fully_specified: entry!(fully_specified),
     exports_fields: entry!(exports_fields),
     imports_fields: entry!(imports_fields),
@@ -162,6 +165,7 @@ fn parse_resolve(resolve: Resolve) -> ResolveWithEntry {
   update_by_value!(restrictions, |i: Option<&_>| i.is_some());
   update_by_value!(roots, |i: Option<&_>| i.is_some());
   update_by_value!(tsconfig, |i: Option<&_>| i.is_some());
+  update_by_value!(import_map, |i: Option<&_>| i.is_some());
 
   res
 }
@@ -356,6 +360,7 @@ fn _merge_resolve(first: Resolve, second: Resolve) -> Resolve {
       |_, b| b
     ),
     tsconfig: merge!(tsconfig, ValueType::Other, |_| false, |_, b| b),
+    import_map: merge!(import_map, ValueType::Other, |_| false, |_, b| b),
     extension_alias: merge!(extension_alias, ValueType::Other, |_| false, |a, b| {
       extend_extension_alias(a, b)
     }),
@@ -388,6 +393,7 @@ fn _merge_resolve(first: Resolve, second: Resolve) -> Resolve {
   setup_by_values!(main_fields);
   setup_by_values!(condition_names);
   setup_by_values!(tsconfig);
+  setup_by_values!(import_map);
   setup_by_values!(modules);
   setup_by_values!(fallback);
   setup_by_values!(fully_specified);
@@ -421,6 +427,7 @@ fn _merge_resolve(first: Resolve, second: Resolve) -> Resolve {
   to_resolve!(main_fields);
   to_resolve!(condition_names);
   to_resolve!(tsconfig);
+  to_resolve!(import_map);
   to_resolve!(modules);
   to_resolve!(fallback);
   to_resolve!(fully_specified);
@@ -450,6 +457,7 @@ fn _merge_resolve(first: Resolve, second: Resolve) -> Resolve {
     main_fields: result_entry.main_fields.base,
     condition_names: result_entry.condition_names.base,
     tsconfig: result_entry.tsconfig.base,
+    import_map: result_entry.import_map.base,
     modules: result_entry.modules.base,
     fallback: result_entry.fallback.base,
     fully_specified: result_entry.fully_specified.base,
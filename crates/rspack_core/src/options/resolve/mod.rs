@@ -6,7 +6,7 @@ use std::borrow::Cow;
 
 use rspack_cacheable::{
   cacheable,
-  with::{AsCacheable, AsMap, AsPreset, AsRefStr, AsTuple2, AsVec},
+  with::{AsCacheable, AsMap, AsOption, AsPreset, AsRefStr, AsTuple2, AsVec},
 };
 use rspack_paths::Utf8PathBuf;
 use rspack_regex::RspackRegex;
@@ -77,6 +77,7 @@ pub(super) type ImportsFields = Vec<Vec<String>>;
 pub(super) type ExtensionAlias = Vec<(String, Vec<String>)>;
 pub(super) type Modules = Vec<String>;
 pub(super) type Roots = Vec<String>;
+pub(super) type ImportMap = Utf8PathBuf;
 pub(super) type Restrictions = Vec<Restriction>;
 
 #[cacheable]
@@ -106,6 +107,10 @@ pub struct Resolve {
   pub condition_names: Option<ConditionNames>,
   /// the path of tsconfig.
   pub tsconfig: Option<TsconfigOptions>,
+  /// The path of a [WICG import map](https://github.com/WICG/import-maps) file, whose
+  /// `imports` and `scopes` are applied before node_modules lookup.
+  #[cacheable(with=AsOption<AsPreset>)]
+  pub import_map: Option<ImportMap>,
   /// A list of directories to resolve modules from, can be absolute path or folder name.
   /// Default is `["node_modules"]`
   pub modules: Option<Modules>,
@@ -1,4 +1,6 @@
 #[derive(Debug, Default)]
 pub struct StatsOptions {
   pub colors: bool,
+  /// Trace the failed resolutions, to attach their traces and suggestions to the resolve errors.
+  pub error_details: bool,
 }
@@ -2,6 +2,7 @@ use std::sync::{Arc, Mutex};
 
 use derive_more::Debug;
 use rspack_error::Diagnostic;
+use rspack_paths::ArcPathSet;
 use rspack_util::fx_hash::FxDashMap;
 
 use crate::{
@@ -82,6 +83,19 @@ impl PluginDriver {
 
   pub fn clear_cache(&self, id: CompilationId) {
     self.resolver_factory.clear_cache();
+    self.clear_plugins_cache(id);
+  }
+
+  /// Like [Self::clear_cache], but keeps the resolver cache except for the entries depending
+  /// on the modified or removed `files`.
+  pub fn invalidate_cache(&self, id: CompilationId, files: &ArcPathSet) {
+    self
+      .resolver_factory
+      .invalidate(files.iter().map(|file| file.as_path()));
+    self.clear_plugins_cache(id);
+  }
+
+  pub fn clear_plugins_cache(&self, id: CompilationId) {
     for plugin in &self.plugins {
       plugin.clear_cache(id);
     }
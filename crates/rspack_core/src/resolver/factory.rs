@@ -1,4 +1,4 @@
-use std::{hash::BuildHasherDefault, sync::Arc};
+use std::{hash::BuildHasherDefault, path::Path, sync::Arc};
 
 use dashmap::DashMap;
 use rspack_fs::ReadableFileSystem;
@@ -29,6 +29,10 @@ impl ResolverFactory {
     self.resolver.clear_cache();
   }
 
+  pub fn invalidate<'a>(&self, paths: impl IntoIterator<Item = &'a Path>) {
+    self.resolver.invalidate(paths);
+  }
+
   pub fn new(options: Resolve, fs: Arc<dyn ReadableFileSystem>) -> Self {
     Self {
       base_options: options.clone(),
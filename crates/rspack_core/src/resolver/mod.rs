@@ -294,6 +294,38 @@ if its extension was not listed in the `resolve.extensions`. Here're some possib
   None
 }
 
+/// The requests which may have been meant by a request which failed to resolve, from the
+/// trace of the resolution and the packages in the module directories it searched.
+async fn resolve_suggestions(
+  trace: &rspack_resolver::ResolveTrace,
+  fs: &dyn ReadableFileSystem,
+) -> Vec<String> {
+  let scope = trace
+    .specifier
+    .strip_prefix('@')
+    .and_then(|specifier| specifier.split_once('/'))
+    .map(|(scope, _)| format!("@{scope}"));
+  let mut packages = vec![];
+  for directory in trace.module_directories() {
+    let Some(directory) = Utf8PathBuf::from_path_buf(directory.to_path_buf()).ok() else {
+      continue;
+    };
+    let (directory, prefix) = match &scope {
+      Some(scope) => (directory.join(scope), format!("{scope}/")),
+      None => (directory, String::new()),
+    };
+    if let Ok(entries) = fs.read_dir(&directory).await {
+      packages.extend(
+        entries
+          .into_iter()
+          .filter(|entry| !entry.starts_with('.'))
+          .map(|entry| format!("{prefix}{entry}")),
+      );
+    }
+  }
+  trace.suggestions(&packages)
+}
+
 /// Main entry point for module resolution.
 // #[tracing::instrument(err, "resolve", skip_all, fields(
 //     resolve.specifier = args.specifier,
@@ -335,6 +367,33 @@ pub async fn resolve(
     );
   }
 
+  // The resolution is run again to trace it, which is only worth it when the details of the
+  // errors are shown, like the log of enhanced-resolve in webpack
+  if result.is_err() && plugin_driver.options.stats.error_details {
+    let (_, trace) = resolver
+      .resolve_with_trace(args.context.as_ref(), args.specifier)
+      .await;
+    let suggestions = resolve_suggestions(&trace, resolver.inner_fs().as_ref())
+      .await
+      .into_iter()
+      .map(|suggestion| format!("'{suggestion}'"))
+      .collect::<Vec<_>>();
+    result = result.map_err(|mut err| {
+      if err.help.is_none()
+        && let Some((last, rest)) = suggestions.split_last()
+      {
+        let suggestions = if rest.is_empty() {
+          last.clone()
+        } else {
+          format!("{} or {last}", rest.join(", "))
+        };
+        err.help = Some(format!("Did you mean {suggestions}?"));
+      }
+      err.details = Some(trace.to_string());
+      err
+    });
+  }
+
   if result.is_err()
     && let Some(hint) = resolve_for_error_hints(args, plugin_driver, resolver.inner_fs()).await
   {
@@ -112,6 +112,11 @@ impl Resolver {
     self.resolver.clear_cache();
   }
 
+  /// Invalidate the cache of the changed or removed `paths` for all resolver instances
+  pub fn invalidate<'a>(&self, paths: impl IntoIterator<Item = &'a Path>) {
+    self.resolver.invalidate(paths);
+  }
+
   /// Create a new resolver by cloning its internal cache.
   pub fn clone_with_options(
     &self,
@@ -143,18 +148,7 @@ impl Resolver {
     path: &Path,
     request: &str,
   ) -> Result<ResolveResult, ResolveInnerError> {
-    match self.resolver.resolve(path, request).await {
-      Ok(r) => Ok(ResolveResult::Resource(Resource {
-        path: r.path().to_path_buf().assert_utf8(),
-        query: r.query().unwrap_or_default().to_string(),
-        fragment: r.fragment().unwrap_or_default().to_string(),
-        description_data: r
-          .package_json()
-          .map(|d| DescriptionData::new(d.directory().to_path_buf(), Arc::clone(d.raw_json()))),
-      })),
-      Err(rspack_resolver::ResolveError::Ignored(_)) => Ok(ResolveResult::Ignored),
-      Err(error) => Err(ResolveInnerError::RspackResolver(error)),
-    }
+    to_resolve_result(self.resolver.resolve(path, request).await)
   }
 
   /// Resolve a specifier to a given path.
@@ -175,19 +169,20 @@ impl Resolver {
       file_dependencies: context.file_dependencies,
       missing_dependencies: context.missing_dependencies,
     };
-    let result = match result {
-      Ok(r) => Ok(ResolveResult::Resource(Resource {
-        path: r.path().to_path_buf().assert_utf8(),
-        query: r.query().unwrap_or_default().to_string(),
-        fragment: r.fragment().unwrap_or_default().to_string(),
-        description_data: r
-          .package_json()
-          .map(|d| DescriptionData::new(d.directory().to_path_buf(), Arc::clone(d.raw_json()))),
-      })),
-      Err(rspack_resolver::ResolveError::Ignored(_)) => Ok(ResolveResult::Ignored),
-      Err(error) => Err(ResolveInnerError::RspackResolver(error)),
-    };
-    (result, dependencies)
+    (to_resolve_result(result), dependencies)
+  }
+
+  /// Resolve a specifier to a given path, recording the steps of the resolution.
+  pub async fn resolve_with_trace(
+    &self,
+    path: &Path,
+    request: &str,
+  ) -> (
+    Result<ResolveResult, ResolveInnerError>,
+    rspack_resolver::ResolveTrace,
+  ) {
+    let (result, trace) = self.resolver.resolve_with_trace(path, request).await;
+    (to_resolve_result(result), trace)
   }
 
   pub fn inner_fs(&self) -> Arc<dyn ReadableFileSystem> {
@@ -195,6 +190,23 @@ impl Resolver {
   }
 }
 
+fn to_resolve_result(
+  result: Result<rspack_resolver::Resolution, rspack_resolver::ResolveError>,
+) -> Result<ResolveResult, ResolveInnerError> {
+  match result {
+    Ok(r) => Ok(ResolveResult::Resource(Resource {
+      path: r.path().to_path_buf().assert_utf8(),
+      query: r.query().unwrap_or_default().to_string(),
+      fragment: r.fragment().unwrap_or_default().to_string(),
+      description_data: r
+        .package_json()
+        .map(|d| DescriptionData::new(d.directory().to_path_buf(), Arc::clone(d.raw_json()))),
+    })),
+    Err(rspack_resolver::ResolveError::Ignored(_)) => Ok(ResolveResult::Ignored),
+    Err(error) => Err(ResolveInnerError::RspackResolver(error)),
+  }
+}
+
 impl ResolveInnerError {
   pub fn into_resolve_error(self, args: &ResolveArgs<'_>) -> Error {
     match self {
@@ -316,6 +328,7 @@ fn to_rspack_resolver_options(
     main_fields,
     condition_names,
     tsconfig,
+    import_map: options.import_map.map(Into::into),
     resolve_to_context,
     fully_specified,
     exports_fields,
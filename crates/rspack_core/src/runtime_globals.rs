@@ -193,6 +193,11 @@ define_runtime_globals! {
    */
   const COMPILE_WASM;
 
+  /**
+   * object with all WebAssembly.instance exports of synchronous wasm modules, keyed by module id
+   */
+  const WASM_INSTANCES;
+
   /**
    * Creates an async module. The body function must be a async function.
    * "module.exports" will be decorated with an AsyncModulePromise.
@@ -404,6 +409,7 @@ pub fn runtime_globals_property_name(runtime_globals: &RuntimeGlobals) -> Option
     RuntimeGlobals::RETURN_EXPORTS_FROM_RUNTIME => "return-exports-from-runtime",
     RuntimeGlobals::INSTANTIATE_WASM => "v",
     RuntimeGlobals::COMPILE_WASM => "vs",
+    RuntimeGlobals::WASM_INSTANCES => "w",
     RuntimeGlobals::ASYNC_MODULE => "a",
     RuntimeGlobals::ASYNC_MODULE_EXPORT_SYMBOL => "aE",
     RuntimeGlobals::BASE_URI => "b",
@@ -1958,9 +1958,11 @@ impl RuntimeCodeTemplate {
       .as_ref()
       .expect("chunk code templates cannot render runtime module templates");
     if let Some((executer, file_content)) = dojang.templates.get(key) {
+      let mut context = Context::new(render_params);
+      context.with_options(dojang.options.clone());
       executer
         .render(
-          &mut Context::new(render_params),
+          &mut context,
           &dojang.templates,
           &dojang.functions,
           file_content,
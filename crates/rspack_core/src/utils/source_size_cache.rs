@@ -14,7 +14,7 @@ use ustr::UstrMap;
 
 use crate::SourceType;
 
-const SOURCE_SIZE_CACHE_SLOTS: usize = 13;
+const SOURCE_SIZE_CACHE_SLOTS: usize = 14;
 const SOURCE_SIZE_UNSET: u64 = u64::MAX;
 
 #[derive(Debug)]
@@ -51,6 +51,7 @@ impl SourceSizeCache {
       SourceType::Unknown => Some(10),
       SourceType::CssImport => Some(11),
       SourceType::Runtime => Some(12),
+      SourceType::Html => Some(13),
       SourceType::Custom(_) => None,
     }
   }
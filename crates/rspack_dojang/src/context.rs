@@ -1,12 +1,51 @@
-use std::{collections::HashMap, fmt, sync::Mutex};
+use std::{
+  collections::HashMap,
+  fmt,
+  sync::{Arc, Mutex},
+};
 
 use serde_json::{Map, Number, Value};
 
-use crate::{eval::*, exec::*, expr::*};
+use crate::{
+  dojang::DojangOptions,
+  error::DojangError,
+  eval::*,
+  exec::*,
+  expr::*,
+  loader::{FileLoader, TemplateLoader},
+};
+
+// Maximum nesting of partials and layouts, which is only reached by recursive includes.
+const MAX_INCLUDE_DEPTH: usize = 64;
+
+// Value of the undefined variables when the strict mode is off.
+static UNDEFINED: Value = Value::Null;
+
+// Predefined functions generated by the parser for object literals and for accessing the value
+// returned by a function.
+pub const OBJECT_FUNCTION: &str = "__object";
+pub const GET_FUNCTION: &str = "__get";
 
 #[derive(Debug)]
 pub struct Context {
   pub context: Value,
+
+  // Loads the partials of "include" and "layout", along with the options to parse them.
+  pub(crate) loader: Arc<dyn TemplateLoader>,
+  pub(crate) options: DojangOptions,
+
+  // Name of the template being rendered. Partials are resolved against it.
+  pub(crate) file_name: Option<String>,
+
+  // Layout set by "layout". It is rendered after the template, with the output of the template
+  // as "body".
+  pub(crate) layout: Option<String>,
+
+  // Content of the blocks rendered so far. The first definition of a block wins, so the blocks of
+  // a template override the ones of its layout.
+  pub(crate) blocks: HashMap<String, String>,
+
+  depth: usize,
 }
 
 pub enum FunctionContainer {
@@ -15,6 +54,7 @@ pub enum FunctionContainer {
   F2(Box<dyn Fn(Operand, Operand) -> Operand + Send + Sync>),
   F3(Box<dyn Fn(Operand, Operand, Operand) -> Operand + Send + Sync>),
   F4(Box<dyn Fn(Operand, Operand, Operand, Operand) -> Operand + Send + Sync>),
+  Variadic(Box<dyn Fn(Vec<Operand>) -> Operand + Send + Sync>),
 }
 
 impl fmt::Debug for FunctionContainer {
@@ -26,6 +66,7 @@ impl fmt::Debug for FunctionContainer {
       FunctionContainer::F2(_) => f.field("F2", &2),
       FunctionContainer::F3(_) => f.field("F3", &3),
       FunctionContainer::F4(_) => f.field("F4", &4),
+      FunctionContainer::Variadic(_) => f.field("Variadic", &".."),
     };
 
     f.finish()
@@ -33,6 +74,7 @@ impl fmt::Debug for FunctionContainer {
 }
 
 impl FunctionContainer {
+  /// Number of the parameters. Variadic functions take any number of parameters and return 0.
   pub fn param_num(&self) -> usize {
     match self {
       FunctionContainer::F0(_) => 0,
@@ -40,13 +82,79 @@ impl FunctionContainer {
       FunctionContainer::F2(_) => 2,
       FunctionContainer::F3(_) => 3,
       FunctionContainer::F4(_) => 4,
+      FunctionContainer::Variadic(_) => 0,
     }
   }
+
+  pub fn is_variadic(&self) -> bool {
+    matches!(self, FunctionContainer::Variadic(_))
+  }
 }
 
 impl Context {
   pub fn new(context: Value) -> Self {
-    Context { context }
+    Context {
+      context,
+      loader: Arc::new(FileLoader),
+      options: DojangOptions::default(),
+      file_name: None,
+      layout: None,
+      blocks: HashMap::new(),
+      depth: 0,
+    }
+  }
+
+  pub fn with_options(&mut self, options: DojangOptions) {
+    self.options = options;
+  }
+
+  // Creates the context of a partial rendered from this context.
+  fn partial(&self, context: Value, file_name: String) -> Result<Self, String> {
+    if self.depth >= MAX_INCLUDE_DEPTH {
+      return Err(format!(
+        "Partials are nested more than {MAX_INCLUDE_DEPTH} levels at {file_name}, is it including itself?"
+      ));
+    }
+
+    Ok(Context {
+      context,
+      loader: self.loader.clone(),
+      options: self.options.clone(),
+      file_name: Some(file_name),
+      layout: None,
+      blocks: HashMap::new(),
+      depth: self.depth + 1,
+    })
+  }
+
+  // Renders the layout set by the template, with the output of the template as "body".
+  pub(crate) fn render_layout(
+    &mut self,
+    layout: &str,
+    body: String,
+    templates: &HashMap<String, (Executer, String)>,
+    functions: &HashMap<String, FunctionContainer>,
+    includes: &mut Mutex<HashMap<String, String>>,
+  ) -> Result<String, DojangError> {
+    if self.depth >= MAX_INCLUDE_DEPTH {
+      return Err(DojangError::from(format!(
+        "Layouts are nested more than {MAX_INCLUDE_DEPTH} levels at {layout}, is it using itself?"
+      )));
+    }
+
+    self.set_value(&vec!["body"], &Value::String(body))?;
+    self.depth += 1;
+    let rendered = if let Some((executer, template)) = templates.get(layout) {
+      executer.render(self, templates, functions, template, includes)
+    } else {
+      let (file_name, executer, template) = load_template(layout, self, includes)?;
+      let issuer = self.file_name.replace(file_name);
+      let rendered = executer.render(self, templates, functions, &template, includes);
+      self.file_name = issuer;
+      rendered
+    };
+    self.depth -= 1;
+    rendered
   }
 
   fn get_value(&self, names: &Vec<&str>) -> Result<&Value, String> {
@@ -59,6 +167,7 @@ impl Context {
       Some(v) => {
         value = v;
       }
+      _ if !self.options.strict => return Ok(&UNDEFINED),
       _ => {
         return Err(format!(
           "ReferenceError: {} is not defined",
@@ -91,6 +200,7 @@ impl Context {
           Some(v) => {
             value = v;
           }
+          _ if !self.options.strict => return Ok(&UNDEFINED),
           _ => {
             return Err(format!(
               "ReferenceError: {} is not defined",
@@ -157,7 +267,7 @@ pub trait ComputeExpr {
     templates: &HashMap<String, (Executer, String)>,
     functions: &HashMap<String, FunctionContainer>,
     includes: &mut Mutex<HashMap<String, String>>,
-  ) -> Result<Operand, String>;
+  ) -> Result<Operand, DojangError>;
 
   fn run_for_range(
     &self,
@@ -165,14 +275,14 @@ pub trait ComputeExpr {
     templates: &HashMap<String, (Executer, String)>,
     functions: &HashMap<String, FunctionContainer>,
     includes: &mut Mutex<HashMap<String, String>>,
-  ) -> Result<Operand, String>;
+  ) -> Result<Operand, DojangError>;
 
   fn run_for_loop(
     &self,
     context: &mut Context,
     range: &Operand,
     for_index: usize,
-  ) -> Result<bool, String>;
+  ) -> Result<bool, DojangError>;
 }
 
 impl ComputeExpr for Eval {
@@ -182,7 +292,7 @@ impl ComputeExpr for Eval {
     templates: &HashMap<String, (Executer, String)>,
     functions: &HashMap<String, FunctionContainer>,
     includes: &mut Mutex<HashMap<String, String>>,
-  ) -> Result<Operand, String> {
+  ) -> Result<Operand, DojangError> {
     let mut operands: Vec<Operand> = Vec::new();
 
     for expr in self.expr.iter().rev() {
@@ -194,9 +304,9 @@ impl ComputeExpr for Eval {
           optr => {
             let num_operands = operator_num_operands(optr);
             if operands.len() < num_operands {
-              return Err(format!(
+              return Err(DojangError::from(format!(
                 "Number of operands for {optr:?} is less than {num_operands}"
-              ));
+              )));
             }
 
             if num_operands == 1 {
@@ -205,7 +315,7 @@ impl ComputeExpr for Eval {
                 Ok(operand) => {
                   operands.push(operand);
                 }
-                Err(e) => return Err(e),
+                Err(e) => return Err(DojangError::from(e)),
               }
             } else if num_operands == 2 {
               // Since we are iterating from back, left is the top most operand.
@@ -216,7 +326,7 @@ impl ComputeExpr for Eval {
                 Ok(operand) => {
                   operands.push(operand);
                 }
-                Err(e) => return Err(e),
+                Err(e) => return Err(DojangError::from(e)),
               }
             }
           }
@@ -233,21 +343,21 @@ impl ComputeExpr for Eval {
             let function_to_run = match functions.get(&function.name) {
               Some(f) => f,
               None => {
-                return Err(format!(
+                return Err(DojangError::from(format!(
                   "Function {:?} is not registered; Registered : {:?}",
                   function.name, functions
-                ));
+                )));
               }
             };
 
             let params = &function.params;
-            if params.len() != function_to_run.param_num() {
-              return Err(format!(
+            if !function_to_run.is_variadic() && params.len() != function_to_run.param_num() {
+              return Err(DojangError::from(format!(
                 "# of function params mismatch! {} takes {} params but provided {} params",
                 function.name,
                 function_to_run.param_num(),
                 params.len()
-              ));
+              )));
             }
 
             let mut evals = Vec::new();
@@ -257,6 +367,7 @@ impl ComputeExpr for Eval {
             }
 
             let return_value = match function_to_run {
+              FunctionContainer::Variadic(f) => f(evals),
               FunctionContainer::F0(f) => f(),
               FunctionContainer::F1(f) => f(evals.pop().unwrap()),
               FunctionContainer::F2(f) => {
@@ -303,9 +414,9 @@ impl ComputeExpr for Eval {
     }
 
     if operands.len() != 1 {
-      return Err(format!(
+      return Err(DojangError::from(format!(
         "# of operands after computing is not zero. {operands:?}"
-      ));
+      )));
     }
 
     match operands.pop().unwrap() {
@@ -322,29 +433,29 @@ impl ComputeExpr for Eval {
     templates: &HashMap<String, (Executer, String)>,
     functions: &HashMap<String, FunctionContainer>,
     includes: &mut Mutex<HashMap<String, String>>,
-  ) -> Result<Operand, String> {
+  ) -> Result<Operand, DojangError> {
     if self.expr.len() != 3 {
-      return Err(format!(
+      return Err(DojangError::from(format!(
         "For loop must use 'for a in b' format, yours use {:?}",
         self.expr
-      ));
+      )));
     }
 
     match self.expr.first().unwrap() {
       Expr::Op(Op::Operand(Operand::Object(_))) => {}
       _ => {
-        return Err(format!(
+        return Err(DojangError::from(format!(
           "Range declaration in for loop must be an object; Yours : {:?}",
           self.expr
-        ));
+        )));
       }
     }
 
     if self.expr[1] != Expr::Op(Op::Operand(Operand::Keyword(Keyword::In))) {
-      return Err(format!(
+      return Err(DojangError::from(format!(
         "'in' is missing in your for-loop. Yours : {:?}",
         self.expr
-      ));
+      )));
     }
 
     let range = Eval {
@@ -360,21 +471,21 @@ impl ComputeExpr for Eval {
     context: &mut Context,
     range: &Operand,
     for_index: usize,
-  ) -> Result<bool, String> {
+  ) -> Result<bool, DojangError> {
     if self.expr.len() != 3 {
-      return Err(format!(
+      return Err(DojangError::from(format!(
         "For loop must use 'for a in b' format, yours use {:?}",
         self.expr
-      ));
+      )));
     }
 
     let object_name = match self.expr.first().unwrap() {
       Expr::Op(Op::Operand(Operand::Object(object))) => &object.name,
       _ => {
-        return Err(format!(
+        return Err(DojangError::from(format!(
           "Range declaration in for loop must be an object; Yours : {:?}",
           self.expr
-        ));
+        )));
       }
     };
 
@@ -392,40 +503,106 @@ impl ComputeExpr for Eval {
   }
 }
 
+// Loads a partial through the loader of the context. Returns the resolved file name along with
+// the parsed partial.
+pub(crate) fn load_template(
+  request: &str,
+  context: &Context,
+  includes: &mut Mutex<HashMap<String, String>>,
+) -> Result<(String, Executer, String), DojangError> {
+  let file_name = context
+    .loader
+    .resolve(request, context.file_name.as_deref());
+
+  let includes = includes.get_mut().unwrap();
+  let template = match includes.get(&file_name) {
+    Some(template) => template.clone(),
+    None => {
+      let template = context
+        .loader
+        .load(&file_name)
+        .map_err(|e| format!("Unable to load '{request}' ({file_name}): {e}"))?;
+      includes.insert(file_name.clone(), template.clone());
+      template
+    }
+  };
+
+  let executer =
+    Executer::compile(&template, context.options.clone()).map_err(|e| e.in_file(&file_name))?;
+  Ok((file_name, executer, template))
+}
+
+fn get_property(value: Value, key: &str, strict: bool) -> Result<Value, String> {
+  match value {
+    Value::Object(mut obj) if !strict => Ok(obj.remove(key).unwrap_or_default()),
+    Value::Null if !strict => Ok(Value::Null),
+    Value::Array(mut arr) => match key.parse::<usize>() {
+      Ok(index) if index < arr.len() => Ok(arr.swap_remove(index)),
+      _ => Err(format!(
+        "Element at the specified index of the array does not exist : index : {key:?}"
+      )),
+    },
+    Value::Object(mut obj) => obj
+      .remove(key)
+      .ok_or_else(|| format!("ReferenceError: {key} is not defined")),
+    value => Err(format!("Unable to access {key:?} of {value:?}")),
+  }
+}
+
 fn handle_predefined_functions(
   f: &Function,
   context: &mut Context,
   templates: &HashMap<String, (Executer, String)>,
   functions: &HashMap<String, FunctionContainer>,
   includes: &mut Mutex<HashMap<String, String>>,
-) -> Result<Option<Operand>, String> {
+) -> Result<Option<Operand>, DojangError> {
   if f.name == "include" {
-    if f.params.len() != 1 {
-      return Err(format!(
-        "Predefined function 'includes' must provide 1 string parameter. You gave : {f:?}"
-      ));
+    if f.params.is_empty() || f.params.len() > 2 {
+      return Err(DojangError::from(format!(
+        "Predefined function 'include' must provide a file name and optional parameters. You gave : {f:?}"
+      )));
     }
 
-    let file_name = f
+    let request = f
       .params
       .first()
       .unwrap()
       .run(context, templates, functions, includes)?
       .to_str();
-    if let Some(file_data) = includes.get_mut().unwrap().get(&file_name) {
-      return Ok(Some(Operand::Value(Value::String(file_data.clone()))));
-    } else {
-      let file_content = std::fs::read(&file_name).map_err(|e| e.to_string())?;
 
-      return Ok(Some(Operand::Value(Value::String(
-        String::from_utf8_lossy(&file_content).to_string(),
-      ))));
+    // Partials see the data of the including template, overridden by the given parameters.
+    let mut data = context.context.clone();
+    if let Some(param) = f.params.get(1) {
+      match convert_operand_to_value(param.run(context, templates, functions, includes)?) {
+        Value::Object(params) => {
+          if !data.is_object() {
+            data = Value::Object(Map::new());
+          }
+          data.as_object_mut().unwrap().extend(params);
+        }
+        Value::Null => {}
+        params => {
+          return Err(DojangError::from(format!(
+            "Parameters of 'include' must be an object. You gave : {params:?}"
+          )));
+        }
+      }
     }
+
+    let (file_name, executer, template) = load_template(&request, context, includes)?;
+    let mut partial_context = context.partial(data, file_name)?;
+    return Ok(Some(Operand::Value(Value::String(executer.render(
+      &mut partial_context,
+      templates,
+      functions,
+      &template,
+      includes,
+    )?))));
   } else if f.name == "include_template" {
     if f.params.len() != 1 {
-      return Err(format!(
+      return Err(DojangError::from(format!(
         "Predefined function 'includes' must provide 1 string parameter. You gave : {f:?}"
-      ));
+      )));
     }
 
     let file_name = f
@@ -436,14 +613,59 @@ fn handle_predefined_functions(
       .to_str();
 
     if let Some((executer, template)) = templates.get(&file_name) {
-      return Ok(Some(Operand::Value(Value::String(
-        executer.render(context, templates, functions, template, includes)?,
-      ))));
+      // Errors of the included template are located in that template.
+      let issuer = context.file_name.replace(file_name);
+      let rendered = executer.render(context, templates, functions, template, includes);
+      context.file_name = issuer;
+      return Ok(Some(Operand::Value(Value::String(rendered?))));
     } else {
-      return Err(format!(
+      return Err(DojangError::from(format!(
         "Predefined function 'include_template' must only use the pre-registered template. You gave : {file_name}"
-      ));
+      )));
+    }
+  } else if f.name == "layout" {
+    if f.params.len() != 1 {
+      return Err(DojangError::from(format!(
+        "Predefined function 'layout' must provide 1 string parameter. You gave : {f:?}"
+      )));
+    }
+
+    // The layout is rendered once the template is rendered.
+    context.layout = Some(
+      f.params
+        .first()
+        .unwrap()
+        .run(context, templates, functions, includes)?
+        .to_str(),
+    );
+    return Ok(Some(Operand::Value(Value::String(String::new()))));
+  } else if f.name == OBJECT_FUNCTION {
+    let mut object = Map::new();
+    for entry in f.params.chunks(2) {
+      let [key, value] = entry else {
+        return Err(DojangError::from(format!(
+          "Object literal has a key without value : {f:?}"
+        )));
+      };
+
+      let key = key.run(context, templates, functions, includes)?.to_str();
+      let value = convert_operand_to_value(value.run(context, templates, functions, includes)?);
+      object.insert(key, value);
+    }
+    return Ok(Some(Operand::Value(Value::Object(object))));
+  } else if f.name == GET_FUNCTION {
+    let Some((target, keys)) = f.params.split_first() else {
+      return Err(DojangError::from(format!(
+        "Property access without target : {f:?}"
+      )));
+    };
+
+    let mut value = convert_operand_to_value(target.run(context, templates, functions, includes)?);
+    for key in keys {
+      let key = key.run(context, templates, functions, includes)?.to_str();
+      value = get_property(value, &key, context.options.strict)?;
     }
+    return Ok(Some(convert_value_to_operand(value)));
   }
 
   Ok(None)
@@ -35,3 +35,67 @@ pub fn val_stringify(op: Operand) -> Operand {
     serde_json::to_string(&convert_operand_to_value(op)).unwrap(),
   ))
 }
+
+fn operand_to_string(op: Operand) -> String {
+  match convert_operand_to_value(op) {
+    Value::String(s) => s,
+    Value::Null => String::new(),
+    val => val.to_string(),
+  }
+}
+
+pub fn val_escape(op: Operand) -> Operand {
+  Operand::Value(Value::from(
+    html_escape::encode_safe(&operand_to_string(op)).to_string(),
+  ))
+}
+
+pub fn val_escape_attr(op: Operand) -> Operand {
+  Operand::Value(Value::from(
+    html_escape::encode_quoted_attribute(&operand_to_string(op)).to_string(),
+  ))
+}
+
+pub fn val_upper(op: Operand) -> Operand {
+  Operand::Value(Value::from(
+    operand_to_string(op)
+      .chars()
+      .flat_map(char::to_uppercase)
+      .collect::<String>(),
+  ))
+}
+
+pub fn val_lower(op: Operand) -> Operand {
+  Operand::Value(Value::from(
+    operand_to_string(op)
+      .chars()
+      .flat_map(char::to_lowercase)
+      .collect::<String>(),
+  ))
+}
+
+pub fn val_trim(op: Operand) -> Operand {
+  Operand::Value(Value::from(operand_to_string(op).trim()))
+}
+
+pub fn val_default(op: Operand, default: Operand) -> Operand {
+  match convert_operand_to_value(op) {
+    Value::Null => default,
+    Value::String(s) if s.is_empty() => default,
+    val => Operand::Value(val),
+  }
+}
+
+pub fn val_join(op: Operand, separator: Operand) -> Operand {
+  let separator = operand_to_string(separator);
+  match convert_operand_to_value(op) {
+    Value::Array(arr) => Operand::Value(Value::from(
+      arr
+        .into_iter()
+        .map(|val| operand_to_string(Operand::Value(val)))
+        .collect::<Vec<_>>()
+        .join(&separator),
+    )),
+    val => Operand::Value(Value::from(operand_to_string(Operand::Value(val)))),
+  }
+}
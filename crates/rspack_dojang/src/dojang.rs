@@ -1,24 +1,35 @@
-use std::{collections::HashMap, fs, io, path::PathBuf, sync::Mutex};
+use std::{
+  collections::HashMap,
+  fs, io,
+  path::PathBuf,
+  sync::{Arc, Mutex},
+};
 
 use serde_json::Value;
 
 use crate::{
   context::*,
-  default_functions::{val_length, val_range, val_stringify},
+  default_functions::*,
+  error::DojangError,
   exec::*,
   expr::*,
+  loader::{FileLoader, TemplateLoader},
 };
 
 #[derive(Debug, Clone)]
 pub struct DojangOptions {
   pub escape: String,
   pub unescape: String,
+  /// Reports undefined variables and properties as errors, which is the default. When turned
+  /// off they are treated as null, and null renders nothing like EJS.
+  pub strict: bool,
 }
 impl Default for DojangOptions {
   fn default() -> Self {
     Self {
       escape: "=".to_string(),
       unescape: "-".to_string(),
+      strict: true,
     }
   }
 }
@@ -35,6 +46,9 @@ pub struct Dojang {
 
   // part of ejs config options, see https://github.com/mde/ejs#options
   pub options: DojangOptions,
+
+  /// Resolves and reads the partials used by "include" and "layout".
+  pub loader: Arc<dyn TemplateLoader>,
 }
 
 impl Default for Dojang {
@@ -47,6 +61,12 @@ impl Dojang {
   pub fn with_options(&mut self, options: DojangOptions) {
     self.options = options;
   }
+
+  /// Sets the loader of the partials. Partials are read from the local file system by default.
+  pub fn with_loader(&mut self, loader: Arc<dyn TemplateLoader>) {
+    self.loader = loader;
+  }
+
   /// Creates a template engine.
   pub fn new() -> Self {
     let mut functions = HashMap::<String, FunctionContainer>::new();
@@ -65,11 +85,48 @@ impl Dojang {
       FunctionContainer::F1(Box::new(val_stringify)),
     );
 
+    // Filters, which are usually chained with pipes (e.g <%- title | trim | escape %>).
+    functions.insert(
+      "escape".to_string(),
+      FunctionContainer::F1(Box::new(val_escape)),
+    );
+
+    functions.insert(
+      "escape_attr".to_string(),
+      FunctionContainer::F1(Box::new(val_escape_attr)),
+    );
+
+    functions.insert(
+      "upper".to_string(),
+      FunctionContainer::F1(Box::new(val_upper)),
+    );
+
+    functions.insert(
+      "lower".to_string(),
+      FunctionContainer::F1(Box::new(val_lower)),
+    );
+
+    functions.insert(
+      "trim".to_string(),
+      FunctionContainer::F1(Box::new(val_trim)),
+    );
+
+    functions.insert(
+      "default".to_string(),
+      FunctionContainer::F2(Box::new(val_default)),
+    );
+
+    functions.insert(
+      "join".to_string(),
+      FunctionContainer::F2(Box::new(val_join)),
+    );
+
     Dojang {
       templates: HashMap::new(),
       functions,
       includes: Mutex::new(HashMap::new()),
       options: Default::default(),
+      loader: Arc::new(FileLoader),
     }
   }
 
@@ -90,30 +147,33 @@ impl Dojang {
   /// // Constructs the template "tmpl" with the content "<%= 1 + 1 %>".
   /// dojang.add("tmpl".to_string(), "<%= 1 + 1 %>".to_string());
   /// ```
-  pub fn add(&mut self, file_name: String, template: String) -> Result<&Self, String> {
+  pub fn add(&mut self, file_name: String, template: String) -> Result<&Self, DojangError> {
     if self.templates.contains_key(&file_name) {
-      return Err(format!("{file_name} is already added as a template"));
+      return Err(DojangError::new(format!(
+        "{file_name} is already added as a template"
+      )));
     }
 
-    self.templates.insert(
-      file_name,
-      (Executer::new(Parser::parse(&template)?)?, template),
-    );
+    let executer =
+      Executer::compile(&template, DojangOptions::default()).map_err(|e| e.in_file(&file_name))?;
+    self.templates.insert(file_name, (executer, template));
 
     Ok(self)
   }
-  pub fn add_with_option(&mut self, file_name: String, template: String) -> Result<&Self, String> {
+  pub fn add_with_option(
+    &mut self,
+    file_name: String,
+    template: String,
+  ) -> Result<&Self, DojangError> {
     if self.templates.contains_key(&file_name) {
-      return Err(format!("{file_name} is already added as a template"));
+      return Err(DojangError::new(format!(
+        "{file_name} is already added as a template"
+      )));
     }
 
-    self.templates.insert(
-      file_name,
-      (
-        Executer::new(Parser::parse_with_options(&template, self.options.clone())?)?,
-        template,
-      ),
-    );
+    let executer =
+      Executer::compile(&template, self.options.clone()).map_err(|e| e.in_file(&file_name))?;
+    self.templates.insert(file_name, (executer, template));
 
     Ok(self)
   }
@@ -253,6 +313,43 @@ impl Dojang {
     Ok(self)
   }
 
+  /// Adds a function taking any number of parameters that can be used in the template.
+  ///
+  /// The parameters are provided as they are, so the function should convert them by itself.
+  /// Functions returning a map (e.g serde_json::Map) can be used to build objects whose
+  /// properties are accessed in the template (e.g `<%= meta("a", 1).a %>`).
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// use rspack_dojang::{Operand, dojang::Dojang};
+  ///
+  /// fn concat(params: Vec<Operand>) -> String {
+  ///   params.into_iter().map(String::from).collect()
+  /// }
+  ///
+  /// let mut dj = Dojang::new();
+  ///
+  /// dj.add_function_variadic("concat".to_string(), concat);
+  /// ```
+  pub fn add_function_variadic<V>(
+    &mut self,
+    function_name: String,
+    function: fn(Vec<Operand>) -> V,
+  ) -> Result<&Self, String>
+  where
+    V: 'static + Into<Operand>,
+  {
+    if self.functions.contains_key(&function_name) {
+      return Err(format!("{function_name} is already added as a function"));
+    }
+
+    self
+      .functions
+      .insert(function_name, to_function_container_variadic(function));
+    Ok(self)
+  }
+
   /// Load files under the provided directory as templates.
   ///
   /// Note that it does not recursively visit every underlying directories. Only the files that
@@ -272,7 +369,7 @@ impl Dojang {
   /// // Add every files under ./tests as a template.
   /// dojang.load("./tests");
   /// ```
-  pub fn load(&mut self, dir_name: &str) -> Result<&mut Self, String> {
+  pub fn load(&mut self, dir_name: &str) -> Result<&mut Self, DojangError> {
     match get_all_file_path_under_dir(dir_name) {
       Ok(files) => {
         for file in files {
@@ -296,13 +393,12 @@ impl Dojang {
           }
 
           let file_content = file_content.unwrap();
-          self.templates.insert(
-            file_name,
-            (Executer::new(Parser::parse(&file_content)?)?, file_content),
-          );
+          let executer = Executer::compile(&file_content, DojangOptions::default())
+            .map_err(|e| e.in_file(&file_name))?;
+          self.templates.insert(file_name, (executer, file_content));
         }
       }
-      Err(e) => return Err(e.to_string()),
+      Err(e) => return Err(DojangError::new(e.to_string())),
     }
 
     Ok(self)
@@ -327,19 +423,45 @@ impl Dojang {
   ///   serde_json::from_str(r#"{ "test" : { "title" : "Welcome to Dojang"} }"#).unwrap(),
   /// );
   /// ```
-  pub fn render(&mut self, file_name: &str, value: Value) -> Result<String, String> {
+  pub fn render(&mut self, file_name: &str, value: Value) -> Result<String, DojangError> {
     if let Some((executer, file_content)) = self.templates.get(file_name) {
+      let mut context = Context::new(value);
+      context.loader = self.loader.clone();
+      context.options = self.options.clone();
+      context.file_name = Some(file_name.to_string());
+
       executer.render(
-        &mut Context::new(value),
+        &mut context,
         &self.templates,
         &self.functions,
         file_content,
         &mut self.includes,
       )
     } else {
-      Err(format!("Template {file_name} is not found"))
+      Err(DojangError::new(format!(
+        "Template {file_name} is not found"
+      )))
     }
   }
+
+  /// Returns the content of a template or a partial read so far, which can be used to show where
+  /// the error (see `DojangError::file_name`) happened.
+  pub fn source(&self, file_name: &str) -> Option<String> {
+    if let Some((_, template)) = self.templates.get(file_name) {
+      return Some(template.clone());
+    }
+
+    let includes = self.includes.lock().unwrap_or_else(|e| e.into_inner());
+    includes.get(file_name).cloned()
+  }
+
+  /// Returns the resolved names of the partials read by "include" and "layout" so far.
+  pub fn included_files(&self) -> Vec<String> {
+    let includes = self.includes.lock().unwrap_or_else(|e| e.into_inner());
+    let mut files: Vec<String> = includes.keys().cloned().collect();
+    files.sort();
+    files
+  }
 }
 
 fn get_all_file_path_under_dir(dir_name: &str) -> io::Result<Vec<PathBuf>> {
@@ -352,6 +474,14 @@ pub fn to_function_container0<V: 'static + Into<Operand>>(func: fn() -> V) -> Fu
   FunctionContainer::F0(Box::new(move || -> Operand { func().into() }))
 }
 
+pub fn to_function_container_variadic<V: 'static + Into<Operand>>(
+  func: fn(Vec<Operand>) -> V,
+) -> FunctionContainer {
+  FunctionContainer::Variadic(Box::new(move |v: Vec<Operand>| -> Operand {
+    func(v).into()
+  }))
+}
+
 pub fn to_function_container1<T: 'static + From<Operand>, V: 'static + Into<Operand>>(
   func: fn(T) -> V,
 ) -> FunctionContainer {
@@ -426,6 +556,7 @@ fn custom_escape_unescape() {
   dojang.with_options(DojangOptions {
     escape: "-".to_string(),
     unescape: "=".to_string(),
+    ..Default::default()
   });
   assert!(
     dojang
@@ -562,3 +693,199 @@ fn use_json_stringify_function() {
     "[1,2,3]"
   );
 }
+
+#[test]
+fn include_with_params() {
+  let template =
+    r#"<ul><% for name in names { %><%- include("test_partial.html", { name: name }) %><% } %></ul>"#
+      .to_string();
+  let mut dojang = Dojang::new();
+  assert!(
+    dojang
+      .add("./tests/page.html".to_string(), template)
+      .is_ok()
+  );
+
+  assert_eq!(
+    dojang
+      .render(
+        "./tests/page.html",
+        serde_json::json!({"names" : ["a", "b"], "site" : "rspack"})
+      )
+      .unwrap(),
+    "<ul><li>a from rspack</li>\n<li>b from rspack</li>\n</ul>"
+  );
+  assert_eq!(
+    dojang.included_files(),
+    vec!["./tests/test_partial.html".to_string()]
+  );
+}
+
+#[test]
+fn render_with_layout() {
+  let template =
+    r#"<% layout("./tests/test_layout.html") %><% block title { %><%= title %><% } %><p>content</p>"#
+      .to_string();
+  let mut dojang = Dojang::new();
+  assert!(dojang.add("some_template".to_string(), template).is_ok());
+
+  assert_eq!(
+    dojang
+      .render("some_template", serde_json::json!({"title" : "Rspack"}))
+      .unwrap(),
+    "<html><title>Rspack</title><body><p>content</p></body></html>\n"
+  );
+}
+
+#[test]
+fn render_with_layout_default_block() {
+  let template = r#"<% layout("./tests/test_layout.html") %><p>content</p>"#.to_string();
+  let mut dojang = Dojang::new();
+  assert!(dojang.add("some_template".to_string(), template).is_ok());
+
+  assert_eq!(
+    dojang
+      .render("some_template", serde_json::json!({}))
+      .unwrap(),
+    "<html><title>Default</title><body><p>content</p></body></html>\n"
+  );
+}
+
+#[test]
+fn use_filters() {
+  let template =
+    r#"<%- title | trim | upper %>,<%- missing | default("none") %>,<%- list | join(", ") %>,<%- html | escape %>"#
+      .to_string();
+  let mut dojang = Dojang::new();
+  assert!(dojang.add("some_template".to_string(), template).is_ok());
+
+  assert_eq!(
+    dojang
+      .render(
+        "some_template",
+        serde_json::json!({"title" : " rspack ", "missing" : null, "list" : [1, 2], "html" : "<b>"})
+      )
+      .unwrap(),
+    "RSPACK,none,1, 2,&lt;b&gt;"
+  );
+}
+
+#[test]
+fn use_filters_in_function_params() {
+  let template = r#"<%= func(a | length, (b | length) + 1) %>"#.to_string();
+  let mut dojang = Dojang::new();
+  assert!(dojang.add("some_template".to_string(), template).is_ok());
+  assert!(dojang.add_function_2("func".to_string(), func).is_ok());
+
+  assert_eq!(
+    dojang
+      .render(
+        "some_template",
+        serde_json::json!({"a" : "abc", "b" : "de"})
+      )
+      .unwrap(),
+    "6"
+  );
+}
+
+#[cfg(test)]
+fn concat(params: Vec<Operand>) -> String {
+  params.into_iter().map(String::from).collect()
+}
+
+#[cfg(test)]
+fn meta(name: Operand, content: Operand) -> serde_json::Map<String, Value> {
+  let mut map = serde_json::Map::new();
+  map.insert("name".to_string(), convert_operand_to_value(name));
+  map.insert("content".to_string(), convert_operand_to_value(content));
+  map
+}
+
+#[test]
+fn use_variadic_and_map_functions() {
+  let template =
+    r#"<%= concat("a", "b", "c") %>,<%= concat() %>,<%= meta("og", "x").name %>:<%= meta("og", "x")["content"] %>"#
+      .to_string();
+  let mut dojang = Dojang::new();
+  assert!(dojang.add("some_template".to_string(), template).is_ok());
+  assert!(
+    dojang
+      .add_function_variadic("concat".to_string(), concat)
+      .is_ok()
+  );
+  assert!(dojang.add_function_2("meta".to_string(), meta).is_ok());
+
+  assert_eq!(
+    dojang
+      .render("some_template", serde_json::json!({}))
+      .unwrap(),
+    "abc,,og:x"
+  );
+}
+
+#[test]
+fn undefined_variables_without_strict_mode() {
+  let template = "[<%= a %>][<%= o.x %>][<% if o.x { %>x<% } %>][<%= n %>]".to_string();
+  let mut dojang = Dojang::new();
+  dojang.with_options(DojangOptions {
+    strict: false,
+    ..Default::default()
+  });
+  assert!(dojang.add("some_template".to_string(), template).is_ok());
+
+  assert_eq!(
+    dojang
+      .render("some_template", serde_json::json!({"o" : {}, "n" : null}))
+      .unwrap(),
+    "[][][][]"
+  );
+}
+
+#[test]
+fn undefined_variables_in_strict_mode() {
+  let template = "<html>\n  <%= o.x %>\n</html>".to_string();
+  let mut dojang = Dojang::new();
+  assert!(dojang.add("some_template".to_string(), template).is_ok());
+
+  let error = dojang
+    .render("some_template", serde_json::json!({"o" : {}}))
+    .unwrap_err();
+  assert_eq!(error.message, "ReferenceError: o.x is not defined");
+  assert_eq!(error.file_name.as_deref(), Some("some_template"));
+
+  let span = error.span.unwrap();
+  assert_eq!((span.line, span.column), (2, 3));
+  assert_eq!((span.start, span.end), (9, 19));
+}
+
+#[test]
+fn locate_parse_error() {
+  let template = "<p>\n<%= a %>\n<%= a | 1 %>".to_string();
+  let mut dojang = Dojang::new();
+
+  let error = dojang
+    .add("some_template".to_string(), template)
+    .err()
+    .unwrap();
+  assert_eq!(error.file_name.as_deref(), Some("some_template"));
+
+  let span = error.span.unwrap();
+  assert_eq!((span.line, span.column), (3, 1));
+}
+
+#[test]
+fn locate_error_in_partial() {
+  let template = r#"<p><%- include("./tests/test_partial.html") %></p>"#.to_string();
+  let mut dojang = Dojang::new();
+  assert!(dojang.add("some_template".to_string(), template).is_ok());
+
+  let error = dojang
+    .render("some_template", serde_json::json!({"name" : "a"}))
+    .unwrap_err();
+  assert_eq!(error.message, "ReferenceError: site is not defined");
+  assert_eq!(
+    error.file_name.as_deref(),
+    Some("./tests/test_partial.html")
+  );
+  assert_eq!(error.span.unwrap().column, 22);
+}
@@ -0,0 +1,131 @@
+use std::fmt;
+
+/// Location of an error in the template.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Span {
+  /// [start, end) of the original template, in bytes.
+  pub start: usize,
+  pub end: usize,
+
+  /// Line and column of the start, both of them are 1-based.
+  pub line: usize,
+  pub column: usize,
+}
+
+impl Span {
+  pub fn new(template: &str, start: usize, end: usize) -> Self {
+    let start = start.min(template.len());
+    let end = end.clamp(start, template.len());
+
+    let before = template.get(..start).unwrap_or_default();
+    let line = before.matches('\n').count() + 1;
+    let line_start = before.rfind('\n').map_or(0, |index| index + 1);
+    let column = before[line_start..].chars().count() + 1;
+
+    Span {
+      start,
+      end,
+      line,
+      column,
+    }
+  }
+}
+
+/// Error while parsing or rendering a template.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DojangError {
+  pub message: String,
+
+  /// Name of the template (or the partial) where the error happened.
+  pub file_name: Option<String>,
+
+  /// Location of the tag which caused the error.
+  pub span: Option<Span>,
+}
+
+impl DojangError {
+  pub fn new(message: impl Into<String>) -> Self {
+    DojangError {
+      message: message.into(),
+      file_name: None,
+      span: None,
+    }
+  }
+
+  // Sets the location if the error is not located yet. Errors from partials are already located
+  // within the partial, which is more precise than the tag including it.
+  pub(crate) fn locate(
+    mut self,
+    file_name: Option<&str>,
+    template: &str,
+    start: usize,
+    end: usize,
+  ) -> Self {
+    if self.span.is_none() {
+      self.file_name = file_name.map(ToString::to_string);
+      self.span = Some(Span::new(template, start, end));
+    }
+    self
+  }
+
+  pub(crate) fn in_file(mut self, file_name: &str) -> Self {
+    if self.file_name.is_none() {
+      self.file_name = Some(file_name.to_string());
+    }
+    self
+  }
+}
+
+impl fmt::Display for DojangError {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match (&self.file_name, &self.span) {
+      (Some(file_name), Some(span)) => write!(
+        f,
+        "{} ({}:{}:{})",
+        self.message, file_name, span.line, span.column
+      ),
+      (None, Some(span)) => write!(f, "{} ({}:{})", self.message, span.line, span.column),
+      _ => write!(f, "{}", self.message),
+    }
+  }
+}
+
+impl std::error::Error for DojangError {}
+
+impl From<String> for DojangError {
+  fn from(message: String) -> Self {
+    DojangError::new(message)
+  }
+}
+
+impl From<&str> for DojangError {
+  fn from(message: &str) -> Self {
+    DojangError::new(message)
+  }
+}
+
+// Errors were plain strings before, callers propagating them with `?` keep compiling.
+impl From<DojangError> for String {
+  fn from(error: DojangError) -> Self {
+    error.to_string()
+  }
+}
+
+#[test]
+fn span_line_and_column() {
+  let template = "<html>\n  <%= a %>\n</html>";
+  let span = Span::new(template, 9, 17);
+  assert_eq!((span.line, span.column), (2, 3));
+  assert_eq!(&template[span.start..span.end], "<%= a %>");
+
+  let error = DojangError::new("ReferenceError: a is not defined").locate(
+    Some("index.html"),
+    template,
+    9,
+    17,
+  );
+  assert_eq!(
+    error.to_string(),
+    "ReferenceError: a is not defined (index.html:2:3)"
+  );
+}
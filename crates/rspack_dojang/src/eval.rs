@@ -1,8 +1,8 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, VecDeque};
 
 use serde_json::Value;
 
-use crate::expr::*;
+use crate::{context::GET_FUNCTION, expr::*};
 
 // Evaluate the parsed expression.
 #[derive(PartialEq, Debug, Clone)]
@@ -30,12 +30,16 @@ impl Eval {
     let mut operands: Vec<Vec<Expr>> = Vec::new();
     let mut operators = Vec::new();
 
+    // Filters are converted into function calls first.
+    expr.ops = apply_filters(std::mem::take(&mut expr.ops))?;
+
     // Wrap the expression with ().
     expr.ops.push(Op::ParenClose);
     expr.ops.insert(0, Op::ParenOpen);
 
     // Convert function calls into Operand::Function.
-    // The corresponding Expr::Function object will be stored at the map.
+    // The corresponding Expr::Function object will be stored at the map, in the order of the
+    // calls since a function can be called several times.
     let mut function_name_to_function = HashMap::new();
     Eval::handle_functions(&mut expr, &mut function_name_to_function)?;
 
@@ -45,7 +49,9 @@ impl Eval {
       match op {
         Op::Operand(operand) => match operand {
           Operand::Function(function_name) => {
-            let function = function_name_to_function.remove(&function_name);
+            let function = function_name_to_function
+              .get_mut(&function_name)
+              .and_then(VecDeque::pop_front);
             if function.is_none() {
               return Err(format!(
                 "Function {function_name:?} does not have matching entry"
@@ -114,7 +120,7 @@ impl Eval {
 
   fn handle_functions(
     expr: &mut Tokens,
-    function_name_to_function: &mut HashMap<String, Function>,
+    function_name_to_function: &mut HashMap<String, VecDeque<Function>>,
   ) -> Result<(), String> {
     let mut inst_index = 0;
     while inst_index < expr.ops.len() {
@@ -125,7 +131,30 @@ impl Eval {
           Some(Op::ParenOpen) => {
             // Then this is the start of the function.
             let function_tokens = get_tokens_belong_to_function(&mut expr.ops, inst_index)?;
-            let function = handle_function_tokens(function_tokens)?;
+            let mut function = handle_function_tokens(function_tokens)?;
+
+            // The returned value is accessed (e.g meta().title), which is handled by the
+            // predefined function that takes the returned value and the properties.
+            if let Some(Op::BracketOpen | Op::Dot) = expr.ops.get(inst_index) {
+              expr.ops.insert(
+                inst_index,
+                Op::Operand(Operand::Object(Object {
+                  name: GET_FUNCTION.to_string(),
+                })),
+              );
+              let accessor_tokens = get_tokens_belong_to_accessor(&mut expr.ops, inst_index)?;
+              let accessor = handle_accessor_tokens(accessor_tokens)?;
+
+              let mut params = vec![Eval {
+                expr: vec![Expr::Function(function)],
+              }];
+              params.extend(accessor.params);
+              function = Function {
+                name: GET_FUNCTION.to_string(),
+                params,
+                is_accessor: false,
+              };
+            }
 
             // Now insert the Operand::Function as a placeholder.
             expr.ops.insert(
@@ -135,7 +164,10 @@ impl Eval {
 
             // Finally, register this function. Operator::Function will be later
             // replaced by the Expr::Function.
-            function_name_to_function.insert(function.name.clone(), function);
+            function_name_to_function
+              .entry(function.name.clone())
+              .or_default()
+              .push_back(function);
           }
           Some(Op::BracketOpen | Op::Dot) => {
             // Then this is the start of the property accessor ([] or .)
@@ -148,7 +180,10 @@ impl Eval {
               Op::Operand(Operand::Function(accessor.name.clone())),
             );
 
-            function_name_to_function.insert(accessor.name.clone(), accessor);
+            function_name_to_function
+              .entry(accessor.name.clone())
+              .or_default()
+              .push_back(accessor);
           }
           _ => {}
         }
@@ -175,6 +210,99 @@ impl Eval {
   }
 }
 
+// Converts filters into function calls, e.g "a | f | g(b)" becomes "g(f((a)), b)". The filters
+// within parentheses, brackets and function parameters are converted separately.
+fn apply_filters(ops: Vec<Op>) -> Result<Vec<Op>, String> {
+  if !ops.contains(&Op::Pipe) {
+    return Ok(ops);
+  }
+
+  let mut segments: Vec<Vec<Op>> = vec![Vec::new()];
+  let mut ops = ops.into_iter();
+  while let Some(op) = ops.next() {
+    match op {
+      Op::ParenOpen | Op::BracketOpen => {
+        let mut opened_paren = 1;
+        let mut params: Vec<Vec<Op>> = vec![Vec::new()];
+        let mut close = None;
+        for op in ops.by_ref() {
+          match op {
+            Op::ParenOpen | Op::BracketOpen => opened_paren += 1,
+            Op::ParenClose | Op::BracketClose => {
+              opened_paren -= 1;
+              if opened_paren == 0 {
+                close = Some(op);
+                break;
+              }
+            }
+            Op::Comma if opened_paren == 1 => {
+              params.push(Vec::new());
+              continue;
+            }
+            _ => {}
+          }
+          params.last_mut().unwrap().push(op);
+        }
+
+        let Some(close) = close else {
+          return Err(format!("Missing closing ')' or ']' for {op:?}"));
+        };
+
+        let segment = segments.last_mut().unwrap();
+        segment.push(op);
+        for (index, param) in params.into_iter().enumerate() {
+          if index > 0 {
+            segment.push(Op::Comma);
+          }
+          segment.extend(apply_filters(param)?);
+        }
+        segment.push(close);
+      }
+      Op::Pipe => segments.push(Vec::new()),
+      op => segments.last_mut().unwrap().push(op),
+    }
+  }
+
+  let mut segments = segments.into_iter();
+  let mut value = segments.next().unwrap();
+  for filter in segments {
+    if value.is_empty() {
+      return Err(format!("Filter {filter:?} is not applied to any value"));
+    }
+
+    let (name, params) = match filter.as_slice() {
+      [Op::Operand(Operand::Object(name))] => (name.clone(), &[][..]),
+      [
+        Op::Operand(Operand::Object(name)),
+        Op::ParenOpen,
+        params @ ..,
+        Op::ParenClose,
+      ] => (name.clone(), params),
+      _ => {
+        return Err(format!(
+          "Filter must be a function name or a function call; Yours : {filter:?}"
+        ));
+      }
+    };
+
+    let mut call = vec![
+      Op::Operand(Operand::Object(name)),
+      Op::ParenOpen,
+      Op::ParenOpen,
+    ];
+    call.append(&mut value);
+    call.push(Op::ParenClose);
+    if !params.is_empty() {
+      call.push(Op::Comma);
+      call.extend_from_slice(params);
+    }
+    call.push(Op::ParenClose);
+    value = call;
+  }
+
+  Ok(value)
+}
+
 fn get_tokens_belong_to_function(
   ops: &mut Vec<Op>,
   mut inst_index: usize,
@@ -925,3 +1053,45 @@ fn get_tokens_belong_to_accessor_test() {
     expected
   );
 }
+
+#[test]
+fn apply_filters_test() {
+  let object = |name: &str| {
+    Op::Operand(Operand::Object(Object {
+      name: name.to_string(),
+    }))
+  };
+
+  // a | f | g(1)
+  let tokens = vec![
+    object("a"),
+    Op::Pipe,
+    object("f"),
+    Op::Pipe,
+    object("g"),
+    Op::ParenOpen,
+    Op::Operand(Operand::Value(Value::from(1))),
+    Op::ParenClose,
+  ];
+
+  // g(f((a)), 1)
+  let expected = vec![
+    object("g"),
+    Op::ParenOpen,
+    Op::ParenOpen,
+    object("f"),
+    Op::ParenOpen,
+    Op::ParenOpen,
+    object("a"),
+    Op::ParenClose,
+    Op::ParenClose,
+    Op::ParenClose,
+    Op::Comma,
+    Op::Operand(Operand::Value(Value::from(1))),
+    Op::ParenClose,
+  ];
+
+  assert_eq!(apply_filters(tokens).unwrap(), expected);
+  assert!(apply_filters(vec![Op::Pipe, object("f")]).is_err());
+  assert!(apply_filters(vec![object("a"), Op::Pipe, Op::Plus]).is_err());
+}
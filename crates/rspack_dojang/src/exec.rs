@@ -1,16 +1,18 @@
 use std::{
   collections::{BTreeMap, HashMap},
+  ops::Range,
   sync::Mutex,
 };
 
 use html_escape::encode_safe;
+use serde_json::Value;
 #[cfg(test)]
-use serde_json::{Value, json};
+use serde_json::json;
 
-use crate::{context::*, eval::*, expr::*};
+use crate::{context::*, dojang::DojangOptions, error::DojangError, eval::*, expr::*};
 
 type JumpTable = HashMap<usize, usize>;
-type JumpTables = (JumpTable, JumpTable);
+type JumpTables = (JumpTable, JumpTable, JumpTable);
 
 // The executer that renders the template.
 #[derive(Debug)]
@@ -24,6 +26,13 @@ pub struct Executer {
 
   // Mapping between index of "Break" and the corresponding For.
   break_table: HashMap<usize, usize>,
+
+  // Mapping between the End of a block and the corresponding Block.
+  block_table: HashMap<usize, usize>,
+
+  // [start, end) of the tag in the template that each instruction comes from. Used to locate
+  // the errors, which is empty if the Executer is not constructed from the template.
+  spans: Vec<Range<usize>>,
 }
 
 impl Executer {
@@ -36,11 +45,36 @@ impl Executer {
 
     insts.reverse();
 
-    let (jump_table, break_table) = Executer::compute_jump_table(&insts)?;
+    let (jump_table, break_table, block_table) = Executer::compute_jump_table(&insts)?;
+    Ok(Executer {
+      jump_table,
+      break_table,
+      block_table,
+      insts,
+      spans: Vec::new(),
+    })
+  }
+
+  // Parses the template and keeps where each instruction comes from, so that the errors of the
+  // template can be located.
+  pub fn compile(template: &str, options: DojangOptions) -> Result<Self, DojangError> {
+    let (parser, spans) = Parser::parse_with_spans(template, options)?;
+
+    let mut insts = Vec::new();
+    for (expr, span) in parser.parse_tree.into_iter().zip(&spans) {
+      insts.push(
+        convert_expr_to_eval(expr)
+          .map_err(|e| DojangError::from(e).locate(None, template, span.start, span.end))?,
+      );
+    }
+
+    let (jump_table, break_table, block_table) = Executer::compute_jump_table(&insts)?;
     Ok(Executer {
       jump_table,
       break_table,
+      block_table,
       insts,
+      spans,
     })
   }
 
@@ -49,6 +83,7 @@ impl Executer {
 
     let mut jump_table = HashMap::new();
     let mut break_table = HashMap::new();
+    let mut block_table = HashMap::new();
 
     let mut if_matching_ends = BTreeMap::new();
 
@@ -58,6 +93,9 @@ impl Executer {
     // Every '{' for loops (for, while). This will be used by break and continue.
     let mut loop_opened = Vec::new();
 
+    // Every '{' for blocks. Jumping out of a block is not allowed.
+    let mut block_opened = Vec::new();
+
     // Mapping between location of break/continue to the corresponding loop.
     let mut break_and_continue_pos = HashMap::new();
 
@@ -72,6 +110,10 @@ impl Executer {
           opened.push(inst_index);
           loop_opened.push(inst_index);
         }
+        Action::Block(_) => {
+          opened.push(inst_index);
+          block_opened.push(inst_index);
+        }
         Action::Else() => {
           if let Some(Action::If(_)) = insts.get(inst_index + 1) {
             inst_index += 1;
@@ -88,6 +130,10 @@ impl Executer {
               return Err("Cannot break/continue within non-loop context.".to_string());
             }
 
+            if block_opened.last() > loop_opened.last() {
+              return Err("Cannot break/continue out of a block.".to_string());
+            }
+
             break_and_continue_pos.insert(inst_index, *loop_opened.last().unwrap());
           }
         }
@@ -116,6 +162,10 @@ impl Executer {
               jump_table.insert(inst_index, open_index);
               loop_opened.pop();
             }
+            Action::Block(_) => {
+              block_table.insert(inst_index, open_index);
+              block_opened.pop();
+            }
             _ => {
               return Err(format!("Unknown action {insts:?} with closing parentheses"));
             }
@@ -187,7 +237,7 @@ impl Executer {
       }
     }
 
-    Ok((jump_table, break_table))
+    Ok((jump_table, break_table, block_table))
   }
 
   pub fn render(
@@ -197,16 +247,50 @@ impl Executer {
     functions: &HashMap<String, FunctionContainer>,
     template: &str,
     includes: &mut Mutex<HashMap<String, String>>,
-  ) -> Result<String, String> {
+  ) -> Result<String, DojangError> {
+    let mut inst_index = 0;
+    let rendered = self
+      .render_insts(
+        context,
+        templates,
+        functions,
+        template,
+        includes,
+        &mut inst_index,
+      )
+      .map_err(|e| match self.spans.get(inst_index) {
+        Some(span) => e.locate(context.file_name.as_deref(), template, span.start, span.end),
+        None => e,
+      })?;
+
+    if let Some(layout) = context.layout.take() {
+      return context.render_layout(&layout, rendered, templates, functions, includes);
+    }
+
+    Ok(rendered)
+  }
+
+  // Renders the instructions, where inst_index tells the instruction that failed on error.
+  fn render_insts(
+    &self,
+    context: &mut Context,
+    templates: &HashMap<String, (Executer, String)>,
+    functions: &HashMap<String, FunctionContainer>,
+    template: &str,
+    includes: &mut Mutex<HashMap<String, String>>,
+    inst_index: &mut usize,
+  ) -> Result<String, DojangError> {
     let mut rendered = String::new();
     let mut for_index_counter = HashMap::new();
 
     // Contains the range value of the for-loop.
     let mut for_range_container = HashMap::new();
 
-    let mut inst_index = 0;
-    while inst_index < self.insts.len() {
-      match &self.insts[inst_index] {
+    // Name of the blocks being rendered, along with the output before each block.
+    let mut block_stack = Vec::new();
+
+    while *inst_index < self.insts.len() {
+      match &self.insts[*inst_index] {
         Action::Show(show) => match show {
           Show::Html { start, end } => {
             rendered.push_str(&template[*start..*end]);
@@ -225,19 +309,22 @@ impl Executer {
         Action::If(eval) | Action::While(eval) => {
           // Jump only if the condition is false. Otherwise just go to next instruction.
           if !eval.run(context, templates, functions, includes)?.is_true() {
-            if let Some(next) = self.jump_table.get(&inst_index) {
-              inst_index = *next;
+            if let Some(next) = self.jump_table.get(inst_index) {
+              *inst_index = *next;
               continue;
             } else {
-              return Err(format!(
-                "Jump of the if statement is not set: {:?} index : {}",
-                self.insts, inst_index
-              ));
+              return Err(
+                format!(
+                  "Jump of the if statement is not set: {:?} index : {}",
+                  self.insts, inst_index
+                )
+                .into(),
+              );
             }
           }
         }
         Action::For(eval) => {
-          let iter_index = match for_index_counter.get(&inst_index) {
+          let iter_index = match for_index_counter.get(inst_index) {
             Some(index) => *index,
             None => 0usize,
           };
@@ -245,46 +332,61 @@ impl Executer {
           // The for loop is first encountered. Compute the "range" part.
           if iter_index == 0 {
             for_range_container.insert(
-              inst_index,
+              *inst_index,
               eval.run_for_range(context, templates, functions, includes)?,
             );
           }
 
-          for_index_counter.insert(inst_index, iter_index + 1);
+          for_index_counter.insert(*inst_index, iter_index + 1);
 
-          let range = &for_range_container[&inst_index];
+          let range = &for_range_container[inst_index];
           if !eval.run_for_loop(context, range, iter_index)? {
-            if let Some(next) = self.jump_table.get(&inst_index) {
+            if let Some(next) = self.jump_table.get(inst_index) {
               // Reset the index counter.
-              for_index_counter.insert(inst_index, 0);
+              for_index_counter.insert(*inst_index, 0);
 
-              inst_index = *next;
+              *inst_index = *next;
 
               continue;
             } else {
-              return Err(format!(
-                "Jump of the if statement is not set: {:?} index : {}",
-                self.insts, inst_index
-              ));
+              return Err(
+                format!(
+                  "Jump of the if statement is not set: {:?} index : {}",
+                  self.insts, inst_index
+                )
+                .into(),
+              );
             }
           }
         }
+        Action::Block(eval) => {
+          block_stack.push((get_block_name(eval)?, std::mem::take(&mut rendered)));
+        }
+        Action::End() if self.block_table.contains_key(inst_index) => {
+          let (name, before_block) = block_stack.pop().unwrap();
+          let content = std::mem::replace(&mut rendered, before_block);
+          let content = context.blocks.entry(name).or_insert(content);
+          // Blocks of a template using a layout are only shown by the layout.
+          if context.layout.is_none() {
+            rendered.push_str(content);
+          }
+        }
         Action::End() => {
-          if let Some(next) = self.jump_table.get(&inst_index) {
-            inst_index = *next;
+          if let Some(next) = self.jump_table.get(inst_index) {
+            *inst_index = *next;
             continue;
           }
         }
         Action::Do(eval) => match eval.get_keyword() {
           Some(Keyword::Break) => {
             // Reset the iter counter of the loop that we escape.
-            for_index_counter.insert(self.break_table[&inst_index], 0);
+            for_index_counter.insert(self.break_table[inst_index], 0);
 
-            inst_index = self.jump_table[&inst_index];
+            *inst_index = self.jump_table[inst_index];
             continue;
           }
           Some(Keyword::Continue) => {
-            inst_index = self.jump_table[&inst_index];
+            *inst_index = self.jump_table[inst_index];
             continue;
           }
           _ => {
@@ -294,7 +396,7 @@ impl Executer {
         Action::Else() => {}
       }
 
-      inst_index += 1;
+      *inst_index += 1;
     }
 
     Ok(rendered)
@@ -307,12 +409,28 @@ impl Executer {
     functions: &HashMap<String, FunctionContainer>,
     eval: &Eval,
     includes: &mut Mutex<HashMap<String, String>>,
-  ) -> Result<String, String> {
+  ) -> Result<String, DojangError> {
     if eval.is_empty() {
       return Ok(String::new());
     }
 
-    Ok(eval.run(context, templates, functions, includes)?.to_str())
+    // Undefined variables are null in non strict mode, which renders nothing like EJS.
+    match eval.run(context, templates, functions, includes)? {
+      Operand::Value(Value::Null) if !context.options.strict => Ok(String::new()),
+      operand => Ok(operand.to_str()),
+    }
+  }
+}
+
+// Blocks are named by an identifier (e.g "block title { ... }") or a string.
+fn get_block_name(eval: &Eval) -> Result<String, String> {
+  match eval.expr.as_slice() {
+    [Expr::Op(Op::Operand(Operand::Object(object)))] => Ok(object.name.clone()),
+    [Expr::Op(Op::Operand(Operand::Value(Value::String(name))))] => Ok(name.clone()),
+    _ => Err(format!(
+      "Block must be named by an identifier or a string; Yours : {:?}",
+      eval.expr
+    )),
   }
 }
 
@@ -322,7 +440,7 @@ fn pretty_print_insts(insts: &[Action<Eval>]) -> String {
   let mut padding = String::new();
   for action in insts {
     match action {
-      Action::If(_) | Action::While(_) | Action::For(_) => {
+      Action::If(_) | Action::While(_) | Action::For(_) | Action::Block(_) => {
         result.push_str(&format!("{padding}{action:?} \n"));
         padding.push(' ');
       }
@@ -350,6 +468,7 @@ fn convert_expr_to_eval(action: Action<Tokens>) -> Result<Action<Eval>, String>
     Action::While(expr) => Ok(Action::While(Eval::new(expr)?)),
     Action::Do(expr) => Ok(Action::Do(Eval::new(expr)?)),
     Action::For(expr) => Ok(Action::For(Eval::new(expr)?)),
+    Action::Block(expr) => Ok(Action::Block(Eval::new(expr)?)),
     Action::Else() => Ok(Action::Else()),
     Action::End() => Ok(Action::End()),
   }
@@ -1,6 +1,8 @@
+use std::ops::Range;
+
 use serde_json::Value;
 
-use crate::dojang::DojangOptions;
+use crate::{context::OBJECT_FUNCTION, dojang::DojangOptions, error::DojangError};
 
 #[derive(PartialEq, Debug, Clone)]
 pub enum Op {
@@ -24,6 +26,7 @@ pub enum Op {
   Divide,       // /
   Comma,        // ,
   Dot,          // .
+  Pipe,         // |
   Operand(Operand),
 }
 
@@ -60,6 +63,7 @@ pub enum Action<T> {
   If(T),    // If condition
   While(T), // Loop condition
   For(T),   // For condition
+  Block(T), // Block name
   Else(),   // Else
   End(),    // Closing }
   Do(T),
@@ -82,12 +86,27 @@ impl Action<Tokens> {
       Action::For(expr) => {
         expr.ops.push(op);
       }
+      Action::Block(expr) => {
+        expr.ops.push(op);
+      }
       Action::Do(expr) => {
         expr.ops.push(op);
       }
       _ => panic!("Cannot add op to {self:?}; op {op:?}"),
     }
   }
+
+  fn last_op_mut(&mut self) -> Option<&mut Op> {
+    match self {
+      Action::Show(Show::ExprEscaped(expr) | Show::ExprUnescaped(expr))
+      | Action::If(expr)
+      | Action::While(expr)
+      | Action::For(expr)
+      | Action::Block(expr)
+      | Action::Do(expr) => expr.ops.last_mut(),
+      _ => None,
+    }
+  }
 }
 
 #[derive(PartialEq, Debug)]
@@ -103,12 +122,22 @@ pub struct Parser {
 }
 
 impl Parser {
-  pub fn parse(template: &str) -> Result<Self, String> {
+  pub fn parse(template: &str) -> Result<Self, DojangError> {
     Parser::parse_with_options(template, DojangOptions::default())
   }
-  pub fn parse_with_options(mut template: &str, options: DojangOptions) -> Result<Self, String> {
+  pub fn parse_with_options(template: &str, options: DojangOptions) -> Result<Self, DojangError> {
+    Parser::parse_with_spans(template, options).map(|(parser, _)| parser)
+  }
+
+  // Parses the template along with the [start, end) of the tag that each action comes from.
+  pub fn parse_with_spans(
+    source: &str,
+    options: DojangOptions,
+  ) -> Result<(Self, Vec<Range<usize>>), DojangError> {
     let mut parse_tree = Vec::new();
+    let mut spans = Vec::new();
 
+    let mut template = source;
     let mut index_offset = 0;
     while !template.is_empty() {
       match template.find("<%") {
@@ -119,8 +148,10 @@ impl Parser {
               start: index_offset,
               end: index_offset + tag_start,
             }));
+            spans.push(index_offset..index_offset + tag_start);
           }
 
+          let tag_begin = index_offset + tag_start;
           let after_tag = &template[tag_start + 2..];
           index_offset += tag_start + 2;
 
@@ -153,6 +184,7 @@ impl Parser {
                     start: tag_start + 2,
                     end: tag_start + 3,
                   }));
+                  spans.push(tag_begin..index_offset + tag_end + 2);
 
                   template = &after_tag[tag_end + 2..];
                   index_offset += tag_end + 2;
@@ -164,12 +196,22 @@ impl Parser {
                 }
               };
 
-              Parser::parse_tag(tag_to_parse, &mut parse_tree)?;
+              let tag_span = tag_begin..index_offset + tag_end + 2;
+              Parser::parse_tag(tag_to_parse, &mut parse_tree).map_err(|e| {
+                DojangError::from(e).locate(None, source, tag_span.start, tag_span.end)
+              })?;
+              spans.resize(parse_tree.len(), tag_span);
+
               template = &after_tag[tag_end + 2..];
               index_offset += tag_end + 2;
             }
             _ => {
-              return Err("Unmatched %> tag found".to_string());
+              return Err(DojangError::new("Unmatched %> tag found").locate(
+                None,
+                source,
+                tag_begin,
+                tag_begin + 2,
+              ));
             }
           }
         }
@@ -178,18 +220,25 @@ impl Parser {
             start: index_offset,
             end: index_offset + template.len(),
           }));
+          spans.push(index_offset..index_offset + template.len());
           break;
         }
       }
     }
 
-    Ok(Parser { parse_tree })
+    Ok((Parser { parse_tree }, spans))
   }
 
   fn parse_tag(template: &str, parse_tree: &mut Vec<Action<Tokens>>) -> Result<(), String> {
     let mut current = 0;
     let mut token_begin = 0;
 
+    // Number of unclosed '(' (and object literals).
+    let mut opened_paren = 0;
+
+    // Whether each unclosed '{' is an object literal or a statement block.
+    let mut opened_brace = Vec::new();
+
     while current < template.len() {
       let current_char = template.chars().nth(current).unwrap();
 
@@ -216,6 +265,12 @@ impl Parser {
       {
         Parser::handle_operand(&template[token_begin..current], parse_tree);
 
+        if current_char == '(' {
+          opened_paren += 1;
+        } else if current_char == ')' && opened_paren > 0 {
+          opened_paren -= 1;
+        }
+
         match Parser::handle_operator(template, current, parse_tree.last_mut().unwrap()) {
           Ok(end_of_op) => {
             current = end_of_op;
@@ -230,14 +285,47 @@ impl Parser {
         let token = &template[token_begin..current];
         Parser::handle_token(token, parse_tree);
 
+        token_begin = current + 1;
+      } else if current_char == '{' && opened_paren > 0 {
+        // Object literal within a function call, e.g include("a.html", { title: "A" }).
+        // This is converted to a call of the predefined object function.
+        Parser::handle_operand(&template[token_begin..current], parse_tree);
+        let action = parse_tree.last_mut().unwrap();
+        action.add_op(Op::Operand(Operand::Object(Object {
+          name: OBJECT_FUNCTION.to_string(),
+        })));
+        action.add_op(Op::ParenOpen);
+        opened_paren += 1;
+        opened_brace.push(true);
         token_begin = current + 1;
       } else if current_char == '{' {
         Parser::handle_token(&template[token_begin..current], parse_tree);
         parse_tree.push(Action::Do(Tokens { ops: Vec::new() }));
+        opened_brace.push(false);
+        token_begin = current + 1;
+      } else if current_char == '}' && opened_brace.last() == Some(&true) {
+        Parser::handle_operand(&template[token_begin..current], parse_tree);
+        parse_tree.last_mut().unwrap().add_op(Op::ParenClose);
+        opened_paren -= 1;
+        opened_brace.pop();
         token_begin = current + 1;
       } else if current_char == '}' {
         Parser::handle_token(&template[token_begin..current], parse_tree);
         parse_tree.push(Action::End());
+        opened_brace.pop();
+        token_begin = current + 1;
+      } else if current_char == ':' && opened_brace.last() == Some(&true) {
+        // Keys of object literals are either identifiers or strings.
+        Parser::handle_operand(&template[token_begin..current], parse_tree);
+        let action = parse_tree.last_mut().unwrap();
+        if let Some(op) = action.last_op_mut()
+          && let Op::Operand(Operand::Object(object)) = op
+        {
+          *op = Op::Operand(Operand::Value(Value::from(std::mem::take(
+            &mut object.name,
+          ))));
+        }
+        action.add_op(Op::Comma);
         token_begin = current + 1;
       } else if current_char == ';' {
         Parser::handle_token(&template[token_begin..current], parse_tree);
@@ -350,6 +438,7 @@ impl Parser {
       '/' => action.add_op(Op::Divide),
       ',' => action.add_op(Op::Comma),
       '.' => action.add_op(Op::Dot),
+      '|' => action.add_op(Op::Pipe),
       c => {
         return Err(format!("Unknown operator at '{template}', unknown : {c}"));
       }
@@ -367,6 +456,8 @@ impl Parser {
       parse_tree.push(Action::For(Tokens { ops: Vec::new() }));
     } else if token == "else" {
       parse_tree.push(Action::Else());
+    } else if token == "block" {
+      parse_tree.push(Action::Block(Tokens { ops: Vec::new() }));
     } else {
       Parser::handle_operand(token, parse_tree);
     }
@@ -1,6 +1,6 @@
 // List of traits needed for wrapping regular functions to FunctionContainer.
 
-use serde_json::Value;
+use serde_json::{Map, Value};
 
 use crate::expr::*;
 
@@ -87,3 +87,18 @@ impl From<Value> for Operand {
     Operand::Value(val)
   }
 }
+
+impl From<Operand> for Map<String, Value> {
+  fn from(op: Operand) -> Self {
+    if let Operand::Value(Value::Object(map)) = op {
+      return map;
+    }
+    Map::new()
+  }
+}
+
+impl From<Map<String, Value>> for Operand {
+  fn from(map: Map<String, Value>) -> Self {
+    Operand::Value(Value::Object(map))
+  }
+}
@@ -119,15 +119,64 @@
 //! <%= a = 3 %>
 //! <% a %><%# This will print 3 %>
 //! ```
+//!
+//! * Object literals can be used as function parameters (e.g `f({ a: 1, b: c })`).
+//! * Properties of the value returned by a function can be accessed (e.g `f().a`).
+//!
+//! # Partials and Layouts
+//!
+//! `include` renders another template file with the data of the current template, overridden by
+//! the optional parameters. Relative file names are resolved against the including template, and
+//! the files are read through the `TemplateLoader` of `Dojang` (local file system by default).
+//!
+//! ```ejs
+//! <%- include("./item.html", { name: "dojang" }) %>
+//! ```
+//!
+//! `layout` renders the template inside of the given layout, where the output of the template is
+//! provided as `body`. Blocks defined by the template replace the blocks of the same name in the
+//! layout.
+//!
+//! ```ejs
+//! <%# page.html %>
+//! <% layout("./layout.html") %>
+//! <% block title { %>My Page<% } %>
+//! <p>Content</p>
+//!
+//! <%# layout.html %>
+//! <title><% block title { %>Default Title<% } %></title>
+//! <body><%- body %></body>
+//! ```
+//!
+//! # Filters
+//!
+//! Filters are functions chained with pipes. `a | f(b)` is same as `f(a, b)`. Along with the
+//! registered functions, `escape`, `escape_attr`, `upper`, `lower`, `trim`, `default` and `join`
+//! are provided.
+//!
+//! ```ejs
+//! <%- title | trim | default("Untitled") | escape %>
+//! ```
+//!
+//! # Errors
+//!
+//! Parsing and rendering errors are reported as `DojangError`, which tells the template (or the
+//! partial) and the location of the tag that failed. `DojangError` converts into `String`, the error
+//! type returned before. Undefined variables are reported as `ReferenceError`s, unless `strict` of
+//! `DojangOptions` is turned off, in which case they are treated as null and render nothing.
 mod context;
 mod default_functions;
 pub mod dojang;
+mod error;
 mod eval;
 mod exec;
 mod expr;
 pub mod func_helper;
+mod loader;
 pub use crate::{
   context::{Context, FunctionContainer},
   dojang::Dojang,
+  error::{DojangError, Span},
   expr::Operand,
+  loader::{FileLoader, TemplateLoader},
 };
@@ -0,0 +1,40 @@
+use std::{fmt, path::Path};
+
+/// Loads the partials used by `include` and `layout`.
+///
+/// Partials are loaded when they are first used while rendering, so the loader has to be
+/// synchronous.
+pub trait TemplateLoader: fmt::Debug + Send + Sync {
+  /// Resolves the file name of a partial. `issuer` is the name of the template that references
+  /// the partial, if any.
+  fn resolve(&self, request: &str, issuer: Option<&str>) -> String;
+
+  /// Reads the content of a resolved partial.
+  fn load(&self, file_name: &str) -> Result<String, String>;
+}
+
+/// Loads partials from the local file system. Relative requests are resolved against the
+/// directory of the template that references them.
+#[derive(Debug, Default)]
+pub struct FileLoader;
+
+impl TemplateLoader for FileLoader {
+  fn resolve(&self, request: &str, issuer: Option<&str>) -> String {
+    let request_path = Path::new(request);
+    if request_path.is_absolute() {
+      return request.to_string();
+    }
+
+    match issuer.and_then(|issuer| Path::new(issuer).parent()) {
+      Some(dir) if !dir.as_os_str().is_empty() => {
+        dir.join(request_path).to_string_lossy().to_string()
+      }
+      _ => request.to_string(),
+    }
+  }
+
+  fn load(&self, file_name: &str) -> Result<String, String> {
+    let file_content = std::fs::read(file_name).map_err(|e| e.to_string())?;
+    Ok(String::from_utf8_lossy(&file_content).to_string())
+  }
+}
@@ -1,17 +1,25 @@
+use std::sync::Mutex;
+
 use cow_utils::CowUtils;
 use derive_more::Debug;
 use futures::future::BoxFuture;
 use itertools::Itertools;
-use rspack_collections::{Identifier, IdentifierMap};
+use rspack_collections::{Identifier, IdentifierMap, IdentifierSet};
 use rspack_core::{
-  CircularModulesInfo, Compilation, CompilationOptimizeModules, DependencyType, ModuleIdentifier,
-  Plugin,
+  AssetInfo, CircularModulesInfo, Compilation, CompilationAsset, CompilationOptimizeModules,
+  CompilationProcessAssets, DependencyType, ModuleIdentifier, Plugin,
+  rspack_sources::{RawStringSource, SourceExt},
 };
-use rspack_error::{Diagnostic, Result};
+use rspack_error::{Diagnostic, Result, error};
 use rspack_hook::{plugin, plugin_hook};
 use rspack_regex::RspackRegex;
 use rustc_hash::FxHashSet as HashSet;
 
+use crate::components::{
+  BaselineComponent, CircularDependencyComponentsOptions, ComponentStatus, ComponentsBaseline,
+  find_components, shortest_cycle,
+};
+
 struct CycleDetector<'a> {
   module_map: &'a IdentifierMap<GraphModule>,
 }
@@ -123,7 +131,6 @@ impl AggregatedDependency {
 
 #[derive(Debug)]
 struct GraphModule {
-  #[allow(unused)]
   id: Identifier,
   is_source: bool,
   dependencies: IdentifierMap<AggregatedDependency>,
@@ -226,8 +233,14 @@ pub struct CircularDependencyRspackPluginOptions {
   /// Connections are represented as `[from, to]`, where each entry must be an
   /// exact match for the module path.
   pub ignored_connections: Option<Vec<CircularDependencyIgnoredConnection>>,
+  /// Groups cycles into strongly connected components and reports one
+  /// diagnostic per component, with its size and shortest cycle, instead of
+  /// every cycle reachable from each entrypoint.
+  pub components: Option<CircularDependencyComponentsOptions>,
   /// Handler function called for every detected cycle. Providing this handler
   /// overrides the default behavior of adding diagnostics to the compilation.
+  /// With `components`, it's called with an empty entrypoint and the shortest
+  /// cycle of each component.
   #[debug(skip)]
   pub on_detected: Option<CycleHandlerFn>,
   #[debug(skip)]
@@ -243,11 +256,13 @@ pub struct CircularDependencyRspackPluginOptions {
 #[derive(Debug)]
 pub struct CircularDependencyRspackPlugin {
   options: CircularDependencyRspackPluginOptions,
+  /// Components found by the last compilation, emitted as the baseline asset.
+  components_report: Mutex<Option<ComponentsBaseline>>,
 }
 
 impl CircularDependencyRspackPlugin {
   pub fn new(options: CircularDependencyRspackPluginOptions) -> Self {
-    Self::new_inner(options)
+    Self::new_inner(options, Default::default())
   }
 
   fn is_ignored_module(&self, name: &str) -> bool {
@@ -266,6 +281,153 @@ impl CircularDependencyRspackPlugin {
     }
   }
 
+  fn is_excluded_module(&self, module_id: &ModuleIdentifier, compilation: &Compilation) -> bool {
+    compilation
+      .module_by_identifier(module_id)
+      .and_then(|m| m.as_normal_module())
+      .is_some_and(|module| self.is_ignored_module(module.resource_resolved_data().resource()))
+  }
+
+  /// The synchronous edges between source modules that can form a reported
+  /// cycle, with excluded modules and ignored connections removed up front so
+  /// they also split the components they would otherwise connect.
+  fn build_component_edges(
+    &self,
+    module_map: &IdentifierMap<GraphModule>,
+    compilation: &Compilation,
+  ) -> IdentifierMap<Vec<ModuleIdentifier>> {
+    let included = module_map
+      .values()
+      .filter(|module| module.is_source && !self.is_excluded_module(&module.id, compilation))
+      .map(|module| module.id)
+      .collect::<IdentifierSet>();
+
+    included
+      .iter()
+      .map(|module_id| {
+        let mut targets = module_map[module_id]
+          .dependencies
+          .iter()
+          .filter(|(target_id, dependency)| {
+            included.contains(*target_id)
+              && !dependency.is_asynchronous_only()
+              && !self.is_ignored_connection(module_id, target_id)
+          })
+          .map(|(target_id, _)| *target_id)
+          .collect::<Vec<_>>();
+        targets.sort_unstable();
+        (*module_id, targets)
+      })
+      .collect()
+  }
+
+  async fn read_baseline(
+    &self,
+    baseline: &str,
+    compilation: &Compilation,
+    diagnostics: &mut Vec<Diagnostic>,
+  ) -> Result<ComponentsBaseline> {
+    let path = compilation.options.context.as_path().join(baseline);
+    match compilation.input_filesystem.read_to_string(&path).await {
+      Ok(content) => ComponentsBaseline::parse(&content)
+        .map_err(|e| error!("Failed to parse circular dependency baseline {path}: {e}")),
+      Err(e) => {
+        // Without a baseline every component is new, which is what a first
+        // build is expected to report.
+        diagnostics.push(Diagnostic::warn(
+          "Circular Dependency".to_string(),
+          format!("Failed to read circular dependency baseline {path}: {e}"),
+        ));
+        Ok(Default::default())
+      }
+    }
+  }
+
+  async fn report_components(
+    &self,
+    options: &CircularDependencyComponentsOptions,
+    module_map: &IdentifierMap<GraphModule>,
+    compilation: &Compilation,
+    diagnostics: &mut Vec<Diagnostic>,
+  ) -> Result<()> {
+    let baseline = match &options.baseline {
+      Some(baseline) => Some(
+        self
+          .read_baseline(baseline, compilation, diagnostics)
+          .await?,
+      ),
+      None => None,
+    };
+
+    let edges = self.build_component_edges(module_map, compilation);
+    let mut report = ComponentsBaseline::default();
+    for component in find_components(&edges) {
+      let cycle = shortest_cycle(&edges, &component);
+      let mut modules = readable_identifiers(compilation, &component);
+      modules.sort();
+      let readable_cycle = readable_identifiers(compilation, &cycle);
+
+      if let Some(callback) = &self.options.on_detected {
+        callback(
+          String::new(),
+          cycle.iter().map(ToString::to_string).collect(),
+        )
+        .await?;
+      } else {
+        let status = baseline.as_ref().map(|baseline| baseline.status(&modules));
+        diagnostics.push(self.component_diagnostic(modules.len(), &readable_cycle, status));
+      }
+
+      report.components.push(BaselineComponent {
+        size: modules.len(),
+        modules,
+        cycle: readable_cycle,
+      });
+    }
+
+    report.components.sort_by(|a, b| a.modules.cmp(&b.modules));
+    *self.components_report.lock().expect("should get lock") = Some(report);
+    Ok(())
+  }
+
+  fn component_diagnostic(
+    &self,
+    size: usize,
+    cycle: &[String],
+    status: Option<ComponentStatus>,
+  ) -> Diagnostic {
+    let mut message = format!(
+      "Circular dependency component of {size} {} detected:\n {}",
+      if size == 1 { "module" } else { "modules" },
+      cycle.iter().join(" -> ")
+    );
+    let is_regression = match status {
+      None => true,
+      Some(ComponentStatus::Known) => {
+        message.push_str("\nThe component is recorded in the baseline.");
+        false
+      }
+      Some(ComponentStatus::New) => {
+        message.push_str("\nThe component is not recorded in the baseline.");
+        true
+      }
+      Some(ComponentStatus::Grown { added }) => {
+        message.push_str(&format!(
+          "\nThe component grew since the baseline, new modules:\n {}",
+          added.iter().join("\n ")
+        ));
+        true
+      }
+    };
+
+    let diagnostic_factory = if self.options.fail_on_error && is_regression {
+      Diagnostic::error
+    } else {
+      Diagnostic::warn
+    };
+    diagnostic_factory("Circular Dependency".to_string(), message)
+  }
+
   fn is_cycle_ignored(
     &self,
     module_map: &IdentifierMap<GraphModule>,
@@ -327,28 +489,7 @@ impl CircularDependencyRspackPlugin {
       Diagnostic::warn
     };
 
-    let cwd = std::env::current_dir()
-      .expect("cwd should be available")
-      .to_string_lossy()
-      .to_string();
-
-    // remove the root path here.
-    let cycle_without_root: Vec<String> = cycle
-      .iter()
-      .filter_map(|module_identifier| {
-        compilation
-          .module_by_identifier(module_identifier)
-          .map(|module| {
-            module
-              .readable_identifier(&compilation.options.context)
-              .to_string()
-              .cow_replace(&cwd, "")
-              .trim_start_matches('/')
-              .trim_start_matches('\\')
-              .to_string()
-          })
-      })
-      .collect();
+    let cycle_without_root = readable_identifiers(compilation, &cycle);
 
     diagnostics.push(diagnostic_factory(
       "Circular Dependency".to_string(),
@@ -361,6 +502,31 @@ impl CircularDependencyRspackPlugin {
   }
 }
 
+/// Readable identifiers of `modules`, with the cwd removed.
+fn readable_identifiers(compilation: &Compilation, modules: &[ModuleIdentifier]) -> Vec<String> {
+  let cwd = std::env::current_dir()
+    .expect("cwd should be available")
+    .to_string_lossy()
+    .to_string();
+
+  modules
+    .iter()
+    .filter_map(|module_identifier| {
+      compilation
+        .module_by_identifier(module_identifier)
+        .map(|module| {
+          module
+            .readable_identifier(&compilation.options.context)
+            .to_string()
+            .cow_replace(&cwd, "")
+            .trim_start_matches('/')
+            .trim_start_matches('\\')
+            .to_string()
+        })
+    })
+    .collect()
+}
+
 #[plugin_hook(CompilationOptimizeModules for CircularDependencyRspackPlugin)]
 async fn optimize_modules(
   &self,
@@ -373,6 +539,16 @@ async fn optimize_modules(
   };
 
   let module_map = build_module_map(compilation);
+  if let Some(components) = &self.options.components {
+    self
+      .report_components(components, &module_map, compilation, diagnostics)
+      .await?;
+    if let Some(on_end) = &self.options.on_end {
+      on_end().await?;
+    }
+    return Ok(None);
+  }
+
   let mut detector = CycleDetector::new(&module_map);
   for (entrypoint_name, chunk_group_key) in
     compilation.build_chunk_graph_artifact.entrypoints.clone()
@@ -416,12 +592,45 @@ async fn optimize_modules(
   Ok(None)
 }
 
+#[plugin_hook(CompilationProcessAssets for CircularDependencyRspackPlugin, stage = Compilation::PROCESS_ASSETS_STAGE_ADDITIONAL)]
+async fn process_assets(&self, compilation: &mut Compilation) -> Result<()> {
+  let Some(filename) = self
+    .options
+    .components
+    .as_ref()
+    .and_then(|components| components.filename.as_ref())
+  else {
+    return Ok(());
+  };
+  let Some(report) = self
+    .components_report
+    .lock()
+    .expect("should get lock")
+    .take()
+  else {
+    return Ok(());
+  };
+
+  compilation.emit_asset(
+    filename.clone(),
+    CompilationAsset::new(
+      Some(RawStringSource::from(report.to_json_string()).boxed()),
+      AssetInfo::default(),
+    ),
+  );
+  Ok(())
+}
+
 impl Plugin for CircularDependencyRspackPlugin {
   fn apply(&self, ctx: &mut rspack_core::ApplyContext<'_>) -> Result<()> {
     ctx
       .compilation_hooks
       .optimize_modules
       .tap(optimize_modules::new(self));
+    ctx
+      .compilation_hooks
+      .process_assets
+      .tap(process_assets::new(self));
     Ok(())
   }
 }
@@ -0,0 +1,332 @@
+use std::collections::VecDeque;
+
+use rspack_collections::{IdentifierMap, IdentifierSet};
+use rspack_core::ModuleIdentifier;
+use rustc_hash::FxHashSet as HashSet;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Default)]
+pub struct CircularDependencyComponentsOptions {
+  /// Path of a baseline written to `filename` by a previous build, resolved
+  /// against the context. Components recorded in it are reported as
+  /// warnings, only new components and components that grew are reported
+  /// with the `fail_on_error` severity.
+  pub baseline: Option<String>,
+  /// Emits the components found by this build as a JSON asset, which can be
+  /// committed and used as the `baseline` of later builds.
+  pub filename: Option<String>,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub(crate) struct ComponentsBaseline {
+  pub components: Vec<BaselineComponent>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct BaselineComponent {
+  pub size: usize,
+  /// Readable module identifiers, sorted.
+  pub modules: Vec<String>,
+  /// Shortest cycle of the component, starting and ending at the same module.
+  pub cycle: Vec<String>,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub(crate) enum ComponentStatus {
+  Known,
+  New,
+  /// The component contains modules not in the overlapping baseline components.
+  Grown {
+    added: Vec<String>,
+  },
+}
+
+impl ComponentsBaseline {
+  pub fn parse(content: &str) -> serde_json::Result<Self> {
+    serde_json::from_str(content)
+  }
+
+  pub fn to_json_string(&self) -> String {
+    serde_json::to_string_pretty(self).expect("baseline should be serializable")
+  }
+
+  /// Compares a component against every baseline component it overlaps, so a
+  /// component that was split or merged since the baseline only counts as
+  /// grown when it gained modules.
+  pub fn status(&self, modules: &[String]) -> ComponentStatus {
+    let current = modules.iter().map(String::as_str).collect::<HashSet<_>>();
+    let known = self
+      .components
+      .iter()
+      .filter(|component| {
+        component
+          .modules
+          .iter()
+          .any(|module| current.contains(module.as_str()))
+      })
+      .flat_map(|component| component.modules.iter().map(String::as_str))
+      .collect::<HashSet<_>>();
+
+    if known.is_empty() {
+      return ComponentStatus::New;
+    }
+    let added = modules
+      .iter()
+      .filter(|module| !known.contains(module.as_str()))
+      .cloned()
+      .collect::<Vec<_>>();
+    if added.is_empty() {
+      ComponentStatus::Known
+    } else {
+      ComponentStatus::Grown { added }
+    }
+  }
+}
+
+struct Frame {
+  module: ModuleIdentifier,
+  next_edge: usize,
+}
+
+/// Returns the strongly connected components that contain a cycle, with
+/// Tarjan's algorithm: components with more than one module, and single
+/// modules that import themselves. `edges` must only point to modules that
+/// are keys of it.
+pub(crate) fn find_components(
+  edges: &IdentifierMap<Vec<ModuleIdentifier>>,
+) -> Vec<Vec<ModuleIdentifier>> {
+  // sort to keep output stable
+  let mut modules = edges.keys().copied().collect::<Vec<_>>();
+  modules.sort_unstable();
+
+  let mut next_index = 0usize;
+  let mut indexes: IdentifierMap<(usize, usize)> = IdentifierMap::default();
+  let mut stack = vec![];
+  let mut on_stack = IdentifierSet::default();
+  let mut components = vec![];
+
+  for root in modules {
+    if indexes.contains_key(&root) {
+      continue;
+    }
+    indexes.insert(root, (next_index, next_index));
+    next_index += 1;
+    stack.push(root);
+    on_stack.insert(root);
+    let mut visit_stack = vec![Frame {
+      module: root,
+      next_edge: 0,
+    }];
+
+    while let Some(frame) = visit_stack.last_mut() {
+      let module = frame.module;
+      if let Some(target) = edges[&module].get(frame.next_edge).copied() {
+        frame.next_edge += 1;
+        match indexes.get(&target) {
+          None => {
+            indexes.insert(target, (next_index, next_index));
+            next_index += 1;
+            stack.push(target);
+            on_stack.insert(target);
+            visit_stack.push(Frame {
+              module: target,
+              next_edge: 0,
+            });
+          }
+          Some(&(target_index, _)) if on_stack.contains(&target) => {
+            let entry = indexes.get_mut(&module).expect("should be indexed");
+            entry.1 = entry.1.min(target_index);
+          }
+          Some(_) => {}
+        }
+        continue;
+      }
+
+      visit_stack.pop();
+      let (index, low_link) = indexes[&module];
+      if let Some(parent) = visit_stack.last() {
+        let entry = indexes.get_mut(&parent.module).expect("should be indexed");
+        entry.1 = entry.1.min(low_link);
+      }
+      if index == low_link {
+        let mut component = vec![];
+        loop {
+          let current = stack.pop().expect("root should be on the stack");
+          on_stack.remove(&current);
+          component.push(current);
+          if current == module {
+            break;
+          }
+        }
+        if component.len() > 1 || edges[&module].contains(&module) {
+          component.sort_unstable();
+          components.push(component);
+        }
+      }
+    }
+  }
+  components
+}
+
+/// Returns the shortest cycle inside `component`, starting and ending at the
+/// same module. Ties are broken by the module order of `component`.
+pub(crate) fn shortest_cycle(
+  edges: &IdentifierMap<Vec<ModuleIdentifier>>,
+  component: &[ModuleIdentifier],
+) -> Vec<ModuleIdentifier> {
+  let members = component.iter().copied().collect::<IdentifierSet>();
+  let mut shortest: Option<Vec<ModuleIdentifier>> = None;
+
+  for &start in component {
+    // a cycle through `start` can't be shorter than two edges
+    if shortest.as_ref().is_some_and(|cycle| cycle.len() <= 3) {
+      break;
+    }
+    let mut parents: IdentifierMap<ModuleIdentifier> = IdentifierMap::default();
+    let mut queue = VecDeque::from([(start, 0usize)]);
+    let mut found = None;
+    while let Some((module, depth)) = queue.pop_front() {
+      // paths this long can't beat the shortest cycle found so far
+      if shortest
+        .as_ref()
+        .is_some_and(|cycle| depth + 2 >= cycle.len())
+      {
+        break;
+      }
+      if let Some(targets) = edges.get(&module) {
+        if targets.contains(&start) {
+          found = Some(module);
+          break;
+        }
+        for target in targets {
+          if members.contains(target) && *target != start && !parents.contains_key(target) {
+            parents.insert(*target, module);
+            queue.push_back((*target, depth + 1));
+          }
+        }
+      }
+    }
+
+    if let Some(mut module) = found {
+      let mut path = vec![];
+      while module != start {
+        path.push(module);
+        module = parents[&module];
+      }
+      path.reverse();
+      let mut cycle = vec![start];
+      cycle.extend(path);
+      cycle.push(start);
+      shortest = Some(cycle);
+    }
+  }
+  shortest.unwrap_or_default()
+}
+
+#[cfg(test)]
+mod test {
+  use super::*;
+
+  fn edges(graph: &[(&str, &[&str])]) -> IdentifierMap<Vec<ModuleIdentifier>> {
+    graph
+      .iter()
+      .map(|(module, targets)| {
+        (
+          ModuleIdentifier::from(*module),
+          targets
+            .iter()
+            .map(|target| ModuleIdentifier::from(*target))
+            .collect(),
+        )
+      })
+      .collect()
+  }
+
+  fn names(modules: &[ModuleIdentifier]) -> Vec<&str> {
+    modules.iter().map(|module| module.as_str()).collect()
+  }
+
+  #[test]
+  fn should_find_components_with_cycles() {
+    let edges = edges(&[
+      ("a", &["b"]),
+      ("b", &["c"]),
+      ("c", &["a", "d"]),
+      ("d", &["e"]),
+      ("e", &[]),
+      ("f", &["f"]),
+    ]);
+    let components = find_components(&edges);
+    assert_eq!(
+      components
+        .iter()
+        .map(|component| names(component))
+        .collect::<Vec<_>>(),
+      [vec!["a", "b", "c"], vec!["f"]]
+    );
+  }
+
+  #[test]
+  fn should_find_the_shortest_cycle() {
+    // a -> b -> c -> d -> a and the shortcut c -> a
+    let edges = edges(&[
+      ("a", &["b"]),
+      ("b", &["c"]),
+      ("c", &["a", "d"]),
+      ("d", &["a"]),
+    ]);
+    let components = find_components(&edges);
+    assert_eq!(components.len(), 1);
+    assert_eq!(
+      names(&shortest_cycle(&edges, &components[0])),
+      ["a", "b", "c", "a"]
+    );
+
+    let edges = self::edges(&[("a", &["a", "b"]), ("b", &[])]);
+    let components = find_components(&edges);
+    assert_eq!(names(&shortest_cycle(&edges, &components[0])), ["a", "a"]);
+  }
+
+  fn baseline(components: &[&[&str]]) -> ComponentsBaseline {
+    ComponentsBaseline {
+      components: components
+        .iter()
+        .map(|modules| BaselineComponent {
+          size: modules.len(),
+          modules: modules.iter().map(ToString::to_string).collect(),
+          cycle: vec![],
+        })
+        .collect(),
+    }
+  }
+
+  fn modules(modules: &[&str]) -> Vec<String> {
+    modules.iter().map(ToString::to_string).collect()
+  }
+
+  #[test]
+  fn should_compare_components_with_the_baseline() {
+    let baseline = baseline(&[&["a", "b"], &["c", "d"]]);
+    assert_eq!(
+      baseline.status(&modules(&["a", "b"])),
+      ComponentStatus::Known
+    );
+    // merged components are known as long as no module was added
+    assert_eq!(
+      baseline.status(&modules(&["a", "b", "c", "d"])),
+      ComponentStatus::Known
+    );
+    assert_eq!(baseline.status(&modules(&["e", "f"])), ComponentStatus::New);
+    assert_eq!(
+      baseline.status(&modules(&["a", "b", "e"])),
+      ComponentStatus::Grown {
+        added: modules(&["e"])
+      }
+    );
+
+    let parsed = ComponentsBaseline::parse(&baseline.to_json_string())
+      .expect("should parse the written baseline");
+    assert_eq!(parsed.components.len(), 2);
+    assert_eq!(parsed.components[1].modules, ["c", "d"]);
+  }
+}
@@ -1,6 +1,7 @@
 mod circular_check_rspack_plugin;
 mod circular_dependency_rspack_plugin;
 mod circular_modules_info_plugin;
+mod components;
 
 pub use circular_check_rspack_plugin::{
   CircularCheckHandlerFn, CircularCheckRspackPlugin, CircularCheckRspackPluginOptions,
@@ -11,3 +12,4 @@ pub use circular_dependency_rspack_plugin::{
   CycleHandlerFn,
 };
 pub use circular_modules_info_plugin::CircularModulesInfoPlugin;
+pub use components::CircularDependencyComponentsOptions;
@@ -1,5 +1,6 @@
 use std::{
   borrow::Cow,
+  collections::BTreeMap,
   fmt::Display,
   hash::Hash,
   ops::DerefMut,
@@ -16,7 +17,7 @@ use rspack_core::{
   AssetInfo, AssetInfoRelated, CacheOptions, Compilation, CompilationAsset, CompilationLogger,
   CompilationProcessAssets, Filename, GlobMatchOptions, Logger, PathData, Plugin,
   escape_glob_pattern, extract_glob_base_dir, find_files_by_glob,
-  rspack_sources::{BoxSource, RawBufferSource, SourceExt},
+  rspack_sources::{BoxSource, RawBufferSource, RawStringSource, SourceExt},
   unescape_glob_path,
 };
 use rspack_error::{Diagnostic, Error, Result, ToStringResultToRspackResultExt};
@@ -28,7 +29,7 @@ use sugar_path::SugarPath;
 
 mod pattern_cache;
 
-use pattern_cache::CachedPatternResult;
+use pattern_cache::{CachedFileResult, CachedPatternResult, FileCache};
 
 #[derive(Debug)]
 pub struct CopyRspackPluginOptions {
@@ -106,7 +107,12 @@ pub struct CopyPattern {
   pub copy_permissions: Option<bool>,
   #[debug(skip)]
   pub transform_fn: Option<TransformerFn>,
+  /// Reuse the result of `transform_fn` for files whose content is unchanged.
   pub cache: Option<bool>,
+  /// Emits a JSON asset with this name, mapping the source path of every
+  /// copied file, relative to the pattern context, to its emitted filename.
+  /// Patterns with the same `manifest` share one asset.
+  pub manifest: Option<String>,
 }
 
 #[derive(Debug, Clone)]
@@ -133,12 +139,14 @@ pub struct RunPatternResult {
 pub struct CopyRspackPlugin {
   pub patterns: Vec<CopyPattern>,
   pattern_cache: Mutex<Vec<Option<CachedPatternResult>>>,
+  file_cache: Vec<FileCache>,
 }
 
 struct PendingPattern<'a> {
   index: usize,
   pattern: &'a CopyPattern,
   cacheable: bool,
+  file_cache: Option<&'a FileCache>,
   file_dependencies: FxDashSet<PathBuf>,
   context_dependencies: FxDashSet<PathBuf>,
   diagnostics: Arc<Mutex<Vec<Diagnostic>>>,
@@ -175,7 +183,8 @@ fn order_pattern_results<T>(
 impl CopyRspackPlugin {
   pub fn new(patterns: Vec<CopyPattern>) -> Self {
     let pattern_cache = Mutex::new(vec![None; patterns.len()]);
-    Self::new_inner(patterns, pattern_cache)
+    let file_cache = patterns.iter().map(|_| FileCache::default()).collect();
+    Self::new_inner(patterns, pattern_cache, file_cache)
   }
 
   fn is_cacheable(pattern: &CopyPattern) -> bool {
@@ -186,6 +195,35 @@ impl CopyRspackPlugin {
       && !matches!(pattern.to, Some(ToOption::String(ref to)) if TEMPLATE_RE.is_match(to))
   }
 
+  /// Whether the result of a file only depends on its content, so it can be
+  /// reused while the file is unchanged.
+  fn is_file_cacheable(pattern: &CopyPattern) -> bool {
+    (pattern.transform_fn.is_none() || pattern.cache.unwrap_or(false))
+      && !matches!(pattern.to, Some(ToOption::Fn(_)))
+      && !Self::uses_compilation_hash(pattern)
+  }
+
+  /// `[hash]` and `[fullhash]` change with every compilation, unlike
+  /// `[contenthash]` which only changes with the copied file.
+  fn uses_compilation_hash(pattern: &CopyPattern) -> bool {
+    let Some(ToOption::String(to)) = &pattern.to else {
+      return false;
+    };
+    TEMPLATE_RE.captures_iter(to).any(|captures| {
+      let placeholder = &captures[1];
+      let name = placeholder
+        .split_once(':')
+        .map_or(placeholder, |(name, _)| name);
+      matches!(name, "hash" | "fullhash")
+    })
+  }
+
+  fn get_file_hash(data: &[u8]) -> u64 {
+    let mut hasher = RspackHasher::new(&HashFunction::Xxhash64);
+    hasher.write(data);
+    hasher.finish()
+  }
+
   fn get_content_hash(
     source: &BoxSource,
     function: &HashFunction,
@@ -209,6 +247,7 @@ impl CopyRspackPlugin {
     compilation: &Compilation,
     logger: &CompilationLogger,
     pattern_index: usize,
+    file_cache: Option<&FileCache>,
   ) -> Result<Option<RunPatternResult>> {
     // Exclude directories
     if entry.is_dir() {
@@ -309,7 +348,25 @@ impl CopyRspackPlugin {
       );
     }
 
-    // TODO cache
+    let metadata = match file_cache {
+      Some(file_cache) => {
+        let metadata = compilation
+          .input_filesystem
+          .metadata(&absolute_filename)
+          .await
+          .ok();
+        if let Some(metadata) = &metadata
+          && let Some(cached) = file_cache.get(&absolute_filename)
+          && cached.mtime_ms == metadata.mtime_ms
+          && cached.size == metadata.size
+        {
+          logger.debug(format!("reused '{absolute_filename}' from cache"));
+          return Ok(Some(cached.result.clone()));
+        }
+        metadata
+      }
+      None => None,
+    };
 
     logger.debug(format!("reading '{absolute_filename}'..."));
     let data = compilation.input_filesystem.read(&absolute_filename).await;
@@ -330,10 +387,25 @@ impl CopyRspackPlugin {
       }
     };
 
+    let content_hash = file_cache.map(|_| Self::get_file_hash(&source_vec));
+    if let Some(file_cache) = file_cache
+      && let Some(metadata) = &metadata
+      && let Some(mut cached) = file_cache.get_mut(&absolute_filename)
+      && Some(cached.content_hash) == content_hash
+    {
+      logger.debug(format!(
+        "reused '{absolute_filename}' from cache, content unchanged"
+      ));
+      cached.mtime_ms = metadata.mtime_ms;
+      cached.size = metadata.size;
+      return Ok(Some(cached.result.clone()));
+    }
+
+    let mut has_diagnostics = false;
     let source = if let Some(transformer) = &pattern.transform_fn {
       let mut source = RawBufferSource::from(source_vec.clone()).boxed();
       logger.debug(format!("transforming content for '{absolute_filename}'..."));
-      handle_transform(
+      has_diagnostics = !handle_transform(
         transformer,
         source_vec,
         absolute_filename.clone(),
@@ -378,7 +450,7 @@ impl CopyRspackPlugin {
     };
     let filename = normalize_glob_path_separators(&filename).into_owned();
 
-    Ok(Some(RunPatternResult {
+    let result = RunPatternResult {
       source_filename,
       absolute_filename,
       filename,
@@ -387,7 +459,26 @@ impl CopyRspackPlugin {
       force: pattern.force,
       priority: pattern.priority,
       pattern_index,
-    }))
+    };
+
+    // Only cache results without diagnostics, e.g. of a failed transform.
+    if let Some(file_cache) = file_cache
+      && let Some(metadata) = metadata
+      && let Some(content_hash) = content_hash
+      && !has_diagnostics
+    {
+      file_cache.insert(
+        result.absolute_filename.clone(),
+        CachedFileResult {
+          mtime_ms: metadata.mtime_ms,
+          size: metadata.size,
+          content_hash,
+          result: result.clone(),
+        },
+      );
+    }
+
+    Ok(Some(result))
   }
 
   async fn run_pattern(
@@ -398,6 +489,7 @@ impl CopyRspackPlugin {
     context_dependencies: &FxDashSet<PathBuf>,
     diagnostics: Arc<Mutex<Vec<Diagnostic>>>,
     logger: &CompilationLogger,
+    file_cache: Option<&FileCache>,
   ) -> Result<Option<Vec<RunPatternResult>>> {
     let orig_from = &pattern.from;
     let normalized_orig_from = Utf8PathBuf::from(orig_from);
@@ -563,6 +655,7 @@ impl CopyRspackPlugin {
                 diagnostics.clone(),
                 compilation,
                 logger,
+                file_cache,
               ))
             };
             s.spawn(
@@ -574,6 +667,7 @@ impl CopyRspackPlugin {
                 diagnostics,
                 compilation,
                 logger,
+                file_cache,
               )| async move {
                 Self::analyze_every_entry(
                   entry,
@@ -586,6 +680,7 @@ impl CopyRspackPlugin {
                   compilation,
                   logger,
                   index,
+                  file_cache,
                 )
                 .await
               },
@@ -652,6 +747,7 @@ impl CopyRspackPlugin {
     results_by_pattern: Vec<Option<Vec<RunPatternResult>>>,
   ) -> Vec<(Utf8PathBuf, Utf8PathBuf)> {
     let mut permission_copies = Vec::new();
+    let mut manifests: BTreeMap<&str, BTreeMap<String, String>> = BTreeMap::new();
     for (index, result) in
       order_pattern_results(results_by_pattern, |index| self.patterns[index].priority)
     {
@@ -663,6 +759,15 @@ impl CopyRspackPlugin {
         )
       });
 
+      if let Some(manifest) = &self.patterns[index].manifest
+        && (result.force || !compilation.assets().contains_key(&result.filename))
+      {
+        manifests.entry(manifest).or_default().insert(
+          normalize_glob_path_separators(result.source_filename.as_str()).into_owned(),
+          result.filename.clone(),
+        );
+      }
+
       if let Some(exist_asset) = compilation.assets_mut().get_mut(&result.filename) {
         if !result.force {
           continue;
@@ -695,6 +800,17 @@ impl CopyRspackPlugin {
       }
     }
 
+    for (filename, manifest) in manifests {
+      let json = serde_json::to_string_pretty(&manifest).expect("manifest should be serializable");
+      compilation.emit_asset(
+        filename.to_string(),
+        CompilationAsset::new(
+          Some(RawStringSource::from(json).boxed()),
+          AssetInfo::default(),
+        ),
+      );
+    }
+
     permission_copies
   }
 }
@@ -720,6 +836,9 @@ async fn process_assets(&self, compilation: &mut Compilation) -> Result<()> {
 
     for (index, pattern) in self.patterns.iter().enumerate() {
       let cacheable = pattern_cache_enabled && CopyRspackPlugin::is_cacheable(pattern);
+      let file_cache = (pattern_cache_enabled && CopyRspackPlugin::is_file_cacheable(pattern))
+        .then(|| self.file_cache.get(index))
+        .flatten();
       let cached = &mut pattern_cache[index];
 
       if cacheable
@@ -746,6 +865,7 @@ async fn process_assets(&self, compilation: &mut Compilation) -> Result<()> {
         index,
         pattern,
         cacheable,
+        file_cache,
         file_dependencies: FxDashSet::default(),
         context_dependencies: FxDashSet::default(),
         diagnostics: Arc::new(Mutex::new(Vec::new())),
@@ -764,6 +884,7 @@ async fn process_assets(&self, compilation: &mut Compilation) -> Result<()> {
         &pending.context_dependencies,
         pending.diagnostics.clone(),
         &logger,
+        pending.file_cache,
       )
     })
     .collect::<FuturesOrdered<_>>()
@@ -802,6 +923,20 @@ async fn process_assets(&self, compilation: &mut Compilation) -> Result<()> {
       context_dependencies.extend(pattern_context_dependencies.iter().cloned());
       diagnostics.extend(pattern_diagnostics);
 
+      // Drop the files that are no longer matched by the pattern.
+      if let Some(file_cache) = pending.file_cache {
+        match results.as_ref() {
+          Some(results) => {
+            let matched = results
+              .iter()
+              .map(|result| &result.absolute_filename)
+              .collect::<rustc_hash::FxHashSet<_>>();
+            file_cache.retain(|absolute_filename, _| matched.contains(absolute_filename));
+          }
+          None => file_cache.clear(),
+        }
+      }
+
       if pending.cacheable
         && !has_diagnostics
         && let Some(results) = results.as_ref()
@@ -937,10 +1072,11 @@ async fn handle_transform(
   absolute_filename: Utf8PathBuf,
   source: &mut BoxSource,
   diagnostics: Arc<Mutex<Vec<Diagnostic>>>,
-) {
+) -> bool {
   match transformer(source_vec, absolute_filename.as_str()).await {
     Ok(code) => {
       *source = code;
+      true
     }
     Err(e) => {
       diagnostics
@@ -950,6 +1086,7 @@ async fn handle_transform(
           "Run copy transform fn error".into(),
           e.to_string(),
         ));
+      false
     }
   }
 }
@@ -961,3 +1098,49 @@ fn ensure_info_fields() {
   let info = AssetInfo::default();
   std::hint::black_box(info);
 }
+
+#[test]
+fn ensure_compilation_hash_templates_are_not_cached() {
+  let pattern = |to: &str| CopyPattern {
+    from: "static".into(),
+    to: Some(ToOption::String(to.into())),
+    context: None,
+    to_type: None,
+    no_error_on_missing: false,
+    info: None,
+    force: false,
+    priority: 0,
+    glob_options: CopyGlobOptions {
+      case_sensitive_match: None,
+      dot: None,
+      ignore: None,
+    },
+    copy_permissions: None,
+    transform_fn: None,
+    cache: None,
+    manifest: None,
+  };
+
+  assert!(CopyRspackPlugin::is_file_cacheable(&pattern(
+    "[name].[contenthash:8][ext]"
+  )));
+  assert!(CopyRspackPlugin::is_file_cacheable(&pattern(
+    "assets/[name][ext]"
+  )));
+  assert!(!CopyRspackPlugin::is_file_cacheable(&pattern(
+    "[name].[hash:8][ext]"
+  )));
+  assert!(!CopyRspackPlugin::is_file_cacheable(&pattern(
+    "[fullhash]/[name][ext]"
+  )));
+
+  // templates are never served from the pattern cache
+  assert!(CopyRspackPlugin::is_cacheable(&pattern("assets/")));
+  assert!(!CopyRspackPlugin::is_cacheable(&pattern(
+    "assets/[name][ext]"
+  )));
+  assert!(!CopyRspackPlugin::is_cacheable(&CopyPattern {
+    to_type: Some(ToType::Template),
+    ..pattern("assets")
+  }));
+}
@@ -1,5 +1,8 @@
 use std::path::{Path, PathBuf};
 
+use rspack_paths::Utf8PathBuf;
+use rspack_util::fx_hash::FxDashMap;
+
 use crate::RunPatternResult;
 
 #[derive(Debug, Clone)]
@@ -23,3 +26,18 @@ impl CachedPatternResult {
     })
   }
 }
+
+/// The result of copying a single file, reused when a pattern is run again
+/// while the file is unchanged.
+#[derive(Debug, Clone)]
+pub(super) struct CachedFileResult {
+  pub(super) mtime_ms: u64,
+  pub(super) size: u64,
+  /// Hash of the content before transform, so a file that was only touched
+  /// is not transformed and hashed again.
+  pub(super) content_hash: u64,
+  pub(super) result: RunPatternResult,
+}
+
+/// Copied files of a pattern, by absolute filename.
+pub(super) type FileCache = FxDashMap<Utf8PathBuf, CachedFileResult>;
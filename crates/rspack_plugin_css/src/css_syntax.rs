@@ -228,3 +228,145 @@ pub(crate) fn normalize_url(input: &str) -> Cow<'_, str> {
   }
   result
 }
+
+/// Separates the parts of [`icss_import_placeholder`].
+const ICSS_IMPORT_PLACEHOLDER: &str = "__RSPACK_PLUGIN_CSS_ICSS_IMPORT__";
+
+/// Stands for the value `import_name` imported from `request` inside another
+/// value, until the imported module is known at code generation.
+pub(crate) fn icss_import_placeholder(import_name: &str, request: &str) -> String {
+  format!(
+    "{ICSS_IMPORT_PLACEHOLDER}{import_name}{ICSS_IMPORT_PLACEHOLDER}{request}{ICSS_IMPORT_PLACEHOLDER}"
+  )
+}
+
+/// Replace the [`icss_import_placeholder`]s of a value with the imported values,
+/// placeholders that can't be resolved are replaced with their import name.
+pub(crate) fn replace_icss_import_placeholders<'a>(
+  value: &'a str,
+  mut resolve: impl FnMut(&str, &str) -> Option<String>,
+) -> Cow<'a, str> {
+  if !value.contains(ICSS_IMPORT_PLACEHOLDER) {
+    return Cow::Borrowed(value);
+  }
+  let mut output = String::with_capacity(value.len());
+  let mut rest = value;
+  while let Some(start) = rest.find(ICSS_IMPORT_PLACEHOLDER) {
+    let placeholder = &rest[start + ICSS_IMPORT_PLACEHOLDER.len()..];
+    let Some((import_name, placeholder)) = placeholder.split_once(ICSS_IMPORT_PLACEHOLDER) else {
+      break;
+    };
+    let Some((request, placeholder)) = placeholder.split_once(ICSS_IMPORT_PLACEHOLDER) else {
+      break;
+    };
+    output.push_str(&rest[..start]);
+    output.push_str(&resolve(import_name, request).unwrap_or_else(|| import_name.to_string()));
+    rest = placeholder;
+  }
+  output.push_str(rest);
+  Cow::Owned(output)
+}
+
+/// Replace the identifiers of an ICSS value which refer to other symbols, e.g.
+/// `base` in `@value large: calc(base * 2)`. Strings, urls and function names
+/// are left as they are.
+pub(crate) fn replace_icss_symbols<'a>(
+  value: &'a str,
+  mut replacement: impl FnMut(&str) -> Option<String>,
+) -> Cow<'a, str> {
+  let mut input = cssparser::ParserInput::new(value);
+  let mut parser = cssparser::Parser::new(&mut input);
+  let mut output = String::new();
+  let start = parser.position();
+  let mut last = start;
+  replace_icss_symbols_in(&mut parser, &mut replacement, &mut output, &mut last);
+  if last == start {
+    return Cow::Borrowed(value);
+  }
+  output.push_str(parser.slice_from(last));
+  Cow::Owned(output)
+}
+
+fn replace_icss_symbols_in(
+  parser: &mut cssparser::Parser<'_, '_>,
+  replacement: &mut impl FnMut(&str) -> Option<String>,
+  output: &mut String,
+  last: &mut cssparser::SourcePosition,
+) {
+  loop {
+    let start = parser.position();
+    let Ok(token) = parser.next_including_whitespace_and_comments().cloned() else {
+      break;
+    };
+    match token {
+      cssparser::Token::Ident(name) => {
+        if let Some(value) = replacement(&name) {
+          output.push_str(parser.slice(*last..start));
+          output.push_str(&value);
+          *last = parser.position();
+        }
+      }
+      cssparser::Token::Function(_)
+      | cssparser::Token::ParenthesisBlock
+      | cssparser::Token::SquareBracketBlock
+      | cssparser::Token::CurlyBracketBlock => {
+        let _ = parser.parse_nested_block(|block| {
+          replace_icss_symbols_in(block, replacement, output, last);
+          Ok::<_, cssparser::ParseError<'_, ()>>(())
+        });
+      }
+      _ => {}
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::{icss_import_placeholder, replace_icss_import_placeholders, replace_icss_symbols};
+
+  fn local_values(name: &str) -> Option<String> {
+    match name {
+      "base" => Some("8px".into()),
+      "primary" => Some("#f00".into()),
+      _ => None,
+    }
+  }
+
+  #[test]
+  fn replaces_local_symbols() {
+    assert_eq!(
+      replace_icss_symbols("calc(base * 2)", local_values),
+      "calc(8px * 2)"
+    );
+    assert_eq!(
+      replace_icss_symbols("1px solid primary", local_values),
+      "1px solid #f00"
+    );
+    // strings, urls and function names are kept
+    assert_eq!(
+      replace_icss_symbols("base(\"base\") url(base.png)", local_values),
+      "base(\"base\") url(base.png)"
+    );
+    assert_eq!(replace_icss_symbols("none", local_values), "none");
+  }
+
+  #[test]
+  fn replaces_imported_symbols() {
+    let value = replace_icss_symbols("calc(spacing * 2) primary", |name| match name {
+      "spacing" => Some(icss_import_placeholder("spacing", "./sizes.css")),
+      name => local_values(name),
+    });
+    let resolved = replace_icss_import_placeholders(&value, |import_name, request| {
+      assert_eq!((import_name, request), ("spacing", "./sizes.css"));
+      Some("4px".into())
+    });
+    assert_eq!(resolved, "calc(4px * 2) #f00");
+
+    // unresolved imports fall back to their name
+    assert_eq!(
+      replace_icss_import_placeholders(&value, |_, _| None),
+      "calc(spacing * 2) #f00"
+    );
+    assert_eq!(replace_icss_import_placeholders("8px", |_, _| None), "8px");
+  }
+}
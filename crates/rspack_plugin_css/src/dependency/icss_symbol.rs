@@ -1,3 +1,5 @@
+use std::borrow::Cow;
+
 use rspack_cacheable::{cacheable, cacheable_dyn};
 use rspack_core::{
   AsContextDependency, AsModuleDependency, Compilation, CssExport, Dependency, DependencyCategory,
@@ -7,7 +9,9 @@ use rspack_core::{
 };
 use rustc_hash::FxHashSet;
 
-use crate::utils::replace_css_module_id_placeholder;
+use crate::{
+  css_syntax::replace_icss_import_placeholders, utils::replace_css_module_id_placeholder,
+};
 
 #[cacheable]
 #[derive(Debug, Clone)]
@@ -94,7 +98,14 @@ impl DependencyTemplate for CssIcssSymbolDependencyTemplate {
       .expect("CssIcssSymbolDependencyTemplate should be used for CssIcssSymbolDependency");
 
     let value = match &dep.value {
-      CssIcssSymbolValue::Literal(value) => Some(value.clone()),
+      CssIcssSymbolValue::Literal(value) => Some(
+        resolve_icss_value(
+          value,
+          code_generatable_context.compilation,
+          code_generatable_context.module,
+        )
+        .into_owned(),
+      ),
       CssIcssSymbolValue::Import {
         local_name,
         import_name,
@@ -120,6 +131,43 @@ fn resolve_icss_import(
   local_name: &str,
   import_name: &str,
   request: &str,
+) -> Option<String> {
+  resolve_icss_import_in(
+    compilation,
+    module,
+    import_name,
+    request,
+    &mut FxHashSet::from_iter([(module.identifier(), local_name.to_string())]),
+  )
+}
+
+/// Replaces the values imported inside the value of an ICSS symbol, see
+/// [`icss_import_placeholder`](crate::css_syntax::icss_import_placeholder).
+pub(crate) fn resolve_icss_value<'a>(
+  value: &'a str,
+  compilation: &Compilation,
+  module: &dyn Module,
+) -> Cow<'a, str> {
+  resolve_icss_value_in(value, compilation, module, &mut FxHashSet::default())
+}
+
+fn resolve_icss_value_in<'a>(
+  value: &'a str,
+  compilation: &Compilation,
+  module: &dyn Module,
+  seen: &mut FxHashSet<(ModuleIdentifier, String)>,
+) -> Cow<'a, str> {
+  replace_icss_import_placeholders(value, |import_name, request| {
+    resolve_icss_import_in(compilation, module, import_name, request, seen)
+  })
+}
+
+fn resolve_icss_import_in(
+  compilation: &Compilation,
+  module: &dyn Module,
+  import_name: &str,
+  request: &str,
+  seen: &mut FxHashSet<(ModuleIdentifier, String)>,
 ) -> Option<String> {
   let module_graph = compilation.get_module_graph();
   let imported_module = module.get_dependencies().iter().find_map(|id| {
@@ -135,12 +183,7 @@ fn resolve_icss_import(
     }
   })?;
   let imported_module = module_graph.module_by_identifier(&imported_module.module_identifier)?;
-  resolve_css_export_value(
-    compilation,
-    imported_module.as_ref(),
-    import_name,
-    &mut FxHashSet::from_iter([(module.identifier(), local_name.to_string())]),
-  )
+  resolve_css_export_value(compilation, imported_module.as_ref(), import_name, seen)
 }
 
 fn resolve_css_export_value(
@@ -164,7 +207,8 @@ fn resolve_css_export_value(
       };
       resolved.push(value);
     } else {
-      resolved.push(replace_css_module_id_placeholder(ident, compilation, module).to_string());
+      let ident = resolve_icss_value_in(ident, compilation, module, seen);
+      resolved.push(replace_css_module_id_placeholder(&ident, compilation, module).to_string());
     }
   }
 
@@ -22,7 +22,7 @@ use rustc_hash::FxHashSet as HashSet;
 
 use crate::{
   css_syntax::unescape_identifier,
-  dependency::CssImportDependency,
+  dependency::{CssImportDependency, resolve_icss_value},
   parser_and_generator::{
     CssExportsRef, CssSourceBuilder, get_unused_local_ident, get_used_exports,
   },
@@ -425,12 +425,14 @@ impl<'a, 'g> CssModuleGenerator<'a, 'g> {
     json_stringify_str(&builder.into_css_text())
   }
 
-  fn css_source_builder(&self, with_charset: bool) -> CssSourceBuilder {
+  fn css_source_builder(&self, with_charset: bool) -> CssSourceBuilder<'g> {
+    let compilation = self.generate_context.compilation;
     CssSourceBuilder::new(
       with_charset,
       !self.module.get_source_map_kind().no_sources(),
-      self.generate_context.compilation.options.context.clone(),
+      compilation.options.context.clone(),
     )
+    .with_importer_sources(compilation.get_module_graph())
   }
 
   fn render_require_call_parts(&mut self) -> (String, &'static str, &'static str) {
@@ -785,8 +787,9 @@ impl<'a, 'g> CssModuleGenerator<'a, 'g> {
   }
 
   fn render_local_css_export(&self, ident: &str) -> String {
-    let ident =
-      replace_css_module_id_placeholder(ident, self.generate_context.compilation, self.module);
+    let compilation = self.generate_context.compilation;
+    let ident = resolve_icss_value(ident, compilation, self.module);
+    let ident = replace_css_module_id_placeholder(&ident, compilation, self.module);
     json_stringify_str(&ident)
   }
 
@@ -1071,8 +1074,8 @@ impl<'a> CssConcatenationState<'a> {
         }
         StaticCssExportStep::Resolve { module, css_export } => match css_export.from.as_deref() {
           None => {
-            let value =
-              replace_css_module_id_placeholder(&css_export.ident, self.compilation, module);
+            let value = resolve_icss_value(&css_export.ident, self.compilation, module);
+            let value = replace_css_module_id_placeholder(&value, self.compilation, module);
             if let Some(frame) = self.static_export_queue.back_mut() {
               push_joined(&mut frame.resolved, value.as_ref(), " ");
             }
@@ -4,10 +4,12 @@ use once_cell::sync::OnceCell;
 use rspack_core::{
   BoxDependency, ConstDependency, CssAutoOrModuleParserOptions, CssExport, CssExportType,
   CssExports, CssExportsConvention, CssLayer, CssLocalNames, CssModuleGeneratorOptions,
-  CssModuleRenderCondition, CssParserImport, CssParserImportContext, Dependency,
-  DependencyCodeGenerationRef, DependencyId, DependencyRange, ModuleType, ParseContext,
+  CssModuleRenderCondition, CssModuleRenderConditionLoc, CssParserImport, CssParserImportContext,
+  Dependency, DependencyCodeGenerationRef, DependencyId, DependencyRange, ModuleType, ParseContext,
   ParseResult, ResourceData, StaticExportsDependency, StaticExportsSpec,
-  diagnostics::map_box_diagnostics_to_module_parse_diagnostics, remove_bom, rspack_sources::Source,
+  diagnostics::map_box_diagnostics_to_module_parse_diagnostics,
+  remove_bom,
+  rspack_sources::{Source, utf16_len},
   topological_sort,
 };
 use rspack_error::{Diagnostic, IntoTWithDiagnosticArray, Result, Severity, TWithDiagnosticArray};
@@ -16,7 +18,7 @@ use smol_str::SmolStr;
 
 use super::is_css_module;
 use crate::{
-  css_syntax::{normalize_url, unescape_identifier},
+  css_syntax::{icss_import_placeholder, normalize_url, replace_icss_symbols, unescape_identifier},
   dependency::{
     CssComposeDependency, CssExportDependency, CssIcssSymbolDependency, CssIcssSymbolValue,
     CssImportDependency, CssLocalIdentDependency, CssSelfReferenceLocalIdentDependency,
@@ -1017,7 +1019,8 @@ impl<'context> CssModuleParser<'context> {
       media.map(|media| media.trim().into()),
       supports.map(|supports| supports.trim().into()),
       layer,
-    );
+    )
+    .with_loc(self.css_import_loc(range.start));
     let dep = CssImportDependency::new(
       request,
       DependencyRange::new(range.start, range.end),
@@ -1030,6 +1033,16 @@ impl<'context> CssModuleParser<'context> {
     Ok(())
   }
 
+  fn css_import_loc(&self, start: u32) -> CssModuleRenderConditionLoc {
+    let before = &self.source_code[..start as usize];
+    let line_start = before.rfind('\n').map_or(0, |index| index + 1);
+    CssModuleRenderConditionLoc {
+      module: self.parse_context.module_identifier,
+      line: before.matches('\n').count() as u32 + 1,
+      column: utf16_len(&before[line_start..]) as u32,
+    }
+  }
+
   fn css_import_inherited_render_conditions(&self) -> Vec<CssModuleRenderCondition> {
     let (mut inherited_render_conditions, render_condition) = self
       .parse_context
@@ -1554,7 +1567,17 @@ impl<'context> CssModuleParser<'context> {
   fn handle_icss_export_value(&mut self, prop: &str, value: &str) {
     let convention = self.convention();
     let convention_names = export_locals_convention(prop, convention);
-    let definition = self.resolve_icss_definition(value);
+    // values can refer to the values defined or imported before them, as in
+    // css-loader, imported values are resolved during generation
+    let value = replace_icss_symbols(value, |name| match self.icss_definitions.get(name) {
+      Some(IcssDefinition::Value(value)) => Some(value.clone()),
+      Some(IcssDefinition::Import {
+        import_name,
+        request,
+      }) if name != value.trim() => Some(icss_import_placeholder(import_name, request)),
+      _ => None,
+    });
+    let definition = self.resolve_icss_definition(&value);
     self
       .icss_definitions
       .insert(prop.to_string(), definition.clone());
@@ -1,29 +1,50 @@
-use cow_utils::CowUtils;
+use concat_string::concat_string;
 use rspack_core::{
-  Context, CssLayer, CssModuleRenderCondition,
+  Context, CssLayer, CssModuleRenderCondition, CssModuleRenderConditionLoc, Module, ModuleGraph,
+  ModuleIdentifier,
   rspack_sources::{
-    BoxSource, ConcatSource, MapOptions, ObjectPool, RawStringSource, ReplaceSource, Source,
-    SourceExt,
+    BoxSource, ConcatSource, MapOptions, Mapping, ObjectPool, OriginalLocation, RawStringSource,
+    ReplaceSource, Source, SourceExt, SourceMap, SourceMapSource, WithoutOriginalOptions,
+    encode_mappings,
   },
 };
 use rspack_util::{base64::encode_to_string, identifier::make_paths_relative};
 
 const CSS_UTF8_CHARSET: &str = r#"@charset "UTF-8";"#;
 
-pub(crate) struct CssSourceBuilder {
+/// Looks up the source of a module with an `@import`, so that the at-rules wrapping the imported
+/// module can be mapped to it.
+pub(crate) trait CssImporterSources {
+  /// The source name and the source of the module, `None` when it has no source maps.
+  fn importer_source(&self, module: &ModuleIdentifier) -> Option<(&str, &BoxSource)>;
+}
+
+impl CssImporterSources for ModuleGraph {
+  fn importer_source(&self, module: &ModuleIdentifier) -> Option<(&str, &BoxSource)> {
+    let module = self.module_by_identifier(module)?;
+    if !module.get_source_map_kind().enabled() {
+      return None;
+    }
+    // named as the module names its own source
+    Some((module.as_normal_module()?.request(), module.source()?))
+  }
+}
+
+pub(crate) struct CssSourceBuilder<'a> {
   source: ConcatSource,
   has_charset: bool,
   include_sources_content: bool,
   source_map_context: Context,
+  importer_sources: Option<&'a dyn CssImporterSources>,
 }
 
-impl Default for CssSourceBuilder {
+impl Default for CssSourceBuilder<'_> {
   fn default() -> Self {
     Self::new(true, true, Default::default())
   }
 }
 
-impl CssSourceBuilder {
+impl<'a> CssSourceBuilder<'a> {
   pub(crate) fn new(
     with_charset: bool,
     include_sources_content: bool,
@@ -34,13 +55,24 @@ impl CssSourceBuilder {
       has_charset: with_charset,
       include_sources_content,
       source_map_context,
+      importer_sources: None,
     }
   }
 
-  pub(crate) fn push_css_source<'a>(
+  /// The at-rules wrapping an imported module are mapped to the `@import` in the source of the
+  /// importing module.
+  pub(crate) fn with_importer_sources(
+    mut self,
+    importer_sources: &'a dyn CssImporterSources,
+  ) -> Self {
+    self.importer_sources = Some(importer_sources);
+    self
+  }
+
+  pub(crate) fn push_css_source<'c>(
     &mut self,
     source: BoxSource,
-    conditions: impl IntoIterator<Item = &'a CssModuleRenderCondition>,
+    conditions: impl IntoIterator<Item = &'c CssModuleRenderCondition>,
     trim_source_start: bool,
   ) -> bool {
     let Some(source) = Self::prepare_source(source, trim_source_start) else {
@@ -48,33 +80,32 @@ impl CssSourceBuilder {
     };
 
     let mut depth = 0;
+    // The wrapped source isn't indented and starts on its own line, so its mappings are only moved
+    // down by the lines of the at-rules.
     // TODO: use PrefixSource to create indent
     for conditions in conditions {
       if let Some(media) = &conditions.media {
-        self.add(RawStringSource::from_static("@media "));
-        self.add(RawStringSource::from(media.to_string()));
-        self.add(RawStringSource::from_static("{\n"));
+        self.add_at_rule(
+          concat_string!("@media ", media, "{\n"),
+          conditions.loc.as_ref(),
+        );
         depth += 1;
       }
 
       if let Some(supports) = &conditions.supports {
-        self.add(RawStringSource::from_static("@supports ("));
-        self.add(RawStringSource::from(supports.to_string()));
-        self.add(RawStringSource::from_static(") {\n"));
+        self.add_at_rule(
+          concat_string!("@supports (", supports, ") {\n"),
+          conditions.loc.as_ref(),
+        );
         depth += 1;
       }
 
       if let Some(layer) = &conditions.layer {
-        match layer {
-          CssLayer::Named(layer) => {
-            self.add(RawStringSource::from_static("@layer "));
-            self.add(RawStringSource::from(layer.to_string()));
-            self.add(RawStringSource::from_static(" {\n"));
-          }
-          CssLayer::Anonymous => {
-            self.add(RawStringSource::from_static("@layer {\n"));
-          }
-        }
+        let at_rule = match layer {
+          CssLayer::Named(layer) => concat_string!("@layer ", layer, " {\n"),
+          CssLayer::Anonymous => "@layer {\n".to_string(),
+        };
+        self.add_at_rule(at_rule, conditions.loc.as_ref());
         depth += 1;
       }
     }
@@ -113,12 +144,8 @@ impl CssSourceBuilder {
   pub(crate) fn into_css_text(self) -> String {
     let include_sources_content = self.include_sources_content;
     let source_map_context = self.source_map_context.clone();
-    let source = self.into_source();
-    let mut css_text = source
-      .source()
-      .into_string_lossy()
-      .cow_replace(crate::utils::AUTO_PUBLIC_PATH_PLACEHOLDER, "")
-      .into_owned();
+    let source = Self::remove_auto_public_path_placeholder(self.into_source());
+    let mut css_text = source.source().into_string_lossy().into_owned();
 
     if let Some(mut source_map) = source.map(&ObjectPool::default(), &MapOptions::default()) {
       if !source_map_context.as_str().is_empty() {
@@ -145,22 +172,77 @@ impl CssSourceBuilder {
     css_text
   }
 
+  /// The placeholder is removed on the source rather than on the text, so that the
+  /// columns of the source map after a rewritten `url()` move along with it.
+  fn remove_auto_public_path_placeholder(source: BoxSource) -> BoxSource {
+    let text = source.source().into_string_lossy();
+    let placeholder = crate::utils::AUTO_PUBLIC_PATH_PLACEHOLDER;
+    let ranges = text
+      .match_indices(placeholder)
+      .map(|(index, _)| (index as u32, (index + placeholder.len()) as u32))
+      .collect::<Vec<_>>();
+    drop(text);
+    if ranges.is_empty() {
+      return source;
+    }
+
+    let mut source = ReplaceSource::new(source);
+    for (start, end) in ranges {
+      source.replace(start, end, String::new(), None);
+    }
+    source.boxed()
+  }
+
   fn add<S: Source + 'static>(&mut self, source: S) {
     self.source.add(source);
   }
 
+  /// An at-rule wrapping an imported module is mapped to the `@import` it comes from.
+  fn add_at_rule(&mut self, at_rule: String, loc: Option<&CssModuleRenderConditionLoc>) {
+    let importer = loc
+      .zip(self.importer_sources)
+      .and_then(|(loc, importer_sources)| {
+        let (name, source) = importer_sources.importer_source(&loc.module)?;
+        Some((loc, name, source))
+      });
+    let Some((loc, name, source)) = importer else {
+      self.add(RawStringSource::from(at_rule));
+      return;
+    };
+
+    let mappings = encode_mappings(std::iter::once(Mapping {
+      generated_line: 1,
+      generated_column: 0,
+      original: Some(OriginalLocation {
+        source_index: 0,
+        original_line: loc.line,
+        original_column: loc.column,
+        name_index: None,
+      }),
+    }));
+    // The importing module is named and has the content as in its own source, so that the two
+    // are merged into one source of the map
+    self.add(SourceMapSource::new(WithoutOriginalOptions {
+      value: at_rule,
+      name,
+      source_map: SourceMap::new(
+        mappings,
+        vec![name.to_string().into()],
+        vec![source.source().into_string_lossy().into_owned().into()],
+        Vec::new(),
+      ),
+    }));
+  }
+
   fn prepare_source(source: BoxSource, trim_source_start: bool) -> Option<BoxSource> {
     if !trim_source_start {
       return Some(source);
     }
 
+    // `ReplaceSource` offsets are in bytes
     let source_text = source.source().into_string_lossy();
-    let source_len = source_text.chars().map(char::len_utf16).sum::<usize>() as u32;
-    let leading_len = source_text
-      .chars()
-      .take_while(|ch| ch.is_whitespace())
-      .map(char::len_utf16)
-      .sum::<usize>() as u32;
+    let source_len = source_text.len() as u32;
+    let leading_len = (source_len as usize - source_text.trim_start().len()) as u32;
     drop(source_text);
 
     if leading_len == source_len {
@@ -179,8 +261,7 @@ impl CssSourceBuilder {
 
 #[cfg(test)]
 mod tests {
-  use concat_string::concat_string;
-  use rspack_core::rspack_sources::{RawStringSource, Source, SourceExt};
+  use rspack_core::rspack_sources::{OriginalSource, RawStringSource, Source, SourceExt};
 
   use super::*;
 
@@ -192,6 +273,14 @@ mod tests {
     source.source().into_string_lossy().into_owned()
   }
 
+  struct TestImporterSources(BoxSource);
+
+  impl CssImporterSources for TestImporterSources {
+    fn importer_source(&self, module: &ModuleIdentifier) -> Option<(&str, &BoxSource)> {
+      (module.as_str() == "index.css").then_some(("index.css", &self.0))
+    }
+  }
+
   fn css_import_conditions(source: &str) -> Vec<CssModuleRenderCondition> {
     let (deps, warnings) =
       css_module_lexer::collect_dependencies(source, css_module_lexer::Mode::Css);
@@ -321,6 +410,85 @@ mod tests {
     );
   }
 
+  #[test]
+  fn css_source_builder_maps_wrapped_css_imports() {
+    let conditions = css_import_conditions(
+      r#"@import url("./a.css") layer(theme) supports(display: grid) screen;"#,
+    )
+    .into_iter()
+    .map(|condition| {
+      condition.with_loc(CssModuleRenderConditionLoc {
+        module: "index.css".into(),
+        line: 2,
+        column: 2,
+      })
+    })
+    .collect::<Vec<_>>();
+    let importer_sources = TestImporterSources(
+      OriginalSource::new(
+        "/* a */\n  @import url(\"./a.css\") layer(theme) supports(display: grid) screen;",
+        "index.css",
+      )
+      .boxed(),
+    );
+    let original = "\n  .a{color:red}\n.b{color:blue}";
+    let mut builder = CssSourceBuilder::new(false, true, Default::default())
+      .with_importer_sources(&importer_sources);
+    builder.push_css_source(
+      OriginalSource::new(original, "a.css").boxed(),
+      &conditions,
+      true,
+    );
+
+    let source = builder.into_source();
+    let generated = source_text(source.clone());
+    assert_eq!(
+      generated,
+      r#"@media screen{
+@supports (display: grid) {
+@layer theme {
+.a{color:red}
+.b{color:blue}
+}
+}
+}"#
+    );
+
+    let map = source
+      .map(&ObjectPool::default(), &MapOptions::default())
+      .expect("should have source map");
+    let mappings = map
+      .decoded_mappings()
+      .filter_map(|mapping| {
+        mapping.original.map(|original| {
+          (
+            mapping.generated_line,
+            mapping.generated_column,
+            map
+              .get_source(original.source_index as usize)
+              .expect("should have source")
+              .to_string(),
+            original.original_line,
+            original.original_column,
+          )
+        })
+      })
+      .collect::<Vec<_>>();
+    // each at-rule is mapped to the `@import`, `.a` is on the fourth line after the at-rules and
+    // `.b` on the fifth, both in their columns of `a.css`
+    for generated_line in 1..=3 {
+      assert!(mappings.contains(&(generated_line, 0, "index.css".to_string(), 2, 2)));
+    }
+    assert!(mappings.contains(&(4, 0, "a.css".to_string(), 2, 2)));
+    assert!(mappings.contains(&(5, 0, "a.css".to_string(), 3, 0)));
+    assert_eq!(map.sources(), ["index.css", "a.css"]);
+    assert!(
+      map
+        .get_source_content(0)
+        .is_some_and(|content| content.contains("@import"))
+    );
+  }
+
   #[test]
   fn css_source_builder_pushes_lines_explicitly() {
     let mut builder = CssSourceBuilder::new(false, true, Default::default());
@@ -356,4 +524,36 @@ mod tests {
 .a{background:url();}"#
     );
   }
+
+  #[test]
+  fn css_source_builder_keeps_mappings_after_auto_public_path_placeholder() {
+    let original = concat_string!(
+      ".a{background:url(",
+      crate::utils::AUTO_PUBLIC_PATH_PLACEHOLDER,
+      "img.png)}.b{color:red}"
+    );
+    let mut builder = CssSourceBuilder::new(false, true, Default::default());
+    builder.push_css_source(
+      OriginalSource::new(original.clone(), "a.css").boxed(),
+      &[],
+      false,
+    );
+
+    let source = CssSourceBuilder::remove_auto_public_path_placeholder(builder.into_source());
+    let generated = source_text(source.clone());
+    assert_eq!(generated, ".a{background:url(img.png)}.b{color:red}");
+
+    let map = source
+      .map(&ObjectPool::default(), &MapOptions::default())
+      .expect("should have source map");
+    let generated_column = generated.find(".b").expect("should have .b") as u32;
+    let original_column = original.find(".b").expect("should have .b") as u32;
+    assert!(map.decoded_mappings().any(|mapping| {
+      mapping.generated_line == 1
+        && mapping.generated_column == generated_column
+        && mapping
+          .original
+          .is_some_and(|original| original.original_column == original_column)
+    }));
+  }
 }
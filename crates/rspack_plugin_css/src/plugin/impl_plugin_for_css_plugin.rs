@@ -198,7 +198,8 @@ impl CssPlugin {
           s.spawn(
             |(compilation, chunk, module, cur_source, render_conditions, hooks)| async move {
               let mut post_module_container = {
-                let mut builder = CssSourceBuilder::new(false, true, Default::default());
+                let mut builder = CssSourceBuilder::new(false, true, Default::default())
+                  .with_importer_sources(compilation.get_module_graph());
                 if builder.push_css_source(
                   cur_source.clone(),
                   &render_conditions,
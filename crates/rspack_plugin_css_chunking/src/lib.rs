@@ -1,11 +1,18 @@
-use std::sync::atomic::{AtomicBool, Ordering};
+mod report;
+
+use std::sync::{
+  Mutex,
+  atomic::{AtomicBool, Ordering},
+};
 
 use rspack_collections::{
   Identifier, IdentifierIndexMap, IdentifierIndexSet, IdentifierMap, IdentifierSet,
 };
 use rspack_core::{
-  ChunkUkey, Compilation, CompilationOptimizeChunks, CompilationParams, CompilerCompilation,
-  Logger, Module, ModuleIdentifier, Plugin, SourceType,
+  AssetInfo, ChunkUkey, Compilation, CompilationAsset, CompilationOptimizeChunks,
+  CompilationParams, CompilationProcessAssets, CompilerCompilation, Logger, Module,
+  ModuleIdentifier, Plugin, SourceType,
+  rspack_sources::{RawStringSource, SourceExt},
 };
 use rspack_error::Result;
 use rspack_hook::{plugin, plugin_hook};
@@ -13,9 +20,19 @@ use rspack_plugin_css::CssPlugin;
 use rspack_regex::RspackRegex;
 use rustc_hash::{FxHashMap, FxHashSet};
 
+use crate::report::{CssChunkingReport, CssOrderConflict};
+
 const MIN_CSS_CHUNK_SIZE: f64 = 30_f64 * 1024_f64;
 const MAX_CSS_CHUNK_SIZE: f64 = 100_f64 * 1024_f64;
 
+fn is_css_source_type(source_type: &SourceType) -> bool {
+  match source_type {
+    SourceType::Css | SourceType::CssImport => true,
+    SourceType::Custom(str) => str == "css/mini-extract",
+    _ => false,
+  }
+}
+
 fn is_global_css(name_for_condition: &Option<Box<str>>) -> bool {
   name_for_condition.as_ref().is_some_and(|s| {
     !s.ends_with(".module.css") && !s.ends_with(".module.scss") && !s.ends_with(".module.sass")
@@ -28,6 +45,16 @@ pub struct CssChunkingPluginOptions {
   pub min_size: Option<f64>,
   pub max_size: Option<f64>,
   pub exclude: Option<RspackRegex>,
+  /// Filename of the JSON report of the ordering conflicts
+  pub report: Option<String>,
+}
+
+#[derive(Debug, Default)]
+struct CssOrderConflicts {
+  conflicts: Vec<CssOrderConflict>,
+  /// Names of the chunks of the conflicts, for the chunks removed once their modules
+  /// are moved to the css chunks.
+  chunk_names: FxHashMap<ChunkUkey, String>,
 }
 
 #[plugin]
@@ -38,6 +65,8 @@ pub struct CssChunkingPlugin {
   min_size: f64,
   max_size: f64,
   exclude: Option<RspackRegex>,
+  report: Option<String>,
+  conflicts: Mutex<CssOrderConflicts>,
 }
 
 impl CssChunkingPlugin {
@@ -48,6 +77,8 @@ impl CssChunkingPlugin {
       options.min_size.unwrap_or(MIN_CSS_CHUNK_SIZE),
       options.max_size.unwrap_or(MAX_CSS_CHUNK_SIZE),
       options.exclude,
+      options.report,
+      Default::default(),
     )
   }
 }
@@ -59,6 +90,7 @@ async fn compilation(
   _params: &mut CompilationParams,
 ) -> Result<()> {
   self.once.store(false, Ordering::Relaxed);
+  *self.conflicts.lock().expect("should get lock") = Default::default();
   Ok(())
 }
 
@@ -102,12 +134,10 @@ async fn optimize_chunks(&self, compilation: &mut Compilation) -> Result<Option<
       .get_chunk_modules(chunk_ukey, module_graph)
       .into_iter()
       .filter(|module| {
-        module.source_types(module_graph).iter().any(|t| match t {
-          SourceType::Css => true,
-          SourceType::CssImport => true,
-          SourceType::Custom(str) => str == "css/mini-extract",
-          _ => false,
-        })
+        module
+          .source_types(module_graph)
+          .iter()
+          .any(is_css_source_type)
       })
       .map(|module| module.as_ref())
       .collect();
@@ -153,7 +183,13 @@ async fn optimize_chunks(&self, compilation: &mut Compilation) -> Result<Option<
       let module = module_graph
         .module_by_identifier(module_identifier)
         .unwrap();
-      let size = module.size(None, None);
+      // Only the css of the modules goes into the css chunks
+      let size = module
+        .source_types(module_graph)
+        .iter()
+        .filter(|source_type| is_css_source_type(source_type))
+        .map(|source_type| module.size(Some(source_type), Some(compilation)))
+        .sum::<f64>();
       result.insert(*module_identifier, (size, module.name_for_condition()));
     }
     result
@@ -209,8 +245,15 @@ async fn optimize_chunks(&self, compilation: &mut Compilation) -> Result<Option<
     logger.time_end(start);
   }
 
-  // Stores the new chunk for every module
-  let mut new_chunks_by_module: IdentifierMap<ChunkUkey> = IdentifierMap::default();
+  // The positions of the modules in the chunks, the ones of the start modules are
+  // moved forward as modules are added to their new chunks
+  let positions = chunk_states_by_module.clone();
+  let mut conflicts: Vec<CssOrderConflict> = vec![];
+  let mut conflicting_modules: FxHashSet<(ModuleIdentifier, ModuleIdentifier)> =
+    FxHashSet::default();
+
+  // The modules of the new chunks, in order
+  let mut new_css_chunks: Vec<NewCssChunk> = vec![];
 
   // Process through all modules
   let start = logger.time("process through all modules");
@@ -227,7 +270,7 @@ async fn optimize_chunks(&self, compilation: &mut Compilation) -> Result<Option<
       .unwrap();
 
     // The list of modules that goes into the new chunk
-    let mut new_chunk_modules = IdentifierSet::default();
+    let mut new_chunk_modules = IdentifierIndexSet::default();
     new_chunk_modules.insert(start_module_identifier);
 
     // The current size of the new chunk
@@ -312,7 +355,23 @@ async fn optimize_chunks(&self, compilation: &mut Compilation) -> Result<Option<
                 }
               }
               Some(&prev_idx) if prev_idx + 1 == *i => {}
-              _ => continue 'outer,
+              Some(&prev_idx) => {
+                // The module is before the end of the new chunk in this chunk, it's a
+                // conflict when it is after it in another chunk
+                if *i < prev_idx {
+                  let last_module_identifier = chunk_states[chunk_ukey].modules[prev_idx];
+                  if conflicting_modules.insert((last_module_identifier, next_module_identifier))
+                    && let Some(conflict) = CssOrderConflict::new(
+                      last_module_identifier,
+                      next_module_identifier,
+                      &positions,
+                    )
+                  {
+                    conflicts.push(conflict);
+                  }
+                }
+                continue 'outer;
+              }
             }
           }
         }
@@ -369,6 +428,29 @@ async fn optimize_chunks(&self, compilation: &mut Compilation) -> Result<Option<
         break;
       }
     }
+    for module_identifier in &new_chunk_modules {
+      remaining_modules.shift_remove(module_identifier);
+    }
+    new_css_chunks.push(NewCssChunk {
+      modules: new_chunk_modules.into_iter().collect(),
+      size: current_size,
+    });
+  }
+  logger.time_end(start);
+
+  let start = logger.time("balance the sizes of the new chunks");
+  balance_new_css_chunks(
+    &mut new_css_chunks,
+    &positions,
+    &module_infos,
+    self.min_size,
+    self.max_size,
+  );
+  logger.time_end(start);
+
+  // Stores the new chunk for every module
+  let mut new_chunks_by_module: IdentifierMap<ChunkUkey> = IdentifierMap::default();
+  for new_css_chunk in new_css_chunks {
     let new_chunk_ukey =
       Compilation::add_chunk(&mut compilation.build_chunk_graph_artifact.chunk_by_ukey);
     #[allow(clippy::unwrap_used)]
@@ -380,8 +462,7 @@ async fn optimize_chunks(&self, compilation: &mut Compilation) -> Result<Option<
     new_chunk.prevent_integration();
     new_chunk.add_id_name_hints("css".to_string());
     let chunk_graph = &mut compilation.build_chunk_graph_artifact.chunk_graph;
-    for module_identifier in &new_chunk_modules {
-      remaining_modules.shift_remove(module_identifier);
+    for module_identifier in &new_css_chunk.modules {
       chunk_graph.connect_chunk_and_module(new_chunk_ukey, *module_identifier);
       new_chunks_by_module.insert(*module_identifier, new_chunk_ukey);
     }
@@ -411,9 +492,175 @@ async fn optimize_chunks(&self, compilation: &mut Compilation) -> Result<Option<
   }
   logger.time_end(start);
 
+  if !conflicts.is_empty() {
+    let chunk_group_by_ukey = &compilation.build_chunk_graph_artifact.chunk_group_by_ukey;
+    let chunk_names = chunk_states
+      .keys()
+      .map(|chunk_ukey| {
+        let chunk = compilation
+          .build_chunk_graph_artifact
+          .chunk_by_ukey
+          .expect_get(chunk_ukey);
+        let name = chunk.name().map(ToString::to_string).or_else(|| {
+          chunk
+            .groups()
+            .iter()
+            .filter_map(|group| chunk_group_by_ukey.expect_get(group).name())
+            .min()
+            .map(ToString::to_string)
+        });
+        (*chunk_ukey, name.unwrap_or_default())
+      })
+      .collect();
+    *self.conflicts.lock().expect("should get lock") = CssOrderConflicts {
+      conflicts,
+      chunk_names,
+    };
+  }
+
   Ok(None)
 }
 
+#[plugin_hook(CompilationProcessAssets for CssChunkingPlugin, stage = Compilation::PROCESS_ASSETS_STAGE_REPORT)]
+async fn process_assets(&self, compilation: &mut Compilation) -> Result<()> {
+  let CssOrderConflicts {
+    conflicts,
+    chunk_names,
+  } = std::mem::take(&mut *self.conflicts.lock().expect("should get lock"));
+  if conflicts.is_empty() && self.report.is_none() {
+    return Ok(());
+  }
+
+  // Reported once the chunks have their ids
+  let report = CssChunkingReport::new(&conflicts, &chunk_names, compilation);
+  compilation.extend_diagnostics(report.diagnostics().collect::<Vec<_>>());
+  if let Some(filename) = &self.report {
+    compilation.emit_asset(
+      filename.clone(),
+      CompilationAsset::new(
+        Some(RawStringSource::from(report.to_json_string()).boxed()),
+        AssetInfo::default(),
+      ),
+    );
+  }
+  Ok(())
+}
+
+#[derive(Debug)]
+struct NewCssChunk {
+  modules: Vec<ModuleIdentifier>,
+  size: f64,
+}
+
+/// Whether the modules follow each other, in this order, in all the chunks they are in.
+/// Such modules can be moved between new chunks without changing the order of the css.
+fn is_contiguous<'a>(
+  modules: impl IntoIterator<Item = &'a ModuleIdentifier>,
+  positions: &IdentifierIndexMap<FxHashMap<ChunkUkey, usize>>,
+) -> bool {
+  let mut previous: Option<&FxHashMap<ChunkUkey, usize>> = None;
+  for module_identifier in modules {
+    let Some(current) = positions.get(module_identifier) else {
+      return false;
+    };
+    if let Some(previous) = previous
+      && (previous.len() != current.len()
+        || current
+          .iter()
+          .any(|(chunk_ukey, i)| previous.get(chunk_ukey).is_none_or(|prev| prev + 1 != *i)))
+    {
+      return false;
+    }
+    previous = Some(current);
+  }
+  true
+}
+
+/// Like `SplitChunksPlugin` with `minSize`, the new chunks smaller than `min_size` are
+/// merged into a neighbouring chunk, or take modules from it, as long as the order of
+/// the css is kept and no chunk grows over `max_size`.
+fn balance_new_css_chunks(
+  new_css_chunks: &mut Vec<NewCssChunk>,
+  positions: &IdentifierIndexMap<FxHashMap<ChunkUkey, usize>>,
+  module_infos: &IdentifierMap<(f64, Option<Box<str>>)>,
+  min_size: f64,
+  max_size: f64,
+) {
+  let mut index = 0;
+  while index < new_css_chunks.len() {
+    if new_css_chunks[index].size >= min_size {
+      index += 1;
+      continue;
+    }
+
+    let small = &new_css_chunks[index];
+    let neighbour = new_css_chunks
+      .iter()
+      .enumerate()
+      .filter(|(other, _)| *other != index)
+      .find_map(|(other, chunk)| {
+        if is_contiguous(chunk.modules.iter().chain(&small.modules), positions) {
+          Some((other, true))
+        } else if is_contiguous(small.modules.iter().chain(&chunk.modules), positions) {
+          Some((other, false))
+        } else {
+          None
+        }
+      });
+    let Some((other, before)) = neighbour else {
+      index += 1;
+      continue;
+    };
+
+    if new_css_chunks[other].size + new_css_chunks[index].size <= max_size {
+      let small = new_css_chunks.remove(index);
+      let other = if other > index { other - 1 } else { other };
+      let neighbour = &mut new_css_chunks[other];
+      if before {
+        neighbour.modules.extend(small.modules);
+      } else {
+        neighbour.modules.splice(0..0, small.modules);
+      }
+      neighbour.size += small.size;
+      // The merged chunk may still be too small
+      index = 0;
+      continue;
+    }
+
+    // Too large to be merged, the modules next to the small chunk are moved into it
+    while new_css_chunks[index].size < min_size && new_css_chunks[other].modules.len() > 1 {
+      let modules = &new_css_chunks[other].modules;
+      #[allow(clippy::unwrap_used)]
+      let module_identifier = if before {
+        *modules.last().unwrap()
+      } else {
+        modules[0]
+      };
+      let size = module_infos[&module_identifier].0;
+      if new_css_chunks[other].size - size < min_size
+        || new_css_chunks[index].size + size > max_size
+      {
+        break;
+      }
+      let neighbour = &mut new_css_chunks[other];
+      if before {
+        neighbour.modules.pop();
+      } else {
+        neighbour.modules.remove(0);
+      }
+      neighbour.size -= size;
+      let small = &mut new_css_chunks[index];
+      if before {
+        small.modules.insert(0, module_identifier);
+      } else {
+        small.modules.push(module_identifier);
+      }
+      small.size += size;
+    }
+    index += 1;
+  }
+}
+
 impl Plugin for CssChunkingPlugin {
   fn name(&self) -> &'static str {
     "rspack.CssChunkingPlugin"
@@ -427,6 +674,119 @@ impl Plugin for CssChunkingPlugin {
       .optimize_chunks
       .tap(optimize_chunks::new(self));
 
+    ctx
+      .compilation_hooks
+      .process_assets
+      .tap(process_assets::new(self));
+
     Ok(())
   }
 }
+
+#[cfg(test)]
+mod test {
+  use super::*;
+
+  fn positions(chunks: &[(ChunkUkey, &[&str])]) -> IdentifierIndexMap<FxHashMap<ChunkUkey, usize>> {
+    let mut positions: IdentifierIndexMap<FxHashMap<ChunkUkey, usize>> = Default::default();
+    for (chunk_ukey, modules) in chunks {
+      for (i, module) in modules.iter().enumerate() {
+        positions
+          .entry(ModuleIdentifier::from(*module))
+          .or_default()
+          .insert(*chunk_ukey, i);
+      }
+    }
+    positions
+  }
+
+  fn module_infos(sizes: &[(&str, f64)]) -> IdentifierMap<(f64, Option<Box<str>>)> {
+    sizes
+      .iter()
+      .map(|(module, size)| (ModuleIdentifier::from(*module), (*size, None)))
+      .collect()
+  }
+
+  fn new_css_chunk(
+    modules: &[&str],
+    module_infos: &IdentifierMap<(f64, Option<Box<str>>)>,
+  ) -> NewCssChunk {
+    let modules = modules
+      .iter()
+      .map(|module| ModuleIdentifier::from(*module))
+      .collect::<Vec<_>>();
+    let size = modules.iter().map(|module| module_infos[module].0).sum();
+    NewCssChunk { modules, size }
+  }
+
+  fn modules_of(new_css_chunks: &[NewCssChunk]) -> Vec<Vec<String>> {
+    new_css_chunks
+      .iter()
+      .map(|chunk| chunk.modules.iter().map(ToString::to_string).collect())
+      .collect()
+  }
+
+  #[test]
+  fn contiguous_modules() {
+    let positions = positions(&[
+      (ChunkUkey::new(), &["a", "b", "c"]),
+      (ChunkUkey::new(), &["a", "b"]),
+    ]);
+    let is_contiguous = |modules: &[&str]| {
+      let modules = modules
+        .iter()
+        .map(|module| ModuleIdentifier::from(*module))
+        .collect::<Vec<_>>();
+      is_contiguous(&modules, &positions)
+    };
+    assert!(is_contiguous(&["a"]));
+    assert!(is_contiguous(&["a", "b"]));
+    // `c` isn't in all the chunks of `b`
+    assert!(!is_contiguous(&["b", "c"]));
+    assert!(!is_contiguous(&["b", "a"]));
+    assert!(!is_contiguous(&["a", "c"]));
+    assert!(!is_contiguous(&["a", "unknown"]));
+  }
+
+  #[test]
+  fn balance_merges_small_chunks() {
+    let positions = positions(&[(ChunkUkey::new(), &["a", "b", "c"])]);
+    let module_infos = module_infos(&[("a", 5.0), ("b", 5.0), ("c", 5.0)]);
+    let mut new_css_chunks = vec![
+      new_css_chunk(&["a", "b"], &module_infos),
+      new_css_chunk(&["c"], &module_infos),
+    ];
+    balance_new_css_chunks(&mut new_css_chunks, &positions, &module_infos, 20.0, 100.0);
+    assert_eq!(modules_of(&new_css_chunks), vec![vec!["a", "b", "c"]]);
+    assert_eq!(new_css_chunks[0].size, 15.0);
+  }
+
+  #[test]
+  fn balance_moves_modules_within_max_size() {
+    let positions = positions(&[(ChunkUkey::new(), &["a", "b", "c"])]);
+    let module_infos = module_infos(&[("a", 45.0), ("b", 45.0), ("c", 20.0)]);
+    let mut new_css_chunks = vec![
+      new_css_chunk(&["a", "b"], &module_infos),
+      new_css_chunk(&["c"], &module_infos),
+    ];
+    balance_new_css_chunks(&mut new_css_chunks, &positions, &module_infos, 30.0, 100.0);
+    assert_eq!(modules_of(&new_css_chunks), vec![vec!["a"], vec!["b", "c"]]);
+    assert_eq!(new_css_chunks[0].size, 45.0);
+    assert_eq!(new_css_chunks[1].size, 65.0);
+  }
+
+  #[test]
+  fn balance_keeps_the_order_of_the_css() {
+    let positions = positions(&[
+      (ChunkUkey::new(), &["a", "b"]),
+      (ChunkUkey::new(), &["b", "a"]),
+    ]);
+    let module_infos = module_infos(&[("a", 5.0), ("b", 5.0)]);
+    let mut new_css_chunks = vec![
+      new_css_chunk(&["a"], &module_infos),
+      new_css_chunk(&["b"], &module_infos),
+    ];
+    balance_new_css_chunks(&mut new_css_chunks, &positions, &module_infos, 20.0, 100.0);
+    assert_eq!(modules_of(&new_css_chunks), vec![vec!["a"], vec!["b"]]);
+  }
+}
@@ -0,0 +1,194 @@
+use rspack_collections::IdentifierIndexMap;
+use rspack_core::{ChunkUkey, Compilation, ModuleIdentifier};
+use rspack_error::Diagnostic;
+use rustc_hash::FxHashMap;
+use serde::Serialize;
+
+/// Two css modules which are in opposite orders in two chunks, so they can't be merged
+/// in one chunk without changing the order of one of them.
+#[derive(Debug)]
+pub(crate) struct CssOrderConflict {
+  first: ModuleIdentifier,
+  second: ModuleIdentifier,
+  /// Chunks where `first` is before `second`.
+  chunks: Vec<ChunkUkey>,
+  /// Chunks where `second` is before `first`.
+  reversed_chunks: Vec<ChunkUkey>,
+}
+
+impl CssOrderConflict {
+  pub(crate) fn new(
+    first: ModuleIdentifier,
+    second: ModuleIdentifier,
+    positions: &IdentifierIndexMap<FxHashMap<ChunkUkey, usize>>,
+  ) -> Option<Self> {
+    let first_positions = positions.get(&first)?;
+    let second_positions = positions.get(&second)?;
+    let mut chunks = vec![];
+    let mut reversed_chunks = vec![];
+    for (chunk, first_index) in first_positions {
+      match second_positions.get(chunk) {
+        Some(second_index) if first_index < second_index => chunks.push(*chunk),
+        Some(_) => reversed_chunks.push(*chunk),
+        None => {}
+      }
+    }
+    if chunks.is_empty() || reversed_chunks.is_empty() {
+      return None;
+    }
+    Some(Self {
+      first,
+      second,
+      chunks,
+      reversed_chunks,
+    })
+  }
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct CssOrderConflictReport {
+  first: String,
+  second: String,
+  chunks: Vec<String>,
+  reversed_chunks: Vec<String>,
+}
+
+/// The ordering conflicts which prevented css modules from being merged, resolved to
+/// the names of the modules and chunks once the chunks have their ids.
+#[derive(Debug, Default, Serialize)]
+pub(crate) struct CssChunkingReport {
+  conflicts: Vec<CssOrderConflictReport>,
+}
+
+impl CssChunkingReport {
+  pub(crate) fn new(
+    conflicts: &[CssOrderConflict],
+    chunk_names: &FxHashMap<ChunkUkey, String>,
+    compilation: &Compilation,
+  ) -> Self {
+    let module_graph = compilation.get_module_graph();
+    let module_name = |module: &ModuleIdentifier| {
+      module_graph
+        .module_by_identifier(module)
+        .map(|module| {
+          module
+            .readable_identifier(&compilation.options.context)
+            .to_string()
+        })
+        .unwrap_or_else(|| module.to_string())
+    };
+    let chunk_name = |chunk: &ChunkUkey| {
+      compilation
+        .build_chunk_graph_artifact
+        .chunk_by_ukey
+        .get(chunk)
+        .and_then(|chunk| {
+          chunk
+            .name()
+            .map(ToString::to_string)
+            .or_else(|| chunk.id().map(ToString::to_string))
+        })
+        .or_else(|| chunk_names.get(chunk).cloned())
+        .unwrap_or_default()
+    };
+
+    let mut conflicts = conflicts
+      .iter()
+      .map(|conflict| CssOrderConflictReport {
+        first: module_name(&conflict.first),
+        second: module_name(&conflict.second),
+        chunks: conflict.chunks.iter().map(chunk_name).collect(),
+        reversed_chunks: conflict.reversed_chunks.iter().map(chunk_name).collect(),
+      })
+      .collect::<Vec<_>>();
+    for conflict in &mut conflicts {
+      conflict.chunks.sort();
+      conflict.reversed_chunks.sort();
+    }
+    conflicts.sort_by(|a, b| (&a.first, &a.second).cmp(&(&b.first, &b.second)));
+    Self { conflicts }
+  }
+
+  pub(crate) fn diagnostics(&self) -> impl Iterator<Item = Diagnostic> + '_ {
+    self.conflicts.iter().map(|conflict| {
+      Diagnostic::warn(
+        "Conflicting order".into(),
+        format!(
+          "CSS modules can't be merged in one chunk, as they are in opposite orders:\n  {} is before {} in chunk {}\n  {} is before {} in chunk {}",
+          conflict.first,
+          conflict.second,
+          conflict.chunks.join(", "),
+          conflict.second,
+          conflict.first,
+          conflict.reversed_chunks.join(", "),
+        ),
+      )
+    })
+  }
+
+  pub(crate) fn to_json_string(&self) -> String {
+    serde_json::to_string_pretty(self).expect("report should be serializable")
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use super::*;
+
+  #[test]
+  fn conflict_of_opposite_orders() {
+    let (foo, bar, baz) = (ChunkUkey::new(), ChunkUkey::new(), ChunkUkey::new());
+    let (a, b) = (ModuleIdentifier::from("a"), ModuleIdentifier::from("b"));
+    let mut positions: IdentifierIndexMap<FxHashMap<ChunkUkey, usize>> = Default::default();
+    positions.insert(a, [(foo, 0), (bar, 1), (baz, 0)].into_iter().collect());
+    positions.insert(b, [(foo, 1), (bar, 0)].into_iter().collect());
+
+    let conflict = CssOrderConflict::new(a, b, &positions).expect("should be a conflict");
+    assert_eq!(conflict.chunks, vec![foo]);
+    assert_eq!(conflict.reversed_chunks, vec![bar]);
+
+    positions.insert(b, [(foo, 1), (baz, 1)].into_iter().collect());
+    assert!(CssOrderConflict::new(a, b, &positions).is_none());
+  }
+
+  #[test]
+  fn report_as_warnings_and_json() {
+    let report = CssChunkingReport {
+      conflicts: vec![CssOrderConflictReport {
+        first: "./a.css".into(),
+        second: "./b.css".into(),
+        chunks: vec!["foo".into()],
+        reversed_chunks: vec!["bar".into(), "baz".into()],
+      }],
+    };
+
+    let diagnostics = report.diagnostics().collect::<Vec<_>>();
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(
+      diagnostics[0].message,
+      "CSS modules can't be merged in one chunk, as they are in opposite orders:
+  ./a.css is before ./b.css in chunk foo
+  ./b.css is before ./a.css in chunk bar, baz"
+    );
+
+    assert_eq!(
+      report.to_json_string(),
+      r#"{
+  "conflicts": [
+    {
+      "first": "./a.css",
+      "second": "./b.css",
+      "chunks": [
+        "foo"
+      ],
+      "reversedChunks": [
+        "bar",
+        "baz"
+      ]
+    }
+  ]
+}"#
+    );
+  }
+}
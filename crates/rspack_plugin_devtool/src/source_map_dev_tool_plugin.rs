@@ -7,17 +7,17 @@ use std::{
 use cow_utils::CowUtils;
 use derive_more::Debug;
 use futures::future::BoxFuture;
-use itertools::Itertools;
+use itertools::{Either, Itertools};
 use regex::Regex;
 use rspack_core::{
   AssetInfo, CacheCount, Chunk, ChunkUkey, Compilation, CompilationAsset, CompilationParams,
-  CompilationProcessAssets, CompilerCompilation, Filename, Logger, ModuleIdentifier, PathData,
-  Plugin,
+  CompilationProcessAssets, CompilerCompilation, Filename, Logger, ModuleGraph, ModuleIdentifier,
+  PathData, Plugin,
   cache::persistent::occasion::SourceMapDevToolPluginCache,
   has_content_hash_placeholder,
   rspack_sources::{
-    BoxSource, ConcatSource, MapOptions, ObjectPool, RawBufferSource, RawStringSource, Source,
-    SourceExt, SourceMap, SourceValue,
+    BoxSource, ConcatSource, IndexSourceMap, MapOptions, ObjectPool, RawBufferSource,
+    RawStringSource, Section, Source, SourceExt, SourceMap, SourceValue,
   },
 };
 use rspack_error::{Result, ToStringResultToRspackResultExt, error};
@@ -116,24 +116,36 @@ fn relative_source_name_from_url(
 }
 
 /// Compute source references from a source map's sources list.
+///
+/// The references of an index source map are those of its sections, in order.
 fn compute_source_references(
   compilation: &Compilation,
-  source_map: &SourceMap<'_>,
+  source_map: &AssetSourceMap,
+) -> Vec<SourceReference> {
+  source_references_of(
+    compilation.options.context.as_str(),
+    compilation.get_module_graph(),
+    source_map,
+  )
+}
+
+/// The source references of `source_map`, with the modules looked up in `module_graph`.
+fn source_references_of(
+  context: &str,
+  module_graph: &ModuleGraph,
+  source_map: &AssetSourceMap,
 ) -> Vec<SourceReference> {
   source_map
-    .sources()
-    .iter()
+    .maps()
+    .flat_map(|map| map.sources())
     .map(|source_name| {
       if let Some(stripped) = source_name
         .strip_prefix("webpack://")
         .or_else(|| source_name.strip_prefix("rspack://"))
       {
-        let source_name = make_paths_absolute(compilation.options.context.as_str(), stripped);
+        let source_name = make_paths_absolute(context, stripped);
         let identifier = ModuleIdentifier::from(source_name.as_str());
-        match compilation
-          .get_module_graph()
-          .module_by_identifier(&identifier)
-        {
+        match module_graph.module_by_identifier(&identifier) {
           Some(module) => SourceReference::Module(module.identifier()),
           None => SourceReference::Source(Arc::from(source_name)),
         }
@@ -144,6 +156,33 @@ fn compute_source_references(
     .collect()
 }
 
+/// Names the sources of an asset, a source with a scheme keeps its name and the others are named
+/// by `create_source_name`.
+async fn create_source_name_entries<F, Fut>(
+  source_references: &[SourceReference],
+  unresolved_source_map_path: Option<&Utf8PathBuf>,
+  create_source_name: F,
+) -> Result<Vec<(SourceReference, SourceNameWithBaseUrl)>>
+where
+  F: Fn(SourceReference) -> Fut,
+  Fut: Future<Output = Result<String>>,
+{
+  let mut source_name_entries = Vec::with_capacity(source_references.len());
+  for source_reference in source_references {
+    let source_name = match source_reference {
+      SourceReference::Source(source_name) if is_schema_source(source_name.as_ref()) => {
+        source_name.to_string()
+      }
+      _ => create_source_name(source_reference.clone()).await?,
+    };
+    source_name_entries.push((
+      source_reference.clone(),
+      SourceNameWithBaseUrl::new(source_name, unresolved_source_map_path.cloned()),
+    ));
+  }
+  Ok(source_name_entries)
+}
+
 static URL_FORMATTING_REGEXP: LazyLock<Regex> = LazyLock::new(|| {
   Regex::new(r"^\n\/\/(.*)$").expect("failed to compile URL_FORMATTING_REGEXP regex")
 });
@@ -195,6 +234,8 @@ pub struct SourceMapDevToolPluginOptions {
   pub include: Option<AssetConditions>,
   pub exclude: Option<AssetConditions>,
   pub debug_ids: bool,
+  /// Emit an index source map made of the source maps of the parts of each asset, instead of merging them into a single source map.
+  pub index_map: bool,
 }
 
 impl SourceMapDevToolPluginOptions {
@@ -216,10 +257,28 @@ enum SourceMappingUrlCommentRef<'a> {
   Fn(&'a AppendFn),
 }
 
+/// The source map of an asset, either a single map or an index map with a section for each
+/// part of the asset.
+enum AssetSourceMap {
+  Map(SourceMap<'static>),
+  Index(IndexSourceMap<'static>),
+}
+
+impl AssetSourceMap {
+  fn maps(&self) -> impl Iterator<Item = &SourceMap<'static>> {
+    match self {
+      AssetSourceMap::Map(map) => Either::Left(std::iter::once(map)),
+      AssetSourceMap::Index(index_map) => {
+        Either::Right(index_map.sections().iter().map(|section| &section.map))
+      }
+    }
+  }
+}
+
 struct SourceMapTask {
   pub asset_filename: Arc<str>,
   pub source: BoxSource,
-  pub source_map: SourceMap<'static>,
+  pub source_map: AssetSourceMap,
   pub unresolved_source_map_path: Option<Utf8PathBuf>,
   pub source_references: Vec<SourceReference>,
 }
@@ -315,6 +374,7 @@ pub struct SourceMapDevToolPlugin {
   include: Option<AssetConditions>,
   exclude: Option<AssetConditions>,
   debug_ids: bool,
+  index_map: bool,
 }
 
 impl SourceMapDevToolPlugin {
@@ -336,6 +396,7 @@ impl SourceMapDevToolPlugin {
       include,
       exclude,
       debug_ids,
+      index_map,
     } = options;
 
     let source_mapping_url_comment = match append {
@@ -369,9 +430,27 @@ impl SourceMapDevToolPlugin {
       include,
       exclude,
       debug_ids,
+      index_map,
     )
   }
 
+  fn get_source_map(
+    &self,
+    source: BoxSource,
+    object_pool: &ObjectPool,
+    map_options: &MapOptions,
+  ) -> Option<AssetSourceMap> {
+    if self.index_map {
+      source
+        .index_map_static(object_pool, map_options)
+        .map(AssetSourceMap::Index)
+    } else {
+      source
+        .map_static(object_pool, map_options)
+        .map(AssetSourceMap::Map)
+    }
+  }
+
   // Only used when resolving [relative-resource-path].
   // It does not provide values for placeholders, so no rendering is performed here.
   // External source maps use the source map file path as the base; inline source maps
@@ -525,209 +604,189 @@ impl SourceMapDevToolPlugin {
       .unwrap_or(default_module_filename_template);
 
     let results: Vec<Result<Option<TaskAndSourceNames>>> = match module_filename_template {
-      ModuleFilenameTemplate::String(template) => rspack_parallel::scope::<
-        _,
-        Result<Option<TaskAndSourceNames>>,
-      >(|token| {
-        for (asset_filename, asset) in compilation_assets {
-          let is_match = if need_match {
-            match_object(&condition_object, &asset_filename)
-          } else {
-            true
-          };
-          if !is_match {
-            continue;
-          }
-          let source = match asset.get_source() {
-            Some(s) => s.clone(),
-            None => continue,
-          };
-
-          let map_options = map_options.clone();
-          let s = unsafe {
-            token.used((
-              self,
-              compilation,
-              file_to_chunk,
-              output_path,
-              template,
-              &tls,
-            ))
-          };
-          s.spawn(
-            |(plugin, compilation, file_to_chunk, output_path, template, tls)| async move {
-              let source_map = {
-                let object_pool = tls.get_or(ObjectPool::default);
-                match source.clone().map_static(object_pool, &map_options) {
-                  Some(sm) => sm,
-                  None => return Ok(None),
-                }
-              };
-
-              let source_references = compute_source_references(compilation, &source_map);
-
-              let asset_filename: Arc<str> = Arc::from(asset_filename);
-              let unresolved_source_map_path = plugin
-                .get_unresolved_source_map_path(compilation, output_path, &asset_filename)
+      ModuleFilenameTemplate::String(template) => {
+        rspack_parallel::scope::<_, Result<Option<TaskAndSourceNames>>>(|token| {
+          for (asset_filename, asset) in compilation_assets {
+            let is_match = if need_match {
+              match_object(&condition_object, &asset_filename)
+            } else {
+              true
+            };
+            if !is_match {
+              continue;
+            }
+            let source = match asset.get_source() {
+              Some(s) => s.clone(),
+              None => continue,
+            };
+
+            let map_options = map_options.clone();
+            let s = unsafe {
+              token.used((
+                self,
+                compilation,
+                file_to_chunk,
+                output_path,
+                template,
+                &tls,
+              ))
+            };
+            s.spawn(
+              |(plugin, compilation, file_to_chunk, output_path, template, tls)| async move {
+                let source_map = {
+                  let object_pool = tls.get_or(ObjectPool::default);
+                  match plugin.get_source_map(source.clone(), object_pool, &map_options) {
+                    Some(sm) => sm,
+                    None => return Ok(None),
+                  }
+                };
+
+                let source_references = compute_source_references(compilation, &source_map);
+
+                let asset_filename: Arc<str> = Arc::from(asset_filename);
+                let unresolved_source_map_path = plugin
+                  .get_unresolved_source_map_path(compilation, output_path, &asset_filename)
+                  .await?;
+
+                let chunk = file_to_chunk.get(asset_filename.as_ref());
+                let path_data = match chunk {
+                  Some(chunk) => PathData::default()
+                    .chunk(chunk.ukey(), compilation)
+                    .chunk_id_optional(chunk.id().map(|id| id.as_str()))
+                    .chunk_name_optional(chunk.name())
+                    .chunk_hash_optional(chunk.rendered_hash(
+                      &compilation.chunk_hashes_artifact,
+                      compilation.options.output.hash_digest_length,
+                    )),
+                  None => PathData::default(),
+                };
+
+                let filename = Filename::from(plugin.namespace.clone());
+                let namespace = compilation.get_path(&filename, path_data).await?;
+                let namespace = namespace.as_str();
+                let source_map_path = unresolved_source_map_path.as_deref();
+                let source_name_entries = create_source_name_entries(
+                  &source_references,
+                  unresolved_source_map_path.as_ref(),
+                  move |source_reference| async move {
+                    Ok(ModuleFilenameHelpers::create_filename_of_string_template(
+                      &source_reference,
+                      compilation,
+                      template,
+                      &compilation.options.output,
+                      namespace,
+                      source_map_path,
+                    ))
+                  },
+                )
                 .await?;
 
-              let chunk = file_to_chunk.get(asset_filename.as_ref());
-              let path_data = match chunk {
-                Some(chunk) => PathData::default()
-                  .chunk(chunk.ukey(), compilation)
-                  .chunk_id_optional(chunk.id().map(|id| id.as_str()))
-                  .chunk_name_optional(chunk.name())
-                  .chunk_hash_optional(chunk.rendered_hash(
-                    &compilation.chunk_hashes_artifact,
-                    compilation.options.output.hash_digest_length,
-                  )),
-                None => PathData::default(),
-              };
-
-              let filename = Filename::from(plugin.namespace.clone());
-              let namespace = compilation.get_path(&filename, path_data).await?;
-
-              let mut source_name_entries = Vec::with_capacity(source_references.len());
-              for source_reference in &source_references {
-                if let SourceReference::Source(sn) = source_reference
-                  && is_schema_source(sn.as_ref())
-                {
-                  source_name_entries.push((
-                    source_reference.clone(),
-                    SourceNameWithBaseUrl::new(sn.to_string(), unresolved_source_map_path.clone()),
-                  ));
-                  continue;
-                }
-
-                let source_name = ModuleFilenameHelpers::create_filename_of_string_template(
-                  source_reference,
-                  compilation,
-                  template,
-                  &compilation.options.output,
-                  &namespace,
-                  unresolved_source_map_path.as_ref().map(|p| p.as_path()),
-                );
-                source_name_entries.push((
-                  source_reference.clone(),
-                  SourceNameWithBaseUrl::new(source_name, unresolved_source_map_path.clone()),
-                ));
-              }
-
-              let raw_source = match source.source() {
-                SourceValue::String(cow) => RawStringSource::from(cow.into_owned()).boxed(),
-                SourceValue::Buffer(cow) => RawBufferSource::from(cow.into_owned()).boxed(),
-              };
-              let task = SourceMapTask {
-                asset_filename,
-                source: raw_source,
-                source_map,
-                unresolved_source_map_path,
-                source_references,
-              };
-
-              Ok(Some((task, source_name_entries)))
-            },
-          );
-        }
-      })
-      .await
-      .into_iter()
-      .map(|r| r.to_rspack_result().flatten())
-      .collect::<Vec<_>>(),
-      ModuleFilenameTemplate::Fn(f) => rspack_parallel::scope::<
-        _,
-        Result<Option<TaskAndSourceNames>>,
-      >(|token| {
-        for (asset_filename, asset) in compilation_assets {
-          let is_match = if need_match {
-            match_object(&condition_object, &asset_filename)
-          } else {
-            true
-          };
-          if !is_match {
-            continue;
+                let raw_source = match source.source() {
+                  SourceValue::String(cow) => RawStringSource::from(cow.into_owned()).boxed(),
+                  SourceValue::Buffer(cow) => RawBufferSource::from(cow.into_owned()).boxed(),
+                };
+                let task = SourceMapTask {
+                  asset_filename,
+                  source: raw_source,
+                  source_map,
+                  unresolved_source_map_path,
+                  source_references,
+                };
+
+                Ok(Some((task, source_name_entries)))
+              },
+            );
           }
-          let source = match asset.get_source() {
-            Some(s) => s.clone(),
-            None => continue,
-          };
-
-          let asset_filename: Arc<str> = Arc::from(asset_filename);
-          let map_options = map_options.clone();
-          let s = unsafe {
-            token.used((
-              self,
-              compilation,
-              output_path,
-              f,
-              source,
-              asset_filename,
-              &tls,
-            ))
-          };
-          s.spawn(
-            |(plugin, compilation, output_path, f, source, asset_filename, tls)| async move {
-              let source_map = {
-                let object_pool = tls.get_or(ObjectPool::default);
-                match source.clone().map_static(object_pool, &map_options) {
-                  Some(sm) => sm,
-                  None => return Ok(None),
-                }
-              };
-
-              let source_references = compute_source_references(compilation, &source_map);
-              let unresolved_source_map_path = plugin
-                .get_unresolved_source_map_path(compilation, output_path, &asset_filename)
-                .await?;
-
-              let mut source_name_entries = Vec::with_capacity(source_references.len());
-              for source_reference in &source_references {
-                if let SourceReference::Source(sn) = source_reference
-                  && is_schema_source(sn.as_ref())
-                {
-                  source_name_entries.push((
-                    source_reference.clone(),
-                    SourceNameWithBaseUrl::new(sn.to_string(), unresolved_source_map_path.clone()),
-                  ));
-                  continue;
-                }
-
-                let source_name = ModuleFilenameHelpers::create_filename_of_fn_template(
-                  source_reference,
-                  compilation,
-                  f,
-                  &compilation.options.output,
-                  &plugin.namespace,
-                  unresolved_source_map_path.as_ref().map(|p| p.as_path()),
+        })
+        .await
+        .into_iter()
+        .map(|r| r.to_rspack_result().flatten())
+        .collect::<Vec<_>>()
+      }
+      ModuleFilenameTemplate::Fn(f) => {
+        rspack_parallel::scope::<_, Result<Option<TaskAndSourceNames>>>(|token| {
+          for (asset_filename, asset) in compilation_assets {
+            let is_match = if need_match {
+              match_object(&condition_object, &asset_filename)
+            } else {
+              true
+            };
+            if !is_match {
+              continue;
+            }
+            let source = match asset.get_source() {
+              Some(s) => s.clone(),
+              None => continue,
+            };
+
+            let asset_filename: Arc<str> = Arc::from(asset_filename);
+            let map_options = map_options.clone();
+            let s = unsafe {
+              token.used((
+                self,
+                compilation,
+                output_path,
+                f,
+                source,
+                asset_filename,
+                &tls,
+              ))
+            };
+            s.spawn(
+              |(plugin, compilation, output_path, f, source, asset_filename, tls)| async move {
+                let source_map = {
+                  let object_pool = tls.get_or(ObjectPool::default);
+                  match plugin.get_source_map(source.clone(), object_pool, &map_options) {
+                    Some(sm) => sm,
+                    None => return Ok(None),
+                  }
+                };
+
+                let source_references = compute_source_references(compilation, &source_map);
+                let unresolved_source_map_path = plugin
+                  .get_unresolved_source_map_path(compilation, output_path, &asset_filename)
+                  .await?;
+
+                let source_map_path = unresolved_source_map_path.as_deref();
+                let source_name_entries = create_source_name_entries(
+                  &source_references,
+                  unresolved_source_map_path.as_ref(),
+                  move |source_reference| async move {
+                    ModuleFilenameHelpers::create_filename_of_fn_template(
+                      &source_reference,
+                      compilation,
+                      f,
+                      &compilation.options.output,
+                      &plugin.namespace,
+                      source_map_path,
+                    )
+                    .await
+                  },
                 )
                 .await?;
-                source_name_entries.push((
-                  source_reference.clone(),
-                  SourceNameWithBaseUrl::new(source_name, unresolved_source_map_path.clone()),
-                ));
-              }
-
-              let raw_source = match source.source() {
-                SourceValue::String(cow) => RawStringSource::from(cow.into_owned()).boxed(),
-                SourceValue::Buffer(cow) => RawBufferSource::from(cow.into_owned()).boxed(),
-              };
-              let task = SourceMapTask {
-                asset_filename,
-                source: raw_source,
-                source_map,
-                unresolved_source_map_path,
-                source_references,
-              };
 
-              Ok(Some((task, source_name_entries)))
-            },
-          );
-        }
-      })
-      .await
-      .into_iter()
-      .map(|r| r.to_rspack_result().flatten())
-      .collect::<Vec<_>>(),
+                let raw_source = match source.source() {
+                  SourceValue::String(cow) => RawStringSource::from(cow.into_owned()).boxed(),
+                  SourceValue::Buffer(cow) => RawBufferSource::from(cow.into_owned()).boxed(),
+                };
+                let task = SourceMapTask {
+                  asset_filename,
+                  source: raw_source,
+                  source_map,
+                  unresolved_source_map_path,
+                  source_references,
+                };
+
+                Ok(Some((task, source_name_entries)))
+              },
+            );
+          }
+        })
+        .await
+        .into_iter()
+        .map(|r| r.to_rspack_result().flatten())
+        .collect::<Vec<_>>()
+      }
     };
 
     let mut tasks: Vec<SourceMapTask> = Vec::with_capacity(results.len());
@@ -906,15 +965,66 @@ impl SourceMapDevToolPlugin {
 
   fn source_map_to_json<'a>(
     &'a self,
-    mut source_map: SourceMap<'a>,
+    source_map: &'a AssetSourceMap,
     reference_to_source_name_mapping: &'a ReferenceToSourceNameMapping,
     asset_filename: &'a str,
     source_map_path: Option<&'a Utf8Path>,
     source_references: &'a [SourceReference],
     debug_id: Option<&'a str>,
   ) -> String {
-    // Update source_map with deduplicated source names
-    source_map.set_file(Some(Cow::Borrowed(asset_filename)));
+    match source_map {
+      AssetSourceMap::Map(source_map) => {
+        let mut source_map = source_map.as_borrowed();
+        source_map.set_file(Some(Cow::Borrowed(asset_filename)));
+        self.update_source_map(
+          &mut source_map,
+          reference_to_source_name_mapping,
+          asset_filename,
+          source_map_path,
+          source_references,
+        );
+        if let Some(debug_id) = debug_id {
+          source_map.set_debug_id(Some(Cow::Borrowed(debug_id)));
+        }
+        source_map.to_json()
+      }
+      AssetSourceMap::Index(index_map) => {
+        // The references of the sections follow each other
+        let mut source_references = source_references;
+        let mut sections = Vec::with_capacity(index_map.sections().len());
+        for section in index_map.sections() {
+          let (section_references, rest) = source_references.split_at(section.map.sources().len());
+          source_references = rest;
+          let mut map = section.map.as_borrowed();
+          self.update_source_map(
+            &mut map,
+            reference_to_source_name_mapping,
+            asset_filename,
+            source_map_path,
+            section_references,
+          );
+          sections.push(Section {
+            offset: section.offset,
+            map,
+          });
+        }
+        let mut index_map = IndexSourceMap::new(sections);
+        index_map.set_file(Some(Cow::Borrowed(asset_filename)));
+        index_map.set_debug_id(debug_id.map(Cow::Borrowed));
+        index_map.to_json()
+      }
+    }
+  }
+
+  /// Update a source map with deduplicated source names and the options of the plugin.
+  fn update_source_map<'a>(
+    &'a self,
+    source_map: &mut SourceMap<'a>,
+    reference_to_source_name_mapping: &'a ReferenceToSourceNameMapping,
+    asset_filename: &'a str,
+    source_map_path: Option<&'a Utf8Path>,
+    source_references: &'a [SourceReference],
+  ) {
     source_map.set_sources(source_references.iter().map(|source_reference| {
       reference_to_source_name_mapping
         .get(source_reference)
@@ -949,12 +1059,6 @@ impl SourceMapDevToolPlugin {
     if let Some(source_root) = &self.source_root {
       source_map.set_source_root(Some(Cow::Borrowed(source_root.as_ref())));
     }
-
-    if let Some(debug_id) = debug_id {
-      source_map.set_debug_id(Some(Cow::Borrowed(debug_id)));
-    }
-
-    source_map.to_json()
   }
 
   /// Create a single MappedAsset: update the source map and emit asset + optional source map file.
@@ -966,7 +1070,7 @@ impl SourceMapDevToolPlugin {
     reference_to_source_name_mapping: &ReferenceToSourceNameMapping,
     asset_filename: Arc<str>,
     source: BoxSource,
-    source_map: SourceMap<'static>,
+    source_map: AssetSourceMap,
     unresolved_source_map_path: Option<Utf8PathBuf>,
     source_references: Vec<SourceReference>,
   ) -> Result<MappedAsset> {
@@ -977,7 +1081,7 @@ impl SourceMapDevToolPlugin {
     let asset_filename_ref = asset_filename.as_ref();
 
     let source_map_json = plugin.source_map_to_json(
-      source_map.as_borrowed(),
+      &source_map,
       reference_to_source_name_mapping,
       asset_filename_ref,
       unresolved_source_map_path.as_ref().map(|p| p.as_path()),
@@ -1280,3 +1384,108 @@ impl Plugin for SourceMapDevToolPlugin {
     Ok(())
   }
 }
+
+#[cfg(test)]
+mod test {
+  use rspack_core::rspack_sources::{OriginalSource, SourceMapIssue, render_listing};
+
+  use super::*;
+
+  fn plugin(index_map: bool) -> SourceMapDevToolPlugin {
+    SourceMapDevToolPlugin::new(SourceMapDevToolPluginOptions {
+      append: None,
+      columns: true,
+      fallback_module_filename_template: None,
+      file_context: None,
+      filename: Some("[file].map".to_string()),
+      ignore_list: None,
+      module: true,
+      module_filename_template: None,
+      namespace: None,
+      no_sources: false,
+      public_path: None,
+      source_root: None,
+      test: None,
+      include: None,
+      exclude: None,
+      debug_ids: false,
+      index_map,
+    })
+  }
+
+  fn asset() -> BoxSource {
+    ConcatSource::new([
+      RawStringSource::from("(() => {\n").boxed(),
+      OriginalSource::new("const a = 1;\nconsole.log(a);\n", "a.js").boxed(),
+      RawStringSource::from("/* b */ ").boxed(),
+      OriginalSource::new("export default b;", "b.js").boxed(),
+      RawStringSource::from("\n})();").boxed(),
+    ])
+    .boxed()
+  }
+
+  /// Serialize the source map of `source` like the plugin does, and validate it against the
+  /// code of the asset. The sources are named as they are, since there is no module.
+  async fn validate(plugin: &SourceMapDevToolPlugin, source: &BoxSource) -> Vec<SourceMapIssue> {
+    let source_map = plugin
+      .get_source_map(
+        source.clone(),
+        &ObjectPool::default(),
+        &MapOptions::new(true),
+      )
+      .expect("should have a source map");
+    let source_references = source_references_of("/", &ModuleGraph::default(), &source_map);
+    let reference_to_source_name_mapping =
+      create_source_name_entries(&source_references, None, |source_reference| async move {
+        Ok(match source_reference {
+          SourceReference::Source(source) => source.to_string(),
+          SourceReference::Module(module) => module.to_string(),
+        })
+      })
+      .await
+      .expect("should name the sources")
+      .into_iter()
+      .collect::<ReferenceToSourceNameMapping>();
+    let json = plugin.source_map_to_json(
+      &source_map,
+      &reference_to_source_name_mapping,
+      "main.js",
+      None,
+      &source_references,
+      None,
+    );
+    IndexSourceMap::from_json(json)
+      .expect("should parse the source map")
+      .validate(&source.source().into_string_lossy())
+  }
+
+  #[tokio::test]
+  async fn source_map_should_only_leave_the_runtime_unmapped() {
+    let source = asset();
+    let expected = vec![
+      SourceMapIssue::UnmappedCode {
+        generated_line: 1,
+        start_column: 0,
+        end_column: 8,
+      },
+      SourceMapIssue::UnmappedCode {
+        generated_line: 4,
+        start_column: 0,
+        end_column: 7,
+      },
+      SourceMapIssue::UnmappedCode {
+        generated_line: 5,
+        start_column: 0,
+        end_column: 5,
+      },
+    ];
+    for index_map in [false, true] {
+      assert_eq!(
+        validate(&plugin(index_map), &source).await,
+        expected,
+        "index map: {index_map}\n{}",
+        render_listing(source.as_ref())
+      );
+    }
+  }
+}
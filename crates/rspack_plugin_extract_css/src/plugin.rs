@@ -15,8 +15,8 @@ use rspack_core::{
   ParserAndGenerator, ParserOptions, PathData, Plugin, RenderManifestEntry, RuntimeGlobals,
   RuntimeModule, SourceType, get_undo_path, remove_bom,
   rspack_sources::{
-    BoxSource, CachedSource, ConcatSource, RawStringSource, SourceExt, SourceMap, SourceMapSource,
-    WithoutOriginalOptions,
+    BoxSource, CachedSource, ConcatSource, RawStringSource, ReplaceSource, SourceExt, SourceMap,
+    SourceMapSource, WithoutOriginalOptions,
   },
 };
 use rspack_error::{Diagnostic, Result};
@@ -475,17 +475,6 @@ despite it was not able to fulfill desired ordering with these modules:
           self.options.enforce_relative,
         );
 
-        let content = content.cow_replace(ABSOLUTE_PUBLIC_PATH, "");
-        let content = content.cow_replace(SINGLE_DOT_PATH_SEGMENT, ".");
-        let content = content.cow_replace(AUTO_PUBLIC_PATH, &undo_path);
-        let content = content.cow_replace(
-          BASE_URI,
-          chunk
-            .get_entry_options(&compilation.build_chunk_graph_artifact.chunk_group_by_ukey)
-            .and_then(|entry_options| entry_options.base_uri.as_ref())
-            .unwrap_or(&undo_path),
-        );
-
         let rendered: BoxSource = if let Some(source_map) = &module.source_map {
           SourceMapSource::new(WithoutOriginalOptions {
             value: content.to_string(),
@@ -497,6 +486,24 @@ despite it was not able to fulfill desired ordering with these modules:
           RawStringSource::from(content.to_string()).boxed()
         };
 
+        // The placeholders are replaced on the rendered source rather than on `content`,
+        // so that the module's source map columns after a rewritten `url()` move along
+        // with the replaced bytes.
+        let base_uri = chunk
+          .get_entry_options(&compilation.build_chunk_graph_artifact.chunk_group_by_ukey)
+          .and_then(|entry_options| entry_options.base_uri.as_deref())
+          .unwrap_or(&undo_path);
+        let rendered = replace_placeholders(
+          rendered,
+          &content,
+          &[
+            (ABSOLUTE_PUBLIC_PATH, ""),
+            (SINGLE_DOT_PATH_SEGMENT, "."),
+            (AUTO_PUBLIC_PATH, &undo_path),
+            (BASE_URI, base_uri),
+          ],
+        );
+
         // Stripped on the rendered source rather than on `content`, so that the
         // module's source map columns move along with the removed bytes.
         source.add(remove_bom(rendered));
@@ -522,6 +529,39 @@ despite it was not able to fulfill desired ordering with these modules:
   }
 }
 
+/// Replaces the placeholders of `content` on its rendered `source`. The earlier
+/// placeholders take precedence when two of them overlap.
+fn replace_placeholders(
+  source: BoxSource,
+  content: &str,
+  placeholders: &[(&str, &str)],
+) -> BoxSource {
+  let mut matches = placeholders
+    .iter()
+    .enumerate()
+    .flat_map(|(precedence, (placeholder, value))| {
+      content
+        .match_indices(placeholder)
+        .map(move |(start, _)| (start, precedence, start + placeholder.len(), *value))
+    })
+    .collect::<Vec<_>>();
+  if matches.is_empty() {
+    return source;
+  }
+  matches.sort_unstable_by_key(|(start, precedence, ..)| (*start, *precedence));
+
+  let mut source = ReplaceSource::new(source);
+  let mut last_end = 0;
+  for (start, _, end, value) in matches {
+    if start < last_end {
+      continue;
+    }
+    source.replace(start as u32, end as u32, value.to_string(), None);
+    last_end = end;
+  }
+  source.boxed()
+}
+
 #[plugin_hook(CompilerCompilation for PluginCssExtract)]
 async fn compilation(
   &self,
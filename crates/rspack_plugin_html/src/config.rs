@@ -172,6 +172,10 @@ pub struct HtmlRspackPluginOptions {
   pub meta: Option<FxHashMap<String, FxHashMap<String, String>>>,
   pub hash: Option<bool>,
   pub base: Option<HtmlRspackPluginBaseOptions>,
+  /// report undefined variables of the template as errors, otherwise they render nothing
+  pub strict: Option<bool>,
+  /// inline the css rules used by the html, and load the stylesheets asynchronously
+  pub critical_css: Option<bool>,
   /// uid is used to identify the plugin instance on javascript side
   pub uid: Option<u32>,
 }
@@ -212,6 +216,8 @@ impl Default for HtmlRspackPluginOptions {
       meta: None,
       hash: None,
       base: None,
+      strict: None,
+      critical_css: None,
       uid: None,
     }
   }
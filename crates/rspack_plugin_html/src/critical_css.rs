@@ -0,0 +1,911 @@
+use cow_utils::CowUtils;
+use cssparser::{ParseError, Parser, ParserInput, SourcePosition, Token, serialize_string};
+use rspack_util::fx_hash::FxHashMap;
+use swc_core::{atoms::Atom, common::DUMMY_SP};
+use swc_html::{
+  ast::{Child, Element, Namespace, Text},
+  visit::{VisitMut, VisitMutWith},
+};
+
+use crate::parser::CompiledDocument;
+
+#[derive(Debug, Default)]
+struct DomElement {
+  tag_name: String,
+  attributes: Vec<(String, String)>,
+  parent: Option<usize>,
+  prev_sibling: Option<usize>,
+}
+
+impl DomElement {
+  fn attribute(&self, name: &str) -> Option<&str> {
+    self
+      .attributes
+      .iter()
+      .find(|(attr_name, _)| attr_name.eq_ignore_ascii_case(name))
+      .map(|(_, value)| value.as_str())
+  }
+
+  fn has_class(&self, class: &str) -> bool {
+    self
+      .attribute("class")
+      .is_some_and(|value| value.split_ascii_whitespace().any(|c| c == class))
+  }
+}
+
+/// The elements of the rendered html, used to find the css rules needed for the first paint.
+#[derive(Debug, Default)]
+pub struct CriticalDom {
+  elements: Vec<DomElement>,
+}
+
+impl CriticalDom {
+  pub fn from_document(document: &CompiledDocument) -> Self {
+    let mut dom = CriticalDom::default();
+    let children = match document {
+      CompiledDocument::Document(ast) | CompiledDocument::DocumentWithoutDoctype(ast) => {
+        &ast.children
+      }
+      CompiledDocument::DocumentFragment(ast) => &ast.children,
+    };
+    dom.push_children(children, None);
+    dom
+  }
+
+  fn push_children(&mut self, children: &[Child], parent: Option<usize>) {
+    let mut prev_sibling = None;
+    for child in children {
+      let Child::Element(element) = child else {
+        continue;
+      };
+      let index = self.elements.len();
+      self.elements.push(DomElement {
+        tag_name: element.tag_name.to_string(),
+        attributes: element
+          .attributes
+          .iter()
+          .map(|attr| {
+            (
+              attr.name.to_string(),
+              attr
+                .value
+                .as_ref()
+                .map(ToString::to_string)
+                .unwrap_or_default(),
+            )
+          })
+          .collect(),
+        parent,
+        prev_sibling,
+      });
+      prev_sibling = Some(index);
+      self.push_children(&element.children, Some(index));
+      if let Some(content) = &element.content {
+        self.push_children(&content.children, Some(index));
+      }
+    }
+  }
+
+  fn matches(&self, selector: &Selector) -> bool {
+    (0..self.elements.len()).any(|index| self.matches_from(selector, selector.len() - 1, index))
+  }
+
+  fn matches_from(&self, selector: &Selector, position: usize, index: usize) -> bool {
+    let (combinator, compound) = &selector[position];
+    if !compound.matches(&self.elements[index]) {
+      return false;
+    }
+    if position == 0 {
+      return true;
+    }
+    let element = &self.elements[index];
+    match combinator {
+      Combinator::Child => element
+        .parent
+        .is_some_and(|parent| self.matches_from(selector, position - 1, parent)),
+      Combinator::Descendant => {
+        let mut ancestor = element.parent;
+        while let Some(index) = ancestor {
+          if self.matches_from(selector, position - 1, index) {
+            return true;
+          }
+          ancestor = self.elements[index].parent;
+        }
+        false
+      }
+      Combinator::NextSibling => element
+        .prev_sibling
+        .is_some_and(|sibling| self.matches_from(selector, position - 1, sibling)),
+      Combinator::SubsequentSibling => {
+        let mut sibling = element.prev_sibling;
+        while let Some(index) = sibling {
+          if self.matches_from(selector, position - 1, index) {
+            return true;
+          }
+          sibling = self.elements[index].prev_sibling;
+        }
+        false
+      }
+    }
+  }
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Combinator {
+  Descendant,
+  Child,
+  NextSibling,
+  SubsequentSibling,
+}
+
+#[derive(Debug)]
+enum AttributeOperator {
+  Exists,
+  Equal,
+  Includes,
+  DashMatch,
+  Prefix,
+  Suffix,
+  Substring,
+}
+
+#[derive(Debug)]
+struct AttributeSelector {
+  name: String,
+  operator: AttributeOperator,
+  value: String,
+  ignore_case: bool,
+}
+
+impl AttributeSelector {
+  fn matches(&self, element: &DomElement) -> bool {
+    let Some(actual) = element.attribute(&self.name) else {
+      return false;
+    };
+    let eq = |a: &str, b: &str| {
+      if self.ignore_case {
+        a.eq_ignore_ascii_case(b)
+      } else {
+        a == b
+      }
+    };
+    let value = self.value.as_str();
+    match self.operator {
+      AttributeOperator::Exists => true,
+      AttributeOperator::Equal => eq(actual, value),
+      AttributeOperator::Includes => actual.split_ascii_whitespace().any(|item| eq(item, value)),
+      AttributeOperator::DashMatch => {
+        eq(actual, value)
+          || (actual.len() > value.len()
+            && actual.as_bytes()[value.len()] == b'-'
+            && actual
+              .get(..value.len())
+              .is_some_and(|prefix| eq(prefix, value)))
+      }
+      AttributeOperator::Prefix => {
+        !value.is_empty()
+          && actual
+            .get(..value.len())
+            .is_some_and(|prefix| eq(prefix, value))
+      }
+      AttributeOperator::Suffix => {
+        !value.is_empty()
+          && actual.len() >= value.len()
+          && actual
+            .get(actual.len() - value.len()..)
+            .is_some_and(|suffix| eq(suffix, value))
+      }
+      AttributeOperator::Substring => {
+        !value.is_empty()
+          && (0..=actual.len() - value.len().min(actual.len())).any(|start| {
+            actual
+              .get(start..start + value.len())
+              .is_some_and(|part| eq(part, value))
+          })
+      }
+    }
+  }
+}
+
+/// Pseudo-classes and pseudo-elements are ignored, so a compound matches more elements than it
+/// would in the browser, which keeps rules like `a:hover` in the critical css.
+#[derive(Debug, Default)]
+struct Compound {
+  tag_name: Option<String>,
+  ids: Vec<String>,
+  classes: Vec<String>,
+  attributes: Vec<AttributeSelector>,
+}
+
+impl Compound {
+  fn matches(&self, element: &DomElement) -> bool {
+    self
+      .tag_name
+      .as_ref()
+      .is_none_or(|tag_name| tag_name.eq_ignore_ascii_case(&element.tag_name))
+      && self
+        .ids
+        .iter()
+        .all(|id| element.attribute("id") == Some(id.as_str()))
+      && self.classes.iter().all(|class| element.has_class(class))
+      && self
+        .attributes
+        .iter()
+        .all(|attribute| attribute.matches(element))
+  }
+}
+
+type Selector = Vec<(Combinator, Compound)>;
+
+/// Parses a complex selector, returns `None` for the selectors which aren't supported, they are
+/// always kept.
+fn parse_selector(selector: &str) -> Option<Selector> {
+  let mut input = ParserInput::new(selector);
+  let mut parser = Parser::new(&mut input);
+  let mut result: Selector = vec![];
+  let mut combinator = Combinator::Descendant;
+  let mut compound = Compound::default();
+  let mut has_compound = false;
+
+  fn finish(
+    result: &mut Selector,
+    combinator: &mut Combinator,
+    compound: &mut Compound,
+    has_compound: &mut bool,
+  ) {
+    if *has_compound {
+      result.push((*combinator, std::mem::take(compound)));
+      *combinator = Combinator::Descendant;
+      *has_compound = false;
+    }
+  }
+
+  loop {
+    let token = match parser.next_including_whitespace() {
+      Ok(token) => token.clone(),
+      Err(_) => break,
+    };
+    match token {
+      Token::WhiteSpace(_) => {
+        finish(
+          &mut result,
+          &mut combinator,
+          &mut compound,
+          &mut has_compound,
+        );
+      }
+      Token::Delim(c @ ('>' | '+' | '~')) => {
+        finish(
+          &mut result,
+          &mut combinator,
+          &mut compound,
+          &mut has_compound,
+        );
+        if result.is_empty() {
+          return None;
+        }
+        combinator = match c {
+          '>' => Combinator::Child,
+          '+' => Combinator::NextSibling,
+          _ => Combinator::SubsequentSibling,
+        };
+      }
+      Token::Ident(name) if !has_compound => {
+        compound.tag_name = Some(name.to_string());
+        has_compound = true;
+      }
+      Token::Delim('*') if !has_compound => {
+        has_compound = true;
+      }
+      Token::IDHash(id) | Token::Hash(id) => {
+        compound.ids.push(id.to_string());
+        has_compound = true;
+      }
+      Token::Delim('.') => match parser.next_including_whitespace() {
+        Ok(Token::Ident(class)) => {
+          compound.classes.push(class.to_string());
+          has_compound = true;
+        }
+        _ => return None,
+      },
+      Token::SquareBracketBlock => {
+        let attribute = parser
+          .parse_nested_block(|block| {
+            parse_attribute_selector(block).ok_or(block.new_custom_error::<(), ()>(()))
+          })
+          .ok()?;
+        compound.attributes.push(attribute);
+        has_compound = true;
+      }
+      Token::Colon => {
+        // `::before` is a pseudo-element, `:hover` is a pseudo-class, both are ignored.
+        let mut token = parser.next_including_whitespace().ok()?.clone();
+        if matches!(token, Token::Colon) {
+          token = parser.next_including_whitespace().ok()?.clone();
+        }
+        match token {
+          Token::Ident(_) => {}
+          Token::Function(_) => {
+            parser
+              .parse_nested_block(|block| {
+                while block.next().is_ok() {}
+                Ok::<_, ParseError<'_, ()>>(())
+              })
+              .ok()?;
+          }
+          _ => return None,
+        }
+        has_compound = true;
+      }
+      _ => return None,
+    }
+  }
+
+  finish(
+    &mut result,
+    &mut combinator,
+    &mut compound,
+    &mut has_compound,
+  );
+  if result.is_empty() {
+    return None;
+  }
+  Some(result)
+}
+
+fn parse_attribute_selector(parser: &mut Parser<'_, '_>) -> Option<AttributeSelector> {
+  let name = match parser.next().ok()? {
+    Token::Ident(name) => name.to_string(),
+    _ => return None,
+  };
+  let operator = match parser.next() {
+    Err(_) => {
+      return Some(AttributeSelector {
+        name,
+        operator: AttributeOperator::Exists,
+        value: String::new(),
+        ignore_case: false,
+      });
+    }
+    Ok(Token::Delim('=')) => AttributeOperator::Equal,
+    Ok(Token::IncludeMatch) => AttributeOperator::Includes,
+    Ok(Token::DashMatch) => AttributeOperator::DashMatch,
+    Ok(Token::PrefixMatch) => AttributeOperator::Prefix,
+    Ok(Token::SuffixMatch) => AttributeOperator::Suffix,
+    Ok(Token::SubstringMatch) => AttributeOperator::Substring,
+    Ok(_) => return None,
+  };
+  let value = match parser.next().ok()? {
+    Token::Ident(value) | Token::QuotedString(value) => value.to_string(),
+    _ => return None,
+  };
+  let ignore_case = match parser.next() {
+    Ok(Token::Ident(flag)) => flag.eq_ignore_ascii_case("i"),
+    Ok(_) => return None,
+    Err(_) => false,
+  };
+  Some(AttributeSelector {
+    name,
+    operator,
+    value,
+    ignore_case,
+  })
+}
+
+#[derive(Default)]
+struct CriticalCss {
+  keyframes: Vec<(String, String)>,
+}
+
+impl CriticalCss {
+  fn extract_rules(&mut self, parser: &mut Parser<'_, '_>, dom: &CriticalDom) -> String {
+    let mut output = String::new();
+    loop {
+      parser.skip_whitespace();
+      let start = parser.position();
+      let token = match parser.next() {
+        Ok(token) => token.clone(),
+        Err(_) => break,
+      };
+      match token {
+        Token::CDO | Token::CDC => {}
+        Token::AtKeyword(name) => {
+          let prelude_start = parser.position();
+          let (prelude_end, has_block) = loop {
+            let position = parser.position();
+            match parser.next() {
+              Ok(Token::CurlyBracketBlock) => break (position, true),
+              Ok(Token::Semicolon) | Err(_) => break (position, false),
+              Ok(Token::ParenthesisBlock | Token::SquareBracketBlock | Token::Function(_)) => {
+                skip_block(parser)
+              }
+              Ok(_) => {}
+            }
+          };
+          let prelude = parser.slice(prelude_start..prelude_end).trim();
+
+          if name.eq_ignore_ascii_case("font-face")
+            || name.eq_ignore_ascii_case("import")
+            || name.eq_ignore_ascii_case("charset")
+          {
+            // Loaded with the full stylesheet.
+            if has_block {
+              skip_block(parser);
+            }
+          } else if has_block
+            && ["media", "supports", "layer", "container", "scope"]
+              .iter()
+              .any(|n| name.eq_ignore_ascii_case(n))
+          {
+            let rules = parser
+              .parse_nested_block(|block| {
+                Ok::<_, ParseError<'_, ()>>(self.extract_rules(block, dom))
+              })
+              .unwrap_or_default();
+            if !rules.is_empty() {
+              output.push_str(&format!("@{name} {prelude}{{{rules}}}"));
+            }
+          } else if has_block && name.cow_to_ascii_lowercase().ends_with("keyframes") {
+            let block = raw_block(parser);
+            self
+              .keyframes
+              .push((prelude.to_string(), format!("@{name} {prelude}{{{block}}}")));
+          } else if has_block {
+            let block = raw_block(parser);
+            output.push_str(&format!("@{name} {prelude}{{{block}}}"));
+          } else {
+            output.push_str(parser.slice_from(start).trim());
+            if !output.ends_with(';') {
+              output.push(';');
+            }
+          }
+        }
+        _ => {
+          let prelude_end = if matches!(token, Token::CurlyBracketBlock) {
+            start
+          } else {
+            if matches!(
+              token,
+              Token::ParenthesisBlock | Token::SquareBracketBlock | Token::Function(_)
+            ) {
+              skip_block(parser);
+            }
+            let prelude_end = loop {
+              let position = parser.position();
+              match parser.next() {
+                Ok(Token::CurlyBracketBlock) => break Some(position),
+                Ok(Token::ParenthesisBlock | Token::SquareBracketBlock | Token::Function(_)) => {
+                  skip_block(parser)
+                }
+                Ok(_) => {}
+                Err(_) => break None,
+              }
+            };
+            // A qualified rule without a block is invalid and ends the stylesheet.
+            let Some(prelude_end) = prelude_end else {
+              break;
+            };
+            prelude_end
+          };
+          let prelude = parser.slice(start..prelude_end);
+          let block = raw_block(parser);
+          let selectors = split_selectors(prelude)
+            .into_iter()
+            .filter(|selector| parse_selector(selector).is_none_or(|s| dom.matches(&s)))
+            .collect::<Vec<_>>();
+          if !selectors.is_empty() {
+            output.push_str(&format!("{}{{{}}}", selectors.join(","), block.trim()));
+          }
+        }
+      }
+    }
+    output
+  }
+}
+
+fn raw_block(parser: &mut Parser<'_, '_>) -> String {
+  parser
+    .parse_nested_block(|block| {
+      let start = block.position();
+      while block.next_including_whitespace_and_comments().is_ok() {}
+      Ok::<_, ParseError<'_, ()>>(block.slice_from(start).to_string())
+    })
+    .unwrap_or_default()
+}
+
+fn skip_block(parser: &mut Parser<'_, '_>) {
+  let _ = parser.parse_nested_block(|block| {
+    while block.next().is_ok() {}
+    Ok::<_, ParseError<'_, ()>>(())
+  });
+}
+
+/// Splits a selector list on its top level commas.
+fn split_selectors(prelude: &str) -> Vec<&str> {
+  let mut input = ParserInput::new(prelude);
+  let mut parser = Parser::new(&mut input);
+  let mut selectors = vec![];
+  let mut start = parser.position();
+  loop {
+    let position = parser.position();
+    match parser.next_including_whitespace_and_comments() {
+      Ok(Token::Comma) => {
+        selectors.push(parser.slice(start..position).trim());
+        start = parser.position();
+      }
+      Ok(Token::ParenthesisBlock | Token::SquareBracketBlock | Token::Function(_)) => {
+        skip_block(&mut parser)
+      }
+      Ok(_) => {}
+      Err(_) => {
+        selectors.push(parser.slice_from(start).trim());
+        break;
+      }
+    }
+  }
+  selectors.retain(|selector| !selector.is_empty());
+  selectors
+}
+
+/// Returns the rules of `css` used by the elements of `dom`, with the `@keyframes` they refer to.
+///
+/// The relative urls are rebased on the directory of `href`, as the rules are moved from the
+/// stylesheet to the html.
+pub fn extract_critical_css(css: &str, href: &str, dom: &CriticalDom) -> String {
+  let mut input = ParserInput::new(css);
+  let mut parser = Parser::new(&mut input);
+  let mut critical_css = CriticalCss::default();
+  let mut output = critical_css.extract_rules(&mut parser, dom);
+  for (name, keyframes) in std::mem::take(&mut critical_css.keyframes) {
+    if output.contains(name.as_str()) {
+      output.push_str(&keyframes);
+    }
+  }
+
+  let href = stylesheet_path(href);
+  match href.rfind('/') {
+    Some(index) => rebase_urls(&output, &href[..=index]),
+    None => output,
+  }
+}
+
+/// The href of a stylesheet without its query and fragment, e.g. the `?<hash>` appended by the
+/// `hash` option.
+pub fn stylesheet_path(href: &str) -> &str {
+  href.split(['?', '#']).next().unwrap_or_default()
+}
+
+fn rebase_urls(css: &str, base: &str) -> String {
+  let mut input = ParserInput::new(css);
+  let mut parser = Parser::new(&mut input);
+  let mut output = String::with_capacity(css.len());
+  let mut last = parser.position();
+  rebase_urls_in(&mut parser, base, &mut output, &mut last);
+  output.push_str(parser.slice_from(last));
+  output
+}
+
+fn rebase_urls_in(
+  parser: &mut Parser<'_, '_>,
+  base: &str,
+  output: &mut String,
+  last: &mut SourcePosition,
+) {
+  loop {
+    let start = parser.position();
+    let token = match parser.next_including_whitespace_and_comments() {
+      Ok(token) => token.clone(),
+      Err(_) => break,
+    };
+    let url = match token {
+      Token::UnquotedUrl(url) => Some(url.to_string()),
+      Token::Function(name) if name.eq_ignore_ascii_case("url") => parser
+        .parse_nested_block(|block| {
+          block
+            .expect_string()
+            .map(ToString::to_string)
+            .map_err(Into::<ParseError<'_, ()>>::into)
+        })
+        .ok(),
+      Token::Function(_)
+      | Token::ParenthesisBlock
+      | Token::SquareBracketBlock
+      | Token::CurlyBracketBlock => {
+        let _ = parser.parse_nested_block(|block| {
+          rebase_urls_in(block, base, output, last);
+          Ok::<_, ParseError<'_, ()>>(())
+        });
+        None
+      }
+      _ => None,
+    };
+    if let Some(url) = url.filter(|url| is_relative_url(url)) {
+      output.push_str(parser.slice(*last..start));
+      output.push_str("url(");
+      let _ = serialize_string(&format!("{base}{url}"), output);
+      output.push(')');
+      *last = parser.position();
+    }
+  }
+}
+
+fn is_relative_url(url: &str) -> bool {
+  let has_scheme = url
+    .find(':')
+    .is_some_and(|index| !url[..index].contains(['/', '?', '#']));
+  !url.is_empty() && !url.starts_with(['/', '#']) && !has_scheme
+}
+
+/// Inlines the critical css of the stylesheets before their `<link>` tags, and loads the
+/// stylesheets without blocking the first paint.
+#[derive(Debug)]
+pub struct CriticalCssInliner<'a> {
+  /// Critical css by the [stylesheet_path] of the stylesheets.
+  critical_css: &'a FxHashMap<String, String>,
+}
+
+impl<'a> CriticalCssInliner<'a> {
+  pub fn new(critical_css: &'a FxHashMap<String, String>) -> Self {
+    Self { critical_css }
+  }
+
+  fn critical_css_of(&self, element: &Element) -> Option<&'a String> {
+    if &*element.tag_name != "link" {
+      return None;
+    }
+    let attribute = |name: &str| {
+      element
+        .attributes
+        .iter()
+        .find(|attr| &*attr.name == name)
+        .and_then(|attr| attr.value.as_ref())
+    };
+    if !attribute("rel").is_some_and(|rel| rel.eq_ignore_ascii_case("stylesheet")) {
+      return None;
+    }
+    self
+      .critical_css
+      .get(stylesheet_path(attribute("href")?.as_str()))
+  }
+}
+
+impl VisitMut for CriticalCssInliner<'_> {
+  fn visit_mut_element(&mut self, n: &mut Element) {
+    // `<noscript>` fallbacks are left as they are.
+    if &*n.tag_name == "noscript" {
+      return;
+    }
+
+    let mut children = Vec::with_capacity(n.children.len());
+    for child in std::mem::take(&mut n.children) {
+      let Child::Element(element) = &child else {
+        children.push(child);
+        continue;
+      };
+      let Some(critical_css) = self.critical_css_of(element) else {
+        children.push(child);
+        continue;
+      };
+
+      if !critical_css.is_empty() {
+        children.push(Child::Element(Element {
+          span: DUMMY_SP,
+          tag_name: "style".into(),
+          namespace: Namespace::HTML,
+          attributes: vec![],
+          children: vec![Child::Text(Text {
+            span: DUMMY_SP,
+            // `</style` would end the element, it is escaped as in css strings
+            data: Atom::from(critical_css.cow_replace("</style", "<\\/style").as_ref()),
+            raw: None,
+          })],
+          content: None,
+          is_self_closing: false,
+        }));
+      }
+
+      // Loads the stylesheet with a media which doesn't match, and applies it once loaded.
+      let mut async_link = element.clone();
+      let media = async_link
+        .attributes
+        .iter()
+        .position(|attr| &*attr.name == "media")
+        .map(|index| async_link.attributes.remove(index))
+        .and_then(|attr| attr.value)
+        .map_or_else(|| "all".to_string(), |media| media.to_string());
+      async_link
+        .attributes
+        .push(create_attribute("media", "print"));
+      async_link.attributes.push(create_attribute(
+        "onload",
+        &format!("this.media='{}'", escape_single_quoted(&media)),
+      ));
+      children.push(Child::Element(async_link));
+
+      children.push(Child::Element(Element {
+        span: DUMMY_SP,
+        tag_name: "noscript".into(),
+        namespace: Namespace::HTML,
+        attributes: vec![],
+        children: vec![child],
+        content: None,
+        is_self_closing: false,
+      }));
+    }
+    n.children = children;
+
+    n.visit_mut_children_with(self);
+  }
+}
+
+fn escape_single_quoted(value: &str) -> String {
+  let mut escaped = String::with_capacity(value.len());
+  for c in value.chars() {
+    if matches!(c, '\'' | '\\') {
+      escaped.push('\\');
+    }
+    escaped.push(c);
+  }
+  escaped
+}
+
+fn create_attribute(name: &str, value: &str) -> swc_html::ast::Attribute {
+  swc_html::ast::Attribute {
+    span: DUMMY_SP,
+    namespace: None,
+    prefix: None,
+    name: name.into(),
+    raw_name: None,
+    value: Some(value.into()),
+    raw_value: None,
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use super::*;
+
+  fn element(
+    tag_name: &str,
+    attributes: &[(&str, &str)],
+    parent: Option<usize>,
+    prev_sibling: Option<usize>,
+  ) -> DomElement {
+    DomElement {
+      tag_name: tag_name.to_string(),
+      attributes: attributes
+        .iter()
+        .map(|(name, value)| (name.to_string(), value.to_string()))
+        .collect(),
+      parent,
+      prev_sibling,
+    }
+  }
+
+  // <html><body><div id="root" class="app main"><p data-role="intro-text" lang="en-US"></p><a href="/docs"></a></div></body></html>
+  fn dom() -> CriticalDom {
+    CriticalDom {
+      elements: vec![
+        element("html", &[], None, None),
+        element("body", &[], Some(0), None),
+        element(
+          "div",
+          &[("id", "root"), ("class", "app main")],
+          Some(1),
+          None,
+        ),
+        element(
+          "p",
+          &[("data-role", "intro-text"), ("lang", "en-US")],
+          Some(2),
+          None,
+        ),
+        element("a", &[("href", "/docs")], Some(2), Some(3)),
+      ],
+    }
+  }
+
+  fn matches(dom: &CriticalDom, selector: &str) -> bool {
+    dom.matches(&parse_selector(selector).expect("should parse the selector"))
+  }
+
+  #[test]
+  fn selector_matching() {
+    let dom = dom();
+    for selector in [
+      "div",
+      ".app",
+      "#root.main",
+      "*",
+      "body > div",
+      "html p",
+      "p + a",
+      "p ~ a",
+      "div > p + a",
+      "[data-role]",
+      "[data-role=intro-text]",
+      "[data-role^=intro]",
+      "[data-role$=TEXT i]",
+      "[data-role*=\"o-t\"]",
+      "[class~=main]",
+      "[lang|=en]",
+      "a:hover",
+      "p::before",
+      "div:not(.missing) p",
+    ] {
+      assert!(matches(&dom, selector), "{selector} should match");
+    }
+    for selector in [
+      "span",
+      ".missing",
+      ".app.missing",
+      "body > p",
+      "a + p",
+      "a ~ p",
+      "[lang=en]",
+      "[data-role$=TEXT]",
+      "[class~=ma]",
+      "p a",
+    ] {
+      assert!(!matches(&dom, selector), "{selector} should not match");
+    }
+  }
+
+  #[test]
+  fn unsupported_selectors() {
+    assert!(parse_selector("> p").is_none());
+    assert!(parse_selector("p | a").is_none());
+    assert!(parse_selector("").is_none());
+  }
+
+  #[test]
+  fn extract_matched_rules() {
+    let css = "@charset \"utf-8\";.app{color:red}.missing{color:blue}.missing,p{margin:0}\
+      @media (min-width:1px){a{color:green}span{color:black}}\
+      @keyframes fade{from{opacity:0}}@keyframes unused{from{opacity:0}}\
+      div{animation:fade 1s}[unsupported|p]{color:red}";
+    assert_eq!(
+      extract_critical_css(css, "main.css", &dom()),
+      ".app{color:red}p{margin:0}@media (min-width:1px){a{color:green}}\
+      div{animation:fade 1s}[unsupported|p]{color:red}@keyframes fade{from{opacity:0}}"
+    );
+  }
+
+  #[test]
+  fn extract_rebases_on_the_stylesheet() {
+    assert_eq!(
+      extract_critical_css(
+        "a{background:url(a.png)}",
+        "/static/css/main.css?6a7b",
+        &dom()
+      ),
+      "a{background:url(\"/static/css/a.png\")}"
+    );
+    assert_eq!(
+      stylesheet_path("/static/css/main.css?6a7b"),
+      "/static/css/main.css"
+    );
+    assert_eq!(stylesheet_path("main.css#top"), "main.css");
+  }
+
+  #[test]
+  fn rebase_relative_urls() {
+    assert_eq!(
+      rebase_urls(
+        "a{background:url(a.png)}b{background:url('../b.png') no-repeat}",
+        "css/"
+      ),
+      "a{background:url(\"css/a.png\")}b{background:url(\"css/../b.png\") no-repeat}"
+    );
+    assert_eq!(
+      rebase_urls(
+        "a{background:image-set(url(a.png) 1x,url(\"b.png\") 2x)}",
+        "css/"
+      ),
+      "a{background:image-set(url(\"css/a.png\") 1x,url(\"css/b.png\") 2x)}"
+    );
+    let css = "a{background:url(/a.png)}b{background:url(https://example.com/b.png)}\
+      c{background:url(data:image/png;base64,iVBORw0K)}d{filter:url(#blur)}";
+    assert_eq!(rebase_urls(css, "css/"), css);
+  }
+}
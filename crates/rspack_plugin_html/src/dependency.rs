@@ -0,0 +1,97 @@
+use rspack_cacheable::{cacheable, cacheable_dyn};
+use rspack_core::{
+  AffectType, AsContextDependency, AsDependencyCodeGeneration, Dependency, DependencyCategory,
+  DependencyId, DependencyRange, DependencyType, ModuleDependency,
+};
+
+#[cacheable]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HtmlSourceKind {
+  /// `<script src>`, bundled as an entrypoint
+  Script,
+  /// `<link rel="stylesheet" href>`, bundled as an entrypoint
+  Stylesheet,
+  /// `<img src>` and the candidates of `srcset`
+  Asset,
+}
+
+/// A url referenced by an html module, which is rewritten to the emitted
+/// files when the html is written out.
+#[cacheable]
+#[derive(Debug, Clone)]
+pub struct HtmlSourceDependency {
+  id: DependencyId,
+  request: String,
+  kind: HtmlSourceKind,
+  /// Range of the url in the attribute value.
+  range: DependencyRange,
+  /// Range of the element, it is repeated for every file of an entrypoint.
+  element_range: DependencyRange,
+}
+
+impl HtmlSourceDependency {
+  pub fn new(
+    request: String,
+    kind: HtmlSourceKind,
+    range: DependencyRange,
+    element_range: DependencyRange,
+  ) -> Self {
+    Self {
+      id: DependencyId::new(),
+      request,
+      kind,
+      range,
+      element_range,
+    }
+  }
+
+  pub fn kind(&self) -> HtmlSourceKind {
+    self.kind
+  }
+
+  pub fn element_range(&self) -> DependencyRange {
+    self.element_range
+  }
+}
+
+#[cacheable_dyn]
+impl Dependency for HtmlSourceDependency {
+  fn id(&self) -> &DependencyId {
+    &self.id
+  }
+
+  fn category(&self) -> &DependencyCategory {
+    match self.kind {
+      HtmlSourceKind::Script => &DependencyCategory::Esm,
+      HtmlSourceKind::Stylesheet => &DependencyCategory::CssImport,
+      HtmlSourceKind::Asset => &DependencyCategory::Url,
+    }
+  }
+
+  fn dependency_type(&self) -> &DependencyType {
+    &DependencyType::HtmlSource
+  }
+
+  fn range(&self) -> Option<DependencyRange> {
+    Some(self.range)
+  }
+
+  fn could_affect_referencing_module(&self) -> AffectType {
+    AffectType::True
+  }
+}
+
+#[cacheable_dyn]
+impl ModuleDependency for HtmlSourceDependency {
+  fn request(&self) -> &str {
+    &self.request
+  }
+
+  fn user_request(&self) -> &str {
+    &self.request
+  }
+}
+
+impl AsDependencyCodeGeneration for HtmlSourceDependency {}
+
+impl AsContextDependency for HtmlSourceDependency {}
@@ -0,0 +1,296 @@
+use std::{hash::Hash, path::Path};
+
+use cow_utils::CowUtils;
+use rspack_core::{
+  AssetInfo, ChunkUkey, CodeGenerationDataFilename, CodeGenerationDataUrl, Compilation,
+  CompilationAsset, CompilationParams, CompilationProcessAssets, CompilerCompilation,
+  DependenciesBlock, DependencyType, Filename, Module, ModuleType, ParserAndGenerator, PathData,
+  Plugin, SourceType,
+  rspack_sources::{RawStringSource, Source, SourceExt},
+};
+use rspack_error::Result;
+use rspack_hash::RspackHasher;
+use rspack_hook::{plugin, plugin_hook};
+
+use crate::{
+  dependency::{HtmlSourceDependency, HtmlSourceKind},
+  parser_and_generator::HtmlParserAndGenerator,
+};
+
+const AUTO_PUBLIC_PATH_PLACEHOLDER: &str = "__RSPACK_PLUGIN_ASSET_AUTO_PUBLIC_PATH__";
+
+/// Supports `html` modules. Every html module is written out to `filename`
+/// (`[name].html` by default) with the urls of its scripts, stylesheets and
+/// images rewritten to the emitted files.
+#[plugin]
+#[derive(Debug)]
+pub struct HtmlModulesPlugin {
+  filename: Filename,
+}
+
+impl HtmlModulesPlugin {
+  pub fn new(filename: Filename) -> Self {
+    Self::new_inner(filename)
+  }
+}
+
+impl Default for HtmlModulesPlugin {
+  fn default() -> Self {
+    Self::new(Filename::from("[name].html"))
+  }
+}
+
+fn is_html_source_file(kind: HtmlSourceKind, file: &str) -> bool {
+  let extension = Path::new(file.split('?').next().unwrap_or_default())
+    .extension()
+    .and_then(|extension| extension.to_str())
+    .unwrap_or_default();
+  match kind {
+    HtmlSourceKind::Script => {
+      extension.eq_ignore_ascii_case("js") || extension.eq_ignore_ascii_case("mjs")
+    }
+    HtmlSourceKind::Stylesheet => extension.eq_ignore_ascii_case("css"),
+    HtmlSourceKind::Asset => false,
+  }
+}
+
+fn get_asset_url(
+  compilation: &Compilation,
+  module: &dyn Module,
+  public_path: &str,
+) -> Option<String> {
+  // asset modules have the same code generation results for all runtimes
+  let code_generation_result = compilation
+    .code_generation_results
+    .get_one(&module.identifier());
+  if let Some(url) = code_generation_result.data().get::<CodeGenerationDataUrl>() {
+    Some(url.inner().to_string())
+  } else {
+    code_generation_result
+      .data()
+      .get::<CodeGenerationDataFilename>()
+      .map(|data| {
+        let asset_public_path = data
+          .public_path()
+          .cow_replace(AUTO_PUBLIC_PATH_PLACEHOLDER, public_path);
+        format!("{asset_public_path}{}", data.filename())
+      })
+  }
+}
+
+/// Renders the html of `module` with the urls rewritten, `(start, end, content)`
+/// replacements are collected for every source and applied to the original
+/// content.
+fn render_html_module(
+  compilation: &Compilation,
+  module: &dyn Module,
+  content: &str,
+  public_path: &str,
+) -> String {
+  let module_graph = compilation.get_module_graph();
+  let chunk_graph = &compilation.build_chunk_graph_artifact.chunk_graph;
+  let mut replacements: Vec<(usize, usize, String)> = vec![];
+
+  for block_id in module.get_blocks() {
+    let Some(chunk_group) = chunk_graph.get_block_chunk_group(
+      block_id,
+      &compilation.build_chunk_graph_artifact.chunk_group_by_ukey,
+    ) else {
+      continue;
+    };
+    let files = chunk_group.get_files(&compilation.build_chunk_graph_artifact.chunk_by_ukey);
+    let block = module_graph.block_by_id_expect(block_id);
+    for dependency_id in block.get_dependencies() {
+      let Some(dependency) = module_graph
+        .dependency_by_id(dependency_id)
+        .as_any()
+        .downcast_ref::<HtmlSourceDependency>()
+      else {
+        continue;
+      };
+      let (Some(range), element_range) = (dependency.range(), dependency.element_range()) else {
+        continue;
+      };
+      let entry_files = files
+        .iter()
+        .filter(|file| is_html_source_file(dependency.kind(), file))
+        .filter(|file| {
+          compilation.assets().get(*file).is_none_or(|asset| {
+            !asset.info.hot_module_replacement.unwrap_or(false)
+              && !asset.info.development.unwrap_or(false)
+          })
+        })
+        .collect::<Vec<_>>();
+      if entry_files.is_empty() {
+        continue;
+      }
+      // the element is repeated for every file, so attributes like `defer`
+      // or `media` are kept
+      let before = &content[element_range.start as usize..range.start as usize];
+      let after = &content[range.end as usize..element_range.end as usize];
+      replacements.push((
+        element_range.start as usize,
+        element_range.end as usize,
+        entry_files
+          .iter()
+          .map(|file| format!("{before}{public_path}{file}{after}"))
+          .collect(),
+      ));
+    }
+  }
+
+  for dependency_id in module.get_dependencies() {
+    let Some(dependency) = module_graph
+      .dependency_by_id(dependency_id)
+      .as_any()
+      .downcast_ref::<HtmlSourceDependency>()
+    else {
+      continue;
+    };
+    let Some(range) = dependency.range() else {
+      continue;
+    };
+    let Some(url) = module_graph
+      .get_module_by_dependency_id(dependency_id)
+      .and_then(|target| get_asset_url(compilation, target.as_ref(), public_path))
+    else {
+      continue;
+    };
+    replacements.push((range.start as usize, range.end as usize, url));
+  }
+
+  replacements.sort_by_key(|(start, _, _)| *start);
+  let mut html = String::with_capacity(content.len());
+  let mut pos = 0;
+  for (start, end, replacement) in replacements {
+    if start < pos {
+      continue;
+    }
+    html.push_str(&content[pos..start]);
+    html.push_str(&replacement);
+    pos = end;
+  }
+  html.push_str(&content[pos..]);
+  html
+}
+
+#[plugin_hook(CompilerCompilation for HtmlModulesPlugin)]
+async fn compilation(
+  &self,
+  compilation: &mut Compilation,
+  params: &mut CompilationParams,
+) -> Result<()> {
+  compilation.set_dependency_factory(
+    DependencyType::HtmlSource,
+    params.normal_module_factory.clone(),
+  );
+  Ok(())
+}
+
+#[plugin_hook(CompilationProcessAssets for HtmlModulesPlugin, stage = Compilation::PROCESS_ASSETS_STAGE_OPTIMIZE_INLINE)]
+async fn process_assets(&self, compilation: &mut Compilation) -> Result<()> {
+  let mut html_modules: Vec<(ChunkUkey, _)> = vec![];
+  {
+    let module_graph = compilation.get_module_graph();
+    for chunk_ukey in compilation.build_chunk_graph_artifact.chunk_by_ukey.keys() {
+      for module in compilation
+        .build_chunk_graph_artifact
+        .chunk_graph
+        .get_chunk_modules_identifier_by_source_type(chunk_ukey, SourceType::Html, module_graph)
+      {
+        html_modules.push((*chunk_ukey, module));
+      }
+    }
+  }
+
+  let mut assets = vec![];
+  for (chunk_ukey, module_identifier) in html_modules {
+    let chunk = compilation
+      .build_chunk_graph_artifact
+      .chunk_by_ukey
+      .expect_get(&chunk_ukey);
+    let module_graph = compilation.get_module_graph();
+    let Some(module) = module_graph.module_by_identifier(&module_identifier) else {
+      continue;
+    };
+    let Some(source) = compilation
+      .code_generation_results
+      .get(&module_identifier, Some(chunk.runtime()))
+      .get(&SourceType::Html)
+    else {
+      continue;
+    };
+    let content = source.source().into_string_lossy();
+    let resource = module
+      .as_normal_module()
+      .map(|normal_module| {
+        normal_module
+          .resource_resolved_data()
+          .resource()
+          .to_string()
+      })
+      .unwrap_or_default();
+
+    // the public path only depends on the directory of the html, which
+    // doesn't change with the content hash
+    let path_data = || {
+      PathData::default()
+        .chunk_id_optional(chunk.id().map(|id| id.as_str()))
+        .chunk_name_optional(chunk.name_for_filename_template())
+        .filename(&resource)
+    };
+    let filename = compilation
+      .get_path(&self.filename, path_data().content_hash(""))
+      .await?;
+    let public_path = compilation
+      .options
+      .output
+      .public_path
+      .render(compilation, &filename)
+      .await;
+
+    let html = render_html_module(compilation, module.as_ref(), &content, &public_path);
+
+    let mut hasher = RspackHasher::from(&compilation.options.output);
+    html.hash(&mut hasher);
+    let content_hash = hasher.digest(&compilation.options.output.hash_digest);
+    let mut asset_info = AssetInfo::default();
+    let filename = compilation
+      .get_path_with_info(
+        &self.filename,
+        path_data().content_hash(content_hash.encoded()),
+        &mut asset_info,
+      )
+      .await?;
+    assets.push((
+      filename,
+      CompilationAsset::new(Some(RawStringSource::from(html).boxed()), asset_info),
+    ));
+  }
+
+  for (filename, asset) in assets {
+    compilation.emit_asset(filename, asset);
+  }
+  Ok(())
+}
+
+impl Plugin for HtmlModulesPlugin {
+  fn name(&self) -> &'static str {
+    "rspack.HtmlModulesPlugin"
+  }
+
+  fn apply(&self, ctx: &mut rspack_core::ApplyContext<'_>) -> Result<()> {
+    ctx.compiler_hooks.compilation.tap(compilation::new(self));
+    ctx
+      .compilation_hooks
+      .process_assets
+      .tap(process_assets::new(self));
+
+    ctx.register_parser_and_generator_builder(
+      ModuleType::Html,
+      Box::new(move |_| Box::new(HtmlParserAndGenerator::default()) as Box<dyn ParserAndGenerator>),
+    );
+
+    Ok(())
+  }
+}
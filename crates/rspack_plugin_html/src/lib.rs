@@ -5,8 +5,15 @@ pub mod parser;
 pub mod tag;
 pub mod template;
 
+mod critical_css;
+mod dependency;
 mod drive;
+mod html_modules_plugin;
+mod parser_and_generator;
 mod plugin;
 
+pub use dependency::{HtmlSourceDependency, HtmlSourceKind};
 pub use drive::*;
+pub use html_modules_plugin::HtmlModulesPlugin;
+pub use parser_and_generator::HtmlParserAndGenerator;
 pub use plugin::*;
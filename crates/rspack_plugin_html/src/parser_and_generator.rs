@@ -0,0 +1,397 @@
+use std::{borrow::Cow, hash::Hash, sync::Arc};
+
+use rspack_cacheable::{cacheable, cacheable_dyn};
+use rspack_core::{
+  AsyncDependenciesBlock, BoxDependency, ChunkGraph, DependencyLocation, DependencyRange,
+  EntryOptions, GenerateContext, GroupOptions, Module, ModuleGraph, ParseContext, ParseResult,
+  ParserAndGenerator, RealDependencyLocation, SourcePosition, SourceType,
+  rspack_sources::{BoxSource, Source},
+};
+use rspack_error::{Diagnostic, IntoTWithDiagnosticArray, Result, Severity, TWithDiagnosticArray};
+use rspack_hash::RspackHasher;
+use rspack_util::SpanExt;
+use swc_core::common::{FileName, FilePathMapping, SourceMap, Span, sync::Lrc};
+use swc_html::{
+  ast::{Attribute, Element},
+  parser::{parse_file_as_document, parser::ParserConfig},
+  visit::{Visit, VisitWith},
+};
+
+use crate::{
+  dependency::{HtmlSourceDependency, HtmlSourceKind},
+  parser::html_parse_error_to_traceable_error,
+};
+
+static HTML_SOURCE_TYPE: &[SourceType; 1] = &[SourceType::Html];
+
+/// Parser and generator of `html` modules. Scripts and stylesheets referenced
+/// by the html become entrypoints, the html itself is emitted by
+/// [crate::HtmlModulesPlugin] once the files of those entrypoints are known.
+#[cacheable]
+#[derive(Debug, Default)]
+pub struct HtmlParserAndGenerator;
+
+#[derive(Debug)]
+struct HtmlSource {
+  request: String,
+  kind: HtmlSourceKind,
+  range: DependencyRange,
+  element_range: DependencyRange,
+  span: Span,
+}
+
+/// Whether the url should be resolved as a module, absolute urls and urls
+/// with a scheme are kept as is.
+fn is_url_request(url: &str) -> bool {
+  if url.is_empty()
+    || url.starts_with('#')
+    || url.starts_with('/')
+    || url.contains("{{")
+    || url.contains("<%")
+  {
+    return false;
+  }
+  !url.split_once(':').is_some_and(|(scheme, _)| {
+    scheme.starts_with(|c: char| c.is_ascii_alphabetic())
+      && scheme
+        .chars()
+        .all(|c| c.is_ascii_alphanumeric() || matches!(c, '+' | '-' | '.'))
+  })
+}
+
+/// Relative urls like `logo.png` are resolved from the html file, like the
+/// urls of CSS `url()`, instead of from `node_modules`.
+fn to_request(url: &str) -> String {
+  if url.starts_with("./") || url.starts_with("../") || url.starts_with('/') {
+    url.to_string()
+  } else {
+    format!("./{url}")
+  }
+}
+
+/// Range of the raw value of an attribute in the source, without quotes.
+fn get_attribute_value_range(source: &str, attribute: &Attribute) -> Option<(usize, usize)> {
+  let start = attribute.span.real_lo() as usize;
+  let raw = source.get(start..attribute.span.real_hi() as usize)?;
+  let eq = raw.find('=')? + 1;
+  let value_start = eq + raw[eq..].len() - raw[eq..].trim_start().len();
+  let (value_start, value_end) = match raw[value_start..].chars().next()? {
+    quote @ ('"' | '\'') => {
+      let end = raw[value_start + 1..].find(quote)?;
+      (value_start + 1, value_start + 1 + end)
+    }
+    _ => (value_start, raw.len()),
+  };
+  Some((start + value_start, start + value_end))
+}
+
+struct HtmlSourceCollector<'a> {
+  source: &'a str,
+  sources: Vec<HtmlSource>,
+}
+
+impl HtmlSourceCollector<'_> {
+  fn add_url(&mut self, element: &Element, attribute: &Attribute, kind: HtmlSourceKind) {
+    let Some((start, end)) = get_attribute_value_range(self.source, attribute) else {
+      return;
+    };
+    let raw = &self.source[start..end];
+    let url = raw.trim();
+    if !is_url_request(url) {
+      return;
+    }
+    let start = start + raw.len() - raw.trim_start().len();
+    self.sources.push(HtmlSource {
+      // prefer the decoded value when the url contains character references
+      request: to_request(attribute.value.as_ref().map_or(url, |value| value.trim())),
+      kind,
+      range: DependencyRange::new(start as u32, (start + url.len()) as u32),
+      element_range: element.span.into(),
+      span: attribute.span,
+    });
+  }
+
+  fn add_srcset(&mut self, element: &Element, attribute: &Attribute) {
+    let Some((start, end)) = get_attribute_value_range(self.source, attribute) else {
+      return;
+    };
+    for (offset, url) in parse_srcset(&self.source[start..end]) {
+      if !is_url_request(url) {
+        continue;
+      }
+      let url_start = start + offset;
+      self.sources.push(HtmlSource {
+        request: to_request(url),
+        kind: HtmlSourceKind::Asset,
+        range: DependencyRange::new(url_start as u32, (url_start + url.len()) as u32),
+        element_range: element.span.into(),
+        span: attribute.span,
+      });
+    }
+  }
+}
+
+/// Urls of the image candidates of a `srcset` attribute with their offsets,
+/// see <https://html.spec.whatwg.org/multipage/images.html#parse-a-srcset-attribute>.
+/// A url only ends at whitespace, so commas in `data:` urls are kept, and
+/// commas in the parentheses of a descriptor don't start a new candidate.
+fn parse_srcset(value: &str) -> Vec<(usize, &str)> {
+  let bytes = value.as_bytes();
+  let mut candidates = vec![];
+  let mut pos = 0;
+  loop {
+    while pos < bytes.len() && (bytes[pos].is_ascii_whitespace() || bytes[pos] == b',') {
+      pos += 1;
+    }
+    if pos == bytes.len() {
+      return candidates;
+    }
+    let start = pos;
+    while pos < bytes.len() && !bytes[pos].is_ascii_whitespace() {
+      pos += 1;
+    }
+    let url = value[start..pos].trim_end_matches(',');
+    candidates.push((start, url));
+    // a url with trailing commas has no descriptors
+    if url.len() < pos - start {
+      continue;
+    }
+    let mut in_parens = false;
+    while pos < bytes.len() {
+      match bytes[pos] {
+        b'(' => in_parens = true,
+        b')' => in_parens = false,
+        b',' if !in_parens => {
+          pos += 1;
+          break;
+        }
+        _ => {}
+      }
+      pos += 1;
+    }
+  }
+}
+
+impl Visit for HtmlSourceCollector<'_> {
+  fn visit_element(&mut self, element: &Element) {
+    let get_attribute = |name: &str| {
+      element
+        .attributes
+        .iter()
+        .find(|attribute| attribute.name.eq_ignore_ascii_case(name))
+    };
+    match element.tag_name.as_ref() {
+      "script" => {
+        if let Some(src) = get_attribute("src") {
+          self.add_url(element, src, HtmlSourceKind::Script);
+        }
+      }
+      "link" => {
+        let is_stylesheet = get_attribute("rel")
+          .and_then(|rel| rel.value.as_ref())
+          .is_some_and(|rel| {
+            rel
+              .split_ascii_whitespace()
+              .any(|rel| rel.eq_ignore_ascii_case("stylesheet"))
+          });
+        if is_stylesheet && let Some(href) = get_attribute("href") {
+          self.add_url(element, href, HtmlSourceKind::Stylesheet);
+        }
+      }
+      "img" | "source" => {
+        if element.tag_name.as_ref() == "img"
+          && let Some(src) = get_attribute("src")
+        {
+          self.add_url(element, src, HtmlSourceKind::Asset);
+        }
+        if let Some(srcset) = get_attribute("srcset") {
+          self.add_srcset(element, srcset);
+        }
+      }
+      _ => {}
+    }
+    element.visit_children_with(self);
+  }
+}
+
+#[cacheable_dyn]
+#[async_trait::async_trait]
+impl ParserAndGenerator for HtmlParserAndGenerator {
+  fn source_types(&self, _module: &dyn Module, _module_graph: &ModuleGraph) -> &[SourceType] {
+    HTML_SOURCE_TYPE
+  }
+
+  async fn parse<'a>(
+    &mut self,
+    parse_context: ParseContext<'a>,
+  ) -> Result<TWithDiagnosticArray<ParseResult>> {
+    let ParseContext {
+      source,
+      module_identifier,
+      resource_data,
+      compiler_options,
+      ..
+    } = parse_context;
+
+    let content = source.source().into_string_lossy().into_owned();
+    let cm: Lrc<SourceMap> = Lrc::new(SourceMap::new(FilePathMapping::empty()));
+    let fm = cm.new_source_file(
+      Arc::new(FileName::Custom(resource_data.resource().to_string())),
+      content.clone(),
+    );
+
+    let mut errors = vec![];
+    let document = parse_file_as_document(fm.as_ref(), ParserConfig::default(), &mut errors)
+      .map_err(|error| html_parse_error_to_traceable_error(error, &fm))?;
+    // recoverable errors don't prevent the html from being emitted
+    let diagnostics = errors
+      .into_iter()
+      .map(|error| {
+        let mut error = html_parse_error_to_traceable_error(error, &fm);
+        error.severity = Severity::Warning;
+        Diagnostic::from(error)
+      })
+      .collect::<Vec<_>>();
+
+    let mut collector = HtmlSourceCollector {
+      source: &content,
+      sources: vec![],
+    };
+    document.visit_with(&mut collector);
+
+    // the scripts and stylesheets of a page share one runtime, the same as
+    // the entries of a page would with a single runtime chunk
+    let mut hasher = RspackHasher::from(&compiler_options.output);
+    module_identifier.hash(&mut hasher);
+    let runtime = hasher
+      .digest(&compiler_options.output.hash_digest)
+      .rendered(compiler_options.output.hash_digest_length)
+      .to_owned();
+
+    let mut dependencies: Vec<BoxDependency> = vec![];
+    let mut blocks: Vec<Box<AsyncDependenciesBlock>> = vec![];
+    for html_source in collector.sources {
+      let dependency = HtmlSourceDependency::new(
+        html_source.request.clone(),
+        html_source.kind,
+        html_source.range,
+        html_source.element_range,
+      );
+      if html_source.kind == HtmlSourceKind::Asset {
+        dependencies.push(Box::new(dependency));
+        continue;
+      }
+
+      let start = cm.lookup_char_pos(html_source.span.lo);
+      let end = cm.lookup_char_pos(html_source.span.hi);
+      let loc = DependencyLocation::Real(RealDependencyLocation::new(
+        SourcePosition {
+          line: start.line as u32,
+          column: start.col.0 as u32,
+        },
+        Some(SourcePosition {
+          line: end.line as u32,
+          column: end.col.0 as u32,
+        }),
+      ));
+      let mut block = AsyncDependenciesBlock::new(
+        module_identifier,
+        Some(loc),
+        None,
+        vec![Box::new(dependency)],
+        Some(html_source.request),
+      );
+      block.set_group_options(GroupOptions::Entrypoint(Box::new(EntryOptions {
+        runtime: Some(runtime.clone().into()),
+        ..Default::default()
+      })));
+      blocks.push(Box::new(block));
+    }
+
+    Ok(
+      ParseResult {
+        dependencies,
+        blocks,
+        presentational_dependencies: vec![],
+        code_generation_dependencies: vec![],
+        source,
+        side_effects_bailout: None,
+      }
+      .with_diagnostic(diagnostics),
+    )
+  }
+
+  fn size(&self, module: &dyn Module, _source_type: Option<&SourceType>) -> f64 {
+    module.source().map_or(0, |source| source.size()) as f64
+  }
+
+  async fn generate(
+    &self,
+    source: &BoxSource,
+    _module: &dyn Module,
+    _generate_context: &mut GenerateContext,
+  ) -> Result<BoxSource> {
+    // urls are rewritten when the html is emitted, the files of the
+    // entrypoints are unknown until then
+    Ok(source.clone())
+  }
+
+  fn get_concatenation_bailout_reason(
+    &self,
+    _module: &dyn Module,
+    _mg: &ModuleGraph,
+    _cg: &ChunkGraph,
+  ) -> Option<Cow<'static, str>> {
+    Some("Module Concatenation is not implemented for HtmlParserAndGenerator".into())
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use super::*;
+
+  #[test]
+  fn parse_srcset_candidates() {
+    assert_eq!(
+      parse_srcset("a.png, b.png 2x,c.png 300w"),
+      vec![(0, "a.png"), (7, "b.png"), (16, "c.png")]
+    );
+    assert_eq!(
+      parse_srcset("  a.png,, b.png,  "),
+      vec![(2, "a.png"), (10, "b.png")]
+    );
+    assert!(parse_srcset(" , ").is_empty());
+  }
+
+  #[test]
+  fn parse_srcset_keeps_commas_in_urls() {
+    let srcset = "data:image/png;base64,iVBORw0K 1x, b.png 2x";
+    assert_eq!(
+      parse_srcset(srcset),
+      vec![(0, "data:image/png;base64,iVBORw0K"), (35, "b.png")]
+    );
+    assert_eq!(
+      parse_srcset("a.png foo(1,2) 1x, b.png"),
+      vec![(0, "a.png"), (19, "b.png")]
+    );
+  }
+
+  #[test]
+  fn url_requests() {
+    assert!(is_url_request("./a.js"));
+    assert!(is_url_request("a.png"));
+    assert!(!is_url_request("/a.js"));
+    assert!(!is_url_request("#top"));
+    assert!(!is_url_request("https://example.com/a.js"));
+    assert!(!is_url_request("data:image/png;base64,iVBORw0K"));
+    assert!(!is_url_request("{{ url }}"));
+  }
+
+  #[test]
+  fn relative_requests() {
+    assert_eq!(to_request("a.png"), "./a.png");
+    assert_eq!(to_request("images/a.png"), "./images/a.png");
+    assert_eq!(to_request("./a.js"), "./a.js");
+    assert_eq!(to_request("../a.js"), "../a.js");
+  }
+}
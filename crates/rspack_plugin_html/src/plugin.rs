@@ -1,6 +1,6 @@
 use std::{
   borrow::Cow,
-  path::{Path, PathBuf},
+  path::Path,
   sync::{Arc, LazyLock},
 };
 
@@ -11,7 +11,7 @@ use rspack_error::{Diagnostic, Result};
 use rspack_hook::{plugin, plugin_hook};
 #[cfg(allocative)]
 use rspack_util::allocative;
-use rspack_util::fx_hash::FxDashMap;
+use rspack_util::fx_hash::{FxDashMap, FxHashMap};
 use sugar_path::SugarPath;
 
 use crate::{
@@ -19,6 +19,7 @@ use crate::{
   BeforeAssetTagGenerationData, BeforeEmitData, HtmlPluginHooks,
   asset::{HtmlPluginAssetTags, HtmlPluginAssets, create_favicon_asset, create_html_asset},
   config::{HtmlInject, HtmlRspackPluginOptions},
+  critical_css::{CriticalCssInliner, CriticalDom, extract_critical_css, stylesheet_path},
   injector::AssetInjector,
   parser::HtmlCompiler,
   template::HtmlTemplate,
@@ -62,14 +63,13 @@ impl HtmlRspackPlugin {
 async fn generate_html(
   filename: &str,
   html_file_name: &Filename,
+  template: &mut HtmlTemplate,
   config: &HtmlRspackPluginOptions,
   compilation: &mut Compilation,
   hooks: ArcHtmlPluginHooks,
-) -> Result<(String, String, Vec<PathBuf>)> {
+) -> Result<(String, String)> {
   let public_path = config.get_public_path(compilation, filename).await;
 
-  let mut template = HtmlTemplate::new(config, compilation).await?;
-
   let template_file_name = compilation
     .options
     .output
@@ -85,6 +85,24 @@ async fn generate_html(
   )
   .await?;
 
+  // the stylesheets are read before the hooks, which may change the assets of the html
+  let stylesheets = if config.critical_css.unwrap_or_default() {
+    assets_info
+      .0
+      .css
+      .iter()
+      .filter_map(|href| {
+        let source = assets_info.1.get(href)?.get_source()?;
+        Some((
+          href.clone(),
+          source.source().into_string_lossy().into_owned(),
+        ))
+      })
+      .collect::<Vec<_>>()
+  } else {
+    vec![]
+  };
+
   let before_generation_data = hooks
     .borrow()
     .before_asset_tag_generation
@@ -171,14 +189,24 @@ async fn generate_html(
     current_ast.visit_mut_with(&mut visitor);
   }
 
+  if !stylesheets.is_empty() {
+    let dom = CriticalDom::from_document(&current_ast);
+    let critical_css = stylesheets
+      .iter()
+      .map(|(href, css)| {
+        (
+          stylesheet_path(href).to_string(),
+          extract_critical_css(css, href, &dom),
+        )
+      })
+      .collect::<FxHashMap<_, _>>();
+    current_ast.visit_mut_with(&mut CriticalCssInliner::new(&critical_css));
+  }
+
   let raw_html = parser.codegen(&mut current_ast, compilation)?;
   let html = raw_html.cow_replace("$$RSPACK_URL_AMP$$", "&");
 
-  Ok((
-    template_file_name.to_string(),
-    html.into_owned(),
-    template.file_dependencies,
-  ))
+  Ok((template_file_name.to_string(), html.into_owned()))
 }
 
 #[plugin_hook(CompilationProcessAssets for HtmlRspackPlugin, stage = Compilation::PROCESS_ASSETS_STAGE_OPTIMIZE_INLINE)]
@@ -209,21 +237,29 @@ async fn process_assets(&self, compilation: &mut Compilation) -> Result<()> {
 
     let output_file_name = Filename::from(filename.to_string());
 
-    let (template_file_name, html) = match generate_html(
-      filename.as_ref(),
-      &output_file_name,
-      config,
-      compilation,
-      hooks.clone(),
-    )
-    .await
-    {
-      Ok(content) => {
+    let result = match HtmlTemplate::new(config, compilation).await {
+      Ok(mut template) => {
+        let result = generate_html(
+          filename.as_ref(),
+          &output_file_name,
+          &mut template,
+          config,
+          compilation,
+          hooks.clone(),
+        )
+        .await;
+        // the template and its partials are watched even when the generation fails,
+        // so fixing them triggers a rebuild
         compilation
           .file_dependencies
-          .extend(content.2.into_iter().map(Into::into));
-        (content.0, content.1)
+          .extend(template.file_dependencies.into_iter().map(Into::into));
+        result
       }
+      Err(err) => Err(err),
+    };
+
+    let (template_file_name, html) = match result {
+      Ok(content) => content,
       Err(err) => {
         let error_msg = err.to_string();
         compilation.push_diagnostic(Diagnostic::from(err));
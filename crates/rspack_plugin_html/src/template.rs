@@ -1,11 +1,14 @@
-use std::path::PathBuf;
+use std::{path::PathBuf, sync::Arc};
 
 use anyhow::{Context, anyhow};
 use itertools::Itertools;
 use rspack_core::{Compilation, Mode};
-use rspack_dojang::{Dojang, Operand, dojang::DojangOptions};
-use rspack_error::{AnyhowResultToRspackResultExt, Result, ToStringResultToRspackResultExt, error};
-use rspack_paths::AssertUtf8;
+use rspack_dojang::{Dojang, DojangError, Operand, TemplateLoader, dojang::DojangOptions};
+use rspack_error::{
+  AnyhowResultToRspackResultExt, Error, Result, ToStringResultToRspackResultExt, error,
+};
+use rspack_fs::ReadableFileSystem;
+use rspack_paths::{AssertUtf8, Utf8Path, Utf8PathBuf};
 use serde_json::Value;
 
 use crate::{
@@ -27,10 +30,51 @@ pub struct HtmlTemplate {
   pub filename: String,
   pub file_dependencies: Vec<PathBuf>,
   pub parameters: Option<Value>,
+  pub loader: Arc<TemplateFileLoader>,
+}
+
+/// Reads the partials of the template (e.g `include("./header.html")`) from the input file system
+/// of the compilation.
+#[derive(Debug)]
+pub struct TemplateFileLoader {
+  fs: Arc<dyn ReadableFileSystem>,
+  context: Utf8PathBuf,
+}
+
+impl TemplateFileLoader {
+  pub fn new(compilation: &Compilation) -> Self {
+    Self {
+      fs: compilation.input_filesystem.clone(),
+      context: compilation.options.context.as_path().to_path_buf(),
+    }
+  }
+}
+
+impl TemplateLoader for TemplateFileLoader {
+  fn resolve(&self, request: &str, issuer: Option<&str>) -> String {
+    // Relative to the template which includes it, or to the context for inline templates.
+    let dir = issuer
+      .map(Utf8Path::new)
+      .filter(|issuer| issuer.is_absolute())
+      .and_then(Utf8Path::parent)
+      .unwrap_or(self.context.as_path());
+
+    path_clean::clean(dir.join(request))
+      .assert_utf8()
+      .into_string()
+  }
+
+  fn load(&self, file_name: &str) -> std::result::Result<String, String> {
+    self
+      .fs
+      .read_to_string_sync(Utf8Path::new(file_name))
+      .map_err(|err| format!("could not load file `{file_name}`: {err}"))
+  }
 }
 
 impl HtmlTemplate {
   pub async fn new(config: &HtmlRspackPluginOptions, compilation: &Compilation) -> Result<Self> {
+    let loader = Arc::new(TemplateFileLoader::new(compilation));
     if let Some(content) = &config.template_content {
       Ok(Self {
         render: if config.template_fn.is_some() {
@@ -42,6 +86,7 @@ impl HtmlTemplate {
         filename: "template_content.html".to_string(),
         file_dependencies: vec![],
         parameters: None,
+        loader,
       })
     } else if let Some(template) = &config.template {
       // TODO: support loader query form
@@ -62,6 +107,7 @@ impl HtmlTemplate {
           filename: template.clone(),
           file_dependencies: vec![],
           parameters: None,
+          loader,
         })
       } else {
         compilation
@@ -79,6 +125,7 @@ impl HtmlTemplate {
             filename: template.clone(),
             file_dependencies: vec![resolved_template.into_std_path_buf()],
             parameters: None,
+            loader,
           })
           .to_rspack_result_from_anyhow()
       }
@@ -98,6 +145,7 @@ impl HtmlTemplate {
           filename: "src/index.ejs".to_string(),
           file_dependencies: vec![default_src_template.into_std_path_buf()],
           parameters: None,
+          loader,
         })
       } else {
         Ok(Self {
@@ -106,6 +154,7 @@ impl HtmlTemplate {
           filename: "default.html".to_string(),
           file_dependencies: vec![],
           parameters: None,
+          loader,
         })
       }
     }
@@ -203,18 +252,40 @@ impl HtmlTemplate {
         dj.with_options(DojangOptions {
           escape: "-".to_string(),
           unescape: "=".to_string(),
+          strict: config.strict.unwrap_or(true),
         });
 
         dj.add_function_1("toHtml".into(), render_tag)
           .expect("failed to add template function `renderTag`");
 
-        dj.add_with_option(self.url.clone(), content.clone())
-          .expect("failed to add template");
+        dj.with_loader(self.loader.clone());
 
-        dj.render(&self.url, parameters)
-          .to_rspack_result_with_message(|e| {
-            format!("HtmlRspackPlugin: failed to render template from string: {e}")
-          })
+        if let Err(e) = dj.add_with_option(self.url.clone(), content.clone()) {
+          return Err(template_error(
+            e,
+            Some(content.clone()),
+            "HtmlRspackPlugin: failed to parse template",
+          ));
+        }
+
+        let rendered = dj.render(&self.url, parameters);
+
+        // Partials are watched along with the template, even when the rendering fails.
+        self
+          .file_dependencies
+          .extend(dj.included_files().into_iter().map(PathBuf::from));
+
+        rendered.map_err(|e| {
+          let source = e
+            .file_name
+            .as_deref()
+            .and_then(|file_name| dj.source(file_name));
+          template_error(
+            e,
+            source,
+            "HtmlRspackPlugin: failed to render template from string",
+          )
+        })
       }
       TemplateRender::Function => (config
         .template_fn
@@ -231,6 +302,20 @@ impl HtmlTemplate {
   }
 }
 
+// Shows the code frame of the template (or the partial) where the error happened.
+fn template_error(e: DojangError, source: Option<String>, title: &str) -> Error {
+  match (&e.span, source) {
+    (Some(span), Some(source)) => Error::from_string(
+      Some(source),
+      span.start,
+      span.end,
+      title.to_string(),
+      e.to_string(),
+    ),
+    _ => error!("{title}: {e}"),
+  }
+}
+
 fn default_template() -> &'static str {
   r#"<!DOCTYPE html>
 <html>
@@ -251,9 +251,15 @@ impl Dependency for ESMImportSpecifierDependency {
   ) -> Option<Vec<Diagnostic>> {
     let module = module_graph.get_parent_module(&self.id)?;
     let module = module_graph.module_by_identifier(module)?;
-    let should_error = self
-      .export_presence_mode
-      .get_effective_export_presence(module.as_ref())?;
+    // missing exports of wasm modules would only fail when they are called,
+    // so they are always reported as errors
+    let imports_wasm = module_graph
+      .get_module_by_dependency_id(&self.id)
+      .is_some_and(|imported_module| imported_module.module_type().is_wasm_like());
+    let should_error = imports_wasm
+      || self
+        .export_presence_mode
+        .get_effective_export_presence(module.as_ref())?;
 
     if let Some(branch_guard) = &self.branch_guard
       && is_dependency_export_presence_guarded(branch_guard, self, module_graph)
@@ -5,4 +5,10 @@ use rspack_error::Result;
 
 pub trait Backend: std::fmt::Debug + Send + Sync {
   fn current_active_modules(&mut self) -> impl Future<Output = Result<IdentifierSet>> + Send + '_;
+
+  /// Modules deactivated since the last compilation, their proxies are built
+  /// as inactive again.
+  fn disposed_modules(&mut self) -> impl Future<Output = Result<IdentifierSet>> + Send + '_ {
+    async { Ok(IdentifierSet::default()) }
+  }
 }
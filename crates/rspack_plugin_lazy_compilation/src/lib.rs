@@ -1,9 +1,11 @@
 mod backend;
 mod dependency;
 mod factory;
+mod local_backend;
 mod module;
 mod plugin;
 mod utils;
 
 pub use backend::Backend;
+pub use local_backend::{LAZY_COMPILATION_PREFIX, LocalBackend, LocalBackendOptions};
 pub use plugin::{LazyCompilationPlugin, LazyCompilationTest, LazyCompilationTestCheck};
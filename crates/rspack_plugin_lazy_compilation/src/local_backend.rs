@@ -0,0 +1,377 @@
+use std::{
+  net::SocketAddr,
+  sync::{Arc, Mutex},
+  time::{Duration, Instant},
+};
+
+use rspack_collections::{Identifier, IdentifierMap, IdentifierSet};
+use rspack_error::{Result, error};
+use rspack_util::fx_hash::FxHashMap as HashMap;
+use tokio::{
+  io::{AsyncReadExt, AsyncWriteExt},
+  net::{TcpListener, TcpStream},
+  sync::Notify,
+  task::JoinHandle,
+};
+
+use crate::backend::Backend;
+
+/// The path the bundled lazy compilation clients post to by default.
+pub const LAZY_COMPILATION_PREFIX: &str = "/_rspack/lazy/trigger";
+
+#[derive(Debug, Clone)]
+pub struct LocalBackendOptions {
+  /// Address of the HTTP endpoint, e.g. `127.0.0.1:0` for a random port.
+  /// Without it modules are only activated through [`LocalBackend::activate`].
+  pub listen: Option<SocketAddr>,
+  /// Path of the HTTP endpoint.
+  pub prefix: String,
+  /// How long a module stays compiled after its last reference is released,
+  /// `None` keeps it until [`LocalBackend::dispose`] is called.
+  pub idle_timeout: Option<Duration>,
+}
+
+impl Default for LocalBackendOptions {
+  fn default() -> Self {
+    Self {
+      listen: Some(SocketAddr::from(([127, 0, 0, 1], 0))),
+      prefix: LAZY_COMPILATION_PREFIX.to_string(),
+      idle_timeout: None,
+    }
+  }
+}
+
+#[derive(Debug, Default)]
+struct ModuleState {
+  refs: usize,
+  /// Set while `refs` is zero.
+  idle_since: Option<Instant>,
+}
+
+#[derive(Debug, Default)]
+struct State {
+  modules: IdentifierMap<ModuleState>,
+  /// Modules each client requested, by the `client` query parameter.
+  clients: HashMap<String, IdentifierSet>,
+  /// Modules disposed since the last compilation.
+  disposed: IdentifierSet,
+}
+
+#[derive(Debug)]
+struct Shared {
+  options: LocalBackendOptions,
+  state: Mutex<State>,
+  changed: Notify,
+}
+
+impl Shared {
+  fn lock(&self) -> std::sync::MutexGuard<'_, State> {
+    self.state.lock().expect("should get lock")
+  }
+
+  /// Returns whether the module was not compiled before.
+  fn activate(&self, module: Identifier) -> bool {
+    let mut state = self.lock();
+    state.disposed.remove(&module);
+    let module_state = state.modules.entry(module).or_default();
+    let is_new = module_state.refs == 0 && module_state.idle_since.is_none();
+    module_state.refs += 1;
+    module_state.idle_since = None;
+    is_new
+  }
+
+  fn deactivate(&self, module: Identifier) {
+    let mut state = self.lock();
+    let Some(module_state) = state.modules.get_mut(&module) else {
+      return;
+    };
+    module_state.refs = module_state.refs.saturating_sub(1);
+    if module_state.refs == 0 && module_state.idle_since.is_none() {
+      module_state.idle_since = Some(Instant::now());
+    }
+  }
+
+  fn dispose(&self, module: Identifier) -> bool {
+    let mut state = self.lock();
+    if state.modules.remove(&module).is_none() {
+      return false;
+    }
+    for modules in state.clients.values_mut() {
+      modules.remove(&module);
+    }
+    state.disposed.insert(module);
+    true
+  }
+
+  /// Disposes the modules idle for longer than the idle timeout, returns
+  /// whether any module was disposed.
+  fn sweep(&self) -> bool {
+    let Some(idle_timeout) = self.options.idle_timeout else {
+      return false;
+    };
+    let mut state = self.lock();
+    let expired = state
+      .modules
+      .iter()
+      .filter(|(_, module_state)| {
+        module_state
+          .idle_since
+          .is_some_and(|idle_since| idle_since.elapsed() >= idle_timeout)
+      })
+      .map(|(module, _)| *module)
+      .collect::<Vec<_>>();
+    for module in &expired {
+      state.modules.remove(module);
+    }
+    state.disposed.extend(expired.iter().copied());
+    !expired.is_empty()
+  }
+
+  /// Adds the modules requested by `client`, returns whether a module was
+  /// activated for the first time.
+  ///
+  /// The bundled clients only post the modules that are still being compiled,
+  /// so a module missing from a request is not released. Each client holds at
+  /// most one reference to a module, however often it requests it.
+  fn update_client(&self, client: String, modules: IdentifierSet) -> bool {
+    let added = {
+      let mut state = self.lock();
+      let requested = state.clients.entry(client).or_default();
+      modules
+        .into_iter()
+        .filter(|module| requested.insert(*module))
+        .collect::<Vec<_>>()
+    };
+    let mut activated = false;
+    for module in added {
+      activated |= self.activate(module);
+    }
+    activated
+  }
+}
+
+/// A [`Backend`] that runs in the compiler process, for tests, SSR and other
+/// setups without a JS dev server.
+///
+/// Modules are activated through a local HTTP endpoint that speaks the
+/// protocol of the bundled lazy compilation clients, or directly with
+/// [`LocalBackend::activate`]. Like the JS dev server middleware, a request
+/// only adds modules: a module requested over HTTP stays compiled until it is
+/// disposed with [`LocalBackend::dispose`]. Modules activated directly are
+/// reference counted and disposed once released for `idle_timeout`.
+#[derive(Debug, Clone)]
+pub struct LocalBackend {
+  shared: Arc<Shared>,
+  addr: Option<SocketAddr>,
+  handles: Arc<Vec<JoinHandle<()>>>,
+}
+
+impl LocalBackend {
+  /// Creates the backend and starts the HTTP endpoint if `listen` is set.
+  pub async fn start(options: LocalBackendOptions) -> Result<Self> {
+    let shared = Arc::new(Shared {
+      options,
+      state: Default::default(),
+      changed: Notify::new(),
+    });
+
+    let mut handles = vec![];
+    let mut addr = None;
+    if let Some(listen) = shared.options.listen {
+      let listener = TcpListener::bind(listen)
+        .await
+        .map_err(|e| error!("Failed to start lazy compilation server on {listen}: {e}"))?;
+      addr = Some(
+        listener
+          .local_addr()
+          .map_err(|e| error!("Failed to start lazy compilation server on {listen}: {e}"))?,
+      );
+
+      let server_shared = Arc::clone(&shared);
+      handles.push(tokio::spawn(async move {
+        while let Ok((stream, _)) = listener.accept().await {
+          let shared = Arc::clone(&server_shared);
+          tokio::spawn(async move {
+            if let Err(e) = handle_connection(stream, &shared).await {
+              tracing::debug!("lazy compilation request failed: {e}");
+            }
+          });
+        }
+      }));
+    }
+
+    if let Some(idle_timeout) = shared.options.idle_timeout {
+      let sweeper_shared = Arc::downgrade(&shared);
+      handles.push(tokio::spawn(async move {
+        // Check a few times per timeout so a module is disposed soon after it expires.
+        let mut ticker = tokio::time::interval((idle_timeout / 4).max(Duration::from_millis(10)));
+        ticker.tick().await;
+        while let Some(shared) = sweeper_shared.upgrade() {
+          if shared.sweep() {
+            shared.changed.notify_one();
+          }
+          drop(shared);
+          ticker.tick().await;
+        }
+      }));
+    }
+
+    Ok(Self {
+      shared,
+      addr,
+      handles: Arc::new(handles),
+    })
+  }
+
+  /// Url of the HTTP endpoint.
+  pub fn url(&self) -> Option<String> {
+    self
+      .addr
+      .map(|addr| format!("http://{addr}{}", self.shared.options.prefix))
+  }
+
+  /// The `client` option for [`crate::LazyCompilationPlugin`], which passes
+  /// the endpoint url to the client module as its resource query.
+  pub fn client_request(&self, client: &str) -> String {
+    match self.url() {
+      Some(url) => format!("{client}?{}", urlencoding::encode(&url)),
+      None => client.to_string(),
+    }
+  }
+
+  /// Adds a reference to `module`, the proxy module identifier.
+  pub fn activate(&self, module: impl Into<Identifier>) {
+    if self.shared.activate(module.into()) {
+      self.shared.changed.notify_one();
+    }
+  }
+
+  /// Releases a reference to `module`. It stays compiled until it is
+  /// disposed, either explicitly or by the idle timeout.
+  pub fn deactivate(&self, module: impl Into<Identifier>) {
+    self.shared.deactivate(module.into());
+  }
+
+  /// Drops `module` regardless of its references, its proxy is built as
+  /// inactive again by the next compilation.
+  pub fn dispose(&self, module: impl Into<Identifier>) {
+    if self.shared.dispose(module.into()) {
+      self.shared.changed.notify_one();
+    }
+  }
+
+  pub fn active_modules(&self) -> IdentifierSet {
+    self.shared.lock().modules.keys().copied().collect()
+  }
+
+  /// Resolves when a module was activated or disposed since the last call,
+  /// so the caller can trigger a rebuild.
+  pub async fn changed(&self) {
+    self.shared.changed.notified().await;
+  }
+}
+
+impl Drop for LocalBackend {
+  fn drop(&mut self) {
+    // the tasks are shared by every clone
+    if Arc::strong_count(&self.handles) == 1 {
+      for handle in self.handles.iter() {
+        handle.abort();
+      }
+    }
+  }
+}
+
+impl Backend for LocalBackend {
+  async fn current_active_modules(&mut self) -> Result<IdentifierSet> {
+    self.shared.sweep();
+    Ok(self.active_modules())
+  }
+
+  async fn disposed_modules(&mut self) -> Result<IdentifierSet> {
+    Ok(std::mem::take(&mut self.shared.lock().disposed))
+  }
+}
+
+fn find_head_end(request: &[u8]) -> Option<usize> {
+  request
+    .windows(4)
+    .position(|window| window == b"\r\n\r\n")
+    .map(|position| position + 4)
+}
+
+async fn handle_connection(mut stream: TcpStream, shared: &Shared) -> std::io::Result<()> {
+  let mut request = vec![];
+  let mut buf = [0u8; 8192];
+
+  // read the request head
+  let head_end = loop {
+    let n = stream.read(&mut buf).await?;
+    if n == 0 {
+      return Ok(());
+    }
+    request.extend_from_slice(&buf[..n]);
+    if let Some(head_end) = find_head_end(&request) {
+      break head_end;
+    }
+  };
+
+  let head = String::from_utf8_lossy(&request[..head_end]).into_owned();
+  let (request_line, headers) = head.split_once("\r\n").unwrap_or((&head, ""));
+  let mut parts = request_line.split(' ');
+  let method = parts.next().unwrap_or_default();
+  let target = parts.next().unwrap_or_default();
+  let (path, query) = target.split_once('?').unwrap_or((target, ""));
+
+  // read the request body
+  let content_length = headers
+    .lines()
+    .filter_map(|line| line.split_once(':'))
+    .find(|(name, _)| name.trim().eq_ignore_ascii_case("content-length"))
+    .and_then(|(_, value)| value.trim().parse::<usize>().ok())
+    .unwrap_or(0);
+  while request.len() < head_end + content_length {
+    let n = stream.read(&mut buf).await?;
+    if n == 0 {
+      break;
+    }
+    request.extend_from_slice(&buf[..n]);
+  }
+
+  let status = if path != shared.options.prefix {
+    "404 Not Found"
+  } else {
+    match method {
+      // preflight of clients on another origin
+      "OPTIONS" => "204 No Content",
+      "POST" => {
+        let end = request.len().min(head_end + content_length);
+        let modules = String::from_utf8_lossy(&request[head_end..end])
+          .lines()
+          .filter(|line| !line.is_empty())
+          .map(Identifier::from)
+          .collect::<IdentifierSet>();
+        let client = query
+          .split('&')
+          .find_map(|param| param.strip_prefix("client="))
+          .unwrap_or_default()
+          .to_string();
+        if shared.update_client(client, modules) {
+          shared.changed.notify_one();
+        }
+        "200 OK"
+      }
+      _ => "405 Method Not Allowed",
+    }
+  };
+
+  let body = if status == "204 No Content" { "" } else { "\n" };
+  let head = format!(
+    "HTTP/1.1 {status}\r\nContent-Length: {}\r\nAccess-Control-Allow-Origin: *\r\nAccess-Control-Allow-Methods: POST\r\nAccess-Control-Allow-Headers: Content-Type\r\nConnection: close\r\n\r\n",
+    body.len()
+  );
+  stream.write_all(head.as_bytes()).await?;
+  stream.write_all(body.as_bytes()).await?;
+  stream.flush().await?;
+  stream.shutdown().await
+}
@@ -233,7 +233,12 @@ async fn normal_module_factory_module(
 
 #[plugin_hook(CompilerMake for LazyCompilationPlugin<T: Backend, F: LazyCompilationTestCheck>)]
 async fn compiler_make(&self, compilation: &mut Compilation) -> Result<()> {
-  let active_modules = self.backend.lock().await.current_active_modules().await?;
+  let (active_modules, disposed_modules) = {
+    let mut backend = self.backend.lock().await;
+    let active_modules = backend.current_active_modules().await?;
+    let disposed_modules = backend.disposed_modules().await?;
+    (active_modules, disposed_modules)
+  };
 
   // Dep ids of every entry dependency the current compilation knows about.
   // Used below to detect an entry proxy whose only remaining edge points to
@@ -285,6 +290,24 @@ async fn compiler_make(&self, compilation: &mut Compilation) -> Result<()> {
 
       to_invalidate.insert(*module_id);
     }
+
+    // Rebuild the proxies of disposed modules, they are inactive now.
+    for module_id in &disposed_modules {
+      if active_modules.contains(module_id) {
+        continue;
+      }
+      next_active_modules.remove(module_id);
+      if module_graph
+        .module_by_identifier(module_id)
+        .is_some_and(|module| {
+          module
+            .downcast_ref::<LazyCompilationProxyModule>()
+            .is_some()
+        })
+      {
+        to_invalidate.insert(*module_id);
+      }
+    }
   }
 
   let module_graph = compilation
@@ -0,0 +1,286 @@
+use std::{
+  fs::File,
+  io::{BufWriter, Write},
+  path::PathBuf,
+  sync::Mutex,
+  time::{Instant, SystemTime, UNIX_EPOCH},
+};
+
+use rspack_error::{Result, error};
+use serde_json::json;
+
+use crate::{
+  ProgressPluginHandlerInfo, ProgressPluginStateInfo,
+  summary::{ProfileSummary, phase_name},
+};
+
+/// Where newline-delimited JSON progress events are written to
+#[derive(Debug, Clone)]
+pub enum ProgressJsonOutput {
+  Stderr,
+  /// The file is truncated when the first event is written
+  File(PathBuf),
+}
+
+#[derive(Debug, Clone)]
+pub struct ProgressPluginJsonOptions {
+  pub output: ProgressJsonOutput,
+  // writes a summary of the phase durations when the compilation is done.
+  pub profile: bool,
+}
+
+/// Progress counters of a single event
+#[derive(Debug)]
+pub struct ProgressJsonEvent {
+  pub percent: f64,
+  pub msg: String,
+  pub info: ProgressPluginHandlerInfo,
+  pub modules_count: u32,
+  pub active_modules: usize,
+}
+
+/// Writes progress updates as newline-delimited JSON
+///
+/// Every update is written as a `progress` event. When the phase changes a
+/// `phase` event with the duration of the finished phase is written first,
+/// and in profile mode a `summary` event closes each compilation.
+pub struct JsonReporter {
+  options: ProgressPluginJsonOptions,
+  writer: Mutex<Option<Box<dyn Write + Send>>>,
+  last_state_info: Mutex<Option<ProgressPluginStateInfo>>,
+  summary: Mutex<ProfileSummary>,
+}
+
+impl std::fmt::Debug for JsonReporter {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    f.debug_struct("JsonReporter")
+      .field("options", &self.options)
+      .finish()
+  }
+}
+
+impl JsonReporter {
+  pub fn new(options: ProgressPluginJsonOptions) -> Self {
+    Self {
+      options,
+      writer: Default::default(),
+      last_state_info: Default::default(),
+      summary: Default::default(),
+    }
+  }
+
+  fn open(&self) -> Result<Box<dyn Write + Send>> {
+    Ok(match &self.options.output {
+      ProgressJsonOutput::Stderr => Box::new(std::io::stderr()),
+      ProgressJsonOutput::File(path) => {
+        let file = File::create(path)
+          .map_err(|e| error!("ProgressPlugin failed to create '{}': {e}", path.display()))?;
+        Box::new(BufWriter::new(file))
+      }
+    })
+  }
+
+  fn write(&self, event: serde_json::Value) -> Result<()> {
+    let mut writer = self.writer.lock().expect("should get lock");
+    if writer.is_none() {
+      *writer = Some(self.open()?);
+    }
+    let writer = writer.as_mut().expect("should have writer");
+    // flush every line so that consumers can follow the output while building
+    writeln!(writer, "{event}")
+      .and_then(|_| writer.flush())
+      .map_err(|e| error!("ProgressPlugin failed to write progress event: {e}"))
+  }
+
+  /// Clears the phase timings of the previous compilation
+  pub fn reset(&self) {
+    self.last_state_info.lock().expect("should get lock").take();
+    self.summary.lock().expect("should get lock").clear();
+  }
+
+  pub fn report(&self, event: ProgressJsonEvent) -> Result<()> {
+    let now = Instant::now();
+    let phase = phase_name(&event.msg);
+    let finished_phase = {
+      let mut last_state_info = self.last_state_info.lock().expect("should get lock");
+      match &*last_state_info {
+        // a module update of the current phase
+        Some(state_info) if state_info.value == phase => None,
+        _ => last_state_info
+          .replace(ProgressPluginStateInfo {
+            value: phase.to_string(),
+            time: now,
+            duration: None,
+          })
+          .map(|state_info| {
+            let duration = state_info.duration.unwrap_or_else(|| now - state_info.time);
+            (state_info.value, duration)
+          }),
+      }
+    };
+
+    let timestamp = SystemTime::now()
+      .duration_since(UNIX_EPOCH)
+      .expect("failed to get current time")
+      .as_millis() as u64;
+
+    if let Some((phase, duration)) = finished_phase {
+      self
+        .summary
+        .lock()
+        .expect("should get lock")
+        .add(&phase, duration);
+      self.write(json!({
+        "type": "phase",
+        "timestamp": timestamp,
+        "phase": phase,
+        "duration": duration.as_millis() as u64,
+      }))?;
+    }
+
+    self.write(json!({
+      "type": "progress",
+      "timestamp": timestamp,
+      "percent": event.percent,
+      "phase": phase,
+      "message": event.msg,
+      "builtModules": event.info.built_modules,
+      "modulesCount": event.modules_count,
+      "activeModules": event.active_modules,
+      "moduleIdentifier": event.info.module_identifier,
+    }))?;
+
+    if event.percent == 1.0 && self.options.profile {
+      let summary = self.summary.lock().expect("should get lock");
+      if !summary.is_empty() {
+        self.write(summary.to_json())?;
+      }
+    }
+    Ok(())
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use serde_json::Value;
+
+  use super::*;
+
+  fn reporter(name: &str, profile: bool) -> (JsonReporter, PathBuf) {
+    let path = std::env::temp_dir().join(format!(
+      "rspack-progress-{}-{name}.jsonl",
+      std::process::id()
+    ));
+    let reporter = JsonReporter::new(ProgressPluginJsonOptions {
+      output: ProgressJsonOutput::File(path.clone()),
+      profile,
+    });
+    (reporter, path)
+  }
+
+  fn report(reporter: &JsonReporter, percent: f64, msg: &str, built_modules: u32) {
+    reporter
+      .report(ProgressJsonEvent {
+        percent,
+        msg: msg.to_string(),
+        info: ProgressPluginHandlerInfo {
+          built_modules,
+          module_identifier: (built_modules > 0).then(|| format!("./{built_modules}.js")),
+        },
+        modules_count: 3,
+        active_modules: 1,
+      })
+      .expect("should report");
+  }
+
+  fn events(path: &PathBuf) -> Vec<Value> {
+    let content = std::fs::read_to_string(path).expect("should read output");
+    std::fs::remove_file(path).expect("should remove output");
+    content
+      .lines()
+      .map(|line| serde_json::from_str(line).expect("every line should be JSON"))
+      .collect()
+  }
+
+  fn describe(event: &Value) -> String {
+    match event["type"].as_str() {
+      Some("progress") => format!("progress {}", event["message"].as_str().unwrap_or_default()),
+      Some("phase") => format!("phase {}", event["phase"].as_str().unwrap_or_default()),
+      Some(ty) => ty.to_string(),
+      None => "?".to_string(),
+    }
+  }
+
+  #[test]
+  fn should_write_progress_and_phase_events() {
+    let (reporter, path) = reporter("events", false);
+    report(&reporter, 0.1, "build modules", 0);
+    report(&reporter, 0.3, "build modules (1)", 1);
+    report(&reporter, 0.5, "build modules (2)", 2);
+    report(&reporter, 0.7, "seal", 2);
+    report(&reporter, 1.0, "done", 2);
+    drop(reporter);
+
+    let events = events(&path);
+    assert_eq!(
+      events.iter().map(describe).collect::<Vec<_>>(),
+      [
+        "progress build modules",
+        "progress build modules (1)",
+        "progress build modules (2)",
+        "phase build modules",
+        "progress seal",
+        "phase seal",
+        "progress done",
+      ]
+    );
+
+    let progress = &events[1];
+    assert_eq!(progress["percent"], 0.3);
+    assert_eq!(progress["phase"], "build modules");
+    assert_eq!(progress["builtModules"], 1);
+    assert_eq!(progress["modulesCount"], 3);
+    assert_eq!(progress["activeModules"], 1);
+    assert_eq!(progress["moduleIdentifier"], "./1.js");
+    assert!(progress["timestamp"].is_u64());
+    assert_eq!(events[0]["moduleIdentifier"], Value::Null);
+    assert!(events[3]["duration"].is_u64());
+  }
+
+  #[test]
+  fn should_write_summary_in_profile_mode() {
+    let (reporter, path) = reporter("summary", true);
+    report(&reporter, 0.1, "build modules", 0);
+    report(&reporter, 0.7, "seal", 0);
+    report(&reporter, 1.0, "done", 0);
+    drop(reporter);
+
+    let events = events(&path);
+    let summary = events.last().expect("should have events");
+    assert_eq!(summary["type"], "summary");
+    assert!(summary["total"].is_u64());
+    let mut phases = summary["phases"]
+      .as_array()
+      .expect("should have phases")
+      .iter()
+      .map(|phase| phase["phase"].as_str().unwrap_or_default())
+      .collect::<Vec<_>>();
+    phases.sort_unstable();
+    assert_eq!(phases, ["build modules", "seal"]);
+  }
+
+  #[test]
+  fn should_start_a_new_phase_after_reset() {
+    let (reporter, path) = reporter("reset", false);
+    std::fs::write(&path, "stale\n").expect("should write output");
+    report(&reporter, 0.1, "build modules", 0);
+    reporter.reset();
+    report(&reporter, 0.7, "seal", 0);
+    drop(reporter);
+
+    assert_eq!(
+      events(&path).iter().map(describe).collect::<Vec<_>>(),
+      ["progress build modules", "progress seal"]
+    );
+  }
+}
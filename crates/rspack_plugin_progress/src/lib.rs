@@ -1,3 +1,6 @@
+mod json;
+mod summary;
+
 use std::{
   cmp,
   cmp::Ordering,
@@ -27,6 +30,12 @@ use rspack_error::{Diagnostic, Result};
 use rspack_hook::{plugin, plugin_hook};
 use tokio::sync::Mutex;
 
+pub use self::json::{ProgressJsonOutput, ProgressPluginJsonOptions};
+use self::{
+  json::{JsonReporter, ProgressJsonEvent},
+  summary::{ProfileSummary, phase_name},
+};
+
 type HandlerFn = Arc<
   dyn Fn(f64, String, ProgressPluginHandlerInfo) -> BoxFuture<'static, Result<()>> + Send + Sync,
 >;
@@ -40,6 +49,8 @@ pub struct ProgressPluginHandlerInfo {
 pub enum ProgressPluginOptions {
   Handler(HandlerFn),
   Default(ProgressPluginDisplayOptions),
+  /// Writes newline-delimited JSON events instead of drawing a progress bar
+  Json(ProgressPluginJsonOptions),
 }
 
 impl std::fmt::Debug for ProgressPluginOptions {
@@ -52,6 +63,10 @@ impl std::fmt::Debug for ProgressPluginOptions {
         .debug_struct("ProgressPluginOptions::Default")
         .field("options", &options)
         .finish(),
+      ProgressPluginOptions::Json(options) => f
+        .debug_struct("ProgressPluginOptions::Json")
+        .field("options", &options)
+        .finish(),
     }
   }
 }
@@ -79,11 +94,36 @@ pub struct ProgressPluginStateInfo {
   pub duration: Option<Duration>,
 }
 
+/// How progress is reported, with the state of the reporter
+enum ProgressMode {
+  Handler(HandlerFn),
+  Default {
+    options: ProgressPluginDisplayOptions,
+    progress_bar: ProgressBar,
+  },
+  Json(JsonReporter),
+}
+
+impl std::fmt::Debug for ProgressMode {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    match self {
+      ProgressMode::Handler(_handler) => f.debug_struct("ProgressMode::Handler").finish(),
+      ProgressMode::Default { options, .. } => f
+        .debug_struct("ProgressMode::Default")
+        .field("options", &options)
+        .finish(),
+      ProgressMode::Json(json_reporter) => f
+        .debug_struct("ProgressMode::Json")
+        .field("json_reporter", &json_reporter)
+        .finish(),
+    }
+  }
+}
+
 #[plugin]
 #[derive(Debug)]
 pub struct ProgressPlugin {
-  pub options: ProgressPluginOptions,
-  pub progress_bar: Option<ProgressBar>,
+  mode: ProgressMode,
   pub modules_count: Arc<AtomicU32>,
   pub modules_done: Arc<AtomicU32>,
   pub active_modules: Arc<Mutex<IdentifierMap<Instant>>>,
@@ -91,12 +131,14 @@ pub struct ProgressPlugin {
   pub last_active_module: Arc<Mutex<Option<ModuleIdentifier>>>,
   pub last_state_info: Arc<Mutex<Vec<ProgressPluginStateInfo>>>,
   pub last_updated: Arc<AtomicU32>,
+  profile_summary: Arc<Mutex<ProfileSummary>>,
 }
 
 impl ProgressPlugin {
   pub fn new(options: ProgressPluginOptions) -> Self {
-    let progress_bar = match &options {
-      ProgressPluginOptions::Handler(_fn) => None,
+    let mode = match options {
+      ProgressPluginOptions::Handler(handler) => ProgressMode::Handler(handler),
+      ProgressPluginOptions::Json(options) => ProgressMode::Json(JsonReporter::new(options)),
       ProgressPluginOptions::Default(options) => {
         let progress_bar = MULTI_PROGRESS.add(ProgressBar::new(100));
 
@@ -113,12 +155,14 @@ impl ProgressPlugin {
           );
         }
         progress_bar.set_style(progress_bar_style);
-        Some(progress_bar)
+        ProgressMode::Default {
+          options,
+          progress_bar,
+        }
       }
     };
     Self::new_inner(
-      options,
-      progress_bar,
+      mode,
       Default::default(),
       Default::default(),
       Default::default(),
@@ -186,20 +230,32 @@ impl ProgressPlugin {
     info: ProgressPluginHandlerInfo,
     time: Option<Duration>,
   ) -> Result<()> {
-    match &self.options {
-      ProgressPluginOptions::Handler(handler) => handler(percent, msg, info).await?,
-      ProgressPluginOptions::Default(options) => {
+    match &self.mode {
+      ProgressMode::Handler(handler) => handler(percent, msg, info).await?,
+      ProgressMode::Default {
+        options,
+        progress_bar,
+      } => {
         if options.profile {
           self.default_handler(percent, msg, time).await;
         } else {
-          self.progress_bar_handler(percent, msg);
+          Self::progress_bar_handler(progress_bar, percent, msg);
         }
       }
+      ProgressMode::Json(json_reporter) => {
+        json_reporter.report(ProgressJsonEvent {
+          percent,
+          msg,
+          info,
+          modules_count: self.modules_count.load(Relaxed),
+          active_modules: self.active_modules.lock().await.len(),
+        })?;
+      }
     };
     Ok(())
   }
 
-  async fn default_handler(&self, _: f64, msg: String, duration: Option<Duration>) {
+  async fn default_handler(&self, percent: f64, msg: String, duration: Option<Duration>) {
     let full_state = [msg.clone()];
     let now = Instant::now();
     {
@@ -227,6 +283,10 @@ impl ProgressPlugin {
           } else {
             last_state_info[i].value.clone()
           };
+          self.profile_summary.lock().await.add(
+            phase_name(&report_state),
+            Duration::from_millis(diff as u64),
+          );
 
           if diff > 5 {
             // TODO: color map
@@ -264,19 +324,25 @@ impl ProgressPlugin {
         }
       }
     }
-  }
 
-  fn progress_bar_handler(&self, percent: f64, msg: String) {
-    if let Some(progress_bar) = &self.progress_bar {
-      if percent == 1.0 {
-        progress_bar.finish_with_message(msg);
-      } else {
-        progress_bar.set_message(msg);
-        progress_bar.set_position((percent * 100.0) as u64);
+    if percent == 1.0 {
+      let mut profile_summary = self.profile_summary.lock().await;
+      if !profile_summary.is_empty() {
+        println!("{profile_summary}");
+        profile_summary.clear();
       }
     }
   }
 
+  fn progress_bar_handler(progress_bar: &ProgressBar, percent: f64, msg: String) {
+    if percent == 1.0 {
+      progress_bar.finish_with_message(msg);
+    } else {
+      progress_bar.set_message(msg);
+      progress_bar.set_position((percent * 100.0) as u64);
+    }
+  }
+
   async fn sealing_hooks_report(&self, name: &str, index: i32) -> Result<()> {
     let number_of_sealing_hooks = 38;
     self
@@ -290,9 +356,9 @@ impl ProgressPlugin {
   }
 
   pub fn is_profile(&self) -> bool {
-    match &self.options {
-      ProgressPluginOptions::Handler(_) => false,
-      ProgressPluginOptions::Default(options) => options.profile,
+    match &self.mode {
+      ProgressMode::Handler(_) | ProgressMode::Json(_) => false,
+      ProgressMode::Default { options, .. } => options.profile,
     }
   }
 }
@@ -303,12 +369,16 @@ async fn this_compilation(
   _compilation: &mut Compilation,
   _params: &mut CompilationParams,
 ) -> Result<()> {
-  if let ProgressPluginOptions::Default(options) = &self.options {
-    let progress_bar = self.progress_bar.as_ref().unwrap_or_else(|| unreachable!());
-    if !options.profile {
+  match &self.mode {
+    ProgressMode::Default {
+      options,
+      progress_bar,
+    } if !options.profile => {
       progress_bar.reset();
       progress_bar.set_prefix(options.prefix.clone());
     }
+    ProgressMode::Json(json_reporter) => json_reporter.reset(),
+    _ => {}
   }
 
   self
@@ -372,9 +442,12 @@ async fn build_module(
     .lock()
     .await
     .replace(module.identifier());
-  if let ProgressPluginOptions::Default(options) = &self.options
-    && !options.profile
-  {
+  let should_update = match &self.mode {
+    ProgressMode::Default { options, .. } => !options.profile,
+    ProgressMode::Json(_) => true,
+    ProgressMode::Handler(_) => false,
+  };
+  if should_update {
     self.update_throttled().await?;
   }
 
@@ -575,7 +648,7 @@ async fn after_emit(&self, _compilation: &mut Compilation) -> Result<()> {
 
 #[plugin_hook(CompilerClose for ProgressPlugin)]
 async fn close(&self, _compilation: &Compilation) -> Result<()> {
-  if let Some(progress_bar) = &self.progress_bar {
+  if let ProgressMode::Default { progress_bar, .. } = &self.mode {
     MULTI_PROGRESS.remove(progress_bar);
   }
   Ok(())
@@ -0,0 +1,137 @@
+use std::{fmt, time::Duration};
+
+use serde_json::json;
+
+/// Strips the module counter from messages like `build modules (42)`
+pub(crate) fn phase_name(msg: &str) -> &str {
+  msg
+    .strip_suffix(')')
+    .and_then(|msg| msg.rsplit_once(" ("))
+    .filter(|(_, count)| count.chars().all(|c| c.is_ascii_digit()))
+    .map_or(msg, |(phase, _)| phase)
+}
+
+#[derive(Debug)]
+struct PhaseTiming {
+  name: String,
+  duration: Duration,
+  count: u32,
+}
+
+/// Accumulated durations of the progress phases of a compilation
+#[derive(Debug, Default)]
+pub struct ProfileSummary {
+  phases: Vec<PhaseTiming>,
+}
+
+impl ProfileSummary {
+  pub fn add(&mut self, name: &str, duration: Duration) {
+    if let Some(phase) = self.phases.iter_mut().find(|phase| phase.name == name) {
+      phase.duration += duration;
+      phase.count += 1;
+    } else {
+      self.phases.push(PhaseTiming {
+        name: name.to_string(),
+        duration,
+        count: 1,
+      });
+    }
+  }
+
+  pub fn clear(&mut self) {
+    self.phases.clear();
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.phases.is_empty()
+  }
+
+  fn total(&self) -> Duration {
+    self.phases.iter().map(|phase| phase.duration).sum()
+  }
+
+  /// Phases sorted by duration, the slowest first
+  fn sorted_phases(&self) -> Vec<&PhaseTiming> {
+    let mut phases = self.phases.iter().collect::<Vec<_>>();
+    phases.sort_by_key(|phase| std::cmp::Reverse(phase.duration));
+    phases
+  }
+
+  pub fn to_json(&self) -> serde_json::Value {
+    json!({
+      "type": "summary",
+      "total": self.total().as_millis() as u64,
+      "phases": self
+        .sorted_phases()
+        .into_iter()
+        .map(|phase| json!({
+          "phase": phase.name,
+          "duration": phase.duration.as_millis() as u64,
+          "count": phase.count,
+        }))
+        .collect::<Vec<_>>(),
+    })
+  }
+}
+
+impl fmt::Display for ProfileSummary {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    let total = self.total();
+    let name_width = self
+      .phases
+      .iter()
+      .map(|phase| phase.name.len())
+      .max()
+      .unwrap_or_default()
+      .max("Phase".len());
+
+    writeln!(f, "{:<name_width$} {:>10} {:>7}", "Phase", "Time", "Share")?;
+    for phase in self.sorted_phases() {
+      let share = if total.is_zero() {
+        0.0
+      } else {
+        phase.duration.as_secs_f64() * 100.0 / total.as_secs_f64()
+      };
+      writeln!(
+        f,
+        "{:<name_width$} {:>7} ms {share:>6.1}%",
+        phase.name,
+        phase.duration.as_millis()
+      )?;
+    }
+    writeln!(f, "{:<name_width$} {:>7} ms", "Total", total.as_millis())
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use super::*;
+
+  #[test]
+  fn should_strip_module_counters() {
+    assert_eq!(phase_name("build modules (42)"), "build modules");
+    assert_eq!(phase_name("build modules"), "build modules");
+    assert_eq!(
+      phase_name("process assets (stage)"),
+      "process assets (stage)"
+    );
+  }
+
+  #[test]
+  fn should_sum_phases() {
+    let mut summary = ProfileSummary::default();
+    summary.add("seal", Duration::from_millis(10));
+    summary.add("build modules", Duration::from_millis(30));
+    summary.add("seal", Duration::from_millis(5));
+
+    let json = summary.to_json();
+    assert_eq!(json["total"], 45);
+    assert_eq!(json["phases"][0]["phase"], "build modules");
+    assert_eq!(json["phases"][1]["phase"], "seal");
+    assert_eq!(json["phases"][1]["duration"], 15);
+    assert_eq!(json["phases"][1]["count"], 2);
+
+    summary.clear();
+    assert!(summary.is_empty());
+  }
+}
@@ -6,7 +6,10 @@ use cow_utils::CowUtils;
 use napi::bindgen_prelude::Buffer;
 use rspack_fs::WritableFileSystem;
 use rspack_paths::Utf8Path;
-use rspack_util::{base64, current_time, fx_hash::FxHashMap};
+use rspack_util::{
+  base64, current_time,
+  fx_hash::{FxHashMap, FxHashSet},
+};
 use serde::{Deserialize, Serialize};
 use sha2::{Digest, Sha512};
 use url::Url;
@@ -72,9 +75,37 @@ pub struct RedirectFetchResult {
   meta: FetchResultMeta,
 }
 
+/// A lockfile entry whose cached content can't be used
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LockfileIssue {
+  /// The cache has no content for the url
+  MissingContent { url: String },
+  /// The cached content doesn't match the integrity in the lockfile
+  IntegrityMismatch {
+    url: String,
+    expected: String,
+    actual: String,
+  },
+}
+
+impl std::fmt::Display for LockfileIssue {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    match self {
+      Self::MissingContent { url } => write!(f, "{url}: content is missing from the cache"),
+      Self::IntegrityMismatch {
+        url,
+        expected,
+        actual,
+      } => write!(
+        f,
+        "{url}: content doesn't match the lockfile integrity (expected {expected}, got {actual})"
+      ),
+    }
+  }
+}
+
 pub enum FetchResultType {
   Content(ContentFetchResult),
-  #[allow(dead_code)]
   Redirect(RedirectFetchResult),
 }
 
@@ -107,6 +138,16 @@ impl HttpCache {
     url: &str,
     options: &HttpUriPluginOptions,
   ) -> Result<FetchResultType> {
+    if options.frozen {
+      return self.fetch_frozen(url).await;
+    }
+
+    if let Some(redirect) = self.read_redirect(url).await?
+      && (!options.upgrade || redirect.meta.fresh)
+    {
+      return Ok(FetchResultType::Redirect(redirect));
+    }
+
     let cached_result = self.read_from_cache(url).await?;
 
     if let Some(ref cached) = cached_result
@@ -118,6 +159,121 @@ impl HttpCache {
     self.fetch_content_raw(url, cached_result).await
   }
 
+  /// Serves `url` from the lockfile and the cache only, the http client is
+  /// never called. A locked redirect is returned as is and its location is
+  /// served from the lockfile in turn.
+  async fn fetch_frozen(&self, url: &str) -> Result<FetchResultType> {
+    if self.cache_location.is_none() || !self.lockfile_cache.has_location() {
+      return Err(anyhow::anyhow!(
+        "Frozen mode requires both a lockfile location and a cache location"
+      ));
+    }
+
+    let lockfile = self.lockfile_cache.get_lockfile().await?;
+    let Some(entry) = lockfile.lock().await.get_entry(url).cloned() else {
+      return Err(anyhow::anyhow!(
+        "{url} is not in the lockfile. Frozen mode doesn't access the network, build without `frozen` to add it"
+      ));
+    };
+
+    if entry.is_redirect(url) {
+      return Ok(FetchResultType::Redirect(RedirectFetchResult {
+        location: entry.resolved,
+        meta: FetchResultMeta {
+          store_cache: false,
+          store_lock: false,
+          valid_until: entry.valid_until,
+          etag: entry.etag,
+          fresh: true,
+        },
+      }));
+    }
+
+    let content = match self.check_entry(url, &entry).await {
+      Ok(content) => content,
+      Err(issue) => return Err(anyhow::anyhow!("Frozen mode can't serve {issue}")),
+    };
+
+    Ok(FetchResultType::Content(ContentFetchResult {
+      meta: FetchResultMeta {
+        store_cache: false,
+        store_lock: false,
+        valid_until: entry.valid_until,
+        etag: entry.etag.clone(),
+        fresh: true,
+      },
+      entry,
+      content: BufferOrBytes::Bytes(content),
+    }))
+  }
+
+  /// Reads the cached content of a lockfile entry and checks its integrity.
+  async fn check_entry(
+    &self,
+    url: &str,
+    entry: &LockfileEntry,
+  ) -> std::result::Result<Vec<u8>, LockfileIssue> {
+    let Some(content) = self.read_cached_content(&entry.resolved).await else {
+      return Err(LockfileIssue::MissingContent {
+        url: url.to_string(),
+      });
+    };
+    let integrity = compute_integrity(&content);
+    if integrity != entry.integrity {
+      return Err(LockfileIssue::IntegrityMismatch {
+        url: url.to_string(),
+        expected: entry.integrity.clone(),
+        actual: integrity,
+      });
+    }
+    Ok(content)
+  }
+
+  /// Checks that every lockfile entry has cached content matching its
+  /// integrity. Redirects have no content and are skipped.
+  pub async fn verify(&self) -> Result<Vec<LockfileIssue>> {
+    let lockfile = self.lockfile_cache.get_lockfile().await?;
+    let mut entries = lockfile
+      .lock()
+      .await
+      .entries()
+      .filter(|(url, entry)| !entry.is_redirect(url))
+      .map(|(url, entry)| (url.clone(), entry.clone()))
+      .collect::<Vec<_>>();
+    entries.sort_by(|a, b| a.0.cmp(&b.0));
+
+    let mut issues = vec![];
+    for (url, entry) in entries {
+      if let Err(issue) = self.check_entry(&url, &entry).await {
+        issues.push(issue);
+      }
+    }
+    Ok(issues)
+  }
+
+  /// Removes the lockfile entries and cached content of the urls not in
+  /// `used` nor redirected to by a used url, returns the removed urls.
+  pub async fn prune(&self, used: &FxHashSet<String>) -> Result<Vec<String>> {
+    let lockfile = self.lockfile_cache.get_lockfile().await?;
+    let removed = lockfile.lock().await.prune(used);
+    if removed.is_empty() {
+      return Ok(vec![]);
+    }
+    self.lockfile_cache.save_lockfile().await?;
+
+    if let Some(cache_location) = &self.cache_location {
+      for (_, entry) in removed
+        .iter()
+        .filter(|(url, entry)| !entry.is_redirect(url))
+      {
+        let cache_path_buf = cache_location.join(self.get_cache_key(&entry.resolved));
+        let cache_path = Utf8Path::from_path(&cache_path_buf).expect("Invalid cache path");
+        self.filesystem.remove_file(cache_path).await.ok();
+      }
+    }
+    Ok(removed.into_iter().map(|(url, _)| url).collect())
+  }
+
   async fn fetch_content_raw(
     &self,
     url: &str,
@@ -203,6 +359,29 @@ impl HttpCache {
       //   }));
       // }
 
+      // Lock the redirect so that frozen builds can follow it offline
+      if store_lock {
+        let lockfile = self.lockfile_cache.get_lockfile().await?;
+        let mut lock_guard = lockfile.lock().await;
+        let entry = LockfileEntry {
+          resolved: absolute_location.clone(),
+          integrity: String::new(),
+          content_type: String::new(),
+          valid_until,
+          etag: etag.clone(),
+        };
+        let should_update = lock_guard.get_entry(url).is_none_or(|cached| {
+          cached.resolved != entry.resolved
+            || cached.valid_until != entry.valid_until
+            || cached.etag != entry.etag
+        });
+        if should_update {
+          lock_guard.entries_mut().insert(url.to_string(), entry);
+          drop(lock_guard);
+          self.lockfile_cache.save_lockfile().await?;
+        }
+      }
+
       return Ok(FetchResultType::Redirect(RedirectFetchResult {
         location: absolute_location,
         meta: FetchResultMeta {
@@ -280,12 +459,17 @@ impl HttpCache {
       let lockfile = self.lockfile_cache.get_lockfile().await?;
       let lock_guard = lockfile.lock().await;
 
-      if let Some(entry) = lock_guard.get_entry(resource) {
+      if let Some(entry) = lock_guard.get_entry(resource)
+        && !entry.is_redirect(resource)
+      {
         let cache_key = self.get_cache_key(&entry.resolved);
         let cache_path_buf = cache_location.join(&cache_key);
         let cache_path = Utf8Path::from_path(&cache_path_buf).expect("Invalid cache path");
 
-        if let Ok(content) = self.filesystem.read_file(cache_path).await {
+        // content that doesn't match the lockfile is fetched again
+        if let Ok(content) = self.filesystem.read_file(cache_path).await
+          && compute_integrity(&content) == entry.integrity
+        {
           let meta = FetchResultMeta {
             store_cache: true,
             store_lock: true,
@@ -307,6 +491,34 @@ impl HttpCache {
     Ok(None)
   }
 
+  /// Reads the locked redirect of `resource`.
+  async fn read_redirect(&self, resource: &str) -> Result<Option<RedirectFetchResult>> {
+    let lockfile = self.lockfile_cache.get_lockfile().await?;
+    let lock_guard = lockfile.lock().await;
+    Ok(
+      lock_guard
+        .get_entry(resource)
+        .filter(|entry| entry.is_redirect(resource))
+        .map(|entry| RedirectFetchResult {
+          location: entry.resolved.clone(),
+          meta: FetchResultMeta {
+            store_cache: false,
+            store_lock: true,
+            valid_until: entry.valid_until,
+            etag: entry.etag.clone(),
+            fresh: entry.valid_until >= current_time(),
+          },
+        }),
+    )
+  }
+
+  async fn read_cached_content(&self, resource: &str) -> Option<Vec<u8>> {
+    let cache_location = self.cache_location.as_ref()?;
+    let cache_path_buf = cache_location.join(self.get_cache_key(resource));
+    let cache_path = Utf8Path::from_path(&cache_path_buf)?;
+    self.filesystem.read_file(cache_path).await.ok()
+  }
+
   async fn write_to_cache(&self, resource: &str, content: &[u8]) -> Result<()> {
     if let Some(cache_location) = &self.cache_location {
       // Generate cache key using webpack-compatible format
@@ -422,15 +634,19 @@ impl HttpCache {
   }
 }
 
+impl From<&HttpUriPluginOptions> for HttpCache {
+  fn from(options: &HttpUriPluginOptions) -> Self {
+    HttpCache::new(
+      options.cache_location.clone(),
+      options.lockfile_location.clone(),
+      options.filesystem.clone(),
+      options.http_client.clone(),
+    )
+  }
+}
+
 pub async fn fetch_content(url: &str, options: &HttpUriPluginOptions) -> Result<FetchResultType> {
-  let http_cache = HttpCache::new(
-    options.cache_location.clone(),
-    options.lockfile_location.clone(),
-    options.filesystem.clone(),
-    options.http_client.clone(),
-  );
-
-  http_cache.fetch_content(url, options).await
+  HttpCache::from(options).fetch_content(url, options).await
 }
 
 fn parse_cache_control(cache_control: &Option<String>, request_time: u64) -> (bool, bool, u64) {
@@ -468,3 +684,187 @@ fn compute_integrity(content: &[u8]) -> String {
   // Use base64 for integrity as that's the standard format
   format!("sha512-{}", base64::encode_to_string(digest))
 }
+
+#[cfg(test)]
+mod test {
+  use rspack_fs::MemoryFileSystem;
+  use rspack_util::asset_condition::{AssetCondition, AssetConditions};
+
+  use super::*;
+  use crate::http_uri::{HttpUriOptionsAllowedUris, resolve_content};
+
+  const URL: &str = "https://example.com/lib.js";
+  const OLD_URL: &str = "https://example.com/old/lib.js";
+
+  #[derive(Debug)]
+  struct OfflineClient;
+
+  #[async_trait]
+  impl HttpClient for OfflineClient {
+    async fn get(&self, url: &str, _headers: &FxHashMap<String, String>) -> Result<HttpResponse> {
+      panic!("{url} should not be requested in frozen mode")
+    }
+  }
+
+  /// Redirects the old url to [URL] and serves it.
+  #[derive(Debug)]
+  struct RedirectClient;
+
+  #[async_trait]
+  impl HttpClient for RedirectClient {
+    async fn get(&self, url: &str, _headers: &FxHashMap<String, String>) -> Result<HttpResponse> {
+      let (status, headers, body) = match url {
+        OLD_URL => (301, vec![("location", "/lib.js")], vec![]),
+        URL => (
+          200,
+          vec![("content-type", "application/javascript")],
+          b"export default 1".to_vec(),
+        ),
+        _ => (404, vec![], vec![]),
+      };
+      Ok(HttpResponse {
+        status,
+        headers: headers
+          .into_iter()
+          .map(|(key, value)| (key.to_string(), value.to_string()))
+          .collect(),
+        body: body.into(),
+      })
+    }
+  }
+
+  fn redirect_options(
+    filesystem: Arc<MemoryFileSystem>,
+    frozen: bool,
+    http_client: Arc<dyn HttpClient>,
+  ) -> HttpUriPluginOptions {
+    HttpUriPluginOptions {
+      allowed_uris: HttpUriOptionsAllowedUris::new(AssetConditions::Single(
+        AssetCondition::String("https://example.com/".to_string()),
+      )),
+      lockfile_location: Some("/project/rspack.lock".to_string()),
+      cache_location: Some("/project/rspack.lock.data".to_string()),
+      upgrade: false,
+      frozen,
+      prune_lockfile: false,
+      filesystem,
+      http_client,
+    }
+  }
+
+  async fn frozen_options(content: &[u8]) -> HttpUriPluginOptions {
+    let options = HttpUriPluginOptions {
+      allowed_uris: HttpUriOptionsAllowedUris::new(AssetConditions::Multiple(vec![])),
+      lockfile_location: Some("/project/rspack.lock".to_string()),
+      cache_location: Some("/project/rspack.lock.data".to_string()),
+      upgrade: false,
+      frozen: true,
+      prune_lockfile: false,
+      filesystem: Arc::new(MemoryFileSystem::default()),
+      http_client: Arc::new(OfflineClient),
+    };
+
+    let cache = HttpCache::from(&options);
+    cache.write_to_cache(URL, content).await.unwrap();
+    let lockfile = cache.lockfile_cache.get_lockfile().await.unwrap();
+    lockfile.lock().await.entries_mut().insert(
+      URL.to_string(),
+      LockfileEntry {
+        resolved: URL.to_string(),
+        integrity: compute_integrity(b"export default 1"),
+        content_type: "application/javascript".to_string(),
+        valid_until: 0,
+        etag: None,
+      },
+    );
+    cache.lockfile_cache.save_lockfile().await.unwrap();
+    options
+  }
+
+  #[tokio::test]
+  async fn frozen_mode_serves_locked_content_offline() {
+    let options = frozen_options(b"export default 1").await;
+
+    let Ok(FetchResultType::Content(result)) = fetch_content(URL, &options).await else {
+      panic!("should serve the cached content");
+    };
+    assert_eq!(result.content(), b"export default 1");
+
+    let err = fetch_content("https://example.com/other.js", &options)
+      .await
+      .err()
+      .expect("should reject a url missing from the lockfile");
+    assert!(err.to_string().contains("is not in the lockfile"));
+  }
+
+  #[tokio::test]
+  async fn frozen_mode_rejects_tampered_content() {
+    let options = frozen_options(b"export default 2").await;
+
+    let err = fetch_content(URL, &options)
+      .await
+      .err()
+      .expect("should reject content that doesn't match the integrity");
+    assert!(
+      err
+        .to_string()
+        .contains("doesn't match the lockfile integrity")
+    );
+
+    let issues = HttpCache::from(&options).verify().await.unwrap();
+    assert!(matches!(
+      issues.as_slice(),
+      [LockfileIssue::IntegrityMismatch { url, .. }] if url == URL
+    ));
+  }
+
+  #[tokio::test]
+  async fn prune_removes_unused_entries() {
+    let options = frozen_options(b"export default 1").await;
+    let cache = HttpCache::from(&options);
+    assert!(cache.verify().await.unwrap().is_empty());
+
+    let used = FxHashSet::from_iter([URL.to_string()]);
+    assert!(cache.prune(&used).await.unwrap().is_empty());
+
+    let removed = cache.prune(&FxHashSet::default()).await.unwrap();
+    assert_eq!(removed, vec![URL.to_string()]);
+    let lockfile = cache.lockfile_cache.get_lockfile().await.unwrap();
+    assert_eq!(lockfile.lock().await.entries().count(), 0);
+    assert!(cache.read_cached_content(URL).await.is_none());
+  }
+
+  #[tokio::test]
+  async fn frozen_mode_follows_locked_redirects() {
+    let filesystem = Arc::new(MemoryFileSystem::default());
+    let options = redirect_options(filesystem.clone(), false, Arc::new(RedirectClient));
+    let result = resolve_content(OLD_URL, &options, 0).await.unwrap();
+    assert_eq!(result.entry.resolved, URL);
+
+    let lockfile = HttpCache::from(&options)
+      .lockfile_cache
+      .get_lockfile()
+      .await
+      .unwrap();
+    let redirect = lockfile
+      .lock()
+      .await
+      .get_entry(OLD_URL)
+      .cloned()
+      .expect("should lock the redirect");
+    assert!(redirect.is_redirect(OLD_URL));
+    assert_eq!(redirect.resolved, URL);
+
+    let options = redirect_options(filesystem, true, Arc::new(OfflineClient));
+    let result = resolve_content(OLD_URL, &options, 0).await.unwrap();
+    assert_eq!(result.entry.resolved, URL);
+    assert_eq!(result.content(), b"export default 1");
+
+    let cache = HttpCache::from(&options);
+    assert!(cache.verify().await.unwrap().is_empty());
+    let used = FxHashSet::from_iter([OLD_URL.to_string()]);
+    assert!(cache.prune(&used).await.unwrap().is_empty());
+    let removed = cache.prune(&FxHashSet::default()).await.unwrap();
+    assert_eq!(removed, vec![URL.to_string(), OLD_URL.to_string()]);
+  }
+}
@@ -7,7 +7,7 @@ use std::{
 use async_trait::async_trait;
 use rspack_fs::WritableFileSystem;
 use rspack_paths::Utf8Path;
-use rspack_util::fx_hash::FxHashMap;
+use rspack_util::fx_hash::{FxHashMap, FxHashSet};
 use serde::{Deserialize, Serialize};
 use tokio::sync::Mutex;
 
@@ -20,6 +20,14 @@ pub struct LockfileEntry {
   pub etag: Option<String>,
 }
 
+impl LockfileEntry {
+  /// A redirect entry has no content, `resolved` is the redirect location
+  /// that has its own entry.
+  pub fn is_redirect(&self, url: &str) -> bool {
+    self.resolved != url
+  }
+}
+
 #[derive(Debug, Clone)]
 pub struct Lockfile {
   version: u8,
@@ -57,7 +65,11 @@ impl Lockfile {
           }
         } else {
           LockfileEntry {
-            resolved: key.clone(),
+            resolved: value
+              .get("resolved")
+              .and_then(|v| v.as_str())
+              .unwrap_or(key)
+              .to_string(),
             integrity: value
               .get("integrity")
               .and_then(|v| v.as_str())
@@ -83,9 +95,14 @@ impl Lockfile {
   }
 
   pub fn to_json_string(&self) -> Result<String, serde_json::Error> {
+    // sorted so that pruning and re-adding entries gives stable diffs
+    let entries = self
+      .entries
+      .iter()
+      .collect::<std::collections::BTreeMap<_, _>>();
     let json = serde_json::json!({
         "version": self.version,
-        "entries": self.entries
+        "entries": entries
     });
     serde_json::to_string_pretty(&json)
   }
@@ -94,9 +111,41 @@ impl Lockfile {
     self.entries.get(resource)
   }
 
+  pub fn entries(&self) -> impl Iterator<Item = (&String, &LockfileEntry)> {
+    self.entries.iter()
+  }
+
   pub fn entries_mut(&mut self) -> &mut FxHashMap<String, LockfileEntry> {
     &mut self.entries
   }
+
+  /// Removes the entries whose url is not in `used` and returns their urls
+  /// with the removed entries, sorted by url. The redirect chain of a used
+  /// url is kept.
+  pub fn prune(&mut self, used: &FxHashSet<String>) -> Vec<(String, LockfileEntry)> {
+    let mut kept = FxHashSet::default();
+    for url in used {
+      let mut url = url.as_str();
+      while kept.insert(url)
+        && let Some(entry) = self.entries.get(url)
+        && entry.is_redirect(url)
+      {
+        url = entry.resolved.as_str();
+      }
+    }
+    let unused = self
+      .entries
+      .keys()
+      .filter(|url| !kept.contains(url.as_str()))
+      .cloned()
+      .collect::<Vec<_>>();
+    let mut removed = unused
+      .into_iter()
+      .filter_map(|url| self.entries.remove(&url).map(|entry| (url, entry)))
+      .collect::<Vec<_>>();
+    removed.sort_by(|a, b| a.0.cmp(&b.0));
+    removed
+  }
 }
 
 #[async_trait]
@@ -176,6 +225,10 @@ impl LockfileCache {
     }
   }
 
+  pub fn has_location(&self) -> bool {
+    self.lockfile_path.is_some()
+  }
+
   pub async fn get_lockfile(&self) -> io::Result<Arc<Mutex<Lockfile>>> {
     let mut lockfile = self.lockfile.lock().await;
 
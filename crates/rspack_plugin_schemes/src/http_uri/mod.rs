@@ -3,18 +3,21 @@ mod lockfile;
 
 use std::{fmt::Debug, sync::Arc};
 
-use http_cache::{ContentFetchResult, FetchResultType, fetch_content};
-pub use http_cache::{HttpClient, HttpResponse};
+use http_cache::{ContentFetchResult, FetchResultType, HttpCache, fetch_content};
+pub use http_cache::{HttpClient, HttpResponse, LockfileIssue};
 use once_cell::sync::Lazy;
 use regex::Regex;
 use rspack_core::{
-  Content, ModuleFactoryCreateData, NormalModuleFactoryResolveForScheme,
+  Compilation, CompilerDone, Content, ModuleFactoryCreateData, NormalModuleFactoryResolveForScheme,
   NormalModuleFactoryResolveInScheme, NormalModuleReadResource, Plugin, ResourceData, Scheme,
 };
 use rspack_error::{AnyhowResultToRspackResultExt, Result, error};
 use rspack_fs::{ReadableFileSystem, WritableFileSystem};
 use rspack_hook::{plugin, plugin_hook};
-use rspack_util::asset_condition::{AssetCondition, AssetConditions};
+use rspack_util::{
+  asset_condition::{AssetCondition, AssetConditions},
+  fx_hash::FxHashSet,
+};
 use url::Url;
 
 static EXTERNAL_HTTP_REQUEST: Lazy<Regex> =
@@ -162,7 +165,12 @@ pub struct HttpUriPluginOptions {
   pub cache_location: Option<String>,
   pub upgrade: bool,
   // pub proxy: Option<String>,
-  // pub frozen: Option<bool>,
+  /// Serve every url from the lockfile and the cache without network access.
+  /// A url missing from the lockfile or with content that doesn't match its
+  /// integrity is an error.
+  pub frozen: bool,
+  /// Remove the lockfile entries that the build didn't use.
+  pub prune_lockfile: bool,
   pub filesystem: Arc<dyn WritableFileSystem>,
   pub http_client: Arc<dyn HttpClient>,
 }
@@ -249,6 +257,48 @@ async fn read_resource(
   Ok(None)
 }
 
+#[plugin_hook(CompilerDone for HttpUriPlugin)]
+async fn done(&self, compilation: &Compilation) -> Result<()> {
+  // a failed build may not have reached every module
+  if !self.options.prune_lockfile || compilation.get_errors().next().is_some() {
+    return Ok(());
+  }
+
+  // the redirect chains of the used urls are kept by the lockfile
+  let used = compilation
+    .get_module_graph()
+    .modules()
+    .filter_map(|(_, module)| module.as_normal_module())
+    .map(|module| module.resource_resolved_data().resource())
+    .filter(|resource| parse_url_as_http(resource).is_some())
+    .map(|resource| resource.to_string())
+    .collect::<FxHashSet<_>>();
+
+  prune_lockfile(&self.options, &used).await?;
+  Ok(())
+}
+
+/// Checks that every lockfile entry has cached content that matches its
+/// integrity, as a frozen build requires.
+pub async fn verify_lockfile(options: &HttpUriPluginOptions) -> Result<Vec<LockfileIssue>> {
+  HttpCache::from(options)
+    .verify()
+    .await
+    .to_rspack_result_from_anyhow()
+}
+
+/// Removes the lockfile entries and cached content of the urls that are not
+/// in `used` nor redirected to by a used url, returns the removed urls.
+pub async fn prune_lockfile(
+  options: &HttpUriPluginOptions,
+  used: &FxHashSet<String>,
+) -> Result<Vec<String>> {
+  HttpCache::from(options)
+    .prune(used)
+    .await
+    .to_rspack_result_from_anyhow()
+}
+
 impl Plugin for HttpUriPlugin {
   fn name(&self) -> &'static str {
     "rspack.HttpUriPlugin"
@@ -267,6 +317,7 @@ impl Plugin for HttpUriPlugin {
       .normal_module_hooks
       .read_resource
       .tap(read_resource::new(self));
+    ctx.compiler_hooks.done.tap(done::new(self));
     Ok(())
   }
 }
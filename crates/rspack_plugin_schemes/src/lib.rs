@@ -6,4 +6,5 @@ pub use data_uri::DataUriPlugin;
 pub use file_uri::FileUriPlugin;
 pub use http_uri::{
   HttpClient, HttpResponse, HttpUriOptionsAllowedUris, HttpUriPlugin, HttpUriPluginOptions,
+  LockfileIssue, prune_lockfile, verify_lockfile,
 };
@@ -0,0 +1,421 @@
+use std::io::Write;
+
+use fast_glob::glob_match;
+use rspack_error::{Diagnostic, Result, error};
+use rspack_util::size::format_size;
+use serde::{Deserialize, Serialize};
+
+/// What a budget is measured against
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SizeBudgetTarget {
+  /// Every emitted asset whose filename matches the glob
+  Asset,
+  /// The files of every named chunk whose name matches the glob
+  Chunk,
+  /// The files of every entrypoint whose name matches the glob
+  Entrypoint,
+}
+
+impl SizeBudgetTarget {
+  pub fn as_str(&self) -> &'static str {
+    match self {
+      Self::Asset => "asset",
+      Self::Chunk => "chunk",
+      Self::Entrypoint => "entrypoint",
+    }
+  }
+}
+
+impl TryFrom<&str> for SizeBudgetTarget {
+  type Error = rspack_error::Error;
+
+  fn try_from(value: &str) -> Result<Self> {
+    match value {
+      "asset" => Ok(Self::Asset),
+      "chunk" => Ok(Self::Chunk),
+      "entrypoint" => Ok(Self::Entrypoint),
+      _ => Err(error!(
+        "Invalid size budget type: {value}, expected \"asset\", \"chunk\" or \"entrypoint\""
+      )),
+    }
+  }
+}
+
+/// How the size of a single file is measured
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SizeMeasure {
+  #[default]
+  Raw,
+  Gzip,
+  Brotli,
+}
+
+impl SizeMeasure {
+  pub fn as_str(&self) -> &'static str {
+    match self {
+      Self::Raw => "raw",
+      Self::Gzip => "gzip",
+      Self::Brotli => "brotli",
+    }
+  }
+
+  /// Returns the size of `content` after applying the measure, using the
+  /// highest compression level as a static precompression step would.
+  pub fn measure(&self, content: &[u8]) -> Result<f64> {
+    let size = match self {
+      Self::Raw => content.len(),
+      Self::Gzip => {
+        let mut encoder = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::best());
+        encoder
+          .write_all(content)
+          .and_then(|_| encoder.finish())
+          .map_err(|e| error!("failed to measure gzip size: {e}"))?
+          .len()
+      }
+      Self::Brotli => {
+        let mut encoder = brotli::CompressorWriter::new(Vec::new(), 4096, 11, 22);
+        encoder
+          .write_all(content)
+          .map_err(|e| error!("failed to measure brotli size: {e}"))?;
+        // `into_inner` finishes the stream
+        encoder.into_inner().len()
+      }
+    };
+    Ok(size as f64)
+  }
+}
+
+impl TryFrom<&str> for SizeMeasure {
+  type Error = rspack_error::Error;
+
+  fn try_from(value: &str) -> Result<Self> {
+    match value {
+      "raw" => Ok(Self::Raw),
+      "gzip" => Ok(Self::Gzip),
+      "brotli" => Ok(Self::Brotli),
+      _ => Err(error!(
+        "Invalid size measure: {value}, expected \"raw\", \"gzip\" or \"brotli\""
+      )),
+    }
+  }
+}
+
+/// A size budget for assets, chunks or entrypoints
+///
+/// Each matched asset, chunk or entrypoint is checked on its own. Chunks and
+/// entrypoints are measured as the sum of their files, each file compressed
+/// separately as it would be served.
+#[derive(Debug, Clone)]
+pub struct SizeBudget {
+  pub target: SizeBudgetTarget,
+  /// Glob matched against asset filenames, chunk names or entrypoint names
+  pub test: String,
+  pub measure: SizeMeasure,
+  /// Size in bytes above which a warning is reported
+  pub warning: Option<f64>,
+  /// Size in bytes above which an error is reported
+  pub error: Option<f64>,
+}
+
+impl SizeBudget {
+  /// Stable identifier used to match results against a baseline
+  pub fn id(&self) -> String {
+    format!(
+      "{}:{}:{}",
+      self.target.as_str(),
+      self.test,
+      self.measure.as_str()
+    )
+  }
+
+  pub fn matches(&self, name: &str) -> bool {
+    name == self.test || glob_match(&self.test, name)
+  }
+
+  fn status(&self, size: f64) -> BudgetStatus {
+    if self.error.is_some_and(|limit| size > limit) {
+      BudgetStatus::Error
+    } else if self.warning.is_some_and(|limit| size > limit) {
+      BudgetStatus::Warning
+    } else {
+      BudgetStatus::Ok
+    }
+  }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum BudgetStatus {
+  Ok,
+  Warning,
+  Error,
+  /// Over a threshold, but not larger than in the baseline
+  Baseline,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BudgetResult {
+  pub name: String,
+  pub size: f64,
+  pub files: Vec<String>,
+  pub status: BudgetStatus,
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub baseline: Option<f64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BudgetReport {
+  pub id: String,
+  #[serde(rename = "type")]
+  pub target: SizeBudgetTarget,
+  pub test: String,
+  pub measure: SizeMeasure,
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub warning: Option<f64>,
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub error: Option<f64>,
+  pub results: Vec<BudgetResult>,
+}
+
+impl BudgetReport {
+  pub fn new(budget: &SizeBudget) -> Self {
+    Self {
+      id: budget.id(),
+      target: budget.target,
+      test: budget.test.clone(),
+      measure: budget.measure,
+      warning: budget.warning,
+      error: budget.error,
+      results: vec![],
+    }
+  }
+
+  /// Records the size of a matched asset, chunk or entrypoint
+  pub fn add_result(
+    &mut self,
+    budget: &SizeBudget,
+    name: String,
+    size: f64,
+    files: Vec<String>,
+    baseline: Option<&SizeReport>,
+  ) {
+    let baseline_size = baseline.and_then(|baseline| baseline.size_of(&self.id, &name));
+    let mut status = budget.status(size);
+    // Only regressions against the baseline fail the build.
+    if status != BudgetStatus::Ok
+      && let Some(baseline_size) = baseline_size
+      && size <= baseline_size
+    {
+      status = BudgetStatus::Baseline;
+    }
+    self.results.push(BudgetResult {
+      name,
+      size,
+      files,
+      status,
+      baseline: baseline_size,
+    });
+  }
+}
+
+/// Machine-readable budget report, also used as the baseline format
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct SizeReport {
+  pub budgets: Vec<BudgetReport>,
+}
+
+impl SizeReport {
+  pub fn parse(content: &[u8]) -> Result<Self> {
+    serde_json::from_slice(content).map_err(|e| error!("Invalid size budget baseline: {e}"))
+  }
+
+  pub fn to_json(&self) -> Result<String> {
+    serde_json::to_string_pretty(self).map_err(|e| error!("{e}"))
+  }
+
+  fn size_of(&self, id: &str, name: &str) -> Option<f64> {
+    self
+      .budgets
+      .iter()
+      .filter(|budget| budget.id == id)
+      .flat_map(|budget| &budget.results)
+      .find(|result| result.name == name)
+      .map(|result| result.size)
+  }
+
+  /// Returns one diagnostic for all errors and one for all warnings
+  pub fn diagnostics(&self) -> Vec<Diagnostic> {
+    let mut errors = String::new();
+    let mut warnings = String::new();
+    for budget in &self.budgets {
+      for result in &budget.results {
+        let (list, limit) = match result.status {
+          BudgetStatus::Error => (&mut errors, budget.error),
+          BudgetStatus::Warning => (&mut warnings, budget.warning),
+          BudgetStatus::Ok | BudgetStatus::Baseline => continue,
+        };
+        let baseline = result
+          .baseline
+          .map(|size| format!(", baseline {}", format_size(size)))
+          .unwrap_or_default();
+        list.push_str(&format!(
+          "\n  {} {} ({} {}, limit {}{baseline})",
+          budget.target.as_str(),
+          result.name,
+          format_size(result.size),
+          budget.measure.as_str(),
+          format_size(limit.unwrap_or_default())
+        ));
+      }
+    }
+
+    let mut diagnostics = vec![];
+    if !errors.is_empty() {
+      diagnostics.push(Diagnostic::error(
+        "size budget exceeded".into(),
+        format!("size budget: The following exceed their error threshold:{errors}"),
+      ));
+    }
+    if !warnings.is_empty() {
+      diagnostics.push(Diagnostic::warn(
+        "size budget exceeded".into(),
+        format!("size budget: The following exceed their warning threshold:{warnings}"),
+      ));
+    }
+    diagnostics
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use super::*;
+
+  fn budget(test: &str, warning: Option<f64>, error: Option<f64>) -> SizeBudget {
+    SizeBudget {
+      target: SizeBudgetTarget::Asset,
+      test: test.to_string(),
+      measure: SizeMeasure::Raw,
+      warning,
+      error,
+    }
+  }
+
+  fn report(
+    budget: &SizeBudget,
+    sizes: &[(&str, f64)],
+    baseline: Option<&SizeReport>,
+  ) -> SizeReport {
+    let mut report = BudgetReport::new(budget);
+    for (name, size) in sizes {
+      report.add_result(
+        budget,
+        name.to_string(),
+        *size,
+        vec![name.to_string()],
+        baseline,
+      );
+    }
+    SizeReport {
+      budgets: vec![report],
+    }
+  }
+
+  #[test]
+  fn should_parse_targets_and_measures() {
+    assert_eq!(
+      SizeBudgetTarget::try_from("entrypoint").expect("should parse"),
+      SizeBudgetTarget::Entrypoint
+    );
+    assert_eq!(
+      SizeMeasure::try_from("brotli").expect("should parse"),
+      SizeMeasure::Brotli
+    );
+    assert!(SizeBudgetTarget::try_from("module").is_err());
+    assert!(SizeMeasure::try_from("zstd").is_err());
+  }
+
+  #[test]
+  fn should_match_names_and_globs() {
+    assert!(budget("main.js", None, None).matches("main.js"));
+    assert!(budget("*.js", None, None).matches("main.js"));
+    assert!(budget("**/*.css", None, None).matches("static/css/main.css"));
+    assert!(!budget("*.js", None, None).matches("main.css"));
+  }
+
+  #[test]
+  fn should_measure_sizes() {
+    let content = "a".repeat(10_000);
+    let raw = SizeMeasure::Raw
+      .measure(content.as_bytes())
+      .expect("should measure");
+    let gzip = SizeMeasure::Gzip
+      .measure(content.as_bytes())
+      .expect("should measure");
+    let brotli = SizeMeasure::Brotli
+      .measure(content.as_bytes())
+      .expect("should measure");
+    assert_eq!(raw, 10_000.0);
+    assert!(gzip > 0.0 && gzip < raw);
+    assert!(brotli > 0.0 && brotli < raw);
+  }
+
+  #[test]
+  fn should_check_thresholds() {
+    let budget = budget("*.js", Some(100.0), Some(200.0));
+    let report = report(
+      &budget,
+      &[("a.js", 100.0), ("b.js", 150.0), ("c.js", 250.0)],
+      None,
+    );
+    let statuses = report.budgets[0]
+      .results
+      .iter()
+      .map(|result| result.status)
+      .collect::<Vec<_>>();
+    assert_eq!(
+      statuses,
+      [BudgetStatus::Ok, BudgetStatus::Warning, BudgetStatus::Error]
+    );
+
+    let diagnostics = report.diagnostics();
+    assert_eq!(diagnostics.len(), 2);
+    assert!(diagnostics[0].is_error());
+    assert!(diagnostics[0].message.contains("asset c.js (250"));
+    assert!(!diagnostics[0].message.contains("b.js"));
+    assert!(diagnostics[1].is_warn());
+    assert!(diagnostics[1].message.contains("asset b.js (150"));
+    assert!(!diagnostics[1].message.contains("c.js"));
+  }
+
+  #[test]
+  fn should_only_fail_on_regressions_against_the_baseline() {
+    let budget = budget("*.js", None, Some(100.0));
+    let baseline = report(&budget, &[("a.js", 150.0), ("b.js", 150.0)], None);
+    let baseline = SizeReport::parse(baseline.to_json().expect("should serialize").as_bytes())
+      .expect("should parse");
+
+    let report = report(
+      &budget,
+      &[("a.js", 140.0), ("b.js", 160.0), ("c.js", 120.0)],
+      Some(&baseline),
+    );
+    let results = &report.budgets[0].results;
+    assert_eq!(results[0].status, BudgetStatus::Baseline);
+    assert_eq!(results[0].baseline, Some(150.0));
+    assert_eq!(results[1].status, BudgetStatus::Error);
+    assert_eq!(results[2].status, BudgetStatus::Error);
+    assert_eq!(results[2].baseline, None);
+
+    let diagnostics = report.diagnostics();
+    assert_eq!(diagnostics.len(), 1);
+    assert!(diagnostics[0].message.contains("b.js (160"));
+    assert!(!diagnostics[0].message.contains("a.js"));
+  }
+
+  #[test]
+  fn should_reject_invalid_baselines() {
+    assert!(SizeReport::parse(b"{\"budgets\": 1}").is_err());
+  }
+}
@@ -1,12 +1,21 @@
+mod budget;
+
+pub use budget::{
+  BudgetReport, BudgetResult, BudgetStatus, SizeBudget, SizeBudgetTarget, SizeMeasure, SizeReport,
+};
 use derive_more::Debug;
 use futures::future::BoxFuture;
+use rayon::iter::{IntoParallelIterator, ParallelIterator};
 use rspack_core::{
-  ChunkGroup, ChunkGroupUkey, Compilation, CompilationAsset, CompilerAfterEmit, Plugin,
+  AssetInfo, ChunkGroup, ChunkGroupUkey, Compilation, CompilationAsset, CompilationProcessAssets,
+  CompilerAfterEmit, Plugin,
+  rspack_sources::{RawStringSource, SourceExt},
 };
 use rspack_error::{Diagnostic, Result, ToStringResultToRspackResultExt};
 use rspack_hook::{plugin, plugin_hook};
+use rspack_paths::Utf8PathBuf;
 use rspack_util::size::format_size;
-use rustc_hash::FxHashMap as HashMap;
+use rustc_hash::{FxHashMap as HashMap, FxHashSet as HashSet};
 
 pub type AssetFilterFn = Box<dyn for<'a> Fn(&'a str) -> BoxFuture<'a, Result<bool>> + Sync + Send>;
 
@@ -17,6 +26,11 @@ pub struct SizeLimitsPluginOptions {
   pub hints: Option<String>,
   pub max_asset_size: Option<f64>,
   pub max_entrypoint_size: Option<f64>,
+  pub budgets: Vec<SizeBudget>,
+  /// Filename of the emitted JSON report of all budgets
+  pub report_filename: Option<String>,
+  /// A report of a previous build, budgets exceeded in it only fail when the size grows
+  pub baseline: Option<Utf8PathBuf>,
 }
 
 #[plugin]
@@ -64,6 +78,83 @@ impl SizeLimitsPlugin {
     size
   }
 
+  /// Returns the files of `files` that pass the asset filter
+  async fn filter_files(&self, files: Vec<String>, compilation: &Compilation) -> Vec<String> {
+    let mut filtered = vec![];
+    for filename in files {
+      if let Some(asset) = compilation.assets().get(&filename)
+        && self.asset_filter(&filename, asset).await
+      {
+        filtered.push(filename);
+      }
+    }
+    filtered.sort();
+    filtered
+  }
+
+  /// Returns the matched names and files of a budget, sorted by name
+  async fn budget_subjects(
+    &self,
+    budget: &SizeBudget,
+    compilation: &Compilation,
+  ) -> Vec<(String, Vec<String>)> {
+    let chunk_by_ukey = &compilation.build_chunk_graph_artifact.chunk_by_ukey;
+    let mut subjects = vec![];
+    match budget.target {
+      SizeBudgetTarget::Asset => {
+        for (name, asset) in compilation.assets() {
+          if budget.matches(name) && self.asset_filter(name, asset).await {
+            subjects.push((name.clone(), vec![name.clone()]));
+          }
+        }
+      }
+      SizeBudgetTarget::Chunk => {
+        for chunk in chunk_by_ukey.values() {
+          if let Some(name) = chunk.name()
+            && budget.matches(name)
+          {
+            let files = chunk.files().iter().cloned().collect();
+            subjects.push((
+              name.to_string(),
+              self.filter_files(files, compilation).await,
+            ));
+          }
+        }
+      }
+      SizeBudgetTarget::Entrypoint => {
+        for (name, ukey) in compilation.build_chunk_graph_artifact.entrypoints.iter() {
+          if budget.matches(name) {
+            let entrypoint = compilation
+              .build_chunk_graph_artifact
+              .chunk_group_by_ukey
+              .expect_get(ukey);
+            let files = entrypoint.get_files(chunk_by_ukey);
+            subjects.push((name.clone(), self.filter_files(files, compilation).await));
+          }
+        }
+      }
+    }
+    subjects.sort_by(|(a, _), (b, _)| a.cmp(b));
+    subjects
+  }
+
+  async fn load_baseline(&self, compilation: &mut Compilation) -> Result<Option<SizeReport>> {
+    let Some(baseline) = &self.options.baseline else {
+      return Ok(None);
+    };
+    let path = if baseline.is_absolute() {
+      baseline.clone()
+    } else {
+      compilation.options.context.as_path().join(baseline)
+    };
+    compilation.file_dependencies.insert(path.clone().into());
+    // A missing baseline means there is nothing recorded yet, every budget applies.
+    match compilation.input_filesystem.read(&path).await {
+      Ok(content) => SizeReport::parse(&content).map(Some),
+      Err(_) => Ok(None),
+    }
+  }
+
   fn add_diagnostic(
     hints: &str,
     title: String,
@@ -258,6 +349,71 @@ async fn after_emit(&self, compilation: &mut Compilation) -> Result<()> {
   Ok(())
 }
 
+#[plugin_hook(CompilationProcessAssets for SizeLimitsPlugin, stage = Compilation::PROCESS_ASSETS_STAGE_REPORT)]
+async fn process_assets(&self, compilation: &mut Compilation) -> Result<()> {
+  let budgets = &self.options.budgets;
+  if budgets.is_empty() {
+    return Ok(());
+  }
+
+  let baseline = self.load_baseline(compilation).await?;
+
+  let mut budget_subjects = Vec::with_capacity(budgets.len());
+  for budget in budgets {
+    budget_subjects.push(self.budget_subjects(budget, compilation).await);
+  }
+
+  // Every file is measured once per measure, no matter how many budgets include it.
+  let mut files_to_measure = HashSet::default();
+  for (budget, subjects) in budgets.iter().zip(&budget_subjects) {
+    for (_, files) in subjects {
+      files_to_measure.extend(files.iter().map(|file| (file.as_str(), budget.measure)));
+    }
+  }
+  let sources = files_to_measure
+    .into_iter()
+    .filter_map(|(file, measure)| {
+      let source = compilation.assets().get(file)?.get_source()?.clone();
+      Some((file, measure, source))
+    })
+    .collect::<Vec<_>>();
+  let sizes = sources
+    .into_par_iter()
+    .map(|(file, measure, source)| {
+      let size = measure.measure(&source.buffer())?;
+      Ok(((file.to_string(), measure), size))
+    })
+    .collect::<Result<HashMap<_, _>>>()?;
+
+  let mut report = SizeReport::default();
+  for (budget, subjects) in budgets.iter().zip(budget_subjects) {
+    let mut budget_report = BudgetReport::new(budget);
+    for (name, files) in subjects {
+      let size = files
+        .iter()
+        .filter_map(|file| sizes.get(&(file.clone(), budget.measure)))
+        .sum();
+      budget_report.add_result(budget, name, size, files, baseline.as_ref());
+    }
+    report.budgets.push(budget_report);
+  }
+
+  compilation.extend_diagnostics(report.diagnostics());
+
+  if let Some(report_filename) = &self.options.report_filename {
+    compilation.emit_asset(
+      report_filename.clone(),
+      CompilationAsset::new(
+        Some(RawStringSource::from(report.to_json()?).boxed()),
+        // excluded from the size limits themselves
+        AssetInfo::default().with_development(Some(true)),
+      ),
+    );
+  }
+
+  Ok(())
+}
+
 impl Plugin for SizeLimitsPlugin {
   fn name(&self) -> &'static str {
     "SizeLimitsPlugin"
@@ -265,6 +421,10 @@ impl Plugin for SizeLimitsPlugin {
 
   fn apply(&self, ctx: &mut rspack_core::ApplyContext<'_>) -> Result<()> {
     ctx.compiler_hooks.after_emit.tap(after_emit::new(self));
+    ctx
+      .compilation_hooks
+      .process_assets
+      .tap(process_assets::new(self));
 
     Ok(())
   }
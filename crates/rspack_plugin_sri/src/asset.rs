@@ -1,11 +1,11 @@
-use std::{cmp::Ordering, sync::Arc};
+use std::{cmp::Ordering, collections::BTreeMap, sync::Arc};
 
 use rayon::iter::{IntoParallelIterator, IntoParallelRefIterator, ParallelIterator};
 use rspack_core::{
-  ChunkUkey, Compilation, CompilationAfterProcessAssets, CompilationAssets,
+  ChunkUkey, Compilation, CompilationAfterProcessAssets, CompilationAsset, CompilationAssets,
   CompilationProcessAssets, CrossOriginLoading, ManifestAssetType,
   chunk_graph_chunk::ChunkId,
-  rspack_sources::{ReplaceSource, Source},
+  rspack_sources::{RawStringSource, ReplaceSource, Source, SourceExt},
 };
 use rspack_error::{Diagnostic, Result};
 use rspack_hook::plugin_hook;
@@ -17,7 +17,10 @@ use crate::{
   IntegrityCallbackData, SubresourceIntegrityPlugin, SubresourceIntegrityPluginInner,
   config::IntegrityHtmlPlugin,
   integrity::{SubresourceIntegrityHashFunction, compute_integrity},
-  util::{PLACEHOLDER_PREFIX, PLACEHOLDER_REGEX, make_placeholder, use_any_hash},
+  util::{
+    PLACEHOLDER_PREFIX, PLACEHOLDER_REGEX, find_remote_entry_chunk, get_remote_entry_url,
+    make_placeholder, use_any_hash,
+  },
 };
 
 #[derive(Debug, Clone)]
@@ -184,7 +187,7 @@ fn process_chunk_source(
   }
 
   // compute self integrity and placeholder
-  let integrity = compute_integrity(hash_funcs, new_source.source().into_string_lossy().as_ref());
+  let integrity = compute_integrity(hash_funcs, &new_source.buffer());
   let placeholder = chunk_id.map(|id| make_placeholder(asset_type, hash_funcs, id.as_str()));
 
   ProcessChunkResult {
@@ -241,9 +244,31 @@ fn digest_chunks(compilation: &Compilation) -> Vec<Vec<ChunkUkey>> {
     batches.push(chunk_batch);
   }
   batches.reverse();
+
+  // The runtimes of the hosts contain the integrities of the remote entries emitted by the same
+  // compilation, so the remote entries are hashed before the other entries.
+  let remote_entries = find_remote_entry_chunks(compilation);
+  if !remote_entries.is_empty()
+    && let Some(entry_batch) = batches.pop()
+  {
+    let (remote_entry_batch, entry_batch) = entry_batch
+      .into_iter()
+      .partition(|chunk_ukey| remote_entries.contains(chunk_ukey));
+    batches.push(remote_entry_batch);
+    batches.push(entry_batch);
+  }
   batches
 }
 
+fn find_remote_entry_chunks(compilation: &Compilation) -> HashSet<ChunkUkey> {
+  compilation
+    .get_module_graph()
+    .modules()
+    .filter_map(|(_, module)| get_remote_entry_url(module.as_ref()))
+    .filter_map(|url| find_remote_entry_chunk(url, compilation))
+    .collect()
+}
+
 async fn add_minssing_integrities(
   assets: &CompilationAssets,
   integrities: Arc<RwLock<HashMap<String, String>>>,
@@ -258,8 +283,7 @@ async fn add_minssing_integrities(
           return None;
         }
         asset.source.as_ref().map(|s| {
-          let content = s.source().into_string_lossy();
-          let integrity = compute_integrity(hash_func_names, &content);
+          let integrity = compute_integrity(hash_func_names, &s.buffer());
           (src.clone(), integrity)
         })
       })
@@ -269,9 +293,28 @@ async fn add_minssing_integrities(
   integrities.write().await.extend(new_integrities);
 }
 
+fn has_worker_entrypoints(compilation: &Compilation) -> bool {
+  compilation
+    .build_chunk_graph_artifact
+    .chunk_group_by_ukey
+    .values()
+    .any(|chunk_group| chunk_group.async_entrypoints_iterable().next().is_some())
+}
+
 #[plugin_hook(CompilationProcessAssets for SubresourceIntegrityPlugin, stage = Compilation::PROCESS_ASSETS_STAGE_OPTIMIZE_INLINE - 1)]
 pub async fn handle_assets(&self, compilation: &mut Compilation) -> Result<()> {
   let integrities = process_chunks(&self.options.hash_func_names, compilation);
+
+  // Browsers don't check the integrity of the scripts created by `new Worker()`, only the chunks
+  // they import are checked
+  if self.options.manifest.is_none() && has_worker_entrypoints(compilation) {
+    compilation.push_diagnostic(Diagnostic::warn(
+      "SubresourceIntegrity".to_string(),
+      r#"The integrity of worker entry scripts can't be checked by browsers, their chunks are checked before they are imported.
+Set the "manifest" option to emit their integrities, so they can be verified when deployed."#
+        .to_string(),
+    ));
+  }
   let compilation_integrities =
     SubresourceIntegrityPlugin::get_compilation_integrities_mut(compilation.id());
   compilation_integrities.write().await.extend(integrities);
@@ -302,6 +345,35 @@ pub async fn handle_assets(&self, compilation: &mut Compilation) -> Result<()> {
   Ok(())
 }
 
+/// Emitted after the assets are hashed and optimized, so the integrities match the emitted files.
+#[plugin_hook(CompilationProcessAssets for SubresourceIntegrityPlugin, stage = Compilation::PROCESS_ASSETS_STAGE_REPORT)]
+pub async fn emit_manifest(&self, compilation: &mut Compilation) -> Result<()> {
+  let Some(manifest) = &self.options.manifest else {
+    return Ok(());
+  };
+
+  let integrities = compilation
+    .assets()
+    .par_iter()
+    .filter(|(file, _)| *file != manifest)
+    .filter_map(|(file, asset)| {
+      let source = asset.get_source()?;
+      let integrity = compute_integrity(&self.options.hash_func_names, &source.buffer());
+      Some((file.as_str(), integrity))
+    })
+    .collect::<BTreeMap<_, _>>();
+
+  let content = rspack_util::json_stringify_pretty(&integrities);
+  compilation.emit_asset(
+    manifest.clone(),
+    CompilationAsset::new(
+      Some(RawStringSource::from(content).boxed()),
+      Default::default(),
+    ),
+  );
+  Ok(())
+}
+
 #[plugin_hook(CompilationAfterProcessAssets for SubresourceIntegrityPlugin)]
 pub async fn detect_unresolved_integrity(
   &self,
@@ -350,8 +422,7 @@ pub async fn update_hash(
     .iter()
     .find_map(|(k, v)| if v == old_hash { Some(k.clone()) } else { None });
   if let (Some(key), Some(asset)) = (key, assets.first()) {
-    let content = asset.source().into_string_lossy();
-    let new_integrity = compute_integrity(&self.options.hash_func_names, content.as_ref());
+    let new_integrity = compute_integrity(&self.options.hash_func_names, &asset.buffer());
     compilation_integrities
       .write()
       .await
@@ -44,6 +44,8 @@ pub struct SubresourceIntegrityPluginOptions {
   pub html_plugin: IntegrityHtmlPlugin,
   #[debug(skip)]
   pub integrity_callback: Option<IntegrityCallbackFn>,
+  /// Filename of the emitted manifest mapping every asset to its integrity.
+  pub manifest: Option<String>,
 }
 
 pub type ArcFs = Arc<dyn WritableFileSystem + Send + Sync>;
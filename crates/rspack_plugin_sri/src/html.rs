@@ -3,7 +3,7 @@ use std::sync::Arc;
 use futures::future::join_all;
 use once_cell::sync::Lazy;
 use regex::Regex;
-use rspack_error::{Result, ToStringResultToRspackResultExt};
+use rspack_error::Result;
 use rspack_hook::plugin_hook;
 use rspack_paths::Utf8Path;
 use rspack_plugin_html::{
@@ -273,8 +273,7 @@ async fn compute_file_integrity(
   hash_func_names: &Vec<SubresourceIntegrityHashFunction>,
 ) -> Result<String> {
   let file = fs.read_file(path).await?;
-  let content = String::from_utf8(file).to_rspack_result()?;
-  let integrity = compute_integrity(hash_func_names, &content);
+  let integrity = compute_integrity(hash_func_names, &file);
   Ok(integrity)
 }
 
@@ -7,7 +7,7 @@ mod util;
 
 use std::sync::{Arc, LazyLock};
 
-use asset::{detect_unresolved_integrity, handle_assets, update_hash};
+use asset::{detect_unresolved_integrity, emit_manifest, handle_assets, update_hash};
 use config::SRICompilationContext;
 pub use config::{
   IntegrityCallbackData, IntegrityCallbackFn, IntegrityHtmlPlugin,
@@ -24,10 +24,11 @@ use rspack_hook::{plugin, plugin_hook};
 use rspack_plugin_html::HtmlRspackPlugin;
 use rspack_plugin_real_content_hash::RealContentHashPlugin;
 use rspack_plugin_runtime::RuntimePlugin;
+use rspack_plugin_wasm::FetchCompileAsyncWasmPlugin;
 #[cfg(allocative)]
 use rspack_util::allocative;
 use rspack_util::fx_hash::FxDashMap;
-use runtime::{create_link, create_script, handle_runtime, link_preload};
+use runtime::{create_link, create_script, fetch_wasm, handle_runtime, link_preload};
 use rustc_hash::FxHashMap as HashMap;
 use tokio::sync::RwLock;
 
@@ -164,6 +165,13 @@ async fn handle_compilation(
       .tap(link_preload::new(self));
   }
 
+  {
+    let wasm_loading_plugin_hooks =
+      FetchCompileAsyncWasmPlugin::get_compilation_hooks_mut(compilation.id());
+    let mut wasm_loading_plugin_hooks = wasm_loading_plugin_hooks.borrow_mut();
+    wasm_loading_plugin_hooks.fetch.tap(fetch_wasm::new(self));
+  }
+
   if matches!(self.options.html_plugin, IntegrityHtmlPlugin::NativePlugin) {
     let html_plugin_hooks = HtmlRspackPlugin::get_compilation_hooks_mut(compilation.id());
     let mut html_plugin_hooks = html_plugin_hooks.borrow_mut();
@@ -210,6 +218,10 @@ impl Plugin for SubresourceIntegrityPlugin {
       .compilation_hooks
       .process_assets
       .tap(handle_assets::new(self));
+    ctx
+      .compilation_hooks
+      .process_assets
+      .tap(emit_manifest::new(self));
 
     ctx
       .compilation_hooks
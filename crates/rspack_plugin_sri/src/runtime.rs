@@ -1,19 +1,27 @@
+use std::collections::BTreeMap;
+
 use rspack_core::{
-  ChunkUkey, Compilation, CompilationAdditionalTreeRuntimeRequirements, CrossOriginLoading,
-  ManifestAssetType, RuntimeGlobals, RuntimeModule, RuntimeModuleExt, RuntimeModuleGenerateContext,
-  RuntimeTemplate, SourceType, chunk_graph_chunk::ChunkId, impl_runtime_module,
+  ChunkGraph, ChunkLoading, ChunkLoadingType, ChunkUkey, Compilation,
+  CompilationAdditionalTreeRuntimeRequirements, CrossOriginLoading, ManifestAssetType, ModuleType,
+  RuntimeGlobals, RuntimeModule, RuntimeModuleExt, RuntimeModuleGenerateContext,
+  RuntimeModuleStage, RuntimeTemplate, SourceType, chunk_graph_chunk::ChunkId, impl_runtime_module,
 };
 use rspack_error::{Result, error};
 use rspack_hook::plugin_hook;
 use rspack_plugin_runtime::{
   CreateLinkData, CreateScriptData, LinkPreloadData, RuntimePluginCreateLink,
-  RuntimePluginCreateScript, RuntimePluginLinkPreload,
+  RuntimePluginCreateScript, RuntimePluginLinkPreload, is_enabled_for_chunk,
 };
+use rspack_plugin_wasm::{FetchWasmData, WasmLoadingPluginFetch};
 use rustc_hash::FxHashMap as HashMap;
 
 use crate::{
   SubresourceIntegrityHashFunction, SubresourceIntegrityPlugin, SubresourceIntegrityPluginInner,
-  util::{find_chunks, get_hash_variable, make_placeholder},
+  integrity::compute_integrity,
+  util::{
+    find_chunks, find_remote_entry_chunk, get_fetch_credentials, get_hash_variable,
+    get_remote_entry_url, get_remote_hash_variable, get_wasm_hash_variable, make_placeholder,
+  },
 };
 
 fn add_attribute(
@@ -29,6 +37,25 @@ fn add_attribute(
   )
 }
 
+// Remote entries are loaded by `loadScript` without a chunk id, their integrities are looked up
+// by url. Only the remote entries emitted by the compilation have integrities.
+fn add_script_attribute(
+  hash_variable: &str,
+  remote_hash_variable: &str,
+  code: &str,
+  cross_origin_loading: &CrossOriginLoading,
+) -> String {
+  format!(
+    r#"{code}
+if (chunkId === undefined) {{
+  if ({remote_hash_variable} && {remote_hash_variable}[url]) script.integrity = {remote_hash_variable}[url];
+}} else {{
+  script.integrity = {hash_variable}[chunkId];
+}}
+script.crossOrigin = '{cross_origin_loading}';"#
+  )
+}
+
 #[impl_runtime_module]
 #[derive(Debug)]
 struct SRIHashVariableRuntimeModule {
@@ -101,7 +128,68 @@ impl RuntimeModule for SRIHashVariableRuntimeModule {
       ),
     ];
 
-    let all_chunks = find_chunks(&self.chunk().expect("should attached chunk"), compilation)
+    let all_chunks = find_chunks(&self.chunk().expect("should attached chunk"), compilation);
+
+    // Wasm binaries are emitted as they are generated, so their integrities are known already.
+    let mut wasm_integrities = BTreeMap::default();
+    // Remote entries emitted by the compilation are hashed along with the other chunks, their
+    // placeholders are replaced once the remote entries are hashed.
+    let mut remote_integrities = BTreeMap::default();
+    for chunk_ukey in &all_chunks {
+      let chunk = compilation
+        .build_chunk_graph_artifact
+        .chunk_by_ukey
+        .expect_get(chunk_ukey);
+      for module in compilation
+        .build_chunk_graph_artifact
+        .chunk_graph
+        .get_chunk_modules(chunk_ukey, module_graph)
+      {
+        if let Some(url) = get_remote_entry_url(module.as_ref())
+          && let Some(remote_entry) = find_remote_entry_chunk(url, compilation)
+          && !all_chunks.contains(&remote_entry)
+          && let Some(remote_entry_id) = compilation
+            .build_chunk_graph_artifact
+            .chunk_by_ukey
+            .expect_get(&remote_entry)
+            .id()
+        {
+          remote_integrities.insert(
+            url.to_string(),
+            make_placeholder(
+              ManifestAssetType::JavaScript,
+              &self.hash_funcs,
+              remote_entry_id.as_str(),
+            ),
+          );
+          continue;
+        }
+        if !matches!(
+          module.module_type(),
+          ModuleType::WasmAsync | ModuleType::WasmSync
+        ) {
+          continue;
+        }
+        let Some(source) = compilation
+          .code_generation_results
+          .get(&module.identifier(), Some(chunk.runtime()))
+          .get(&SourceType::Wasm)
+        else {
+          continue;
+        };
+        let Some(module_id) =
+          ChunkGraph::get_module_id(&compilation.module_ids_artifact, module.identifier())
+        else {
+          continue;
+        };
+        wasm_integrities.insert(
+          module_id.to_string(),
+          compute_integrity(&self.hash_funcs, &source.buffer()),
+        );
+      }
+    }
+
+    let all_chunks = all_chunks
       .into_iter()
       .filter(|c| {
         compilation
@@ -159,6 +247,22 @@ impl RuntimeModule for SRIHashVariableRuntimeModule {
       }
     }
 
+    if !wasm_integrities.is_empty() {
+      code.push(format!(
+        "{} = {};",
+        get_wasm_hash_variable(&runtime_require_name),
+        rspack_util::json_stringify(&wasm_integrities)
+      ));
+    }
+
+    if !remote_integrities.is_empty() {
+      code.push(format!(
+        "{} = {};",
+        get_remote_hash_variable(&runtime_require_name),
+        rspack_util::json_stringify(&remote_integrities)
+      ));
+    }
+
     Ok(code.join("\n"))
   }
   fn runtime_requirements(
@@ -193,12 +297,88 @@ fn generate_sri_hash_placeholders(
   )
 }
 
+/// `importScripts` can't check the integrity of the chunks loaded by workers, so every chunk is
+/// fetched with its integrity and the verified response is evaluated instead.
+#[impl_runtime_module]
+#[derive(Debug)]
+struct SRIImportScriptsRuntimeModule {
+  credentials: &'static str,
+}
+
+impl SRIImportScriptsRuntimeModule {
+  pub fn new(runtime_template: &RuntimeTemplate, credentials: &'static str) -> Self {
+    Self::with_default(runtime_template, credentials)
+  }
+}
+
+#[async_trait::async_trait]
+impl RuntimeModule for SRIImportScriptsRuntimeModule {
+  fn runtime_module_variables() -> &'static [&'static str] {
+    &[]
+  }
+
+  async fn generate(&self, context: &RuntimeModuleGenerateContext<'_>) -> Result<String> {
+    let runtime_template = context.runtime_template;
+    let ensure_chunk_handlers =
+      runtime_template.render_runtime_globals(&RuntimeGlobals::ENSURE_CHUNK_HANDLERS);
+    let public_path = runtime_template.render_runtime_globals(&RuntimeGlobals::PUBLIC_PATH);
+    let get_chunk_script_filename =
+      runtime_template.render_runtime_globals(&RuntimeGlobals::GET_CHUNK_SCRIPT_FILENAME);
+    let hash_variable = get_hash_variable(
+      &runtime_template.render_runtime_globals(&RuntimeGlobals::REQUIRE),
+      SourceType::JavaScript,
+    );
+    let credentials = self.credentials;
+
+    Ok(format!(
+      r#"{ensure_chunk_handlers}.i = (function (importChunk) {{
+  var checkedChunks = {{}};
+  return function (chunkId, promises) {{
+    var integrity = {hash_variable} && {hash_variable}[chunkId];
+    if (!integrity) return importChunk(chunkId, promises);
+    if (!checkedChunks[chunkId]) {{
+      var url = {public_path} + {get_chunk_script_filename}(chunkId);
+      checkedChunks[chunkId] = fetch(url, {{ integrity: integrity, credentials: "{credentials}" }}).then(function (response) {{
+        if (!response.ok) throw new Error("Loading chunk " + chunkId + " failed.\n(" + response.status + ": " + url + ")");
+        return response.text();
+      }}).then(function (code) {{
+        // run the verified response, importScripts would fetch the chunk again unchecked
+        (0, eval)(code + "\n//# sourceURL=" + url);
+        importChunk(chunkId, []);
+      }}).catch(function (error) {{
+        delete checkedChunks[chunkId];
+        throw error;
+      }});
+    }}
+    promises.push(checkedChunks[chunkId]);
+  }};
+}})({ensure_chunk_handlers}.i);"#
+    ))
+  }
+
+  fn stage(&self) -> RuntimeModuleStage {
+    RuntimeModuleStage::Trigger
+  }
+
+  fn runtime_requirements(
+    &self,
+    _compilation: &Compilation,
+  ) -> rspack_core::RuntimeModuleRuntimeRequirements {
+    rspack_core::RuntimeModuleRuntimeRequirements {
+      dependencies: RuntimeGlobals::ENSURE_CHUNK_HANDLERS
+        | RuntimeGlobals::PUBLIC_PATH
+        | RuntimeGlobals::GET_CHUNK_SCRIPT_FILENAME,
+      ..Default::default()
+    }
+  }
+}
+
 #[plugin_hook(RuntimePluginCreateScript for SubresourceIntegrityPlugin)]
 pub async fn create_script(&self, mut data: CreateScriptData) -> Result<CreateScriptData> {
   let ctx = SubresourceIntegrityPlugin::get_compilation_sri_context(data.chunk.compilation_id);
-  data.code = add_attribute(
-    "script",
+  data.code = add_script_attribute(
     &get_hash_variable(&ctx.runtime_require_name, SourceType::JavaScript),
+    &get_remote_hash_variable(&ctx.runtime_require_name),
     &data.code,
     &ctx.cross_origin_loading,
   );
@@ -276,11 +456,27 @@ pub async fn link_preload<'a>(
   Ok(data)
 }
 
+#[plugin_hook(WasmLoadingPluginFetch for SubresourceIntegrityPlugin)]
+pub async fn fetch_wasm(
+  &self,
+  compilation: &Compilation,
+  mut data: FetchWasmData,
+) -> Result<FetchWasmData> {
+  let ctx = SubresourceIntegrityPlugin::get_compilation_sri_context(compilation.id());
+  let hash_variable = get_wasm_hash_variable(&ctx.runtime_require_name);
+  // the hashes are only defined when the chunks of the runtime contain wasm modules
+  data.options = Some(format!(
+    r#"{{ integrity: {hash_variable} && {hash_variable}[wasmModuleId], credentials: "{}" }}"#,
+    get_fetch_credentials(&ctx.cross_origin_loading)
+  ));
+  Ok(data)
+}
+
 #[plugin_hook(CompilationAdditionalTreeRuntimeRequirements for SubresourceIntegrityPlugin)]
 pub async fn handle_runtime(
   &self,
   compilation: &Compilation,
-  _chunk_ukey: &ChunkUkey,
+  chunk_ukey: &ChunkUkey,
   _runtime_requirements: &mut RuntimeGlobals,
   runtime_modules: &mut Vec<Box<dyn RuntimeModule>>,
 ) -> Result<()> {
@@ -291,5 +487,32 @@ pub async fn handle_runtime(
     )
     .boxed(),
   );
+
+  // workers load their chunks by `importScripts`
+  let has_async_chunks = compilation
+    .build_chunk_graph_artifact
+    .chunk_by_ukey
+    .get(chunk_ukey)
+    .is_some_and(|chunk| {
+      !chunk
+        .get_all_async_chunks(&compilation.build_chunk_graph_artifact.chunk_group_by_ukey)
+        .is_empty()
+    });
+  if has_async_chunks
+    && is_enabled_for_chunk(
+      chunk_ukey,
+      &ChunkLoading::Enable(ChunkLoadingType::ImportScripts),
+      compilation,
+    )
+  {
+    let ctx = SubresourceIntegrityPlugin::get_compilation_sri_context(compilation.id());
+    runtime_modules.push(
+      SRIImportScriptsRuntimeModule::new(
+        &compilation.runtime_template,
+        get_fetch_credentials(&ctx.cross_origin_loading),
+      )
+      .boxed(),
+    );
+  }
   Ok(())
 }
@@ -2,7 +2,8 @@ use std::{borrow::Cow, sync::LazyLock};
 
 use cow_utils::CowUtils;
 use rspack_core::{
-  AssetInfo, ChunkGroupUkey, ChunkUkey, Compilation, ManifestAssetType, SourceType,
+  AssetInfo, ChunkGroupUkey, ChunkUkey, Compilation, CrossOriginLoading, ManifestAssetType, Module,
+  SourceType, extract_url_and_global,
 };
 use rspack_util::fx_hash::FxIndexSet;
 
@@ -29,6 +30,56 @@ pub fn get_hash_variable(runtime_require_name: &str, source_type: SourceType) ->
   }
 }
 
+pub fn get_wasm_hash_variable(runtime_require_name: &str) -> String {
+  format!("{runtime_require_name}.sriWasmHashes")
+}
+
+pub fn get_remote_hash_variable(runtime_require_name: &str) -> String {
+  format!("{runtime_require_name}.sriRemoteHashes")
+}
+
+/// `credentials` of `fetch()`, which match the `crossorigin` attribute of the tags.
+pub fn get_fetch_credentials(cross_origin_loading: &CrossOriginLoading) -> &'static str {
+  if matches!(
+    cross_origin_loading,
+    CrossOriginLoading::Enable(value) if value == "use-credentials"
+  ) {
+    "include"
+  } else {
+    "same-origin"
+  }
+}
+
+/// Url of the remote entry loaded by a `script` external, e.g. `app@https://example.com/remoteEntry.js`
+/// of module federation.
+pub fn get_remote_entry_url(module: &dyn Module) -> Option<&str> {
+  let external_module = module.as_external_module()?;
+  if external_module.resolve_external_type() != "script" {
+    return None;
+  }
+  extract_url_and_global(external_module.get_request().primary())
+    .ok()
+    .map(|url_and_global| url_and_global.url)
+}
+
+/// Finds the chunk emitted as the remote entry of `url`, so its integrity can be computed like the
+/// one of other chunks. Remote entries of other builds are not known.
+pub fn find_remote_entry_chunk(url: &str, compilation: &Compilation) -> Option<ChunkUkey> {
+  compilation
+    .build_chunk_graph_artifact
+    .chunk_by_ukey
+    .iter()
+    .find(|(_, chunk)| {
+      chunk.files().iter().any(|file| {
+        url == file
+          || url
+            .strip_suffix(file.as_str())
+            .is_some_and(|prefix| prefix.ends_with('/'))
+      })
+    })
+    .map(|(chunk_ukey, _)| *chunk_ukey)
+}
+
 pub fn find_chunks(chunk: &ChunkUkey, compilation: &Compilation) -> FxIndexSet<ChunkUkey> {
   let mut all_chunks = FxIndexSet::default();
   let mut visited_groups = FxIndexSet::default();
@@ -89,7 +140,7 @@ pub fn make_placeholder(
   id: &str,
 ) -> String {
   let placeholder_source = format!("{PLACEHOLDER_PREFIX}{asset_type}{id}");
-  let filler = compute_integrity(hash_funcs, &placeholder_source);
+  let filler = compute_integrity(hash_funcs, placeholder_source.as_bytes());
   format!(
     "{}{}",
     PLACEHOLDER_PREFIX,
@@ -0,0 +1,409 @@
+use std::fmt;
+
+use rspack_cacheable::cacheable;
+use rspack_error::Diagnostic;
+use wasmparser::{
+  CompositeInnerType, ExternalKind, FuncType, GlobalType, Parser, Payload, TypeRef, ValType,
+};
+
+#[cacheable]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WasmExternKind {
+  Func,
+  Global,
+  Memory,
+  Table,
+  Tag,
+}
+
+impl WasmExternKind {
+  pub fn as_str(&self) -> &'static str {
+    match self {
+      WasmExternKind::Func => "function",
+      WasmExternKind::Global => "global",
+      WasmExternKind::Memory => "memory",
+      WasmExternKind::Table => "table",
+      WasmExternKind::Tag => "tag",
+    }
+  }
+}
+
+/// Type of an import or export of a wasm module.
+#[cacheable]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WasmExternType {
+  pub kind: WasmExternKind,
+  /// Signature of a function like `[i32 i32] -> [i32]`, or value type of a
+  /// global like `mut i64`.
+  pub signature: Option<String>,
+}
+
+impl WasmExternType {
+  fn new(kind: WasmExternKind) -> Self {
+    Self {
+      kind,
+      signature: None,
+    }
+  }
+
+  fn func(func_type: Option<&FuncType>) -> Self {
+    Self {
+      kind: WasmExternKind::Func,
+      signature: func_type.map(|func_type| {
+        format!(
+          "[{}] -> [{}]",
+          join_val_types(func_type.params()),
+          join_val_types(func_type.results())
+        )
+      }),
+    }
+  }
+
+  fn global(global_type: &GlobalType) -> Self {
+    Self {
+      kind: WasmExternKind::Global,
+      signature: Some(if global_type.mutable {
+        format!("mut {}", global_type.content_type)
+      } else {
+        global_type.content_type.to_string()
+      }),
+    }
+  }
+
+  /// Whether an export of this type can satisfy an import of type `import`.
+  /// Signatures are only compared when both of them are known.
+  pub fn satisfies(&self, import: &WasmExternType) -> bool {
+    self.kind == import.kind
+      && match (&self.signature, &import.signature) {
+        (Some(export), Some(import)) => export == import,
+        _ => true,
+      }
+  }
+}
+
+impl fmt::Display for WasmExternType {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match &self.signature {
+      Some(signature) => write!(f, "{} {signature}", self.kind.as_str()),
+      None => f.write_str(self.kind.as_str()),
+    }
+  }
+}
+
+fn join_val_types(types: &[ValType]) -> String {
+  types
+    .iter()
+    .map(ToString::to_string)
+    .collect::<Vec<_>>()
+    .join(" ")
+}
+
+#[derive(Debug)]
+pub(crate) struct WasmImport {
+  pub module: String,
+  pub name: String,
+  pub ty: WasmExternType,
+  /// Why the import can't be provided by a JavaScript module.
+  pub js_incompatible: Option<String>,
+}
+
+#[derive(Debug)]
+pub(crate) struct WasmExport {
+  pub name: String,
+  pub ty: WasmExternType,
+  /// Why the export can't be used from a JavaScript module.
+  pub js_incompatible: Option<String>,
+  /// The module and name of the import, when an imported global is exported.
+  pub imported_global: Option<(String, String)>,
+}
+
+#[derive(Debug, Default)]
+pub(crate) struct WasmAnalysis {
+  pub imports: Vec<WasmImport>,
+  pub exports: Vec<WasmExport>,
+  pub diagnostics: Vec<Diagnostic>,
+}
+
+fn is_js_compatible(ty: &ValType) -> bool {
+  match ty {
+    ValType::I32 | ValType::I64 | ValType::F32 | ValType::F64 => true,
+    ValType::Ref(_) => *ty == ValType::EXTERNREF,
+    ValType::V128 => false,
+  }
+}
+
+fn get_js_incompatible_type(func_type: &FuncType) -> Option<String> {
+  if let Some(param) = func_type.params().iter().find(|ty| !is_js_compatible(ty)) {
+    return Some(format!("{param} as parameter"));
+  }
+  func_type
+    .results()
+    .iter()
+    .find(|ty| !is_js_compatible(ty))
+    .map(|result| format!("{result} as result"))
+}
+
+/// Reads the imports and exports of a wasm binary with their types.
+pub(crate) fn analyze(bytes: &[u8]) -> WasmAnalysis {
+  let mut analysis = WasmAnalysis::default();
+
+  let mut func_types: Vec<Option<FuncType>> = vec![];
+  // type index of every function, imported functions first
+  let mut functions: Vec<u32> = vec![];
+  // imported globals first
+  let mut globals: Vec<GlobalType> = vec![];
+  let mut imported_globals: Vec<(String, String)> = vec![];
+
+  for payload in Parser::new(0).parse_all(bytes) {
+    match payload {
+      Ok(payload) => {
+        match payload {
+          Payload::TypeSection(s) => {
+            for rec_group in s {
+              match rec_group {
+                Ok(rec_group) => {
+                  func_types.extend(rec_group.into_types().map(
+                    |ty| match ty.composite_type.inner {
+                      CompositeInnerType::Func(func_type) => Some(func_type),
+                      _ => None,
+                    },
+                  ))
+                }
+                Err(err) => analysis.diagnostics.push(Diagnostic::error(
+                  "Wasm Type Parse Error".into(),
+                  err.to_string(),
+                )),
+              }
+            }
+          }
+          Payload::ImportSection(s) => {
+            for import in s {
+              match import {
+                Ok(import) => {
+                  let (ty, js_incompatible) = match import.ty {
+                    TypeRef::Func(type_index) => {
+                      functions.push(type_index);
+                      let func_type = func_types.get(type_index as usize).and_then(Option::as_ref);
+                      let js_incompatible = func_type
+                        .and_then(get_js_incompatible_type)
+                        .map(|ty| format!("Non-JS-compatible Func Signature ({ty})"));
+                      (WasmExternType::func(func_type), js_incompatible)
+                    }
+                    TypeRef::Global(global_type) => {
+                      globals.push(global_type);
+                      imported_globals.push((import.module.to_string(), import.name.to_string()));
+                      let ty = global_type.content_type;
+                      let js_incompatible = (!is_js_compatible(&ty))
+                        .then(|| format!("Non-JS-compatible Global Type ({ty})"));
+                      (WasmExternType::global(&global_type), js_incompatible)
+                    }
+                    TypeRef::Memory(_) => (
+                      WasmExternType::new(WasmExternKind::Memory),
+                      Some("Memory".to_string()),
+                    ),
+                    TypeRef::Table(_) => (
+                      WasmExternType::new(WasmExternKind::Table),
+                      Some("Table".to_string()),
+                    ),
+                    TypeRef::Tag(_) => (
+                      WasmExternType::new(WasmExternKind::Tag),
+                      Some("Tag".to_string()),
+                    ),
+                  };
+                  analysis.imports.push(WasmImport {
+                    module: import.module.to_string(),
+                    name: import.name.to_string(),
+                    ty,
+                    js_incompatible,
+                  });
+                }
+                Err(err) => analysis.diagnostics.push(Diagnostic::error(
+                  "Wasm Import Parse Error".into(),
+                  err.to_string(),
+                )),
+              }
+            }
+          }
+          Payload::FunctionSection(s) => {
+            for type_index in s {
+              match type_index {
+                Ok(type_index) => functions.push(type_index),
+                Err(err) => analysis.diagnostics.push(Diagnostic::error(
+                  "Wasm Function Parse Error".into(),
+                  err.to_string(),
+                )),
+              }
+            }
+          }
+          Payload::GlobalSection(s) => {
+            for global in s {
+              match global {
+                Ok(global) => globals.push(global.ty),
+                Err(err) => analysis.diagnostics.push(Diagnostic::error(
+                  "Wasm Global Parse Error".into(),
+                  err.to_string(),
+                )),
+              }
+            }
+          }
+          Payload::ExportSection(s) => {
+            for export in s {
+              match export {
+                Ok(export) => {
+                  let index = export.index as usize;
+                  let mut js_incompatible = None;
+                  let ty = match export.kind {
+                    ExternalKind::Func => {
+                      let func_type = functions
+                        .get(index)
+                        .and_then(|type_index| func_types.get(*type_index as usize))
+                        .and_then(Option::as_ref);
+                      js_incompatible = func_type.and_then(get_js_incompatible_type);
+                      WasmExternType::func(func_type)
+                    }
+                    ExternalKind::Global => globals.get(index).map_or_else(
+                      || WasmExternType::new(WasmExternKind::Global),
+                      WasmExternType::global,
+                    ),
+                    ExternalKind::Memory => WasmExternType::new(WasmExternKind::Memory),
+                    ExternalKind::Table => WasmExternType::new(WasmExternKind::Table),
+                    ExternalKind::Tag => WasmExternType::new(WasmExternKind::Tag),
+                  };
+                  let imported_global = (export.kind == ExternalKind::Global)
+                    .then(|| imported_globals.get(index).cloned())
+                    .flatten();
+                  analysis.exports.push(WasmExport {
+                    name: export.name.to_string(),
+                    ty,
+                    js_incompatible,
+                    imported_global,
+                  });
+                }
+                Err(err) => analysis.diagnostics.push(Diagnostic::error(
+                  "Wasm Export Parse Error".into(),
+                  err.to_string(),
+                )),
+              }
+            }
+          }
+          _ => {}
+        }
+      }
+      Err(err) => {
+        analysis.diagnostics.push(Diagnostic::error(
+          "Wasm Parse Error".into(),
+          err.to_string(),
+        ));
+      }
+    }
+  }
+
+  analysis
+}
+
+#[cfg(test)]
+mod test {
+  use super::*;
+
+  /// Builds a wasm binary from `(section id, section content)` pairs.
+  fn module(sections: &[(u8, &[u8])]) -> Vec<u8> {
+    let mut bytes = b"\0asm\x01\0\0\0".to_vec();
+    for (id, content) in sections {
+      bytes.push(*id);
+      bytes.push(u8::try_from(content.len()).expect("section should be small"));
+      bytes.extend_from_slice(content);
+    }
+    bytes
+  }
+
+  #[rustfmt::skip]
+  fn imports_and_exports() -> Vec<u8> {
+    module(&[
+      // type 0: [i32] -> [i32], type 1: [v128] -> []
+      (1, &[0x02, 0x60, 0x01, 0x7f, 0x01, 0x7f, 0x60, 0x01, 0x7b, 0x00]),
+      // "./env" "f": func of type 0, "./env" "g": mut i32 global
+      (2, &[
+        0x02,
+        0x05, b'.', b'/', b'e', b'n', b'v', 0x01, b'f', 0x00, 0x00,
+        0x05, b'.', b'/', b'e', b'n', b'v', 0x01, b'g', 0x03, 0x7f, 0x01,
+      ]),
+      // one function of type 1
+      (3, &[0x01, 0x01]),
+      // "run": function 1, "g": global 0
+      (7, &[0x02, 0x03, b'r', b'u', b'n', 0x00, 0x01, 0x01, b'g', 0x03, 0x00]),
+      // empty body of function 1
+      (10, &[0x01, 0x02, 0x00, 0x0b]),
+    ])
+  }
+
+  #[test]
+  fn should_read_imports_with_their_types() {
+    let analysis = analyze(&imports_and_exports());
+    assert!(analysis.diagnostics.is_empty());
+
+    let imports = analysis
+      .imports
+      .iter()
+      .map(|import| {
+        (
+          import.module.as_str(),
+          import.name.as_str(),
+          import.ty.to_string(),
+          import.js_incompatible.is_some(),
+        )
+      })
+      .collect::<Vec<_>>();
+    assert_eq!(
+      imports,
+      [
+        ("./env", "f", "function [i32] -> [i32]".to_string(), false),
+        ("./env", "g", "global mut i32".to_string(), false),
+      ]
+    );
+  }
+
+  #[test]
+  fn should_read_exports_with_their_types() {
+    let analysis = analyze(&imports_and_exports());
+
+    let [run, global] = analysis.exports.as_slice() else {
+      panic!("should have two exports");
+    };
+    assert_eq!(run.name, "run");
+    // imported functions come first in the function index space
+    assert_eq!(run.ty.to_string(), "function [v128] -> []");
+    assert_eq!(run.js_incompatible.as_deref(), Some("v128 as parameter"));
+    assert_eq!(run.imported_global, None);
+
+    assert_eq!(global.name, "g");
+    assert_eq!(global.ty.to_string(), "global mut i32");
+    assert_eq!(
+      global.imported_global,
+      Some(("./env".to_string(), "g".to_string()))
+    );
+  }
+
+  #[test]
+  fn should_report_invalid_binaries() {
+    assert!(!analyze(b"not wasm").diagnostics.is_empty());
+
+    // the export section is cut off
+    let mut bytes = imports_and_exports();
+    bytes.truncate(bytes.len() - 8);
+    let analysis = analyze(&bytes);
+    assert!(!analysis.diagnostics.is_empty());
+    assert_eq!(analysis.imports.len(), 2);
+  }
+
+  #[test]
+  fn should_compare_export_and_import_types() {
+    let analysis = analyze(&imports_and_exports());
+    let import = &analysis.imports[0].ty;
+    let export = &analysis.exports[0].ty;
+    assert!(!export.satisfies(import));
+    assert!(import.satisfies(import));
+    // unknown signatures are not compared
+    assert!(WasmExternType::new(WasmExternKind::Func).satisfies(import));
+    assert!(!WasmExternType::new(WasmExternKind::Global).satisfies(import));
+  }
+}
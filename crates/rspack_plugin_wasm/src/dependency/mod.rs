@@ -1,2 +1,4 @@
+mod wasm_export_imported_dependency;
 mod wasm_import_dependency;
+pub use wasm_export_imported_dependency::WasmExportImportedDependency;
 pub use wasm_import_dependency::WasmImportDependency;
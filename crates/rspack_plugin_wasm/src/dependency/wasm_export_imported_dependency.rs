@@ -0,0 +1,77 @@
+use rspack_cacheable::{cacheable, cacheable_dyn, with::AsPreset};
+use rspack_core::{
+  AsContextDependency, AsDependencyCodeGeneration, Dependency, DependencyCategory, DependencyId,
+  DependencyType, ExportsInfoArtifact, ModuleDependency, ModuleGraph, ModuleGraphCacheArtifact,
+  ReferencedExport, RuntimeSpec,
+};
+use swc_core::ecma::atoms::Atom;
+
+/// An imported global that is re-exported by a synchronous wasm module.
+#[allow(dead_code)]
+#[cacheable]
+#[derive(Debug)]
+pub struct WasmExportImportedDependency {
+  id: DependencyId,
+  #[cacheable(with=AsPreset)]
+  export_name: Atom,
+  #[cacheable(with=AsPreset)]
+  name: Atom,
+  request: String,
+  value_type: String,
+}
+
+impl WasmExportImportedDependency {
+  pub fn new(export_name: String, request: String, name: String, value_type: String) -> Self {
+    Self {
+      id: DependencyId::new(),
+      export_name: export_name.into(),
+      name: name.into(),
+      request,
+      value_type,
+    }
+  }
+}
+
+#[cacheable_dyn]
+impl Dependency for WasmExportImportedDependency {
+  fn id(&self) -> &DependencyId {
+    &self.id
+  }
+
+  fn category(&self) -> &DependencyCategory {
+    &DependencyCategory::Wasm
+  }
+
+  fn dependency_type(&self) -> &DependencyType {
+    &DependencyType::WasmExportImported
+  }
+
+  fn get_referenced_exports(
+    &self,
+    _module_graph: &ModuleGraph,
+    _module_graph_cache: &ModuleGraphCacheArtifact,
+    _exports_info_artifact: &ExportsInfoArtifact,
+    _runtime: Option<&RuntimeSpec>,
+  ) -> Vec<ReferencedExport> {
+    vec![ReferencedExport::from(&self.name)]
+  }
+
+  fn could_affect_referencing_module(&self) -> rspack_core::AffectType {
+    rspack_core::AffectType::True
+  }
+}
+
+#[cacheable_dyn]
+impl ModuleDependency for WasmExportImportedDependency {
+  fn request(&self) -> &str {
+    &self.request
+  }
+
+  fn user_request(&self) -> &str {
+    &self.request
+  }
+}
+
+impl AsDependencyCodeGeneration for WasmExportImportedDependency {}
+
+impl AsContextDependency for WasmExportImportedDependency {}
@@ -1,11 +1,28 @@
 use rspack_cacheable::{cacheable, cacheable_dyn, with::AsPreset};
 use rspack_core::{
   AsContextDependency, AsDependencyCodeGeneration, Dependency, DependencyCategory, DependencyId,
-  DependencyRange, DependencyType, ExportsInfoArtifact, ModuleDependency, ModuleGraph,
-  ModuleGraphCacheArtifact, ReferencedExport, RuntimeSpec,
+  DependencyRange, DependencyType, ExportProvided, ExportsInfoArtifact, Module, ModuleDependency,
+  ModuleGraph, ModuleGraphCacheArtifact, ReferencedExport, RuntimeSpec,
 };
+use rspack_error::Diagnostic;
 use swc_core::ecma::atoms::Atom;
 
+use crate::{
+  WasmExternType, parser_and_generator::AsyncWasmParserAndGenerator,
+  sync_parser_and_generator::SyncWasmParserAndGenerator,
+};
+
+/// Exports of a wasm module with their types, as read by its parser.
+fn get_wasm_export_types(module: &dyn Module) -> Option<&[(String, WasmExternType)]> {
+  let parser_and_generator = module.as_normal_module()?.parser_and_generator();
+  if let Some(parser) = parser_and_generator.downcast_ref::<AsyncWasmParserAndGenerator>() {
+    return Some(parser.export_types());
+  }
+  parser_and_generator
+    .downcast_ref::<SyncWasmParserAndGenerator>()
+    .map(|parser| parser.export_types())
+}
+
 #[allow(dead_code)]
 #[cacheable]
 #[derive(Debug)]
@@ -14,17 +31,26 @@ pub struct WasmImportDependency {
   #[cacheable(with=AsPreset)]
   name: Atom,
   request: String,
-  // only_direct_import: bool,
+  ty: WasmExternType,
+  /// Set for imports that can't be provided by JavaScript in synchronous wasm
+  /// modules, describes why the import has to come from another wasm module.
+  only_direct_import: Option<String>,
   span: Option<DependencyRange>,
 }
 
 impl WasmImportDependency {
-  pub fn new(request: String, name: String) -> Self {
+  pub fn new(
+    request: String,
+    name: String,
+    ty: WasmExternType,
+    only_direct_import: Option<String>,
+  ) -> Self {
     Self {
       id: DependencyId::new(),
       name: name.into(),
       request,
-      // only_direct_import,
+      ty,
+      only_direct_import,
       span: None,
     }
   }
@@ -32,6 +58,10 @@ impl WasmImportDependency {
   pub fn name(&self) -> &Atom {
     &self.name
   }
+
+  pub fn ty(&self) -> &WasmExternType {
+    &self.ty
+  }
 }
 
 #[cacheable_dyn]
@@ -58,6 +88,66 @@ impl Dependency for WasmImportDependency {
     vec![ReferencedExport::from(&self.name)]
   }
 
+  fn get_diagnostics(
+    &self,
+    module_graph: &ModuleGraph,
+    _module_graph_cache: &ModuleGraphCacheArtifact,
+    exports_info_artifact: &ExportsInfoArtifact,
+  ) -> Option<Vec<Diagnostic>> {
+    let module = module_graph.get_module_by_dependency_id(&self.id)?;
+    if module.module_type().is_wasm_like() {
+      let export_types = get_wasm_export_types(module.as_ref())?;
+      let message = match export_types
+        .iter()
+        .find(|(name, _)| name == self.name.as_str())
+      {
+        None => format!(
+          "Import \"{}\" from \"{}\" is not exported by the WebAssembly module (possible exports: {})",
+          self.name,
+          self.request,
+          export_types
+            .iter()
+            .map(|(name, _)| name.as_str())
+            .collect::<Vec<_>>()
+            .join(", ")
+        ),
+        Some((_, ty)) if !ty.satisfies(&self.ty) => format!(
+          "Import \"{}\" from \"{}\" is expected to be a {} but the WebAssembly module exports a {ty}",
+          self.name, self.request, self.ty
+        ),
+        Some(_) => return None,
+      };
+      return Some(vec![Diagnostic::error("Wasm Import Error".into(), message)]);
+    }
+
+    let mut diagnostics = vec![];
+    if let Some(only_direct_import) = &self.only_direct_import {
+      diagnostics.push(Diagnostic::error(
+        "Wasm Import Error".into(),
+        format!(
+          "Import \"{}\" from \"{}\" with {only_direct_import} can only be used for direct wasm to wasm dependencies",
+          self.name, self.request
+        ),
+      ));
+    }
+    if module.first_error().is_none() {
+      let exports_info = exports_info_artifact.get_exports_info_data(&module.identifier());
+      if matches!(
+        exports_info.is_export_provided(exports_info_artifact, std::slice::from_ref(&self.name)),
+        Some(ExportProvided::NotProvided)
+      ) {
+        diagnostics.push(Diagnostic::error(
+          "Wasm Import Error".into(),
+          format!(
+            "Import \"{}\" from \"{}\" is not exported by the JavaScript module",
+            self.name, self.request
+          ),
+        ));
+      }
+    }
+    (!diagnostics.is_empty()).then_some(diagnostics)
+  }
+
   fn could_affect_referencing_module(&self) -> rspack_core::AffectType {
     rspack_core::AffectType::True
   }
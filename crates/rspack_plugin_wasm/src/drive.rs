@@ -0,0 +1,20 @@
+use rspack_core::Compilation;
+use rspack_hook::define_hook;
+#[cfg(allocative)]
+use rspack_util::allocative;
+
+#[derive(Debug, Clone, Default)]
+pub struct FetchWasmData {
+  /// Expression of the `RequestInit` passed to `fetch`, `wasmModuleId` and
+  /// `wasmModuleHash` are in scope.
+  pub options: Option<String>,
+}
+
+define_hook!(WasmLoadingPluginFetch: SeriesWaterfall(compilation: &Compilation, data: FetchWasmData) -> FetchWasmData);
+
+#[derive(Debug, Default)]
+#[cfg_attr(allocative, derive(allocative::Allocative))]
+pub struct WasmLoadingPluginHooks {
+  #[cfg_attr(allocative, allocative(skip))]
+  pub fetch: WasmLoadingPluginFetchHook,
+}
@@ -1,10 +1,17 @@
+mod analysis;
 mod dependency;
+mod drive;
 mod loading_plugin;
 mod parser_and_generator;
 mod runtime;
+mod sync_parser_and_generator;
+mod sync_wasm_plugin;
 mod wasm_plugin;
 
+pub use analysis::{WasmExternKind, WasmExternType};
+pub use drive::*;
 pub use loading_plugin::{
   FetchCompileAsyncWasmPlugin, UniversalCompileAsyncWasmPlugin, enable_wasm_loading_plugin,
 };
+pub use sync_wasm_plugin::SyncWasmPlugin;
 pub use wasm_plugin::AsyncWasmPlugin;
@@ -1,11 +1,26 @@
+use std::sync::{Arc, LazyLock};
+
+use atomic_refcell::AtomicRefCell;
 use rspack_core::{
-  BoxPlugin, ChunkUkey, Compilation, CompilationRuntimeRequirementInTree, Plugin, PluginExt,
-  RuntimeGlobals, RuntimeModule, RuntimeModuleExt, WasmLoading, WasmLoadingType,
+  BoxPlugin, ChunkUkey, Compilation, CompilationId, CompilationRuntimeRequirementInTree, Plugin,
+  PluginExt, RuntimeGlobals, RuntimeModule, RuntimeModuleExt, WasmLoading, WasmLoadingType,
 };
 use rspack_error::Result;
 use rspack_hook::{plugin, plugin_hook};
+use rspack_util::fx_hash::FxDashMap;
+
+use crate::{
+  drive::{FetchWasmData, WasmLoadingPluginHooks},
+  runtime::{AsyncWasmCompileRuntimeModule, AsyncWasmLoadingRuntimeModule},
+};
+
+/// Safety with [atomic_refcell::AtomicRefCell]:
+///
+/// We should make sure that there's no read-write and write-write conflicts for each hook instance by looking up [FetchCompileAsyncWasmPlugin::get_compilation_hooks_mut]
+type ArcWasmLoadingPluginHooks = Arc<AtomicRefCell<WasmLoadingPluginHooks>>;
 
-use crate::runtime::{AsyncWasmCompileRuntimeModule, AsyncWasmLoadingRuntimeModule};
+static COMPILATION_HOOKS_MAP: LazyLock<FxDashMap<CompilationId, ArcWasmLoadingPluginHooks>> =
+  LazyLock::new(Default::default);
 
 pub fn enable_wasm_loading_plugin(wasm_loading_type: WasmLoadingType) -> BoxPlugin {
   match wasm_loading_type {
@@ -15,10 +30,107 @@ pub fn enable_wasm_loading_plugin(wasm_loading_type: WasmLoadingType) -> BoxPlug
   }
 }
 
+/// Renders the arguments of `fetch`, with the `RequestInit` from the
+/// [WasmLoadingPluginHooks::fetch] hook if any.
+async fn render_fetch_arguments(compilation: &Compilation, url: String) -> Result<String> {
+  let hooks = FetchCompileAsyncWasmPlugin::get_compilation_hooks(compilation.id());
+  let data = hooks
+    .borrow()
+    .fetch
+    .call(compilation, FetchWasmData::default())
+    .await?;
+  Ok(match data.options {
+    Some(options) => format!("{url}, {options}"),
+    None => url,
+  })
+}
+
+pub(crate) async fn fetch_load_binary_code(compilation: &Compilation) -> Result<String> {
+  let runtime_template = compilation
+    .runtime_template
+    .create_runtime_module_code_template();
+  let url = format!(
+    "{} + $PATH",
+    runtime_template.render_runtime_globals(&RuntimeGlobals::PUBLIC_PATH)
+  );
+  Ok(format!(
+    "fetch({})",
+    render_fetch_arguments(compilation, url).await?
+  ))
+}
+
+pub(crate) fn read_file_load_binary_code(compilation: &Compilation) -> String {
+  let import_enabled = compilation.options.output.module
+    && compilation
+      .options
+      .output
+      .environment
+      .supports_dynamic_import();
+
+  if import_enabled {
+    include_str!("runtime/read_file_compile_async_wasm_with_import.js").to_string()
+  } else {
+    include_str!("runtime/read_file_compile_async_wasm.js").to_string()
+  }
+}
+
+/// Returns the code run before loading the binary and the code loading it.
+pub(crate) async fn universal_load_binary_code(
+  compilation: &Compilation,
+) -> Result<(String, String)> {
+  // Generate universal loading code
+  let import_meta_name = &compilation.options.output.import_meta_name;
+
+  // Generate before load binary code: detect environment and set wasmUrl
+  let generate_before_load_binary_code =
+    r#"var useFetch = typeof document !== 'undefined' || typeof self !== 'undefined';
+var wasmUrl = $PATH;"#
+      .to_string();
+
+  // Generate load binary code: use fetch in browser, fs.readFile in Node.js
+  let fetch_arguments = render_fetch_arguments(
+    compilation,
+    format!("new URL(wasmUrl, {import_meta_name}.url)"),
+  )
+  .await?;
+  let generate_load_binary_code = format!(
+    r#"(useFetch
+  ? fetch({fetch_arguments})
+  : Promise.all([import('fs'), import('url')]).then(([{{ readFile }}, {{ URL }}]) => new Promise((resolve, reject) => {{
+      readFile(new URL(wasmUrl, {import_meta_name}.url), (err, buffer) => {{
+        if (err) return reject(err);
+
+        // Fake fetch response
+        resolve({{
+          arrayBuffer() {{ return buffer; }}
+        }});
+      }});
+    }})))"#
+  );
+
+  Ok((generate_before_load_binary_code, generate_load_binary_code))
+}
+
 #[plugin]
 #[derive(Debug, Default)]
 pub struct FetchCompileAsyncWasmPlugin;
 
+impl FetchCompileAsyncWasmPlugin {
+  pub fn get_compilation_hooks(id: CompilationId) -> ArcWasmLoadingPluginHooks {
+    if !COMPILATION_HOOKS_MAP.contains_key(&id) {
+      COMPILATION_HOOKS_MAP.insert(id, Default::default());
+    }
+    COMPILATION_HOOKS_MAP
+      .get(&id)
+      .expect("should have wasm loading plugin hooks")
+      .clone()
+  }
+
+  pub fn get_compilation_hooks_mut(id: CompilationId) -> ArcWasmLoadingPluginHooks {
+    COMPILATION_HOOKS_MAP.entry(id).or_default().clone()
+  }
+}
+
 #[plugin_hook(CompilationRuntimeRequirementInTree for FetchCompileAsyncWasmPlugin)]
 async fn fetch_compile_async_wasm_plugin_runtime_requirements_in_tree(
   &self,
@@ -35,19 +147,14 @@ async fn fetch_compile_async_wasm_plugin_runtime_requirements_in_tree(
     return Ok(None);
   }
 
-  let runtime_template = compilation
-    .runtime_template
-    .create_runtime_module_code_template();
+  let load_binary_code = fetch_load_binary_code(compilation).await?;
   if runtime_requirements.contains(RuntimeGlobals::INSTANTIATE_WASM) {
     runtime_requirements_mut.insert(RuntimeGlobals::PUBLIC_PATH);
     runtime_modules_to_add.push((
       *chunk_ukey,
       AsyncWasmLoadingRuntimeModule::new(
         &compilation.runtime_template,
-        format!(
-          "fetch({} + $PATH)",
-          runtime_template.render_runtime_globals(&RuntimeGlobals::PUBLIC_PATH)
-        ),
+        load_binary_code.clone(),
         true,
       )
       .boxed(),
@@ -57,15 +164,8 @@ async fn fetch_compile_async_wasm_plugin_runtime_requirements_in_tree(
     runtime_requirements_mut.insert(RuntimeGlobals::PUBLIC_PATH);
     runtime_modules_to_add.push((
       *chunk_ukey,
-      AsyncWasmCompileRuntimeModule::new(
-        &compilation.runtime_template,
-        format!(
-          "fetch({} + $PATH)",
-          runtime_template.render_runtime_globals(&RuntimeGlobals::PUBLIC_PATH)
-        ),
-        true,
-      )
-      .boxed(),
+      AsyncWasmCompileRuntimeModule::new(&compilation.runtime_template, load_binary_code, true)
+        .boxed(),
     ));
   }
 
@@ -84,6 +184,10 @@ impl Plugin for FetchCompileAsyncWasmPlugin {
       .tap(fetch_compile_async_wasm_plugin_runtime_requirements_in_tree::new(self));
     Ok(())
   }
+
+  fn clear_cache(&self, id: CompilationId) {
+    COMPILATION_HOOKS_MAP.remove(&id);
+  }
 }
 
 #[plugin]
@@ -112,18 +216,7 @@ async fn read_file_compile_async_wasm_plugin_runtime_requirements_in_tree(
     return Ok(None);
   }
 
-  let import_enabled = compilation.options.output.module
-    && compilation
-      .options
-      .output
-      .environment
-      .supports_dynamic_import();
-
-  let load_binary_code = if import_enabled {
-    include_str!("runtime/read_file_compile_async_wasm_with_import.js").to_string()
-  } else {
-    include_str!("runtime/read_file_compile_async_wasm.js").to_string()
-  };
+  let load_binary_code = read_file_load_binary_code(compilation);
   if runtime_requirements.contains(RuntimeGlobals::INSTANTIATE_WASM) {
     runtime_modules_to_add.push((
       *chunk_ukey,
@@ -198,30 +291,8 @@ async fn universal_compile_async_wasm_plugin_runtime_requirements_in_tree(
     return Ok(None);
   }
 
-  // Generate universal loading code
-  let import_meta_name = &compilation.options.output.import_meta_name;
-
-  // Generate before load binary code: detect environment and set wasmUrl
-  let generate_before_load_binary_code =
-    r#"var useFetch = typeof document !== 'undefined' || typeof self !== 'undefined';
-var wasmUrl = $PATH;"#
-      .to_string();
-
-  // Generate load binary code: use fetch in browser, fs.readFile in Node.js
-  let generate_load_binary_code = format!(
-    r#"(useFetch
-  ? fetch(new URL(wasmUrl, {import_meta_name}.url))
-  : Promise.all([import('fs'), import('url')]).then(([{{ readFile }}, {{ URL }}]) => new Promise((resolve, reject) => {{
-      readFile(new URL(wasmUrl, {import_meta_name}.url), (err, buffer) => {{
-        if (err) return reject(err);
-
-        // Fake fetch response
-        resolve({{
-          arrayBuffer() {{ return buffer; }}
-        }});
-      }});
-    }})))"#
-  );
+  let (generate_before_load_binary_code, generate_load_binary_code) =
+    universal_load_binary_code(compilation).await?;
 
   // Generate before instantiate streaming: return fallback if not useFetch
   let generate_before_instantiate_streaming = r#"if (!useFetch) {
@@ -271,4 +342,8 @@ impl Plugin for UniversalCompileAsyncWasmPlugin {
       .tap(universal_compile_async_wasm_plugin_runtime_requirements_in_tree::new(self));
     Ok(())
   }
+
+  fn clear_cache(&self, id: CompilationId) {
+    COMPILATION_HOOKS_MAP.remove(&id);
+  }
 }
@@ -3,22 +3,59 @@ use std::borrow::Cow;
 use rspack_cacheable::{cacheable, cacheable_dyn};
 use rspack_collections::IdentifierIndexMap;
 use rspack_core::{
-  BoxDependency, BuildMetaExportsType, Dependency, DependencyId, DependencyType, ExportsArgument,
-  GenerateContext, ImportPhase, Module, ModuleArgument, ModuleDependency, ModuleGraph,
-  ModuleInitFragments, ParseContext, ParseResult, ParserAndGenerator, RuntimeGlobals, SourceType,
-  StaticExportsDependency, StaticExportsSpec,
+  BoxDependency, BuildMetaExportsType, Compilation, Dependency, DependencyId, DependencyType,
+  ExportsArgument, GenerateContext, ImportPhase, Module, ModuleArgument, ModuleDependency,
+  ModuleGraph, ModuleInitFragments, ParseContext, ParseResult, ParserAndGenerator, RuntimeGlobals,
+  RuntimeSpec, SourceType, StaticExportsDependency, StaticExportsSpec, UsageState,
   rspack_sources::{BoxSource, RawStringSource, Source, SourceExt},
 };
 use rspack_error::{Diagnostic, IntoTWithDiagnosticArray, Result, TWithDiagnosticArray};
 use rspack_util::{itoa, json_stringify_str};
 use swc_core::atoms::Atom;
-use wasmparser::{Import, Parser, Payload};
 
-use crate::dependency::WasmImportDependency;
+use crate::{WasmExternType, analysis::analyze, dependency::WasmImportDependency};
 
 #[cacheable]
-#[derive(Debug)]
-pub struct AsyncWasmParserAndGenerator;
+#[derive(Debug, Default)]
+pub struct AsyncWasmParserAndGenerator {
+  export_types: Vec<(String, WasmExternType)>,
+}
+
+impl AsyncWasmParserAndGenerator {
+  pub fn export_types(&self) -> &[(String, WasmExternType)] {
+    &self.export_types
+  }
+}
+
+/// Exports of an async wasm module which are unused in `runtime`, or in every
+/// runtime for `None`. They are reported, the binary is emitted unchanged.
+pub(crate) fn get_unused_exports<'a>(
+  module: &'a dyn Module,
+  compilation: &Compilation,
+  runtime: Option<&RuntimeSpec>,
+) -> Vec<&'a str> {
+  let Some(parser_and_generator) = module.as_normal_module().and_then(|normal_module| {
+    normal_module
+      .parser_and_generator()
+      .downcast_ref::<AsyncWasmParserAndGenerator>()
+  }) else {
+    return vec![];
+  };
+  let exports_info = compilation
+    .exports_info_artifact
+    .get_exports_info_data(&module.identifier());
+  parser_and_generator
+    .export_types
+    .iter()
+    .map(|(name, _)| name.as_str())
+    .filter(|name| {
+      exports_info
+        .get_read_only_export_info(&Atom::from(*name))
+        .get_used(runtime)
+        == UsageState::Unused
+    })
+    .collect()
+}
 
 pub(crate) static WASM_SOURCE_TYPE: &[SourceType; 2] = &[SourceType::Wasm, SourceType::JavaScript];
 const WASM_MAGIC_HEADER: &[u8; 4] = b"\0asm";
@@ -79,46 +116,22 @@ impl ParserAndGenerator for AsyncWasmParserAndGenerator {
       );
     }
 
-    for payload in Parser::new(0).parse_all(&source.buffer()) {
-      match payload {
-        Ok(payload) => match payload {
-          Payload::ExportSection(s) => {
-            for export in s {
-              match export {
-                Ok(export) => exports.push(export.name.to_string()),
-                Err(err) => diagnostic.push(Diagnostic::error(
-                  "Wasm Export Parse Error".into(),
-                  err.to_string(),
-                )),
-              };
-            }
-          }
-          Payload::ImportSection(s) => {
-            for import in s {
-              match import {
-                Ok(Import { module, name, .. }) => {
-                  dependencies.push(BoxDependency::new(WasmImportDependency::new(
-                    module.into(),
-                    name.into(),
-                  )));
-                }
-                Err(err) => diagnostic.push(Diagnostic::error(
-                  "Wasm Import Parse Error".into(),
-                  err.to_string(),
-                )),
-              }
-            }
-          }
-          _ => {}
-        },
-        Err(err) => {
-          diagnostic.push(Diagnostic::error(
-            "Wasm Parse Error".into(),
-            err.to_string(),
-          ));
-        }
-      }
+    let analysis = analyze(&source.buffer());
+    for import in analysis.imports {
+      dependencies.push(BoxDependency::new(WasmImportDependency::new(
+        import.module,
+        import.name,
+        import.ty,
+        None,
+      )));
     }
+    self.export_types = analysis
+      .exports
+      .into_iter()
+      .map(|export| (export.name, export.ty))
+      .collect();
+    exports.extend(self.export_types.iter().map(|(name, _)| name.clone()));
+    diagnostic.extend(analysis.diagnostics);
 
     dependencies.push(BoxDependency::new(StaticExportsDependency::new(
       StaticExportsSpec::Array(exports.iter().cloned().map(Atom::from).collect::<Vec<_>>()),
@@ -1,10 +1,14 @@
 use cow_utils::CowUtils;
+use rspack_collections::{IdentifierIndexSet, IdentifierSet};
 use rspack_core::{
-  Compilation, PathData, RuntimeCodeTemplate, RuntimeGlobals, RuntimeModule,
-  RuntimeModuleGenerateContext, RuntimeModuleStage, RuntimeTemplate,
+  BoxModule, ChunkGraph, ChunkUkey, Compilation, ModuleDependency, ModuleGraph, ModuleIdentifier,
+  ModuleType, PathData, RuntimeCodeTemplate, RuntimeGlobals, RuntimeModule,
+  RuntimeModuleGenerateContext, RuntimeModuleStage, RuntimeSpec, RuntimeTemplate, UsedNameItem,
   get_filename_without_hash_length, impl_runtime_module,
 };
-use rspack_util::itoa;
+use rspack_util::{fx_hash::FxIndexMap, itoa, json_stringify, json_stringify_str};
+
+use crate::{WasmExternKind, dependency::WasmImportDependency};
 
 #[impl_runtime_module]
 #[derive(Debug)]
@@ -183,6 +187,343 @@ impl RuntimeModule for AsyncWasmLoadingRuntimeModule {
   }
 }
 
+#[impl_runtime_module]
+#[derive(Debug)]
+pub struct WasmChunkLoadingRuntimeModule {
+  generate_load_binary_code: String,
+  generate_before_load_binary_code: String,
+}
+
+impl WasmChunkLoadingRuntimeModule {
+  pub fn new(
+    runtime_template: &RuntimeTemplate,
+    generate_load_binary_code: String,
+    generate_before_load_binary_code: String,
+  ) -> Self {
+    Self::with_default(
+      runtime_template,
+      generate_load_binary_code,
+      generate_before_load_binary_code,
+    )
+  }
+}
+
+#[async_trait::async_trait]
+impl RuntimeModule for WasmChunkLoadingRuntimeModule {
+  fn runtime_module_variables() -> &'static [&'static str] {
+    &[]
+  }
+
+  fn runtime_requirements(
+    &self,
+    _compilation: &Compilation,
+  ) -> rspack_core::RuntimeModuleRuntimeRequirements {
+    rspack_core::RuntimeModuleRuntimeRequirements {
+      dependencies: RuntimeGlobals::ENSURE_CHUNK_HANDLERS | RuntimeGlobals::REQUIRE,
+      define: RuntimeGlobals::WASM_INSTANCES,
+      ..Default::default()
+    }
+  }
+
+  async fn generate(
+    &self,
+    context: &RuntimeModuleGenerateContext<'_>,
+  ) -> rspack_error::Result<String> {
+    let compilation = context.compilation;
+    let runtime_template = context.runtime_template;
+    let chunk_ukey = self.chunk().expect("should attached chunk");
+    let chunk = compilation
+      .build_chunk_graph_artifact
+      .chunk_by_ukey
+      .expect_get(&chunk_ukey);
+    let module_graph = compilation.get_module_graph();
+    let path = render_wasm_module_path(compilation, &chunk_ukey).await?;
+
+    let wasm_instances = runtime_template.render_runtime_globals(&RuntimeGlobals::WASM_INSTANCES);
+    let require = runtime_template.render_runtime_globals(&RuntimeGlobals::REQUIRE);
+
+    let mut wasm_modules = IdentifierIndexSet::default();
+    let mut wasm_module_map = vec![];
+    for async_chunk in
+      chunk.get_all_async_chunks(&compilation.build_chunk_graph_artifact.chunk_group_by_ukey)
+    {
+      let modules = get_ordered_sync_wasm_modules(compilation, &async_chunk);
+      if modules.is_empty() {
+        continue;
+      }
+      let chunk_id = compilation
+        .build_chunk_graph_artifact
+        .chunk_by_ukey
+        .expect_get(&async_chunk)
+        .expect_id();
+      let module_ids = modules
+        .iter()
+        .map(|module| render_module_id(compilation, module))
+        .collect::<Vec<_>>()
+        .join(", ");
+      wasm_module_map.push(format!("{}: [{module_ids}]", json_stringify(chunk_id)));
+      wasm_modules.extend(modules);
+    }
+    // wasm modules imported from another chunk are loaded with their importer
+    let mut index = 0;
+    while let Some(module) = wasm_modules.get_index(index).copied() {
+      index += 1;
+      for dep_id in module_graph
+        .module_by_identifier(&module)
+        .expect("should have module")
+        .get_dependencies()
+      {
+        if let Some(target) = module_graph.get_module_by_dependency_id(dep_id)
+          && target.module_type() == &ModuleType::WasmSync
+        {
+          wasm_modules.insert(target.identifier());
+        }
+      }
+    }
+
+    let mut import_objects = vec![];
+    let mut module_hashes = vec![];
+    for module_identifier in &wasm_modules {
+      let module = module_graph
+        .module_by_identifier(module_identifier)
+        .expect("should have module");
+      let module_id = render_module_id(compilation, module_identifier);
+      let hash = module
+        .build_info()
+        .hash
+        .as_ref()
+        .map(|hash| hash.rendered(16))
+        .expect("should build info have hash");
+      module_hashes.push(format!("{module_id}: {}", json_stringify_str(hash)));
+      import_objects.push(format!(
+        "{module_id}: {}",
+        generate_import_object(
+          compilation,
+          module,
+          chunk.runtime(),
+          &wasm_instances,
+          &require
+        )
+      ));
+    }
+
+    let load_binary_code = self
+      .generate_load_binary_code
+      .cow_replace(
+        "$IMPORT_META_NAME",
+        compilation.options.output.import_meta_name.as_str(),
+      )
+      .cow_replace("$PATH", &format!("\"{path}\""));
+    let before_load_binary_code = self
+      .generate_before_load_binary_code
+      .cow_replace("$PATH", &format!("\"{path}\""));
+
+    Ok(format!(
+      r#"
+// object to store loaded and loading wasm modules
+var installedWasmModules = {{}};
+{wasm_instances_definition} = {{}};
+
+var wasmImportObjects = {{
+{import_objects}
+}};
+
+var wasmModuleHashes = {{
+{module_hashes}
+}};
+
+var wasmModuleMap = {{
+{wasm_module_map}
+}};
+
+// starts loading a wasm module, or returns the promise of the running or finished load
+function loadWasmModule(wasmModuleId) {{
+  var installedWasmModuleData = installedWasmModules[wasmModuleId];
+  // a Promise means "currently loading" or "already loaded".
+  if(installedWasmModuleData) return installedWasmModuleData;
+  var wasmModuleHash = wasmModuleHashes[wasmModuleId];
+  {before_load_binary_code}
+  var req = {load_binary_code};
+  var importObject = wasmImportObjects[wasmModuleId]();
+  var promise = Promise.all([req, importObject]).then(function(items) {{
+    var res = items[0];
+    if(typeof WebAssembly.instantiateStreaming === "function" && res.headers && res.headers.get("Content-Type") === "application/wasm") {{
+      return WebAssembly.instantiateStreaming(res, items[1]);
+    }}
+    return Promise.resolve(res.arrayBuffer()).then(function(bytes) {{
+      return WebAssembly.instantiate(bytes, items[1]);
+    }});
+  }});
+  return installedWasmModules[wasmModuleId] = promise.then(function(res) {{
+    return {wasm_instances}[wasmModuleId] = (res.instance || res).exports;
+  }});
+}}
+
+{ensure_chunk_handlers}.wasm = function(chunkId, promises) {{
+  var wasmModules = wasmModuleMap[chunkId] || [];
+  wasmModules.forEach(function(wasmModuleId) {{
+    promises.push(loadWasmModule(wasmModuleId));
+  }});
+}};
+"#,
+      wasm_instances_definition =
+        runtime_template.render_runtime_global_definition(&RuntimeGlobals::WASM_INSTANCES),
+      import_objects = import_objects.join(",\n"),
+      module_hashes = module_hashes.join(",\n"),
+      wasm_module_map = wasm_module_map.join(",\n"),
+      ensure_chunk_handlers =
+        runtime_template.render_runtime_globals(&RuntimeGlobals::ENSURE_CHUNK_HANDLERS),
+      load_binary_code = load_binary_code.trim_end_matches(';'),
+    ))
+  }
+}
+
+fn render_module_id(compilation: &Compilation, module: &ModuleIdentifier) -> String {
+  json_stringify(
+    ChunkGraph::get_module_id(&compilation.module_ids_artifact, *module)
+      .expect("should have module id"),
+  )
+}
+
+/// Returns the synchronous wasm modules of a chunk, modules imported by other
+/// wasm modules of the chunk first, so their instances can be awaited.
+fn get_ordered_sync_wasm_modules(
+  compilation: &Compilation,
+  chunk_ukey: &ChunkUkey,
+) -> Vec<ModuleIdentifier> {
+  let module_graph = compilation.get_module_graph();
+  let mut modules = compilation
+    .build_chunk_graph_artifact
+    .chunk_graph
+    .get_chunk_modules_identifier(chunk_ukey)
+    .iter()
+    .filter(|module| {
+      module_graph
+        .module_by_identifier(module)
+        .is_some_and(|module| module.module_type() == &ModuleType::WasmSync)
+    })
+    .copied()
+    .collect::<Vec<_>>();
+  modules.sort_unstable();
+
+  let in_chunk = modules.iter().copied().collect::<IdentifierSet>();
+  let mut visited = IdentifierSet::default();
+  let mut ordered = Vec::with_capacity(modules.len());
+  for module in modules {
+    visit_sync_wasm_module(module_graph, module, &in_chunk, &mut visited, &mut ordered);
+  }
+  ordered
+}
+
+fn visit_sync_wasm_module(
+  module_graph: &ModuleGraph,
+  module: ModuleIdentifier,
+  in_chunk: &IdentifierSet,
+  visited: &mut IdentifierSet,
+  ordered: &mut Vec<ModuleIdentifier>,
+) {
+  if !visited.insert(module) {
+    return;
+  }
+  if let Some(m) = module_graph.module_by_identifier(&module) {
+    for dep_id in m.get_dependencies() {
+      if let Some(target) = module_graph.module_identifier_by_dependency_id(dep_id)
+        && in_chunk.contains(target)
+      {
+        visit_sync_wasm_module(module_graph, *target, in_chunk, visited, ordered);
+      }
+    }
+  }
+  ordered.push(module);
+}
+
+/// Imports from other wasm modules are read from their instances once
+/// `loadWasmModule` resolves, which also loads them when they are in another
+/// chunk. Imports from JavaScript modules are read when the wasm module is
+/// instantiated, functions are wrapped so the JavaScript module is only
+/// required on call.
+fn generate_import_object(
+  compilation: &Compilation,
+  module: &BoxModule,
+  runtime: &RuntimeSpec,
+  wasm_instances: &str,
+  require: &str,
+) -> String {
+  let module_graph = compilation.get_module_graph();
+  let mut wait_for_instances: Vec<String> = vec![];
+  let mut imports: FxIndexMap<&str, Vec<String>> = FxIndexMap::default();
+
+  for dep_id in module.get_dependencies() {
+    let Some(dep) = module_graph
+      .dependency_by_id(dep_id)
+      .as_any()
+      .downcast_ref::<WasmImportDependency>()
+    else {
+      continue;
+    };
+    let Some(target) = module_graph.get_module_by_dependency_id(dep_id) else {
+      continue;
+    };
+    let target_id = render_module_id(compilation, &target.identifier());
+    let name = dep.name();
+    let value = if target.module_type() == &ModuleType::WasmSync {
+      let value = format!(
+        "{wasm_instances}[{target_id}][{}]",
+        json_stringify_str(name)
+      );
+      if !wait_for_instances.contains(&target_id) {
+        wait_for_instances.push(target_id);
+      }
+      value
+    } else {
+      let used_name = match compilation
+        .exports_info_artifact
+        .get_exports_info_data(&target.identifier())
+        .get_read_only_export_info(name)
+        .get_used_name(None, Some(runtime))
+      {
+        Some(UsedNameItem::Str(used_name)) => used_name,
+        _ => name.clone(),
+      };
+      let export = format!("{require}({target_id})[{}]", json_stringify_str(&used_name));
+      if dep.ty().kind == WasmExternKind::Func {
+        format!("function() {{ return {export}.apply(this, arguments); }}")
+      } else {
+        export
+      }
+    };
+    imports
+      .entry(dep.request())
+      .or_default()
+      .push(format!("{}: {value}", json_stringify_str(name)));
+  }
+
+  let imports = imports
+    .into_iter()
+    .map(|(request, items)| {
+      format!(
+        "{}: {{\n{}\n}}",
+        json_stringify_str(request),
+        items.join(",\n")
+      )
+    })
+    .collect::<Vec<_>>()
+    .join(",\n");
+
+  if wait_for_instances.is_empty() {
+    format!("function() {{\nreturn {{\n{imports}\n}};\n}}")
+  } else {
+    let instances = wait_for_instances
+      .iter()
+      .map(|id| format!("loadWasmModule({id})"))
+      .collect::<Vec<_>>()
+      .join(", ");
+    format!(
+      "function() {{\nreturn Promise.all([{instances}]).then(function() {{\nreturn {{\n{imports}\n}};\n}});\n}}"
+    )
+  }
+}
+
 async fn render_wasm_module_path(
   compilation: &rspack_core::Compilation,
   chunk_ukey: &rspack_core::ChunkUkey,
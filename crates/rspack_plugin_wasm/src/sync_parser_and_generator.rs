@@ -0,0 +1,221 @@
+use std::borrow::Cow;
+
+use rspack_cacheable::{cacheable, cacheable_dyn};
+use rspack_collections::IdentifierIndexMap;
+use rspack_core::{
+  BoxDependency, BuildMetaExportsType, DependencyId, DependencyType, GenerateContext, ImportPhase,
+  Module, ModuleArgument, ModuleDependency, ModuleGraph, ModuleType, ParseContext, ParseResult,
+  ParserAndGenerator, RuntimeGlobals, SourceType, StaticExportsDependency, StaticExportsSpec,
+  rspack_sources::{BoxSource, RawStringSource, Source, SourceExt},
+};
+use rspack_error::{IntoTWithDiagnosticArray, Result, TWithDiagnosticArray};
+use rspack_util::itoa;
+use swc_core::atoms::Atom;
+
+use crate::{
+  WasmExternType,
+  analysis::analyze,
+  dependency::{WasmExportImportedDependency, WasmImportDependency},
+  parser_and_generator::WASM_SOURCE_TYPE,
+};
+
+/// Parser and generator of `webassembly/sync` modules. The wasm instance is
+/// created by the chunk loading runtime before the module is executed, so the
+/// generated JavaScript only exposes the exports of the instance.
+#[cacheable]
+#[derive(Debug, Default)]
+pub struct SyncWasmParserAndGenerator {
+  /// Exported functions which can't be called from JavaScript, with the
+  /// incompatible type.
+  js_incompatible_exports: Vec<(String, String)>,
+  export_types: Vec<(String, WasmExternType)>,
+}
+
+impl SyncWasmParserAndGenerator {
+  pub fn js_incompatible_exports(&self) -> &[(String, String)] {
+    &self.js_incompatible_exports
+  }
+
+  pub fn export_types(&self) -> &[(String, WasmExternType)] {
+    &self.export_types
+  }
+}
+
+#[derive(Debug)]
+struct DepModule<'a> {
+  request: &'a str,
+  import_var: String,
+  dep_id: DependencyId,
+}
+
+#[cacheable_dyn]
+#[async_trait::async_trait]
+impl ParserAndGenerator for SyncWasmParserAndGenerator {
+  fn source_types(&self, _module: &dyn Module, _module_graph: &ModuleGraph) -> &[SourceType] {
+    WASM_SOURCE_TYPE
+  }
+
+  async fn parse<'a>(
+    &mut self,
+    parse_context: ParseContext<'a>,
+  ) -> Result<TWithDiagnosticArray<ParseResult>> {
+    parse_context.build_info.strict = true;
+    parse_context
+      .build_meta
+      .set_exports_type(BuildMetaExportsType::Namespace);
+
+    let source = parse_context.source;
+
+    let mut exports = vec![];
+    let mut dependencies: Vec<BoxDependency> = vec![];
+
+    let analysis = analyze(&source.buffer());
+    for import in analysis.imports {
+      dependencies.push(BoxDependency::new(WasmImportDependency::new(
+        import.module,
+        import.name,
+        import.ty,
+        import.js_incompatible,
+      )));
+    }
+    self.js_incompatible_exports.clear();
+    self.export_types.clear();
+    for export in analysis.exports {
+      if let Some(ty) = export.js_incompatible {
+        self.js_incompatible_exports.push((export.name.clone(), ty));
+      }
+      if let Some((module, name)) = export.imported_global {
+        dependencies.push(BoxDependency::new(WasmExportImportedDependency::new(
+          export.name.clone(),
+          module,
+          name,
+          export.ty.signature.clone().unwrap_or_default(),
+        )));
+      }
+      exports.push(export.name.clone());
+      self.export_types.push((export.name, export.ty));
+    }
+
+    dependencies.push(BoxDependency::new(StaticExportsDependency::new(
+      StaticExportsSpec::Array(exports.into_iter().map(Atom::from).collect::<Vec<_>>()),
+      false,
+    )));
+
+    Ok(
+      ParseResult {
+        dependencies,
+        blocks: vec![],
+        presentational_dependencies: vec![],
+        code_generation_dependencies: vec![],
+        source,
+        side_effects_bailout: None,
+      }
+      .with_diagnostic(analysis.diagnostics),
+    )
+  }
+
+  fn size(&self, module: &dyn Module, source_type: Option<&SourceType>) -> f64 {
+    match source_type.unwrap_or(&SourceType::Wasm) {
+      SourceType::JavaScript => {
+        40.0
+          + module
+            .get_presentational_dependencies()
+            .map_or(0.0, |i| i.len() as f64 * 10.0)
+      }
+      SourceType::Wasm => module.source().map_or(0, |source| source.size()) as f64,
+      _ => 0.0,
+    }
+  }
+
+  async fn generate(
+    &self,
+    source: &BoxSource,
+    module: &dyn Module,
+    generate_context: &mut GenerateContext,
+  ) -> Result<BoxSource> {
+    let GenerateContext {
+      compilation,
+      runtime_template,
+      ..
+    } = generate_context;
+
+    match generate_context.requested_source_type {
+      SourceType::JavaScript => {
+        let module_graph = compilation.get_module_graph();
+
+        // imports from JavaScript modules are evaluated in order, imports from
+        // wasm modules are already linked by the instance
+        let mut dep_modules = IdentifierIndexMap::<DepModule>::default();
+        for dep_id in module.get_dependencies() {
+          let dep = module_graph.dependency_by_id(dep_id);
+          if dep.dependency_type() != &DependencyType::WasmImport {
+            continue;
+          }
+          let Some(target) = module_graph.get_module_by_dependency_id(dep_id) else {
+            continue;
+          };
+          if target.module_type() == &ModuleType::WasmSync {
+            continue;
+          }
+          let dep = dep
+            .as_any()
+            .downcast_ref::<WasmImportDependency>()
+            .expect("should be wasm import dependency");
+          let len = dep_modules.len();
+          dep_modules.entry(target.identifier()).or_insert_with(|| {
+            let mut len_buffer = itoa::Buffer::new();
+            DepModule {
+              request: dep.request(),
+              import_var: format!("rspack_import_{}", len_buffer.format(len)),
+              dep_id: *dep_id,
+            }
+          });
+        }
+
+        let imports_code = dep_modules
+          .values()
+          .map(|dep_module| {
+            let (code, compat_code) = runtime_template.import_statement(
+              module,
+              compilation,
+              &dep_module.dep_id,
+              &dep_module.import_var,
+              dep_module.request,
+              ImportPhase::Evaluation,
+              false,
+            );
+            format!("{code}{compat_code}")
+          })
+          .collect::<String>();
+
+        let module_argument = runtime_template.render_module_argument(ModuleArgument::Module);
+        let wasm_instances =
+          runtime_template.render_runtime_globals(&RuntimeGlobals::WASM_INSTANCES);
+        Ok(
+          RawStringSource::from(format!(
+            r#""use strict";
+// Instantiate WebAssembly module
+var wasmExports = {wasm_instances}[{module_argument}.id];
+
+// export exports from WebAssembly module
+{module_argument}.exports = wasmExports;
+
+// exec imports from WebAssembly module (for esm order)
+{imports_code}"#
+          ))
+          .boxed(),
+        )
+      }
+      _ => Ok(source.clone()),
+    }
+  }
+
+  fn get_concatenation_bailout_reason(
+    &self,
+    _module: &dyn Module,
+    _mg: &rspack_core::ModuleGraph,
+    _cg: &rspack_core::ChunkGraph,
+  ) -> Option<Cow<'static, str>> {
+    Some("Module Concatenation is not implemented for SyncWasmParserAndGenerator".into())
+  }
+}
@@ -0,0 +1,235 @@
+use rspack_core::{
+  ChunkUkey, Compilation, CompilationParams, CompilationRenderManifest,
+  CompilationRuntimeRequirementInTree, CompilationSeal, CompilerCompilation, DependencyType,
+  ModuleType, ParserAndGenerator, Plugin, RenderManifestEntry, RuntimeGlobals, RuntimeModule,
+  RuntimeModuleExt, WasmLoading, WasmLoadingType,
+};
+use rspack_error::{Diagnostic, Result};
+use rspack_hook::{plugin, plugin_hook};
+
+use crate::{
+  loading_plugin::{
+    fetch_load_binary_code, read_file_load_binary_code, universal_load_binary_code,
+  },
+  runtime::WasmChunkLoadingRuntimeModule,
+  sync_parser_and_generator::SyncWasmParserAndGenerator,
+  wasm_plugin::render_wasm_modules,
+};
+
+/// Supports `webassembly/sync` modules. Wasm modules are instantiated while
+/// the chunk containing them is loaded, so they can only be placed in async
+/// chunks and their exports are usable without top-level await.
+#[plugin]
+#[derive(Debug, Default)]
+pub struct SyncWasmPlugin {}
+
+#[plugin_hook(CompilerCompilation for SyncWasmPlugin)]
+async fn compilation(
+  &self,
+  compilation: &mut Compilation,
+  params: &mut CompilationParams,
+) -> Result<()> {
+  compilation.set_dependency_factory(
+    DependencyType::WasmImport,
+    params.normal_module_factory.clone(),
+  );
+  compilation.set_dependency_factory(
+    DependencyType::WasmExportImported,
+    params.normal_module_factory.clone(),
+  );
+  Ok(())
+}
+
+#[plugin_hook(CompilationSeal for SyncWasmPlugin)]
+async fn seal(&self, compilation: &Compilation, diagnostics: &mut Vec<Diagnostic>) -> Result<()> {
+  let module_graph = compilation.get_module_graph();
+  for (identifier, module) in module_graph.modules() {
+    if module.module_type() != &ModuleType::WasmSync {
+      continue;
+    }
+    let Some(parser_and_generator) = module.as_normal_module().and_then(|normal_module| {
+      normal_module
+        .parser_and_generator()
+        .downcast_ref::<SyncWasmParserAndGenerator>()
+    }) else {
+      continue;
+    };
+    let js_incompatible_exports = parser_and_generator.js_incompatible_exports();
+    if js_incompatible_exports.is_empty() {
+      continue;
+    }
+
+    for connection in module_graph.get_incoming_connections(identifier) {
+      let Some(origin_module) = connection
+        .original_module_identifier
+        .and_then(|origin| module_graph.module_by_identifier(&origin))
+      else {
+        continue;
+      };
+      // wasm modules can import anything from each other
+      if matches!(
+        origin_module.module_type(),
+        ModuleType::WasmSync | ModuleType::WasmAsync
+      ) {
+        continue;
+      }
+      let Some(dependency) = module_graph
+        .dependency_by_id(&connection.dependency_id)
+        .as_module_dependency()
+      else {
+        continue;
+      };
+      for referenced_export in dependency.get_referenced_exports(
+        module_graph,
+        &compilation.module_graph_cache_artifact,
+        &compilation.exports_info_artifact,
+        None,
+      ) {
+        let Some(name) = referenced_export.name.first() else {
+          continue;
+        };
+        if let Some((_, incompatible_type)) = js_incompatible_exports
+          .iter()
+          .find(|(export, _)| export == name.as_str())
+        {
+          diagnostics.push(Diagnostic::error(
+            "Unsupported WebAssembly Feature".into(),
+            format!(
+              "Export \"{name}\" with {incompatible_type} can only be used for direct wasm to wasm dependencies\nIt's used from {}",
+              origin_module.readable_identifier(&compilation.options.context)
+            ),
+          ));
+        }
+      }
+    }
+  }
+  Ok(())
+}
+
+#[plugin_hook(CompilationRenderManifest for SyncWasmPlugin)]
+async fn render_manifest(
+  &self,
+  compilation: &Compilation,
+  chunk_ukey: &ChunkUkey,
+  manifest: &mut Vec<RenderManifestEntry>,
+  diagnostics: &mut Vec<Diagnostic>,
+) -> Result<()> {
+  let chunk = compilation
+    .build_chunk_graph_artifact
+    .chunk_by_ukey
+    .expect_get(chunk_ukey);
+  if chunk.can_be_initial(&compilation.build_chunk_graph_artifact.chunk_group_by_ukey) {
+    let module_graph = compilation.get_module_graph();
+    for module in compilation
+      .build_chunk_graph_artifact
+      .chunk_graph
+      .get_chunk_modules(chunk_ukey, module_graph)
+    {
+      if module.module_type() != &ModuleType::WasmSync {
+        continue;
+      }
+      diagnostics.push(Diagnostic::error(
+        "WasmInitialChunkError".into(),
+        format!(
+          "WebAssembly module is included in initial chunk.\nThis is not allowed, because WebAssembly download and compilation must happen asynchronous.\nAdd an async split point (i. e. import()) somewhere between your entrypoint and the WebAssembly module:\n{}",
+          module.readable_identifier(&compilation.options.context)
+        ),
+      ));
+    }
+  }
+
+  render_wasm_modules(compilation, chunk_ukey, &ModuleType::WasmSync, manifest).await
+}
+
+#[plugin_hook(CompilationRuntimeRequirementInTree for SyncWasmPlugin)]
+async fn runtime_requirements_in_tree(
+  &self,
+  compilation: &Compilation,
+  chunk_ukey: &ChunkUkey,
+  _all_runtime_requirements: &RuntimeGlobals,
+  runtime_requirements: &RuntimeGlobals,
+  runtime_requirements_mut: &mut RuntimeGlobals,
+  runtime_modules_to_add: &mut Vec<(ChunkUkey, Box<dyn RuntimeModule>)>,
+) -> Result<Option<()>> {
+  if !runtime_requirements.contains(RuntimeGlobals::ENSURE_CHUNK_HANDLERS) {
+    return Ok(None);
+  }
+
+  let chunk = compilation
+    .build_chunk_graph_artifact
+    .chunk_by_ukey
+    .expect_get(chunk_ukey);
+  let module_graph = compilation.get_module_graph();
+  let has_sync_wasm_modules = chunk
+    .get_all_async_chunks(&compilation.build_chunk_graph_artifact.chunk_group_by_ukey)
+    .iter()
+    .any(|async_chunk| {
+      compilation
+        .build_chunk_graph_artifact
+        .chunk_graph
+        .get_chunk_modules(async_chunk, module_graph)
+        .iter()
+        .any(|module| module.module_type() == &ModuleType::WasmSync)
+    });
+  if !has_sync_wasm_modules {
+    return Ok(None);
+  }
+
+  let wasm_loading = chunk
+    .get_entry_options(&compilation.build_chunk_graph_artifact.chunk_group_by_ukey)
+    .and_then(|options| options.wasm_loading.clone())
+    .unwrap_or_else(|| compilation.options.output.wasm_loading.clone());
+  let (before_load_binary_code, load_binary_code) = match wasm_loading {
+    WasmLoading::Enable(WasmLoadingType::Fetch) => {
+      runtime_requirements_mut.insert(RuntimeGlobals::PUBLIC_PATH);
+      (String::new(), fetch_load_binary_code(compilation).await?)
+    }
+    WasmLoading::Enable(WasmLoadingType::AsyncNode) => {
+      (String::new(), read_file_load_binary_code(compilation))
+    }
+    WasmLoading::Enable(WasmLoadingType::Universal) => {
+      universal_load_binary_code(compilation).await?
+    }
+    WasmLoading::Disable => return Ok(None),
+  };
+
+  runtime_modules_to_add.push((
+    *chunk_ukey,
+    WasmChunkLoadingRuntimeModule::new(
+      &compilation.runtime_template,
+      load_binary_code,
+      before_load_binary_code,
+    )
+    .boxed(),
+  ));
+
+  Ok(None)
+}
+
+impl Plugin for SyncWasmPlugin {
+  fn name(&self) -> &'static str {
+    "rspack.WebAssemblyModulesPlugin"
+  }
+
+  fn apply(&self, ctx: &mut rspack_core::ApplyContext<'_>) -> Result<()> {
+    ctx.compiler_hooks.compilation.tap(compilation::new(self));
+    ctx.compilation_hooks.seal.tap(seal::new(self));
+    ctx
+      .compilation_hooks
+      .render_manifest
+      .tap(render_manifest::new(self));
+    ctx
+      .compilation_hooks
+      .runtime_requirement_in_tree
+      .tap(runtime_requirements_in_tree::new(self));
+
+    ctx.register_parser_and_generator_builder(
+      ModuleType::WasmSync,
+      Box::new(move |_| {
+        Box::new(SyncWasmParserAndGenerator::default()) as Box<dyn ParserAndGenerator>
+      }),
+    );
+
+    Ok(())
+  }
+}
@@ -1,14 +1,14 @@
 use std::fmt::Debug;
 
 use rspack_core::{
-  ChunkGraph, ChunkUkey, Compilation, CompilationParams, CompilationRenderManifest,
-  CompilerCompilation, DependencyType, ManifestAssetType, ModuleType, ParserAndGenerator, PathData,
-  Plugin, RenderManifestEntry, SourceType,
+  ChunkGraph, ChunkUkey, Compilation, CompilationAfterCodeGeneration, CompilationParams,
+  CompilationRenderManifest, CompilerCompilation, DependencyType, ManifestAssetType, ModuleType,
+  ParserAndGenerator, PathData, Plugin, RenderManifestEntry, SourceType,
 };
 use rspack_error::{Diagnostic, Result};
 use rspack_hook::{plugin, plugin_hook};
 
-use crate::parser_and_generator::AsyncWasmParserAndGenerator;
+use crate::parser_and_generator::{AsyncWasmParserAndGenerator, get_unused_exports};
 
 #[plugin]
 #[derive(Debug, Default)]
@@ -31,6 +31,52 @@ async fn compilation(
   Ok(())
 }
 
+/// Warns about the exports of wasm modules that no runtime uses. They stay in
+/// the emitted binary, which is never rewritten.
+///
+/// Modules without any used export, e.g. only imported for their source phase
+/// or their side effects, are not reported.
+#[plugin_hook(CompilationAfterCodeGeneration for AsyncWasmPlugin)]
+async fn after_code_generation(
+  &self,
+  compilation: &Compilation,
+  diagnostics: &mut Vec<Diagnostic>,
+) -> Result<()> {
+  let module_graph = compilation.get_module_graph();
+  let mut unused = module_graph
+    .modules()
+    .filter(|(_, module)| module.module_type() == &ModuleType::WasmAsync)
+    .filter(|(identifier, _)| {
+      compilation
+        .exports_info_artifact
+        .get_exports_info_data(identifier)
+        .is_used(None)
+    })
+    .filter_map(|(identifier, module)| {
+      let unused_exports = get_unused_exports(module.as_ref(), compilation, None);
+      (!unused_exports.is_empty()).then(|| {
+        (
+          module.readable_identifier(&compilation.options.context),
+          *identifier,
+          unused_exports.join(", "),
+        )
+      })
+    })
+    .collect::<Vec<_>>();
+  unused.sort_unstable_by(|a, b| a.0.cmp(&b.0));
+  diagnostics.extend(unused.into_iter().map(
+    |(readable_identifier, identifier, unused_exports)| {
+      let mut diagnostic = Diagnostic::warn(
+        "UnusedWasmExportsWarning".into(),
+        format!("{readable_identifier} has unused exports: {unused_exports}"),
+      );
+      diagnostic.module_identifier = Some(identifier);
+      diagnostic
+    },
+  ));
+  Ok(())
+}
+
 #[plugin_hook(CompilationRenderManifest for AsyncWasmPlugin)]
 async fn render_manifest(
   &self,
@@ -38,6 +84,16 @@ async fn render_manifest(
   chunk_ukey: &ChunkUkey,
   manifest: &mut Vec<RenderManifestEntry>,
   _diagnostics: &mut Vec<Diagnostic>,
+) -> Result<()> {
+  render_wasm_modules(compilation, chunk_ukey, &ModuleType::WasmAsync, manifest).await
+}
+
+/// Emits the binaries of the wasm modules of `module_type` in the chunk.
+pub(crate) async fn render_wasm_modules(
+  compilation: &Compilation,
+  chunk_ukey: &ChunkUkey,
+  module_type: &ModuleType,
+  manifest: &mut Vec<RenderManifestEntry>,
 ) -> Result<()> {
   let wasm_filename_template = &compilation.options.output.webassembly_module_filename;
   let chunk = compilation
@@ -52,7 +108,7 @@ async fn render_manifest(
     .get_chunk_modules(chunk_ukey, module_graph);
 
   for m in ordered_modules {
-    if m.module_type() != &ModuleType::WasmAsync {
+    if m.module_type() != module_type {
       continue;
     }
     let Some(source) = compilation
@@ -98,10 +154,16 @@ impl Plugin for AsyncWasmPlugin {
       .compilation_hooks
       .render_manifest
       .tap(render_manifest::new(self));
+    ctx
+      .compilation_hooks
+      .after_code_generation
+      .tap(after_code_generation::new(self));
 
     ctx.register_parser_and_generator_builder(
       ModuleType::WasmAsync,
-      Box::new(move |_| Box::new(AsyncWasmParserAndGenerator) as Box<dyn ParserAndGenerator>),
+      Box::new(move |_| {
+        Box::new(AsyncWasmParserAndGenerator::default()) as Box<dyn ParserAndGenerator>
+      }),
     );
 
     Ok(())
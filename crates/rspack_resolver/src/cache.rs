@@ -5,20 +5,22 @@ use std::{
   hash::{BuildHasherDefault, Hash, Hasher},
   io,
   ops::Deref,
-  path::PathBuf,
+  path::{Path, PathBuf},
   sync::Arc,
 };
 
+use arc_swap::ArcSwap;
 use camino::{Utf8Component, Utf8Path, Utf8PathBuf};
 use dashmap::{DashMap, DashSet};
 use futures::future::BoxFuture;
 use rspack_paths::{ArcPath, hash_path};
-use rustc_hash::FxHasher;
+use rustc_hash::{FxHashSet, FxHasher};
 use tokio::sync::OnceCell as OnceLock;
 
 use crate::{
   FileMetadata, FileSystem, JSONError, ResolveError, ResolveOptions, TsConfig,
   context::ResolveContext as Ctx,
+  import_map::ImportMap,
   package_json::{PackageJson, off_to_location},
 };
 
@@ -27,6 +29,7 @@ pub struct Cache<Fs> {
   pub(crate) fs: Fs,
   paths: DashSet<CachedPath, BuildHasherDefault<IdentityHasher>>,
   tsconfigs: DashMap<PathBuf, Arc<TsConfig>, BuildHasherDefault<FxHasher>>,
+  import_maps: DashMap<PathBuf, Arc<ImportMap>, BuildHasherDefault<FxHasher>>,
 }
 
 impl<Fs: Send + Sync + FileSystem> Cache<Fs> {
@@ -35,12 +38,75 @@ impl<Fs: Send + Sync + FileSystem> Cache<Fs> {
       fs,
       paths: DashSet::default(),
       tsconfigs: DashMap::default(),
+      import_maps: DashMap::default(),
     }
   }
 
   pub fn clear(&self) {
     self.paths.clear();
     self.tsconfigs.clear();
+    self.import_maps.clear();
+  }
+
+  /// Drops the cached results of the changed, added or removed `paths`, of their parent
+  /// directories, and the tsconfigs and import maps which depend on them.
+  ///
+  /// The entries are reset in place, so the paths holding them as parents stay valid.
+  pub fn invalidate<'a>(&self, paths: impl IntoIterator<Item = &'a Path>) {
+    // Directories whose cached descendants are reset too
+    let mut roots = FxHashSet::default();
+    for path in paths {
+      let Some(path) = Utf8Path::from_path(path) else {
+        continue;
+      };
+      self
+        .tsconfigs
+        .retain(|_, tsconfig| !tsconfig.file_dependencies.contains(path));
+      self.import_maps.remove(path.as_std_path());
+
+      if let Some(cached_path) = self.get(path) {
+        // A file has no descendants, anything else may have been a directory or be one now
+        if !cached_path.is_known_file() {
+          roots.insert(cached_path.to_path_buf());
+        }
+        cached_path.reset();
+      }
+      // The parent directory lists the path, e.g. as its `package.json` or `node_modules`.
+      // Ancestors which were missing must exist now, so they are reset as well.
+      let mut ancestor = path.parent();
+      while let Some(path) = ancestor {
+        let cached_path = self.get(path);
+        let was_missing = cached_path
+          .as_ref()
+          .is_none_or(|cached_path| cached_path.is_known_missing());
+        if let Some(cached_path) = cached_path {
+          cached_path.reset();
+        }
+        if !was_missing {
+          break;
+        }
+        ancestor = path.parent();
+      }
+    }
+
+    if roots.is_empty() {
+      return;
+    }
+    for cached_path in self.paths.iter() {
+      if std::iter::successors(cached_path.parent(), |parent| parent.parent())
+        .any(|parent| roots.contains(parent.path()))
+      {
+        cached_path.reset();
+      }
+    }
+  }
+
+  fn get(&self, path: &Utf8Path) -> Option<CachedPath> {
+    let hash = hash_path(path.as_std_path());
+    self
+      .paths
+      .get((hash, path).borrow() as &dyn CacheKey)
+      .map(|cache_entry| cache_entry.clone())
   }
 
   pub fn value(&self, path: &Utf8Path) -> CachedPath {
@@ -101,6 +167,22 @@ impl<Fs: Send + Sync + FileSystem> Cache<Fs> {
       .insert(path.as_std_path().to_path_buf(), Arc::clone(&tsconfig));
     Ok(tsconfig)
   }
+
+  pub async fn import_map(&self, path: &Utf8Path) -> Result<Arc<ImportMap>, ResolveError> {
+    if let Some(import_map_ref) = self.import_maps.get(path.as_std_path()) {
+      return Ok(Arc::clone(import_map_ref.value()));
+    }
+    let import_map_string = self
+      .fs
+      .read_to_string(path.as_std_path())
+      .await
+      .map_err(|_| ResolveError::ImportMapNotFound(path.as_std_path().to_path_buf()))?;
+    let import_map = Arc::new(ImportMap::parse(path, &import_map_string)?);
+    self
+      .import_maps
+      .insert(path.as_std_path().to_path_buf(), Arc::clone(&import_map));
+    Ok(import_map)
+  }
 }
 
 #[derive(Clone)]
@@ -157,16 +239,22 @@ pub struct CachedPathImpl {
   hash: u64,
   path: Box<Utf8Path>,
   parent: Option<CachedPath>,
-  meta: OnceLock<Option<FileMetadata>>,
-  canonicalized: OnceLock<Option<Utf8PathBuf>>,
-  node_modules: OnceLock<Option<CachedPath>>,
-  package_json: OnceLock<Option<Arc<PackageJson>>>,
+  state: ArcSwap<CachedPathState>,
   /// Memoized `<self.path>/package.json` `ArcPath` for the
   /// `missing_dependencies` push that fires on every `package_json` cache-hit
   /// `None` (~97% of `package_json` calls in dep-tracking workloads).
   package_json_dep_path: std::sync::OnceLock<ArcPath>,
 }
 
+/// The results cached for a path, replaced as a whole by [Cache::invalidate].
+#[derive(Default)]
+struct CachedPathState {
+  meta: OnceLock<Option<FileMetadata>>,
+  canonicalized: OnceLock<Option<Utf8PathBuf>>,
+  node_modules: OnceLock<Option<CachedPath>>,
+  package_json: OnceLock<Option<Arc<PackageJson>>>,
+}
+
 impl From<&CachedPathImpl> for ArcPath {
   /// Reuse the cache-side `FxHash` (already computed in `Cache::value`), so interning the
   /// dependency into the `ResolveContext` sink is a lookup with no rehashing, and allocates
@@ -182,10 +270,7 @@ impl CachedPathImpl {
       hash,
       path,
       parent,
-      meta: OnceLock::new(),
-      canonicalized: OnceLock::new(),
-      node_modules: OnceLock::new(),
-      package_json: OnceLock::new(),
+      state: ArcSwap::default(),
       package_json_dep_path: std::sync::OnceLock::new(),
     }
   }
@@ -211,13 +296,27 @@ impl CachedPathImpl {
     self.parent.as_ref()
   }
 
+  fn reset(&self) {
+    self.state.store(Arc::default());
+  }
+
+  fn is_known_file(&self) -> bool {
+    matches!(self.state.load().meta.get(), Some(Some(meta)) if meta.is_file)
+  }
+
+  fn is_known_missing(&self) -> bool {
+    matches!(self.state.load().meta.get(), Some(None))
+  }
+
   async fn meta<Fs: Send + Sync + FileSystem>(&self, fs: &Fs) -> Option<FileMetadata> {
     // Skip the Future state-machine + poll on cache hit. `tokio::sync::OnceCell::get`
     // is sync and bypasses constructing the `get_or_init` future entirely.
-    if let Some(m) = self.meta.get() {
+    if let Some(m) = self.state.load().meta.get() {
       return *m;
     }
-    *self
+    // Hold the state itself, the guard of `load` must not live across awaits
+    let state = self.state.load_full();
+    *state
       .meta
       .get_or_init(|| async { fs.metadata(self.path.as_std_path()).await.ok() })
       .await
@@ -246,7 +345,7 @@ impl CachedPathImpl {
   pub async fn realpath<Fs: FileSystem + Send + Sync>(&self, fs: &Fs) -> io::Result<Utf8PathBuf> {
     // Cache hit: avoid the heap-allocated `Box::pin` for the cache-miss state machine
     // by returning before delegating to the boxed recursive helper.
-    if let Some(cached) = self.canonicalized.get() {
+    if let Some(cached) = self.state.load().canonicalized.get() {
       return Ok(cached.clone().unwrap_or_else(|| self.path.to_path_buf()));
     }
     self.realpath_uncached(fs).await
@@ -257,7 +356,8 @@ impl CachedPathImpl {
     fs: &'a Fs,
   ) -> BoxFuture<'a, io::Result<Utf8PathBuf>> {
     Box::pin(async move {
-      self
+      let state = self.state.load_full();
+      state
         .canonicalized
         .get_or_try_init(|| async move {
           if fs
@@ -310,7 +410,7 @@ impl CachedPathImpl {
     cache: &Cache<Fs>,
     ctx: &mut Ctx,
   ) -> Option<CachedPath> {
-    if let Some(nm) = self.node_modules.get() {
+    if let Some(nm) = self.state.load().node_modules.get() {
       // Replay ctx tracking from the cold path: module_directory -> is_dir calls
       // ctx.add_missing_dependency when node_modules doesn't exist on disk.
       if nm.is_none() {
@@ -318,7 +418,8 @@ impl CachedPathImpl {
       }
       return nm.clone();
     }
-    self
+    let state = self.state.load_full();
+    state
       .node_modules
       .get_or_init(|| self.module_directory("node_modules", cache, ctx))
       .await
@@ -368,7 +469,7 @@ impl CachedPathImpl {
     options: &ResolveOptions,
     ctx: &mut Ctx,
   ) -> Result<Option<Arc<PackageJson>>, ResolveError> {
-    if let Some(pkg) = self.package_json.get() {
+    if let Some(pkg) = self.state.load().package_json.get() {
       // Preserve ctx dependency tracking on cache hit.
       match pkg {
         Some(package_json) => ctx.add_file_dependency(&package_json.path),
@@ -381,7 +482,8 @@ impl CachedPathImpl {
       return Ok(pkg.clone());
     }
     // Change to `std::sync::OnceLock::get_or_try_init` when it is stable.
-    let result = self
+    let state = self.state.load_full();
+    let result = state
       .package_json
       .get_or_try_init(|| async {
         let package_json_path = self.path.join("package.json");
@@ -2,7 +2,7 @@ use std::ops::{Deref, DerefMut};
 
 use rspack_paths::ArcPath;
 
-use crate::error::ResolveError;
+use crate::{error::ResolveError, trace::TraceStep};
 
 #[derive(Debug, Default, Clone)]
 pub struct ResolveContext(ResolveContextImpl);
@@ -21,9 +21,16 @@ pub struct ResolveContextImpl {
   /// Files that were not found on file system
   pub missing_dependencies: Option<Vec<ArcPath>>,
 
+  /// Steps of the resolution, recorded for [crate::ResolverGeneric::resolve_with_trace]
+  pub trace: Option<Vec<TraceStep>>,
+
   /// The current resolving alias for bailing recursion alias.
   pub resolving_alias: Option<String>,
 
+  /// Whether a specifier was mapped by the import map, the map is applied once so that an
+  /// address matching its own key doesn't recurse.
+  pub import_map_applied: bool,
+
   /// For avoiding infinite recursion, which will cause stack overflow.
   depth: u8,
 }
@@ -77,10 +84,29 @@ impl ResolveContext {
     }
   }
 
+  pub fn init_trace(&mut self) {
+    self.trace.replace(vec![]);
+  }
+
+  pub fn is_traced(&self) -> bool {
+    self.trace.is_some()
+  }
+
+  // The step is only built when the resolution is traced.
+  pub fn add_trace_step(&mut self, step: impl FnOnce() -> TraceStep) {
+    if let Some(trace) = &mut self.trace {
+      trace.push(step());
+    }
+  }
+
   pub fn with_resolving_alias(&mut self, alias: String) {
     self.resolving_alias = Some(alias);
   }
 
+  pub fn with_import_map_applied(&mut self) {
+    self.import_map_applied = true;
+  }
+
   pub fn test_for_infinite_recursion(&mut self) -> Result<(), ResolveError> {
     self.depth += 1;
     // 64 should be more than enough for detecting infinite recursion.
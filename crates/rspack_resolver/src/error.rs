@@ -38,6 +38,14 @@ pub enum ResolveError {
   #[error("Tsconfig's project reference path points to this tsconfig {0}")]
   TsconfigSelfReference(PathBuf),
 
+  /// Import map not found
+  #[error("Import map not found {0}")]
+  ImportMapNotFound(PathBuf),
+
+  /// The import map maps the specifier to an invalid address, which blocks it
+  #[error("Cannot find module '{0}', it is blocked by an invalid mapping in the import map {1}")]
+  ImportMapBlocked(/* specifier */ String, /* import map */ PathBuf),
+
   #[error("{0}")]
   IOError(IOError),
 
@@ -0,0 +1,244 @@
+//! [Import maps](https://html.spec.whatwg.org/multipage/webappapis.html#import-maps)
+//!
+//! Parsing and resolution follow the WICG specification, with the paths of the file system
+//! standing for `file:` URLs: the keys and the addresses starting with `/`, `./` or `../` are
+//! resolved against the directory of the import map, which stands for the root of the URLs
+//! starting with `/`. Specifiers starting with `/` are absolute paths.
+
+use std::hash::BuildHasherDefault;
+
+use camino::{Utf8Path, Utf8PathBuf};
+use indexmap::IndexMap;
+use rustc_hash::FxHasher;
+use serde::Deserialize;
+use serde_json::Value;
+
+use crate::{ResolveError, path::PathUtil};
+
+type RawSpecifierMap = IndexMap<String, Value, BuildHasherDefault<FxHasher>>;
+
+#[derive(Debug, Deserialize)]
+struct RawImportMap {
+  #[serde(default)]
+  imports: RawSpecifierMap,
+
+  #[serde(default)]
+  scopes: IndexMap<String, RawSpecifierMap, BuildHasherDefault<FxHasher>>,
+}
+
+/// Keys mapped to their addresses, sorted in descending order of the keys so that the
+/// longest prefixes are tried first.
+///
+/// The address is `None` when the mapping is invalid, which blocks the specifier.
+type SpecifierMap = Vec<(String, Option<String>)>;
+
+#[derive(Debug)]
+pub struct ImportMap {
+  /// Path to the import map file.
+  path: Utf8PathBuf,
+
+  imports: SpecifierMap,
+
+  /// Scope prefixes with their specifier maps, sorted like [SpecifierMap].
+  scopes: Vec<(String, SpecifierMap)>,
+}
+
+/// A key of an [ImportMap] matched by a specifier
+#[derive(Debug)]
+pub struct ImportMapMatch<'a> {
+  pub key: &'a str,
+
+  /// The address the specifier is mapped to, `None` when the specifier is blocked.
+  pub address: Option<String>,
+}
+
+impl ImportMap {
+  pub fn parse(path: &Utf8Path, json: &str) -> Result<Self, ResolveError> {
+    // The keys and the addresses are resolved against the directory of the import map, so the
+    // path must be the one of a file.
+    let Some(base) = path.parent() else {
+      return Err(ResolveError::ImportMapNotFound(
+        path.as_std_path().to_path_buf(),
+      ));
+    };
+    let raw: RawImportMap = serde_json::from_str(json).map_err(|error| {
+      ResolveError::from_serde_json_error(
+        path.as_std_path().to_path_buf(),
+        &error,
+        Some(json.to_string()),
+      )
+    })?;
+    let imports = sort_and_normalize_specifier_map(base, raw.imports);
+    let mut scopes = raw
+      .scopes
+      .into_iter()
+      .map(|(prefix, map)| {
+        let prefix = if is_absolute_url(&prefix) {
+          prefix
+        } else {
+          resolve_map_path(base, &prefix)
+        };
+        (prefix, sort_and_normalize_specifier_map(base, map))
+      })
+      .collect::<Vec<_>>();
+    scopes.sort_by(|(a, _), (b, _)| b.cmp(a));
+    Ok(Self {
+      path: path.to_path_buf(),
+      imports,
+      scopes,
+    })
+  }
+
+  pub fn path(&self) -> &Utf8Path {
+    &self.path
+  }
+
+  /// Resolves `specifier` requested from `directory`.
+  ///
+  /// The scopes are matched against the directory, so only the scopes ending with `/` apply;
+  /// a scope of a single module can never match.
+  pub fn resolve(&self, directory: &Utf8Path, specifier: &str) -> Option<ImportMapMatch<'_>> {
+    let normalized = if is_url_like(specifier) {
+      resolve_path(directory, specifier)
+    } else {
+      specifier.to_string()
+    };
+    // Prefix keys only apply to bare specifiers and URLs of special schemes
+    let allow_prefix = !is_absolute_url(specifier) || is_special_url(specifier);
+
+    let mut referrer = directory.as_str().to_string();
+    if !referrer.ends_with('/') {
+      referrer.push('/');
+    }
+    self
+      .scopes
+      .iter()
+      .filter(|(prefix, _)| prefix.ends_with('/') && referrer.starts_with(prefix.as_str()))
+      .find_map(|(_, map)| resolve_imports_match(map, &normalized, allow_prefix))
+      .or_else(|| resolve_imports_match(&self.imports, &normalized, allow_prefix))
+  }
+}
+
+/// Whether the address is a path of the file system rather than a URL.
+pub fn is_path_address(address: &str) -> bool {
+  !is_absolute_url(address)
+}
+
+fn sort_and_normalize_specifier_map(base: &Utf8Path, map: RawSpecifierMap) -> SpecifierMap {
+  let mut normalized = map
+    .into_iter()
+    .filter(|(key, _)| !key.is_empty())
+    .map(|(key, value)| {
+      let key = if is_url_like(&key) {
+        resolve_map_path(base, &key)
+      } else {
+        key
+      };
+      let address = match value {
+        Value::String(address) if is_absolute_url(&address) => Some(address),
+        Value::String(address) if is_url_like(&address) => Some(resolve_map_path(base, &address)),
+        // Bare addresses and non string values are invalid
+        _ => None,
+      };
+      // A prefix key must be mapped to a prefix
+      let address = address.filter(|address| !key.ends_with('/') || address.ends_with('/'));
+      (key, address)
+    })
+    .collect::<Vec<_>>();
+  normalized.sort_by(|(a, _), (b, _)| b.cmp(a));
+  normalized
+}
+
+fn resolve_imports_match<'a>(
+  map: &'a SpecifierMap,
+  normalized: &str,
+  allow_prefix: bool,
+) -> Option<ImportMapMatch<'a>> {
+  for (key, address) in map {
+    if key == normalized {
+      return Some(ImportMapMatch {
+        key,
+        address: address.clone(),
+      });
+    }
+    if !allow_prefix || !key.ends_with('/') {
+      continue;
+    }
+    if let Some(after_prefix) = normalized.strip_prefix(key.as_str()) {
+      return Some(ImportMapMatch {
+        key,
+        address: address
+          .as_deref()
+          .and_then(|address| join_address(address, after_prefix)),
+      });
+    }
+  }
+  None
+}
+
+/// Appends the rest of a specifier to a prefix address, `None` when the result backtracks
+/// above the address.
+fn join_address(address: &str, after_prefix: &str) -> Option<String> {
+  if !is_path_address(address) {
+    let mut depth = 0usize;
+    for segment in after_prefix.split('/') {
+      match segment {
+        ".." => depth = depth.checked_sub(1)?,
+        "" | "." => {}
+        _ => depth += 1,
+      }
+    }
+    return Some(format!("{address}{after_prefix}"));
+  }
+  let joined = resolve_path(Utf8Path::new(address), after_prefix);
+  joined.starts_with(address).then_some(joined)
+}
+
+/// Resolves a specifier starting with `/`, `./` or `../` against `base`, keeping the trailing
+/// slash of prefixes.
+fn resolve_path(base: &Utf8Path, specifier: &str) -> String {
+  let specifier_path = Utf8Path::new(specifier);
+  let path = if specifier_path.is_absolute() || specifier.starts_with('/') {
+    specifier_path.normalize()
+  } else {
+    base.normalize_with(specifier)
+  };
+  let mut path = path.into_string();
+  if specifier.ends_with('/') && !path.ends_with('/') {
+    path.push('/');
+  }
+  path
+}
+
+/// Resolves a key, a scope or an address of the import map against its directory, a leading `/`
+/// stands for the directory rather than the root of the file system.
+fn resolve_map_path(base: &Utf8Path, specifier: &str) -> String {
+  match specifier.strip_prefix('/') {
+    Some(rest) => resolve_path(base, &format!("./{rest}")),
+    None => resolve_path(base, specifier),
+  }
+}
+
+fn is_url_like(specifier: &str) -> bool {
+  specifier.starts_with('/')
+    || specifier.starts_with("./")
+    || specifier.starts_with("../")
+    || Utf8Path::new(specifier).is_absolute()
+}
+
+fn is_absolute_url(specifier: &str) -> bool {
+  // A single letter is a drive letter of Windows rather than a scheme
+  specifier.split_once(':').is_some_and(|(scheme, _)| {
+    scheme.len() > 1
+      && scheme.starts_with(|c: char| c.is_ascii_alphabetic())
+      && scheme
+        .chars()
+        .all(|c| c.is_ascii_alphanumeric() || matches!(c, '+' | '-' | '.'))
+  })
+}
+
+fn is_special_url(specifier: &str) -> bool {
+  ["ftp:", "file:", "http:", "https:", "ws:", "wss:"]
+    .iter()
+    .any(|scheme| specifier.starts_with(scheme))
+}
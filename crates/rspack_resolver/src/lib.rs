@@ -64,12 +64,14 @@ mod cache;
 mod context;
 mod error;
 mod file_system;
+mod import_map;
 mod options;
 mod package_json;
 mod path;
 mod resolution;
 
 mod specifier;
+mod trace;
 mod tsconfig;
 
 #[cfg(test)]
@@ -96,6 +98,7 @@ use crate::{
   alias_trie::AliasTrie,
   cache::{Cache, CachedPath},
   context::ResolveContext as Ctx,
+  import_map::is_path_address,
   package_json::JSONMap,
   path::{PathUtil, SLASH_START},
   specifier::Specifier,
@@ -111,6 +114,7 @@ pub use crate::{
   },
   package_json::{JSONValue, ModuleType, PackageJson},
   resolution::Resolution,
+  trace::{PackageField, Rejection, ResolveTrace, TraceStep},
 };
 
 type ResolveResult = Result<Option<CachedPath>, ResolveError>;
@@ -228,6 +232,27 @@ impl<Fs: FileSystem + Send + Sync> ResolverGeneric<Fs> {
     }
   }
 
+  /// Invalidate the cache for the changed, added or removed `paths`.
+  ///
+  /// Unlike [Self::clear_cache], only the cached results of the paths, of their parent
+  /// directories and of the `package.json`, tsconfig and import map files depending on them
+  /// are dropped, which keeps the cache warm for the other requests, e.g. in watch mode.
+  pub fn invalidate<P: AsRef<Path>>(&self, paths: impl IntoIterator<Item = P>) {
+    let paths = paths.into_iter().collect::<Vec<_>>();
+    self.cache.invalidate(paths.iter().map(AsRef::as_ref));
+    #[cfg(feature = "yarn_pnp")]
+    if paths.iter().any(|path| {
+      path
+        .as_ref()
+        .file_name()
+        .and_then(|name| name.to_str())
+        .is_some_and(|name| name.starts_with(".pnp."))
+    }) {
+      self.pnp_manifest.store(None);
+      self.pnp_no_manifest_cache.clear();
+    }
+  }
+
   /// Resolve `specifier` at an absolute path to a `directory`.
   ///
   /// A specifier is the string passed to require or import, i.e. `require("specifier")` or `import "specifier"`.
@@ -275,6 +300,27 @@ impl<Fs: FileSystem + Send + Sync> ResolverGeneric<Fs> {
     result
   }
 
+  /// Resolve `specifier` at absolute `path`, recording every step of the resolution.
+  ///
+  /// The [ResolveTrace] explains why the specifier resolved to the path, or why it failed,
+  /// in which case [ResolveTrace::suggestions] are the requests which may have been meant.
+  pub async fn resolve_with_trace<P: Send + AsRef<Path>>(
+    &self,
+    directory: P,
+    specifier: &str,
+  ) -> (Result<Resolution, ResolveError>, ResolveTrace) {
+    let directory = directory.as_ref();
+    let mut ctx = Ctx::default();
+    ctx.init_trace();
+    let result = self.resolve_tracing(directory, specifier, &mut ctx).await;
+    let trace = ResolveTrace {
+      directory: directory.to_path_buf(),
+      specifier: specifier.to_string(),
+      steps: ctx.trace.take().unwrap_or_default(),
+    };
+    (result, trace)
+  }
+
   /// Wrap `resolve_impl` with `tracing` information
   #[cfg_attr(feature="enable_instrument", tracing::instrument(level=tracing::Level::DEBUG, skip_all, fields(path = directory.to_str().expect("path should be UTF-8"), specifier = specifier)))]
   async fn resolve_tracing(
@@ -310,6 +356,10 @@ impl<Fs: FileSystem + Send + Sync> ResolverGeneric<Fs> {
     let cached_path = self.require(&cached_path, specifier, ctx).await?;
     let path = self.load_realpath(&cached_path, ctx).await?;
     if !self.options.restrictions.is_empty() && !self.check_restrictions(&path.normalize()) {
+      ctx.add_trace_step(|| TraceStep::Candidate {
+        path: path.clone().into_std_path_buf(),
+        rejection: Some(Rejection::Restricted),
+      });
       return Err(ResolveError::NotFound(specifier.to_string()));
     }
 
@@ -384,6 +434,11 @@ impl<Fs: FileSystem + Send + Sync> ResolverGeneric<Fs> {
       return Ok(path);
     }
 
+    // import map, before node_modules lookup
+    if let Some(path) = self.load_import_map(cached_path, specifier, ctx).await? {
+      return Ok(path);
+    }
+
     let result = match Utf8Path::new(specifier).components().next() {
       // 2. If X begins with '/'
       Some(Utf8Component::RootDir | Utf8Component::Prefix(_)) => {
@@ -696,6 +751,10 @@ impl<Fs: FileSystem + Send + Sync> ResolverGeneric<Fs> {
             Cow::Owned(format!("./{main_field}"))
           };
 
+          ctx.add_trace_step(|| TraceStep::MainField {
+            package_json: package_json.path.clone(),
+            target: main_field.to_string(),
+          });
           // c. let M = X + (json main field)
           let main_field_path = cached_path.path().normalize_with(main_field.as_ref());
           // d. LOAD_AS_FILE(M)
@@ -752,6 +811,9 @@ impl<Fs: FileSystem + Send + Sync> ResolverGeneric<Fs> {
     ctx: &mut Ctx,
   ) -> ResolveResult {
     if ctx.fully_specified {
+      ctx.add_trace_step(|| TraceStep::FullySpecified {
+        path: path.to_path_buf().into_std_path_buf(),
+      });
       return Ok(None);
     }
     let path = path.path().as_str();
@@ -862,9 +924,28 @@ impl<Fs: FileSystem + Send + Sync> ResolverGeneric<Fs> {
     {
       return Ok(Some(path));
     }
-    if cached_path.is_file(&self.cache.fs, ctx).await && self.check_restrictions(cached_path.path())
-    {
-      return Ok(Some(cached_path.clone()));
+    if cached_path.is_file(&self.cache.fs, ctx).await {
+      if self.check_restrictions(cached_path.path()) {
+        ctx.add_trace_step(|| TraceStep::Candidate {
+          path: cached_path.to_path_buf().into_std_path_buf(),
+          rejection: None,
+        });
+        return Ok(Some(cached_path.clone()));
+      }
+      ctx.add_trace_step(|| TraceStep::Candidate {
+        path: cached_path.to_path_buf().into_std_path_buf(),
+        rejection: Some(Rejection::Restricted),
+      });
+    } else if ctx.is_traced() {
+      let rejection = if cached_path.is_dir(&self.cache.fs, ctx).await {
+        Rejection::Directory
+      } else {
+        Rejection::NotFound
+      };
+      ctx.add_trace_step(|| TraceStep::Candidate {
+        path: cached_path.to_path_buf().into_std_path_buf(),
+        rejection: Some(rejection),
+      });
     }
     Ok(None)
   }
@@ -901,6 +982,9 @@ impl<Fs: FileSystem + Send + Sync> ResolverGeneric<Fs> {
         else {
           continue;
         };
+        ctx.add_trace_step(|| TraceStep::ModuleDirectory {
+          path: cached_path.to_path_buf().into_std_path_buf(),
+        });
         if let Some(path) = self
           .resolve_in_module_dir(&cached_path, specifier, package_name, subpath, ctx)
           .await?
@@ -1004,6 +1088,7 @@ impl<Fs: FileSystem + Send + Sync> ResolverGeneric<Fs> {
       if !cached_path.is_dir(&self.cache.fs, ctx).await {
         continue;
       }
+      ctx.add_trace_step(|| TraceStep::ModuleDirectory { path: dir.clone() });
       if let Some(path) = self
         .resolve_in_module_dir(&cached_path, specifier, package_name, subpath, ctx)
         .await?
@@ -1078,6 +1163,13 @@ impl<Fs: FileSystem + Send + Sync> ResolverGeneric<Fs> {
       pnp::resolve_to_unqualified_via_manifest(manifest, specifier, path.as_std_path());
 
     tracing::debug!("pnp resolve unqualified as: {:?}", resolution);
+    ctx.add_trace_step(|| TraceStep::Pnp {
+      manifest: manifest_path.clone(),
+      resolved: match &resolution {
+        Ok(pnp::Resolution::Resolved(path, _)) => Some(path.clone()),
+        _ => None,
+      },
+    });
 
     match resolution {
       Ok(pnp::Resolution::Resolved(path, subpath)) => {
@@ -1297,6 +1389,11 @@ impl<Fs: FileSystem + Send + Sync> ResolverGeneric<Fs> {
       }
       return Err(ResolveError::Recursion);
     }
+    ctx.add_trace_step(|| TraceStep::AliasField {
+      package_json: package_json.path.clone(),
+      request: module_specifier.unwrap_or(path.as_str()).to_string(),
+      target: new_specifier.to_string(),
+    });
     ctx.with_resolving_alias(new_specifier.to_string());
     ctx.with_fully_specified(false);
     let cached_path = self
@@ -1333,6 +1430,11 @@ impl<Fs: FileSystem + Send + Sync> ResolverGeneric<Fs> {
       for r in specifiers {
         match r {
           AliasValue::Path(alias_value) => {
+            ctx.add_trace_step(|| TraceStep::Alias {
+              key: alias_key.to_string(),
+              target: alias_value.clone(),
+              fallback: std::ptr::eq(trie, &self.fallback_trie),
+            });
             if let Some(path) = self
               .load_alias_value(
                 cached_path,
@@ -1439,6 +1541,10 @@ impl<Fs: FileSystem + Send + Sync> ResolverGeneric<Fs> {
     };
     let path_without_extension = path.with_extension("");
 
+    ctx.add_trace_step(|| TraceStep::ExtensionAlias {
+      path: path.to_path_buf().into_std_path_buf(),
+      extensions: extensions.clone(),
+    });
     ctx.with_fully_specified(true);
     for extension in extensions {
       let mut path_with_extension = path_without_extension.clone().into_string();
@@ -1489,6 +1595,7 @@ impl<Fs: FileSystem + Send + Sync> ResolverGeneric<Fs> {
     }
     if let Some(specifier) = specifier.strip_prefix(SLASH_START) {
       for root in &self.options.roots {
+        ctx.add_trace_step(|| TraceStep::Root { path: root.clone() });
         let cached_path = self
           .cache
           .value(Utf8Path::from_path(root).expect("path should be UTF-8"));
@@ -1522,6 +1629,14 @@ impl<Fs: FileSystem + Send + Sync> ResolverGeneric<Fs> {
       }
     }
     let paths = tsconfig.resolve(cached_path.path(), specifier);
+    if !paths.is_empty() {
+      ctx.add_trace_step(|| TraceStep::TsconfigPaths {
+        paths: paths
+          .iter()
+          .map(|path| path.clone().into_std_path_buf())
+          .collect(),
+      });
+    }
     for path in paths {
       let cached_path = self.cache.value(&path);
       if let Ok(path) = self.require_relative(&cached_path, ".", ctx).await {
@@ -1531,6 +1646,54 @@ impl<Fs: FileSystem + Send + Sync> ResolverGeneric<Fs> {
     Ok(None)
   }
 
+  async fn load_import_map(
+    &self,
+    cached_path: &CachedPath,
+    specifier: &str,
+    ctx: &mut Ctx,
+  ) -> ResolveResult {
+    let Some(import_map_path) = &self.options.import_map else {
+      return Ok(None);
+    };
+    if ctx.import_map_applied {
+      return Ok(None);
+    }
+    // Only the import maps of UTF-8 paths can be loaded
+    let Some(utf8_import_map_path) = Utf8Path::from_path(import_map_path) else {
+      return Err(ResolveError::ImportMapNotFound(import_map_path.clone()));
+    };
+    let import_map = self.cache.import_map(utf8_import_map_path).await?;
+    ctx.add_file_dependency(import_map.path());
+    let Some(matched) = import_map.resolve(cached_path.path(), specifier) else {
+      return Ok(None);
+    };
+    let Some(address) = matched.address else {
+      return Err(ResolveError::ImportMapBlocked(
+        specifier.to_string(),
+        import_map_path.clone(),
+      ));
+    };
+    ctx.add_trace_step(|| TraceStep::ImportMap {
+      import_map: import_map_path.clone(),
+      key: matched.key.to_string(),
+      target: address.clone(),
+    });
+    ctx.with_import_map_applied();
+    // A mapped specifier is never looked up in node_modules.
+    // Boxed to keep the future of `require_without_parse` small for deep recursions.
+    let result = if is_path_address(&address) {
+      let cached_path = self.cache.value(Utf8Path::new(&address));
+      Box::pin(self.require_relative(&cached_path, ".", ctx)).await
+    } else {
+      self.require(cached_path, &address, ctx).await
+    };
+    match result {
+      Ok(path) => Ok(Some(path)),
+      Err(ResolveError::NotFound(_)) => Err(ResolveError::NotFound(specifier.to_string())),
+      Err(err) => Err(err),
+    }
+  }
+
   #[cfg_attr(feature="enable_instrument", tracing::instrument(level=tracing::Level::DEBUG, skip(self), fields(path = path.as_str())))]
   fn load_tsconfig<'a>(
     &'a self,
@@ -1746,6 +1909,9 @@ impl<Fs: FileSystem + Send + Sync> ResolverGeneric<Fs> {
         else {
           continue;
         };
+        ctx.add_trace_step(|| TraceStep::ModuleDirectory {
+          path: cached_path.to_path_buf().into_std_path_buf(),
+        });
         if let Some(path) = self
           .package_resolve_in_dir(&cached_path, package_name, subpath, ctx)
           .await?
@@ -1763,6 +1929,7 @@ impl<Fs: FileSystem + Send + Sync> ResolverGeneric<Fs> {
       if !cached_path.is_dir(&self.cache.fs, ctx).await {
         continue;
       }
+      ctx.add_trace_step(|| TraceStep::ModuleDirectory { path: dir.clone() });
       if let Some(path) = self
         .package_resolve_in_dir(&cached_path, package_name, subpath, ctx)
         .await?
@@ -1825,6 +1992,19 @@ impl<Fs: FileSystem + Send + Sync> ResolverGeneric<Fs> {
     ctx: &'a mut Ctx,
   ) -> BoxFuture<'a, ResolveResult> {
     let fut = async move {
+      ctx.add_trace_step(|| TraceStep::PackageField {
+        field: PackageField::Exports,
+        package_json: package_url.join("package.json").into(),
+        request: subpath.to_string(),
+        keys: match exports {
+          JSONValue::Object(map) => map
+            .keys()
+            .filter(|key| key.starts_with('.'))
+            .map(|key| key.to_string())
+            .collect(),
+          _ => vec![],
+        },
+      });
       let conditions = &self.options.condition_names;
       // 1. If exports is an Object with both a key starting with "." and a key not starting with ".", throw an Invalid Package Configuration error.
       if let JSONValue::Object(map) = exports {
@@ -1961,6 +2141,12 @@ impl<Fs: FileSystem + Send + Sync> ResolverGeneric<Fs> {
           ));
         }
       }
+      ctx.add_trace_step(|| TraceStep::PackageField {
+        field: PackageField::Imports,
+        package_json: package_json.path.clone(),
+        request: specifier.to_string(),
+        keys: imports.keys().map(|key| key.to_string()).collect(),
+      });
       if let Some(path) = self
         .package_imports_exports_resolve(
           specifier,
@@ -2132,6 +2318,10 @@ impl<Fs: FileSystem + Send + Sync> ResolverGeneric<Fs> {
             // 2. If patternMatch is a String, then
             //   1. Return PACKAGE_RESOLVE(target with every instance of "*" replaced by patternMatch, packageURL + "/").
             let target = normalize_string_target(target_key, target, pattern_match, package_url)?;
+            ctx.add_trace_step(|| TraceStep::PackageTarget {
+              key: target_key.to_string(),
+              target: target.to_string(),
+            });
             let package_url = self.cache.value(package_url);
             // // 3. Return PACKAGE_RESOLVE(target, packageURL + "/").
             return self.package_resolve(&package_url, &target, ctx).await;
@@ -2142,6 +2332,10 @@ impl<Fs: FileSystem + Send + Sync> ResolverGeneric<Fs> {
           // 4. Assert: resolvedTarget is contained in packageURL.
           // 5. If patternMatch is null, then
           let target = normalize_string_target(target_key, target, pattern_match, package_url)?;
+          ctx.add_trace_step(|| TraceStep::PackageTarget {
+            key: target_key.to_string(),
+            target: target.to_string(),
+          });
           if Utf8Path::new(target.as_ref()).is_invalid_exports_target() {
             return Err(ResolveError::InvalidPackageTarget(
               target.to_string(),
@@ -2161,8 +2355,13 @@ impl<Fs: FileSystem + Send + Sync> ResolverGeneric<Fs> {
           // 2. For each property p of target, in object insertion order as,
           for (key, target_value) in target.iter() {
             let key = key.to_string();
+            let matched = key == "default" || conditions.contains(&key);
+            ctx.add_trace_step(|| TraceStep::Condition {
+              name: key.clone(),
+              matched,
+            });
             // 1. If p equals "default" or conditions contains an entry for p, then
-            if key == "default" || conditions.contains(&key) {
+            if matched {
               // 1. Let targetValue be the value of the p property in target.
               // 2. Let resolved be the result of PACKAGE_TARGET_RESOLVE( packageURL, targetValue, patternMatch, isImports, conditions).
               let resolved = self
@@ -20,6 +20,15 @@ pub struct ResolveOptions {
   /// Default `None`
   pub tsconfig: Option<TsconfigOptions>,
 
+  /// Path to a [WICG import map](https://github.com/WICG/import-maps) file with `imports` and `scopes`.
+  ///
+  /// The import map is applied with browser semantics after [ResolveOptions::alias] and before
+  /// node_modules lookup. Relative keys, scopes and addresses are resolved against the directory
+  /// of the import map, and scopes ending with `/` are matched against the directory of the request.
+  ///
+  /// Default `None`
+  pub import_map: Option<PathBuf>,
+
   /// Create aliases to import or require certain modules more easily.
   ///
   /// An alias is used to replace a whole path or part of a path.
@@ -499,6 +508,7 @@ impl Default for ResolveOptions {
   fn default() -> Self {
     Self {
       tsconfig: None,
+      import_map: None,
       alias: vec![],
       alias_fields: vec![],
       condition_names: vec![],
@@ -533,6 +543,9 @@ impl fmt::Display for ResolveOptions {
     if let Some(tsconfig) = &self.tsconfig {
       write!(f, "tsconfig:{tsconfig:?},")?;
     }
+    if let Some(import_map) = &self.import_map {
+      write!(f, "import_map:{import_map:?},")?;
+    }
     if !self.alias.is_empty() {
       write!(f, "alias:{:?},", self.alias)?;
     }
@@ -676,6 +689,7 @@ mod test {
       roots: vec![],
       symlinks: false,
       tsconfig: None,
+      import_map: None,
       node_path: false,
     };
 
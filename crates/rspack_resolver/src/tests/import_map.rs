@@ -0,0 +1,135 @@
+//! Tests for [crate::ResolveOptions::import_map]
+
+use crate::{AliasValue, ResolveContext, ResolveError, ResolveOptions, Resolver};
+
+#[tokio::test]
+async fn import_map() {
+  let f = super::fixture_root().join("import-map");
+  let resolver = Resolver::new(ResolveOptions {
+    import_map: Some(f.join("importmap.json")),
+    ..ResolveOptions::default()
+  });
+
+  let legacy_react = f.join("lib/react-legacy.js");
+  let legacy_react = legacy_react.to_str().expect("should be UTF-8");
+
+  #[rustfmt::skip]
+  let pass = [
+    ("exact key before node_modules", f.clone(), "react", f.join("lib/react.js")),
+    ("trailing slash prefix", f.clone(), "utils/a.js", f.join("lib/utils/a.js")),
+    ("scope of the directory", f.join("legacy/nested"), "react", f.join("lib/react-legacy.js")),
+    ("scope of another directory", f.join("src"), "react", f.join("lib/react.js")),
+    ("relative key", f.join("src"), "./config.js", f.join("lib/config.js")),
+    ("url key", f.clone(), "https://cdn.example.com/b.js", f.join("lib/cdn/b.js")),
+    ("unmapped", f.join("src"), "./index.js", f.join("src/index.js")),
+    ("address relative to the import map directory", f.clone(), "root/a.js", f.join("lib/utils/a.js")),
+    ("key relative to the import map directory", f.clone(), legacy_react, f.join("lib/react.js")),
+  ];
+
+  for (comment, path, request, expected) in pass {
+    let resolved_path = resolver
+      .resolve(&path, request)
+      .await
+      .map(|r| r.full_path());
+    assert_eq!(resolved_path, Ok(expected), "{comment} {request}");
+  }
+
+  #[rustfmt::skip]
+  let fail = [
+    ("prefix mapped to a file", "blocked/a.js", ResolveError::ImportMapBlocked("blocked/a.js".into(), f.join("importmap.json"))),
+    ("bare address", "bare", ResolveError::ImportMapBlocked("bare".into(), f.join("importmap.json"))),
+    ("backtracking", "utils/../../importmap.json", ResolveError::ImportMapBlocked("utils/../../importmap.json".into(), f.join("importmap.json"))),
+    ("mapped to a missing file", "utils/missing.js", ResolveError::NotFound("utils/missing.js".into())),
+  ];
+
+  for (comment, request, expected) in fail {
+    let resolution = resolver.resolve(&f, request).await;
+    assert_eq!(resolution, Err(expected), "{comment} {request}");
+  }
+}
+
+#[tokio::test]
+async fn import_map_dependencies() {
+  let f = super::fixture_root().join("import-map");
+  let resolver = Resolver::new(ResolveOptions {
+    import_map: Some(f.join("importmap.json")),
+    ..ResolveOptions::default()
+  });
+
+  let mut ctx = ResolveContext::default();
+  let resolved_path = resolver
+    .resolve_with_context(&f, "react", &mut ctx)
+    .await
+    .map(|r| r.full_path());
+  assert_eq!(resolved_path, Ok(f.join("lib/react.js")));
+  assert!(
+    ctx
+      .file_dependencies
+      .iter()
+      .any(|dependency| dependency.as_ref() == f.join("importmap.json"))
+  );
+}
+
+#[tokio::test]
+async fn import_map_applied_once() {
+  let f = super::fixture_root().join("import-map");
+  let resolver = Resolver::new(ResolveOptions {
+    import_map: Some(f.join("self-prefixed.json")),
+    fallback: vec![(
+      "https://cdn.example.com/v2".into(),
+      vec![AliasValue::Path(
+        f.join("lib/cdn").to_string_lossy().to_string(),
+      )],
+    )],
+    ..ResolveOptions::default()
+  });
+
+  // The address matches its own key, and is resolved by the fallback without the import map
+  let resolved_path = resolver
+    .resolve(&f, "https://cdn.example.com/b.js")
+    .await
+    .map(|r| r.full_path());
+  assert_eq!(resolved_path, Ok(f.join("lib/cdn/b.js")));
+}
+
+#[tokio::test]
+async fn import_map_not_found() {
+  let f = super::fixture_root().join("import-map");
+  let resolver = Resolver::new(ResolveOptions {
+    import_map: Some(f.join("missing.json")),
+    ..ResolveOptions::default()
+  });
+
+  let resolution = resolver.resolve(&f, "react").await;
+  assert_eq!(
+    resolution,
+    Err(ResolveError::ImportMapNotFound(f.join("missing.json")))
+  );
+}
+
+#[test]
+fn import_map_without_directory() {
+  let path = camino::Utf8Path::new("/");
+  assert_eq!(
+    crate::import_map::ImportMap::parse(path, "{}").map(|_| ()),
+    Err(ResolveError::ImportMapNotFound(
+      path.as_std_path().to_path_buf()
+    ))
+  );
+}
+
+#[cfg(unix)]
+#[tokio::test]
+async fn import_map_non_utf8_path() {
+  use std::{ffi::OsStr, os::unix::ffi::OsStrExt};
+
+  let f = super::fixture_root().join("import-map");
+  let import_map = f.join(OsStr::from_bytes(b"importmap-\xff.json"));
+  let resolver = Resolver::new(ResolveOptions {
+    import_map: Some(import_map.clone()),
+    ..ResolveOptions::default()
+  });
+
+  let resolution = resolver.resolve(&f, "react").await;
+  assert_eq!(resolution, Err(ResolveError::ImportMapNotFound(import_map)));
+}
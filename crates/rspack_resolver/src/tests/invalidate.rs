@@ -0,0 +1,162 @@
+//! Tests for [crate::Resolver::invalidate]
+
+use std::{
+  fs,
+  path::{Path, PathBuf},
+};
+
+use crate::{ResolveError, ResolveOptions, Resolver, TsconfigOptions, TsconfigReferences};
+
+/// A fresh directory in the temp directory, `None` when it cannot be created.
+fn fixture(name: &str, files: &[(&str, &str)]) -> Option<PathBuf> {
+  let root = std::env::temp_dir().join(name);
+  _ = fs::remove_dir_all(&root);
+  fs::create_dir_all(&root).ok()?;
+  // `/var` is a symlink on macOS
+  let root = root.canonicalize().ok()?;
+  for (path, content) in files {
+    write(&root.join(path), content);
+  }
+  Some(root)
+}
+
+fn write(path: &Path, content: &str) {
+  fs::create_dir_all(path.parent().unwrap()).unwrap();
+  fs::write(path, content).unwrap();
+}
+
+async fn resolve(
+  resolver: &Resolver,
+  root: &Path,
+  specifier: &str,
+) -> Result<PathBuf, ResolveError> {
+  resolver
+    .resolve(root, specifier)
+    .await
+    .map(|resolution| resolution.into_path_buf())
+}
+
+#[tokio::test]
+async fn package_json() {
+  let Some(f) = fixture(
+    "rspack_resolver_invalidate_package_json",
+    &[
+      ("node_modules/pkg/package.json", r#"{ "main": "a.js" }"#),
+      ("node_modules/pkg/a.js", ""),
+      ("node_modules/pkg/b.js", ""),
+    ],
+  ) else {
+    return;
+  };
+  let resolver = Resolver::default();
+  assert_eq!(
+    resolve(&resolver, &f, "pkg").await,
+    Ok(f.join("node_modules/pkg/a.js"))
+  );
+
+  write(
+    &f.join("node_modules/pkg/package.json"),
+    r#"{ "main": "b.js" }"#,
+  );
+  // Unrelated paths keep the cache warm
+  resolver.invalidate([f.join("index.js")]);
+  assert_eq!(
+    resolve(&resolver, &f, "pkg").await,
+    Ok(f.join("node_modules/pkg/a.js"))
+  );
+
+  resolver.invalidate([f.join("node_modules/pkg/package.json")]);
+  assert_eq!(
+    resolve(&resolver, &f, "pkg").await,
+    Ok(f.join("node_modules/pkg/b.js"))
+  );
+}
+
+#[tokio::test]
+async fn added_and_removed_files() {
+  let Some(f) = fixture(
+    "rspack_resolver_invalidate_added_and_removed_files",
+    &[("a.js", "")],
+  ) else {
+    return;
+  };
+  let resolver = Resolver::default();
+  assert_eq!(resolve(&resolver, &f, "./a").await, Ok(f.join("a.js")));
+  assert_eq!(
+    resolve(&resolver, &f, "new-pkg").await,
+    Err(ResolveError::NotFound("new-pkg".into()))
+  );
+
+  // `node_modules` and `new-pkg` were cached as missing
+  write(&f.join("node_modules/new-pkg/index.js"), "");
+  fs::remove_file(f.join("a.js")).unwrap();
+  resolver.invalidate([f.join("node_modules/new-pkg/index.js"), f.join("a.js")]);
+  assert_eq!(
+    resolve(&resolver, &f, "new-pkg").await,
+    Ok(f.join("node_modules/new-pkg/index.js"))
+  );
+  assert_eq!(
+    resolve(&resolver, &f, "./a").await,
+    Err(ResolveError::NotFound("./a".into()))
+  );
+}
+
+#[tokio::test]
+async fn removed_directory() {
+  let Some(f) = fixture(
+    "rspack_resolver_invalidate_removed_directory",
+    &[("lib/index.js", "")],
+  ) else {
+    return;
+  };
+  let resolver = Resolver::default();
+  assert_eq!(
+    resolve(&resolver, &f, "./lib").await,
+    Ok(f.join("lib/index.js"))
+  );
+
+  fs::remove_dir_all(f.join("lib")).unwrap();
+  resolver.invalidate([f.join("lib")]);
+  assert_eq!(
+    resolve(&resolver, &f, "./lib").await,
+    Err(ResolveError::NotFound("./lib".into()))
+  );
+}
+
+#[tokio::test]
+async fn tsconfig() {
+  let Some(f) = fixture(
+    "rspack_resolver_invalidate_tsconfig",
+    &[
+      (
+        "tsconfig.json",
+        r#"{ "compilerOptions": { "paths": { "@/*": ["./a/*"] } } }"#,
+      ),
+      ("a/index.js", ""),
+      ("b/index.js", ""),
+    ],
+  ) else {
+    return;
+  };
+  let resolver = Resolver::new(ResolveOptions {
+    tsconfig: Some(TsconfigOptions {
+      config_file: f.join("tsconfig.json"),
+      references: TsconfigReferences::Disabled,
+    }),
+    ..ResolveOptions::default()
+  });
+  assert_eq!(
+    resolve(&resolver, &f, "@/index").await,
+    Ok(f.join("a/index.js"))
+  );
+
+  write(
+    &f.join("tsconfig.json"),
+    r#"{ "compilerOptions": { "paths": { "@/*": ["./b/*"] } } }"#,
+  );
+  resolver.invalidate([f.join("tsconfig.json")]);
+  assert_eq!(
+    resolve(&resolver, &f, "@/index").await,
+    Ok(f.join("b/index.js"))
+  );
+}
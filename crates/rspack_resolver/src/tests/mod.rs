@@ -7,8 +7,10 @@ mod extension_alias;
 mod extensions;
 mod fallback;
 mod full_specified;
+mod import_map;
 mod imports_field;
 mod incorrect_description_file;
+mod invalidate;
 mod main_field;
 mod memory_fs;
 mod missing;
@@ -23,6 +25,7 @@ mod roots;
 mod scoped_packages;
 mod simple;
 mod symlink;
+mod trace;
 mod tsconfig_paths;
 mod tsconfig_project_references;
 #[cfg(windows)]
@@ -0,0 +1,106 @@
+use crate::{PackageField, Rejection, ResolveError, ResolveOptions, Resolver, TraceStep};
+
+#[tokio::test]
+async fn records_candidates() {
+  let f = super::fixture();
+  let resolver = Resolver::new(ResolveOptions {
+    extensions: vec![".js".into()],
+    ..ResolveOptions::default()
+  });
+
+  let (result, trace) = resolver.resolve_with_trace(&f, "./missing-file").await;
+  assert_eq!(result, Err(ResolveError::NotFound("./missing-file".into())));
+  assert!(trace.steps.contains(&TraceStep::Candidate {
+    path: f.join("missing-file.js"),
+    rejection: Some(Rejection::NotFound),
+  }));
+  assert!(
+    trace
+      .to_string()
+      .starts_with(&format!("resolve './missing-file' in '{}'", f.display()))
+  );
+
+  let (result, trace) = resolver.resolve_with_trace(&f, "./a").await;
+  assert_eq!(result.map(|r| r.into_path_buf()), Ok(f.join("a.js")));
+  assert_eq!(
+    trace.steps.last(),
+    Some(&TraceStep::Candidate {
+      path: f.join("a.js"),
+      rejection: None,
+    })
+  );
+}
+
+#[tokio::test]
+async fn records_exports_conditions() {
+  let f = super::fixture().join("exports-field");
+  let resolver = Resolver::new(ResolveOptions {
+    condition_names: vec!["node".into()],
+    ..ResolveOptions::default()
+  });
+
+  let (result, trace) = resolver
+    .resolve_with_trace(&f, "exports-field/dist/main.js")
+    .await;
+  assert_eq!(
+    result.map(|r| r.into_path_buf()),
+    Ok(f.join("node_modules/exports-field/lib/main.js"))
+  );
+  assert!(trace.steps.iter().any(|step| matches!(
+    step,
+    TraceStep::PackageField {
+      field: PackageField::Exports,
+      request,
+      ..
+    } if request == "./dist/main.js"
+  )));
+  let conditions = trace
+    .steps
+    .iter()
+    .filter_map(|step| match step {
+      TraceStep::Condition { name, matched } => Some((name.as_str(), *matched)),
+      _ => None,
+    })
+    .collect::<Vec<_>>();
+  assert_eq!(conditions, [("webpack", false), ("node", true)]);
+}
+
+#[tokio::test]
+async fn suggestions() {
+  let f = super::fixture().join("exports-field");
+  let resolver = Resolver::new(ResolveOptions {
+    condition_names: vec!["webpack".into()],
+    ..ResolveOptions::default()
+  });
+  let (result, trace) = resolver
+    .resolve_with_trace(&f, "exports-field/dst/a.js")
+    .await;
+  assert!(matches!(
+    result,
+    Err(ResolveError::PackagePathNotExported(..))
+  ));
+  assert_eq!(trace.suggestions(&[]), ["exports-field/dist/a.js"]);
+
+  let f = super::fixture().join("imports-field");
+  let (result, trace) = resolver.resolve_with_trace(&f, "#imports-feld").await;
+  assert!(matches!(
+    result,
+    Err(ResolveError::PackageImportNotDefined(..))
+  ));
+  assert_eq!(trace.suggestions(&[]), ["#imports-field"]);
+
+  let f = super::fixture();
+  let (result, trace) = resolver.resolve_with_trace(&f, "dash-nam/index").await;
+  assert_eq!(result, Err(ResolveError::NotFound("dash-nam/index".into())));
+  assert!(
+    trace
+      .module_directories()
+      .any(|path| path == f.join("node_modules"))
+  );
+  let packages = [
+    "dash".to_string(),
+    "dash-name".to_string(),
+    "m1".to_string(),
+  ];
+  assert_eq!(trace.suggestions(&packages), ["dash-name/index"]);
+}
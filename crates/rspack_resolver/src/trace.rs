@@ -0,0 +1,344 @@
+use std::{
+  fmt,
+  path::{Path, PathBuf},
+};
+
+use crate::ResolverGeneric;
+
+/// Trace returned from the [ResolverGeneric::resolve_with_trace] API
+///
+/// Records every step of a resolution, to explain why a request resolved to a file
+/// or why it failed.
+#[derive(Debug, Default, Clone)]
+pub struct ResolveTrace {
+  /// The directory the specifier was resolved in
+  pub directory: PathBuf,
+
+  /// The resolved specifier
+  pub specifier: String,
+
+  /// The steps of the resolution, in order
+  pub steps: Vec<TraceStep>,
+}
+
+/// A step of a resolution recorded in a [ResolveTrace]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum TraceStep {
+  /// A key of [crate::ResolveOptions::alias] or [crate::ResolveOptions::fallback]
+  /// matched the specifier, which is resolved with `target` instead.
+  Alias {
+    key: String,
+    target: String,
+    fallback: bool,
+  },
+
+  /// The `key` of the import map matched the specifier, which is resolved with `target`.
+  ImportMap {
+    import_map: PathBuf,
+    key: String,
+    target: String,
+  },
+
+  /// The `paths` of the tsconfig mapped the specifier to these paths.
+  TsconfigPaths { paths: Vec<PathBuf> },
+
+  /// A field of [crate::ResolveOptions::alias_fields], like `browser`, mapped `request`
+  /// to `target`.
+  AliasField {
+    package_json: PathBuf,
+    request: String,
+    target: String,
+  },
+
+  /// The `exports` or `imports` field of a description file was looked up for `request`.
+  ///
+  /// `keys` are the subpaths or the imports defined in the field.
+  PackageField {
+    field: PackageField,
+    package_json: PathBuf,
+    request: String,
+    keys: Vec<String>,
+  },
+
+  /// A condition of a target of the `exports` or `imports` field, matched when it is
+  /// `default` or in [crate::ResolveOptions::condition_names].
+  Condition { name: String, matched: bool },
+
+  /// The `exports` or `imports` field mapped `key` to `target`.
+  PackageTarget { key: String, target: String },
+
+  /// A field of [crate::ResolveOptions::main_fields] points to `target`.
+  MainField {
+    package_json: PathBuf,
+    target: String,
+  },
+
+  /// [crate::ResolveOptions::extension_alias] replaced the extension of `path`.
+  ExtensionAlias {
+    path: PathBuf,
+    extensions: Vec<String>,
+  },
+
+  /// The extensions were not tried for `path`, as the request is fully specified.
+  FullySpecified { path: PathBuf },
+
+  /// The specifier was resolved in a directory of [crate::ResolveOptions::roots].
+  Root { path: PathBuf },
+
+  /// A directory of [crate::ResolveOptions::modules] was searched.
+  ModuleDirectory { path: PathBuf },
+
+  /// The Yarn Plug'n'Play manifest was used, and resolved the package to `resolved`.
+  Pnp {
+    manifest: PathBuf,
+    resolved: Option<PathBuf>,
+  },
+
+  /// A path tried as the file of the resolution, `rejection` is why it was not used.
+  Candidate {
+    path: PathBuf,
+    rejection: Option<Rejection>,
+  },
+}
+
+/// The fields of a description file mapping requests with conditions
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PackageField {
+  Exports,
+  Imports,
+}
+
+/// Why a candidate path of a [TraceStep::Candidate] was rejected
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Rejection {
+  /// Nothing exists at the path
+  NotFound,
+  /// The path is a directory
+  Directory,
+  /// The path is excluded by [crate::ResolveOptions::restrictions]
+  Restricted,
+}
+
+impl ResolveTrace {
+  /// The directories of [crate::ResolveOptions::modules] which were searched.
+  pub fn module_directories(&self) -> impl Iterator<Item = &Path> {
+    self.steps.iter().filter_map(|step| match step {
+      TraceStep::ModuleDirectory { path } => Some(path.as_path()),
+      _ => None,
+    })
+  }
+
+  /// The requests close to the specifier which may have been meant, from the keys of
+  /// the `exports` and `imports` fields which were tried, and from `packages`, the names
+  /// of the packages in the [module directories](Self::module_directories).
+  pub fn suggestions(&self, packages: &[String]) -> Vec<String> {
+    let mut suggestions = vec![];
+    let mut push = |suggestion: String| {
+      if suggestion != self.specifier && !suggestions.contains(&suggestion) {
+        suggestions.push(suggestion);
+      }
+    };
+
+    let (package_name, subpath) =
+      ResolverGeneric::<crate::FileSystemOs>::parse_package_specifier(&self.specifier);
+    for step in &self.steps {
+      let TraceStep::PackageField {
+        field,
+        request,
+        keys,
+        ..
+      } = step
+      else {
+        continue;
+      };
+      let keys = keys
+        .iter()
+        .filter(|key| !key.contains('*') && !key.ends_with('/'));
+      for key in closest_matches(request, keys) {
+        match field {
+          PackageField::Imports => push(key.to_string()),
+          PackageField::Exports if !package_name.is_empty() => {
+            push(format!("{package_name}{}", &key[1..]))
+          }
+          PackageField::Exports => {}
+        }
+      }
+    }
+
+    // The package itself is missing
+    let is_bare = !package_name.is_empty() && !self.specifier.starts_with(['.', '/', '#']);
+    if is_bare
+      && self.module_directories().next().is_some()
+      && !packages.iter().any(|p| p == package_name)
+    {
+      for package in closest_matches(package_name, packages) {
+        push(format!("{package}{subpath}"));
+      }
+    }
+    suggestions
+  }
+}
+
+impl fmt::Display for ResolveTrace {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(
+      f,
+      "resolve '{}' in '{}'",
+      self.specifier,
+      self.directory.display()
+    )?;
+    for step in &self.steps {
+      write!(f, "\n  {step}")?;
+    }
+    Ok(())
+  }
+}
+
+impl fmt::Display for TraceStep {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      Self::Alias {
+        key,
+        target,
+        fallback,
+      } => {
+        let option = if *fallback { "fallback" } else { "alias" };
+        write!(f, "{option} '{key}' matched, trying '{target}'")
+      }
+      Self::ImportMap {
+        import_map,
+        key,
+        target,
+      } => write!(
+        f,
+        "import map {} matched '{key}', trying '{target}'",
+        import_map.display()
+      ),
+      Self::TsconfigPaths { paths } => {
+        write!(f, "tsconfig paths mapped to ")?;
+        write_list(f, paths.iter().map(|path| path.display()))
+      }
+      Self::AliasField {
+        package_json,
+        request,
+        target,
+      } => write!(
+        f,
+        "alias field of {} mapped '{request}' to '{target}'",
+        package_json.display()
+      ),
+      Self::PackageField {
+        field,
+        package_json,
+        request,
+        ..
+      } => {
+        let field = match field {
+          PackageField::Exports => "exports",
+          PackageField::Imports => "imports",
+        };
+        write!(
+          f,
+          "using {field} field of {} for '{request}'",
+          package_json.display()
+        )
+      }
+      Self::Condition { name, matched } => {
+        if *matched {
+          write!(f, "  condition '{name}' matched")
+        } else {
+          write!(f, "  condition '{name}' is not in the condition names")
+        }
+      }
+      Self::PackageTarget { key, target } => write!(f, "  '{key}' mapped to '{target}'"),
+      Self::MainField {
+        package_json,
+        target,
+      } => write!(
+        f,
+        "main field of {} points to '{target}'",
+        package_json.display()
+      ),
+      Self::ExtensionAlias { path, extensions } => {
+        write!(f, "extension alias of {} to ", path.display())?;
+        write_list(f, extensions)
+      }
+      Self::FullySpecified { path } => write!(
+        f,
+        "no extension tried for {}, the request is fully specified",
+        path.display()
+      ),
+      Self::Root { path } => write!(f, "using root {}", path.display()),
+      Self::ModuleDirectory { path } => write!(f, "looking for modules in {}", path.display()),
+      Self::Pnp { manifest, resolved } => match resolved {
+        Some(resolved) => write!(
+          f,
+          "pnp manifest {} resolved to {}",
+          manifest.display(),
+          resolved.display()
+        ),
+        None => write!(f, "pnp manifest {} skipped the request", manifest.display()),
+      },
+      Self::Candidate { path, rejection } => match rejection {
+        None => write!(f, "{} exists, using it", path.display()),
+        Some(Rejection::NotFound) => write!(f, "{} doesn't exist", path.display()),
+        Some(Rejection::Directory) => write!(f, "{} is a directory", path.display()),
+        Some(Rejection::Restricted) => {
+          write!(f, "{} is excluded by the restrictions", path.display())
+        }
+      },
+    }
+  }
+}
+
+fn write_list<T: fmt::Display>(
+  f: &mut fmt::Formatter<'_>,
+  items: impl IntoIterator<Item = T>,
+) -> fmt::Result {
+  for (i, item) in items.into_iter().enumerate() {
+    if i > 0 {
+      write!(f, ", ")?;
+    }
+    write!(f, "'{item}'")?;
+  }
+  Ok(())
+}
+
+/// The candidates within a few edits of `target`, closest first.
+fn closest_matches<'a, S: AsRef<str> + 'a>(
+  target: &str,
+  candidates: impl IntoIterator<Item = &'a S>,
+) -> Vec<&'a str> {
+  let max_distance = (target.chars().count() / 4).max(1);
+  let mut matches = candidates
+    .into_iter()
+    .map(AsRef::as_ref)
+    .filter_map(|candidate| {
+      let distance = edit_distance(target, candidate);
+      (distance <= max_distance).then_some((distance, candidate))
+    })
+    .collect::<Vec<_>>();
+  matches.sort_unstable();
+  matches
+    .into_iter()
+    .take(3)
+    .map(|(_, candidate)| candidate)
+    .collect()
+}
+
+/// Levenshtein distance between `a` and `b`.
+fn edit_distance(a: &str, b: &str) -> usize {
+  let b = b.chars().collect::<Vec<_>>();
+  let mut previous = (0..=b.len()).collect::<Vec<_>>();
+  let mut current = vec![0; b.len() + 1];
+  for (i, a) in a.chars().enumerate() {
+    current[0] = i + 1;
+    for (j, b) in b.iter().enumerate() {
+      let substitution = previous[j] + usize::from(a != *b);
+      current[j + 1] = substitution.min(previous[j + 1] + 1).min(current[j] + 1);
+    }
+    std::mem::swap(&mut previous, &mut current);
+  }
+  previous[b.len()]
+}
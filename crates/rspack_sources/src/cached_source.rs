@@ -8,7 +8,8 @@ use std::{
 use rustc_hash::FxHasher;
 
 use crate::{
-  BoxSource, MapOptions, RawBufferSource, Source, SourceExt, SourceMap,
+  BoxSource, ConcatSource, IndexSourceMap, MapOptions, RawBufferSource, Source, SourceExt,
+  SourceMap,
   helpers::{
     Chunks, GeneratedInfo, StreamChunks, TextSpan, stream_and_get_source_and_map,
     stream_chunks_of_raw_source, stream_chunks_of_source_map,
@@ -127,6 +128,12 @@ impl CachedSource {
       .map_cache(options)
       .get_or_init(|| self.inner.clone().map_static(object_pool, options))
   }
+
+  /// Whether [Source::index_map] is split into the sections of the children of the inner
+  /// [ConcatSource], rather than merging them into a cached map.
+  fn is_split_into_sections(&self, options: &MapOptions) -> bool {
+    self.map_cache(options).get().is_none() && self.inner.as_ref().as_any().is::<ConcatSource>()
+  }
 }
 
 impl Source for CachedSource {
@@ -196,6 +203,30 @@ impl Source for CachedSource {
       .map(|map| map.as_borrowed().into_static(self.clone()))
   }
 
+  fn index_map<'a>(
+    &'a self,
+    object_pool: &ObjectPool,
+    options: &MapOptions,
+  ) -> Option<IndexSourceMap<'a>> {
+    if self.is_split_into_sections(options) {
+      return self.inner.index_map(object_pool, options);
+    }
+    self.map(object_pool, options).map(IndexSourceMap::from)
+  }
+
+  fn index_map_static(
+    self: Arc<Self>,
+    object_pool: &ObjectPool,
+    options: &MapOptions,
+  ) -> Option<IndexSourceMap<'static>> {
+    if self.is_split_into_sections(options) {
+      return self.inner.clone().index_map_static(object_pool, options);
+    }
+    self
+      .map_static(object_pool, options)
+      .map(IndexSourceMap::from)
+  }
+
   fn to_writer(&self, writer: &mut dyn std::io::Write) -> std::io::Result<()> {
     self.inner.to_writer(writer)
   }
@@ -8,7 +8,8 @@ use std::{
 use rustc_hash::FxHashMap as HashMap;
 
 use crate::{
-  BoxSource, MapOptions, RawStringSource, Source, SourceExt, SourceMap, SourceValue,
+  BoxSource, IndexSourceMap, MapOptions, RawStringSource, Section, SectionOffset, Source,
+  SourceExt, SourceMap, SourceValue,
   helpers::{Chunks, GeneratedInfo, StreamChunks, get_map},
   linear_map::LinearMap,
   object_pool::ObjectPool,
@@ -234,6 +235,59 @@ impl Source for ConcatSource {
       .map(|map| map.into_static(owner))
   }
 
+  fn index_map<'a>(
+    &'a self,
+    object_pool: &ObjectPool,
+    options: &MapOptions,
+  ) -> Option<IndexSourceMap<'a>> {
+    let mut sections: Vec<Section<'a>> = Vec::new();
+    let mut offset = SectionOffset::default();
+    // Whether the last section ends in the middle of a line, the code following it on that
+    // line must not be covered by the section
+    let mut need_to_close_section = false;
+    for child in self.optimized_children() {
+      let start = offset;
+      offset = start.advance(SectionOffset::end_of(child.as_ref()));
+      match child.index_map(object_pool, options) {
+        Some(index_map) => {
+          sections.extend(
+            index_map
+              .into_sections()
+              .into_iter()
+              .map(|section| Section {
+                offset: start.advance(section.offset),
+                map: section.map,
+              }),
+          );
+          need_to_close_section = offset.column != 0;
+        }
+        None if start != offset => {
+          if need_to_close_section {
+            sections.push(Section {
+              offset: start,
+              map: SourceMap::empty(),
+            });
+          }
+          need_to_close_section = false;
+        }
+        None => {}
+      }
+    }
+    (!sections.is_empty()).then(|| IndexSourceMap::new(sections))
+  }
+
+  fn index_map_static(
+    self: Arc<Self>,
+    object_pool: &ObjectPool,
+    options: &MapOptions,
+  ) -> Option<IndexSourceMap<'static>> {
+    let owner = self.clone();
+    self
+      .as_ref()
+      .index_map(object_pool, options)
+      .map(|map| map.into_static(owner))
+  }
+
   fn to_writer(&self, writer: &mut dyn std::io::Write) -> std::io::Result<()> {
     for child in self.optimized_children() {
       child.to_writer(writer)?;
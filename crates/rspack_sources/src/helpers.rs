@@ -379,6 +379,19 @@ pub fn utf8_column_to_utf16_column(line: &str, utf8_column: usize) -> Option<usi
   line.get(..utf8_column).map(utf16_len)
 }
 
+/// The byte index of `utf16_column` in `line`, `None` when it is past the end of the line or
+/// in the middle of a character.
+pub(crate) fn utf16_column_to_utf8_column(line: &str, utf16_column: usize) -> Option<usize> {
+  let mut units = 0;
+  for (index, ch) in line.char_indices() {
+    if units >= utf16_column {
+      return (units == utf16_column).then_some(index);
+    }
+    units += ch.len_utf16();
+  }
+  (units == utf16_column).then_some(line.len())
+}
+
 pub struct PotentialTokens<'a> {
   text: &'a str,
 }
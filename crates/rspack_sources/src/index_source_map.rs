@@ -0,0 +1,476 @@
+use std::borrow::Cow;
+
+use rustc_hash::FxHashMap as HashMap;
+use serde::Serialize;
+use simd_json::{BorrowedValue, ErrorType, prelude::*};
+
+use crate::{
+  BoxSource, Result, Source, SourceMap, SourceMapFields,
+  encoder::create_encoder,
+  helpers::utf16_len,
+  source::{Mapping, OriginalLocation, deserialize_source_map_fields, optional_string_field},
+};
+
+/// The position in the generated code where a [Section] starts.
+///
+/// Both the line and the column are zero-based, the column is counted in UTF-16 code units.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+pub struct SectionOffset {
+  /// Generated line.
+  pub line: u32,
+  /// Generated column.
+  pub column: u32,
+}
+
+impl SectionOffset {
+  /// Move the offset past `other`, a position relative to this offset.
+  pub(crate) fn advance(self, other: SectionOffset) -> SectionOffset {
+    if other.line == 0 {
+      SectionOffset {
+        line: self.line,
+        column: self.column + other.column,
+      }
+    } else {
+      SectionOffset {
+        line: self.line + other.line,
+        column: other.column,
+      }
+    }
+  }
+
+  /// The position right after the end of `source`, relative to its start.
+  pub(crate) fn end_of(source: &dyn Source) -> SectionOffset {
+    let mut end = SectionOffset::default();
+    source.rope(&mut |chunk| {
+      let last_line = match memchr::memrchr(b'\n', chunk.as_bytes()) {
+        Some(pos) => {
+          end.line += memchr::memchr_iter(b'\n', chunk.as_bytes()).count() as u32;
+          end.column = 0;
+          &chunk[pos + 1..]
+        }
+        None => chunk,
+      };
+      end.column += utf16_len(last_line) as u32;
+    });
+    end
+  }
+}
+
+/// A [SourceMap] covering the generated code from its `offset` until the next section.
+///
+/// The generated positions of the mappings are relative to the offset: the columns of the
+/// first line are added to `offset.column`, and the lines are added to `offset.line`.
+#[derive(Debug, Serialize)]
+pub struct Section<'a> {
+  /// Where the section starts in the generated code.
+  pub offset: SectionOffset,
+  /// The source map of the section.
+  pub map: SourceMap<'a>,
+}
+
+/// An index source map, a source map made of [Section]s.
+///
+/// - [Source map spec](https://tc39.es/ecma426/#sec-index-source-map).
+///
+/// Created by [Source::index_map], it reuses the maps of the parts of a concatenated
+/// source instead of re-encoding their mappings into a single map.
+///
+/// ```
+/// use rspack_sources::{
+///   ConcatSource, MapOptions, ObjectPool, OriginalSource, RawStringSource, Source, SourceExt,
+/// };
+///
+/// let source = ConcatSource::new([
+///   RawStringSource::from("/* header */\n").boxed(),
+///   OriginalSource::new("console.log('a');\n", "a.js").boxed(),
+///   OriginalSource::new("console.log('b');\n", "b.js").boxed(),
+/// ]);
+///
+/// let index_map = source
+///   .index_map(&ObjectPool::default(), &MapOptions::default())
+///   .unwrap();
+/// assert_eq!(index_map.sections().len(), 2);
+/// assert_eq!(index_map.sections()[1].offset.line, 2);
+/// assert_eq!(
+///   index_map.flatten(),
+///   source
+///     .map(&ObjectPool::default(), &MapOptions::default())
+///     .unwrap()
+/// );
+/// ```
+#[derive(Debug, Serialize)]
+pub struct IndexSourceMap<'a> {
+  version: u8,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  file: Option<Cow<'a, str>>,
+  sections: Vec<Section<'a>>,
+  #[serde(rename = "debugId", skip_serializing_if = "Option::is_none")]
+  debug_id: Option<Cow<'a, str>>,
+}
+
+impl<'a> IndexSourceMap<'a> {
+  /// Create an [IndexSourceMap] with sections sorted by their offsets.
+  pub fn new(sections: Vec<Section<'a>>) -> Self {
+    Self {
+      version: 3,
+      file: None,
+      sections,
+      debug_id: None,
+    }
+  }
+
+  /// Get the file field in [IndexSourceMap].
+  pub fn file(&self) -> Option<&str> {
+    self.file.as_deref()
+  }
+
+  /// Set the file field in [IndexSourceMap].
+  pub fn set_file(&mut self, file: Option<Cow<'a, str>>) {
+    self.file = file;
+  }
+
+  /// Get the debug_id field in [IndexSourceMap].
+  pub fn get_debug_id(&self) -> Option<&str> {
+    self.debug_id.as_deref()
+  }
+
+  /// Set the debug_id field in [IndexSourceMap].
+  pub fn set_debug_id(&mut self, debug_id: Option<Cow<'a, str>>) {
+    self.debug_id = debug_id;
+  }
+
+  /// Get the sections of [IndexSourceMap].
+  pub fn sections(&self) -> &[Section<'a>] {
+    &self.sections
+  }
+
+  /// Get the sections of [IndexSourceMap] to update their maps.
+  pub fn sections_mut(&mut self) -> &mut [Section<'a>] {
+    &mut self.sections
+  }
+
+  /// Consume the [IndexSourceMap] and return its sections.
+  pub fn into_sections(self) -> Vec<Section<'a>> {
+    self.sections
+  }
+
+  pub(crate) fn into_static(self, owner: BoxSource) -> IndexSourceMap<'static> {
+    IndexSourceMap {
+      version: self.version,
+      file: self.file.map(|file| Cow::Owned(file.into_owned())),
+      sections: self
+        .sections
+        .into_iter()
+        .map(|section| Section {
+          offset: section.offset,
+          map: section.map.into_static(owner.clone()),
+        })
+        .collect(),
+      debug_id: self
+        .debug_id
+        .map(|debug_id| Cow::Owned(debug_id.into_owned())),
+    }
+  }
+
+  /// Merge the sections into a single [SourceMap].
+  ///
+  /// The sources are deduplicated by name, a section's `sourceRoot` is prepended to its
+  /// sources. A mapping is inserted at the start of a section when the mapping of the
+  /// previous section would otherwise span into it.
+  pub fn flatten(&self) -> SourceMap<'_> {
+    let mut encoder = create_encoder(true);
+    let mut sources: Vec<Cow<'_, str>> = Vec::new();
+    let mut sources_content: Vec<Cow<'_, str>> = Vec::new();
+    let mut names: Vec<Cow<'_, str>> = Vec::new();
+    let mut ignore_list: Vec<u32> = Vec::new();
+    let mut source_mapping: HashMap<Cow<'_, str>, u32> = HashMap::default();
+    let mut name_mapping: HashMap<&str, u32> = HashMap::default();
+    let mut last_mapping: Option<Mapping> = None;
+
+    for Section { offset, map } in &self.sections {
+      let fields = map.fields();
+      let source_indices = fields
+        .sources()
+        .iter()
+        .enumerate()
+        .map(|(index, source)| {
+          let source = match fields.source_root() {
+            Some(root) if !root.is_empty() => {
+              let separator = if root.ends_with('/') { "" } else { "/" };
+              Cow::Owned(format!("{root}{separator}{source}"))
+            }
+            _ => Cow::Borrowed(source.as_ref()),
+          };
+          *source_mapping.entry(source.clone()).or_insert_with(|| {
+            let global_index = sources.len() as u32;
+            sources.push(source);
+            if let Some(content) = fields.get_source_content(index) {
+              sources_content.resize(global_index as usize, Cow::Borrowed(""));
+              sources_content.push(Cow::Borrowed(content.as_ref()));
+            }
+            if map
+              .ignore_list()
+              .is_some_and(|list| list.contains(&(index as u32)))
+            {
+              ignore_list.push(global_index);
+            }
+            global_index
+          })
+        })
+        .collect::<Vec<_>>();
+      let name_indices = fields
+        .names()
+        .iter()
+        .map(|name| {
+          *name_mapping.entry(name.as_ref()).or_insert_with(|| {
+            names.push(Cow::Borrowed(name.as_ref()));
+            names.len() as u32 - 1
+          })
+        })
+        .collect::<Vec<_>>();
+
+      let mut mappings = fields.decoded_mappings().peekable();
+      // Close the last mapping of the previous section, unless a mapping starts right here
+      let starts_at_offset = mappings
+        .peek()
+        .is_some_and(|first| first.generated_line == 1 && first.generated_column == 0);
+      if !starts_at_offset
+        && last_mapping
+          .take()
+          .is_some_and(|last| last.original.is_some() && last.generated_line == offset.line + 1)
+      {
+        encoder.encode(&Mapping {
+          generated_line: offset.line + 1,
+          generated_column: offset.column,
+          original: None,
+        });
+      }
+      for mapping in mappings {
+        let position = offset.advance(SectionOffset {
+          line: mapping.generated_line - 1,
+          column: mapping.generated_column,
+        });
+        let original = mapping.original.and_then(|original| {
+          Some(OriginalLocation {
+            source_index: *source_indices.get(original.source_index as usize)?,
+            original_line: original.original_line,
+            original_column: original.original_column,
+            name_index: original
+              .name_index
+              .and_then(|name_index| name_indices.get(name_index as usize).copied()),
+          })
+        });
+        let mapping = Mapping {
+          generated_line: position.line + 1,
+          generated_column: position.column,
+          original,
+        };
+        encoder.encode(&mapping);
+        last_mapping = Some(mapping);
+      }
+    }
+
+    let mut map = SourceMap::from_fields(SourceMapFields {
+      version: 3,
+      file: self.file.as_ref().map(|file| Cow::Borrowed(file.as_ref())),
+      sources: Cow::Owned(sources),
+      sources_content: Cow::Owned(sources_content),
+      names: Cow::Owned(names),
+      mappings: Cow::Owned(encoder.drain()),
+      source_root: None,
+      debug_id: self
+        .debug_id
+        .as_ref()
+        .map(|debug_id| Cow::Borrowed(debug_id.as_ref())),
+      ignore_list: None,
+    });
+    if !ignore_list.is_empty() {
+      map.set_ignore_list(Some(Cow::Owned(ignore_list)));
+    }
+    map
+  }
+
+  /// Generate the index source map to a json string.
+  pub fn to_json(&self) -> String {
+    simd_json::to_string(self).unwrap()
+  }
+}
+
+impl IndexSourceMap<'static> {
+  /// Create an [IndexSourceMap] from json string.
+  ///
+  /// A source map without `sections` is read as a single section.
+  pub fn from_json(s: String) -> Result<Self> {
+    let mut bytes = s.into_bytes();
+    let value = simd_json::to_borrowed_value(&mut bytes)?;
+    let object = value
+      .as_object()
+      .ok_or_else(|| simd_json::Error::generic(ErrorType::ExpectedMap))?;
+    let Some(sections) = object.get("sections") else {
+      let fields = deserialize_source_map_fields(&value)?;
+      return Ok(Self::from(SourceMap::from_fields(fields.into_owned())));
+    };
+    let sections = sections
+      .as_array()
+      .ok_or_else(|| simd_json::Error::generic(ErrorType::ExpectedArray))?
+      .iter()
+      .map(|section| {
+        let offset = section
+          .get("offset")
+          .ok_or_else(|| simd_json::Error::generic(ErrorType::ExpectedMap))?;
+        let position = |key: &str| {
+          offset
+            .get(key)
+            .and_then(BorrowedValue::as_u32)
+            .ok_or_else(|| simd_json::Error::generic(ErrorType::ExpectedUnsigned))
+        };
+        let offset = SectionOffset {
+          line: position("line")?,
+          column: position("column")?,
+        };
+        let map = section
+          .get("map")
+          .ok_or_else(|| simd_json::Error::generic(ErrorType::ExpectedMap))?;
+        let fields = deserialize_source_map_fields(map)?;
+        Ok(Section {
+          offset,
+          map: SourceMap::from_fields(fields.into_owned()),
+        })
+      })
+      .collect::<Result<Vec<_>>>()?;
+
+    let mut index_map = Self::new(sections);
+    index_map.file =
+      optional_string_field(object, "file")?.map(|file| Cow::Owned(file.into_owned()));
+    index_map.debug_id =
+      optional_string_field(object, "debugId")?.map(|debug_id| Cow::Owned(debug_id.into_owned()));
+    Ok(index_map)
+  }
+}
+
+impl<'a> From<SourceMap<'a>> for IndexSourceMap<'a> {
+  /// An [IndexSourceMap] of a single section starting at the beginning of the code.
+  fn from(map: SourceMap<'a>) -> Self {
+    Self::new(vec![Section {
+      offset: SectionOffset::default(),
+      map,
+    }])
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::{
+    CachedSource, ConcatSource, MapOptions, ObjectPool, OriginalSource, RawStringSource,
+    ReplaceSource, SourceExt,
+  };
+
+  fn concat() -> ConcatSource {
+    ConcatSource::new([
+      RawStringSource::from("(function() {\n").boxed(),
+      CachedSource::new(OriginalSource::new(
+        "const a = 1;\nconsole.log(a);\n",
+        "a.js",
+      ))
+      .boxed(),
+      RawStringSource::from("/* ü */ ").boxed(),
+      CachedSource::new(OriginalSource::new("export default b;", "b.js")).boxed(),
+      RawStringSource::from(";\n").boxed(),
+      CachedSource::new(OriginalSource::new("console.log(a);\n", "a.js")).boxed(),
+      RawStringSource::from("})();").boxed(),
+    ])
+  }
+
+  #[test]
+  fn should_split_concat_source_into_sections() {
+    let source = concat();
+    let index_map = source
+      .index_map(&ObjectPool::default(), &MapOptions::default())
+      .unwrap();
+    let offsets = index_map
+      .sections()
+      .iter()
+      .map(|section| (section.offset.line, section.offset.column))
+      .collect::<Vec<_>>();
+    // `ü` is a single UTF-16 code unit
+    assert_eq!(offsets, vec![(1, 0), (3, 8), (3, 25), (4, 0)]);
+    assert_eq!(
+      index_map.to_json(),
+      r#"{"version":3,"sections":[{"offset":{"line":1,"column":0},"map":{"version":3,"sources":["a.js"],"sourcesContent":["const a = 1;\nconsole.log(a);\n"],"names":[],"mappings":"AAAA;AACA"}},{"offset":{"line":3,"column":8},"map":{"version":3,"sources":["b.js"],"sourcesContent":["export default b;"],"names":[],"mappings":"AAAA"}},{"offset":{"line":3,"column":25},"map":{"version":3,"sources":[],"names":[],"mappings":""}},{"offset":{"line":4,"column":0},"map":{"version":3,"sources":["a.js"],"sourcesContent":["console.log(a);\n"],"names":[],"mappings":"AAAA"}}]}"#
+    );
+  }
+
+  #[test]
+  fn should_flatten_to_the_map_of_the_concat_source() {
+    let source = concat();
+    let index_map = source
+      .index_map(&ObjectPool::default(), &MapOptions::default())
+      .unwrap();
+    let map = source
+      .map(&ObjectPool::default(), &MapOptions::default())
+      .unwrap();
+    assert_eq!(index_map.flatten(), map);
+  }
+
+  #[test]
+  fn should_read_the_json_of_an_index_map() {
+    let source = concat();
+    let mut index_map = source
+      .index_map(&ObjectPool::default(), &MapOptions::default())
+      .unwrap();
+    index_map.set_file(Some("main.js".into()));
+    let json = index_map.to_json();
+    assert_eq!(
+      IndexSourceMap::from_json(json.clone()).unwrap().to_json(),
+      json
+    );
+
+    let source = OriginalSource::new("let a = 1;", "a.js");
+    let map = source
+      .map(&ObjectPool::default(), &MapOptions::default())
+      .unwrap();
+    let index_map = IndexSourceMap::from_json(map.to_json()).unwrap();
+    assert_eq!(index_map.sections().len(), 1);
+    assert_eq!(index_map.sections()[0].map, map);
+  }
+
+  #[test]
+  fn should_reuse_the_cached_maps_of_children() {
+    let child = CachedSource::new(OriginalSource::new("console.log(a);\n", "a.js")).boxed();
+    let source = CachedSource::new(ConcatSource::new([
+      RawStringSource::from("/* a */\n").boxed(),
+      child.clone(),
+    ]))
+    .boxed();
+    let object_pool = ObjectPool::default();
+    let options = MapOptions::default();
+
+    let index_map = source
+      .clone()
+      .index_map_static(&object_pool, &options)
+      .unwrap();
+    let cached_map = child.map(&object_pool, &options).unwrap();
+    assert!(std::ptr::eq(
+      index_map.sections()[0].map.mappings(),
+      cached_map.mappings()
+    ));
+  }
+
+  #[test]
+  fn should_keep_a_single_section_for_other_sources() {
+    let mut source = ReplaceSource::new(OriginalSource::new("let a = 1;", "a.js").boxed());
+    source.replace(4, 5, "b".to_string(), None);
+    let index_map = source
+      .index_map(&ObjectPool::default(), &MapOptions::default())
+      .unwrap();
+    assert_eq!(index_map.sections().len(), 1);
+    assert_eq!(index_map.sections()[0].offset, SectionOffset::default());
+    assert_eq!(
+      index_map.flatten(),
+      source
+        .map(&ObjectPool::default(), &MapOptions::default())
+        .unwrap()
+    );
+  }
+}
@@ -21,7 +21,9 @@ mod decoder;
 mod encoder;
 mod error;
 mod helpers;
+mod index_source_map;
 mod linear_map;
+mod listing;
 mod object_pool;
 mod original_source;
 mod raw_source;
@@ -29,6 +31,7 @@ mod replace_source;
 mod source;
 mod source_content_lines;
 mod source_map_source;
+mod validation;
 mod with_utf16;
 
 /// Feature for rspack persistent cache serialization/deserialization.
@@ -38,6 +41,8 @@ pub mod cacheable;
 pub use cached_source::CachedSource;
 pub use concat_source::ConcatSource;
 pub use error::{Error, Result};
+pub use index_source_map::{IndexSourceMap, Section, SectionOffset};
+pub use listing::render_listing;
 pub use original_source::OriginalSource;
 pub use raw_source::{RawBufferSource, RawStringSource};
 pub use replace_source::{ReplaceSource, Replacement, ReplacementEnforce};
@@ -46,6 +51,7 @@ pub use source::{
   BoxSource, MapOptions, Mapping, OriginalLocation, Source, SourceExt, SourceMap, SourceValue,
 };
 pub use source_map_source::{SourceMapSource, SourceMapSourceOptions, WithoutOriginalOptions};
+pub use validation::SourceMapIssue;
 
 /// Reexport `StreamChunks` related types.
 pub mod stream_chunks {
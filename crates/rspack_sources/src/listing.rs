@@ -0,0 +1,187 @@
+use std::fmt::Write;
+
+use crate::{
+  MapOptions, ObjectPool, OriginalLocation, Source, SourceMap,
+  helpers::{utf16_column_to_utf8_column, utf16_len},
+};
+
+/// The most characters of code shown in a row of a listing.
+const SNIPPET_WIDTH: usize = 40;
+
+/// Render the generated code of `source` next to the original code it is mapped to, one row
+/// per mapped segment, to debug its source map.
+///
+/// A row shows the generated position, the generated code until the next mapping, then the
+/// original position and the original code from there, or `-` when the code is not mapped.
+/// Segments of whitespace are left out.
+/// Lines are one-based and columns are zero-based, like the positions of a [crate::Mapping].
+///
+/// ```
+/// use rspack_sources::{ConcatSource, OriginalSource, RawStringSource, SourceExt, render_listing};
+///
+/// let source = ConcatSource::new([
+///   RawStringSource::from("/* a */\n").boxed(),
+///   OriginalSource::new("let a = 1;", "a.js").boxed(),
+/// ]);
+/// let listing = render_listing(&source);
+/// let rows = listing
+///   .lines()
+///   .map(|row| row.split_whitespace().collect::<Vec<_>>().join(" "))
+///   .collect::<Vec<_>>();
+/// assert_eq!(
+///   rows,
+///   ["1:0 /* a */ | -", "2:0 let a = 1; | a.js:1:0 let a = 1;"]
+/// );
+/// ```
+pub fn render_listing(source: &dyn Source) -> String {
+  let generated = source.source().into_string_lossy();
+  match source.map(&ObjectPool::default(), &MapOptions::default()) {
+    Some(map) => listing_of(&generated, &map),
+    None => listing_of(&generated, &SourceMap::empty()),
+  }
+}
+
+/// The listing of `generated` with the mappings of `map`.
+fn listing_of(generated: &str, map: &SourceMap) -> String {
+  let original_lines = map
+    .sources_content()
+    .iter()
+    .map(|content| content.split('\n').collect::<Vec<_>>())
+    .collect::<Vec<_>>();
+  let mut mappings = map.decoded_mappings().peekable();
+  let mut listing = String::new();
+  for (index, text) in generated.split('\n').enumerate() {
+    let line = index as u32 + 1;
+    // Skip the mappings past the end of the previous lines
+    while mappings
+      .next_if(|mapping| mapping.generated_line < line)
+      .is_some()
+    {}
+    let mut row = |start: u32, end: Option<u32>, original: Option<OriginalLocation>| {
+      let Some(code) = substring(text, start, end).filter(|code| !code.trim().is_empty()) else {
+        return;
+      };
+      let original = match original {
+        Some(original) => {
+          let source = map
+            .get_source(original.source_index as usize)
+            .unwrap_or("?");
+          // The original code as long as the generated code
+          let original_code = original_lines
+            .get(original.source_index as usize)
+            .and_then(|lines| lines.get(original.original_line.checked_sub(1)? as usize))
+            .and_then(|text| {
+              let end = original
+                .original_column
+                .saturating_add(utf16_len(code) as u32);
+              substring(text, original.original_column, Some(end))
+                .or_else(|| substring(text, original.original_column, None))
+            })
+            .unwrap_or_default();
+          format!(
+            "{source}:{}:{} {}",
+            original.original_line,
+            original.original_column,
+            snippet(original_code)
+          )
+        }
+        None => "-".to_string(),
+      };
+      _ = writeln!(
+        listing,
+        "{line:>6}:{start:<4} {:<width$} | {original}",
+        snippet(code),
+        width = SNIPPET_WIDTH + 1
+      );
+    };
+
+    let mut start = 0;
+    let mut original = None;
+    while let Some(mapping) = mappings.next_if(|mapping| mapping.generated_line == line) {
+      row(start, Some(mapping.generated_column), original);
+      start = mapping.generated_column;
+      original = mapping.original;
+    }
+    row(start, None, original);
+  }
+  listing
+}
+
+/// The part of `text` between the UTF-16 columns `start` and `end`, until the end of `text`
+/// when `end` is `None`.
+fn substring(text: &str, start: u32, end: Option<u32>) -> Option<&str> {
+  let start = utf16_column_to_utf8_column(text, start as usize)?;
+  let end = match end {
+    Some(end) => utf16_column_to_utf8_column(text, end as usize)?,
+    None => text.len(),
+  };
+  text.get(start..end)
+}
+
+fn snippet(code: &str) -> String {
+  let mut snippet = code
+    .chars()
+    .take(SNIPPET_WIDTH)
+    .map(|c| if c.is_control() { ' ' } else { c })
+    .collect::<String>();
+  if code.chars().nth(SNIPPET_WIDTH).is_some() {
+    snippet.push('…');
+  }
+  snippet
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::{OriginalSource, RawStringSource, ReplaceSource};
+
+  /// The rows of `listing` with their runs of whitespace collapsed.
+  fn rows(listing: &str) -> Vec<String> {
+    listing
+      .lines()
+      .map(|row| row.split_whitespace().collect::<Vec<_>>().join(" "))
+      .collect()
+  }
+
+  #[test]
+  fn should_render_a_row_per_mapped_segment() {
+    let mut source = ReplaceSource::new(OriginalSource::new("let a = b;\nlet c;", "a.js"));
+    source.replace(8, 9, "foo".to_string(), None);
+    assert_eq!(
+      rows(&render_listing(&source)),
+      [
+        "1:0 let a = | a.js:1:0 let a =",
+        "1:8 foo | a.js:1:8 b;",
+        "1:11 ; | a.js:1:9 ;",
+        "2:0 let c; | a.js:2:0 let c;",
+      ]
+    );
+  }
+
+  #[test]
+  fn should_render_unmapped_code() {
+    let listing = render_listing(&RawStringSource::from("a;\n\n  \nb;"));
+    assert_eq!(rows(&listing), ["1:0 a; | -", "4:0 b; | -"]);
+  }
+
+  #[test]
+  fn should_use_utf16_columns() {
+    let mut source = ReplaceSource::new(OriginalSource::new("\"魑魅\" + b", "a.js"));
+    // The replaced range is in bytes, the listing is in UTF-16 columns
+    source.replace(11, 12, "c".to_string(), None);
+    assert_eq!(
+      rows(&render_listing(&source)),
+      ["1:0 \"魑魅\" + | a.js:1:0 \"魑魅\" +", "1:7 c | a.js:1:7 b"]
+    );
+  }
+
+  #[test]
+  fn should_truncate_long_code() {
+    let code = "x".repeat(SNIPPET_WIDTH + 1);
+    let listing = render_listing(&RawStringSource::from(code));
+    assert_eq!(
+      rows(&listing),
+      [format!("1:0 {}… | -", "x".repeat(SNIPPET_WIDTH))]
+    );
+  }
+}
@@ -10,7 +10,7 @@ use serde::{Serialize, Serializer};
 use simd_json::{BorrowedValue, ErrorType, prelude::*};
 
 use crate::{
-  Result,
+  IndexSourceMap, Result,
   helpers::{Chunks, StreamChunks, decode_mappings_fields},
   object_pool::ObjectPool,
 };
@@ -130,6 +130,29 @@ pub trait Source: StreamChunks + DynHash + AsAny + DynEq + fmt::Debug + Sync + S
     options: &MapOptions,
   ) -> Option<SourceMap<'static>>;
 
+  /// Get the [IndexSourceMap], whose sections reuse the maps of the parts of the source
+  /// instead of merging them into a single map.
+  ///
+  /// Sources which are not made of other sources return a single section of [Source::map].
+  fn index_map<'a>(
+    &'a self,
+    object_pool: &ObjectPool,
+    options: &MapOptions,
+  ) -> Option<IndexSourceMap<'a>> {
+    self.map(object_pool, options).map(IndexSourceMap::from)
+  }
+
+  /// Get an [IndexSourceMap] that can outlive the borrowed source reference.
+  fn index_map_static(
+    self: Arc<Self>,
+    object_pool: &ObjectPool,
+    options: &MapOptions,
+  ) -> Option<IndexSourceMap<'static>> {
+    self
+      .map_static(object_pool, options)
+      .map(IndexSourceMap::from)
+  }
+
   /// Update hash based on the source.
   fn update_hash(&self, state: &mut dyn Hasher) {
     self.dyn_hash(state);
@@ -174,6 +197,24 @@ impl Source for BoxSource {
     self.as_ref().clone().map_static(object_pool, options)
   }
 
+  #[inline]
+  fn index_map<'a>(
+    &'a self,
+    object_pool: &ObjectPool,
+    options: &MapOptions,
+  ) -> Option<IndexSourceMap<'a>> {
+    self.as_ref().index_map(object_pool, options)
+  }
+
+  #[inline]
+  fn index_map_static(
+    self: Arc<Self>,
+    object_pool: &ObjectPool,
+    options: &MapOptions,
+  ) -> Option<IndexSourceMap<'static>> {
+    self.as_ref().clone().index_map_static(object_pool, options)
+  }
+
   #[inline]
   fn to_writer(&self, writer: &mut dyn std::io::Write) -> std::io::Result<()> {
     self.as_ref().to_writer(writer)
@@ -328,6 +369,24 @@ impl<'a> SourceMapFields<'a> {
     }
   }
 
+  /// Copy the borrowed fields, to keep the map after its json is dropped.
+  pub(crate) fn into_owned(self) -> SourceMapFields<'static> {
+    fn owned_strings(strings: Cow<'_, [Cow<'_, str>]>) -> Cow<'static, [Cow<'static, str>]> {
+      Cow::Owned(strings.iter().map(|s| Cow::Owned(s.to_string())).collect())
+    }
+    SourceMapFields {
+      version: self.version,
+      file: self.file.map(|f| Cow::Owned(f.into_owned())),
+      sources: owned_strings(self.sources),
+      sources_content: owned_strings(self.sources_content),
+      names: owned_strings(self.names),
+      mappings: Cow::Owned(self.mappings.into_owned()),
+      source_root: self.source_root.map(|s| Cow::Owned(s.into_owned())),
+      debug_id: self.debug_id.map(|s| Cow::Owned(s.into_owned())),
+      ignore_list: self.ignore_list.map(|s| Cow::Owned(s.into_owned())),
+    }
+  }
+
   pub(crate) fn mappings(&self) -> &str {
     self.mappings.as_ref()
   }
@@ -403,6 +462,21 @@ impl<'a> SourceMap<'a> {
     }
   }
 
+  /// A source map without any mapping.
+  pub(crate) fn empty() -> Self {
+    Self::from_fields(SourceMapFields {
+      version: 3,
+      file: None,
+      sources: Cow::Borrowed(&[]),
+      sources_content: Cow::Borrowed(&[]),
+      names: Cow::Borrowed(&[]),
+      mappings: Cow::Borrowed(""),
+      source_root: None,
+      debug_id: None,
+      ignore_list: None,
+    })
+  }
+
   pub(crate) fn into_static(self, owner: BoxSource) -> SourceMap<'static> {
     #[allow(unsafe_code)]
     // SAFETY: `fields` must borrow from `owner` or contain owned data. The
@@ -706,7 +780,9 @@ impl Hash for SourceMap<'_> {
   }
 }
 
-fn deserialize_source_map_fields<'a>(value: &'a BorrowedValue<'a>) -> Result<SourceMapFields<'a>> {
+pub(crate) fn deserialize_source_map_fields<'a>(
+  value: &'a BorrowedValue<'a>,
+) -> Result<SourceMapFields<'a>> {
   let object = value
     .as_object()
     .ok_or_else(|| simd_json::Error::generic(ErrorType::ExpectedMap))?;
@@ -736,7 +812,7 @@ fn required_string_field<'a>(
     .ok_or_else(|| simd_json::Error::generic(ErrorType::ExpectedString).into())
 }
 
-fn optional_string_field<'a>(
+pub(crate) fn optional_string_field<'a>(
   object: &'a simd_json::borrowed::Object<'a>,
   key: &str,
 ) -> Result<Option<Cow<'a, str>>> {
@@ -0,0 +1,451 @@
+use std::fmt;
+
+use crate::{
+  IndexSourceMap, SectionOffset, SourceMap,
+  helpers::{utf16_column_to_utf8_column, utf16_len},
+};
+
+/// A problem of a source map found by [SourceMap::validate] or [IndexSourceMap::validate].
+///
+/// Lines are one-based and columns are zero-based, counted in UTF-16 code units, like the
+/// positions of a [crate::Mapping]. The generated positions are relative to the whole
+/// generated code, even for the mappings of a section of an [IndexSourceMap].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum SourceMapIssue {
+  /// A mapping starts after the end of its generated line, or after the last line.
+  GeneratedOutOfRange {
+    generated_line: u32,
+    generated_column: u32,
+  },
+  /// A mapping refers to an index missing from `sources`.
+  InvalidSourceIndex {
+    generated_line: u32,
+    generated_column: u32,
+    source_index: u32,
+  },
+  /// A mapping refers to an index missing from `names`.
+  InvalidNameIndex {
+    generated_line: u32,
+    generated_column: u32,
+    name_index: u32,
+  },
+  /// A mapping points after the end of the content of its source. Only checked for the
+  /// sources with a `sourcesContent`.
+  OriginalOutOfRange {
+    generated_line: u32,
+    generated_column: u32,
+    source: String,
+    original_line: u32,
+    original_column: u32,
+  },
+  /// Generated code, other than whitespace, which is not mapped to an original position.
+  UnmappedCode {
+    generated_line: u32,
+    start_column: u32,
+    end_column: u32,
+  },
+  /// A section of an [IndexSourceMap] starts before the previous section.
+  SectionOutOfOrder {
+    section: usize,
+    generated_line: u32,
+    generated_column: u32,
+  },
+}
+
+impl fmt::Display for SourceMapIssue {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      Self::GeneratedOutOfRange {
+        generated_line,
+        generated_column,
+      } => write!(
+        f,
+        "{generated_line}:{generated_column}: mapping is after the end of the generated code"
+      ),
+      Self::InvalidSourceIndex {
+        generated_line,
+        generated_column,
+        source_index,
+      } => write!(
+        f,
+        "{generated_line}:{generated_column}: mapping refers to the missing source {source_index}"
+      ),
+      Self::InvalidNameIndex {
+        generated_line,
+        generated_column,
+        name_index,
+      } => write!(
+        f,
+        "{generated_line}:{generated_column}: mapping refers to the missing name {name_index}"
+      ),
+      Self::OriginalOutOfRange {
+        generated_line,
+        generated_column,
+        source,
+        original_line,
+        original_column,
+      } => write!(
+        f,
+        "{generated_line}:{generated_column}: mapping points to {source}:{original_line}:{original_column}, after the end of the source"
+      ),
+      Self::UnmappedCode {
+        generated_line,
+        start_column,
+        end_column,
+      } => write!(
+        f,
+        "{generated_line}:{start_column}-{end_column}: generated code is not mapped"
+      ),
+      Self::SectionOutOfOrder {
+        section,
+        generated_line,
+        generated_column,
+      } => write!(
+        f,
+        "{generated_line}:{generated_column}: section {section} starts before the previous section"
+      ),
+    }
+  }
+}
+
+impl SourceMap<'_> {
+  /// Check the mappings against `generated`, the code the map was generated for.
+  ///
+  /// ```
+  /// use rspack_sources::{SourceMap, SourceMapIssue};
+  ///
+  /// let map = SourceMap::new("AAAA,KACA", vec!["a.js".into()], vec!["a;".into()], vec![]);
+  /// assert_eq!(
+  ///   map.validate("a; b;"),
+  ///   vec![SourceMapIssue::OriginalOutOfRange {
+  ///     generated_line: 1,
+  ///     generated_column: 5,
+  ///     source: "a.js".into(),
+  ///     original_line: 2,
+  ///     original_column: 0,
+  ///   }]
+  /// );
+  /// ```
+  pub fn validate(&self, generated: &str) -> Vec<SourceMapIssue> {
+    let mut validator = Validator::new(generated);
+    validator.add_map(SectionOffset::default(), self);
+    validator.finish()
+  }
+}
+
+impl IndexSourceMap<'_> {
+  /// Check the sections and their mappings against `generated`, the code the map was
+  /// generated for.
+  ///
+  /// The indices of the sources and names in the issues are those of the section the
+  /// mapping belongs to.
+  pub fn validate(&self, generated: &str) -> Vec<SourceMapIssue> {
+    let mut validator = Validator::new(generated);
+    let mut previous: Option<SectionOffset> = None;
+    for (index, section) in self.sections().iter().enumerate() {
+      let offset = section.offset;
+      if previous
+        .is_some_and(|previous| (offset.line, offset.column) < (previous.line, previous.column))
+      {
+        validator.issues.push(SourceMapIssue::SectionOutOfOrder {
+          section: index,
+          generated_line: offset.line + 1,
+          generated_column: offset.column,
+        });
+      }
+      validator.add_map(offset, &section.map);
+      previous = Some(offset);
+    }
+    validator.finish()
+  }
+}
+
+/// Where the generated code starts to be mapped, or stops to be.
+struct Segment {
+  line: u32,
+  column: u32,
+  mapped: bool,
+}
+
+struct Validator<'a> {
+  /// The generated lines, with their length in UTF-16 code units.
+  lines: Vec<(&'a str, u32)>,
+  segments: Vec<Segment>,
+  issues: Vec<SourceMapIssue>,
+}
+
+impl<'a> Validator<'a> {
+  fn new(generated: &'a str) -> Self {
+    Self {
+      lines: generated
+        .split('\n')
+        .map(|line| (line, utf16_len(line) as u32))
+        .collect(),
+      segments: Vec::new(),
+      issues: Vec::new(),
+    }
+  }
+
+  fn add_map(&mut self, offset: SectionOffset, map: &SourceMap) {
+    // A section ends the mapping of the previous one
+    self.segments.push(Segment {
+      line: offset.line,
+      column: offset.column,
+      mapped: false,
+    });
+
+    let fields = map.fields();
+    // The line lengths of the contents of the sources, computed when first needed
+    let mut original_lines: Vec<Option<Vec<u32>>> = vec![None; fields.sources().len()];
+    for mapping in map.decoded_mappings() {
+      let line = offset
+        .line
+        .saturating_add(mapping.generated_line.saturating_sub(1));
+      let column = if mapping.generated_line <= 1 {
+        offset.column.saturating_add(mapping.generated_column)
+      } else {
+        mapping.generated_column
+      };
+      let generated_line = line.saturating_add(1);
+      if self
+        .lines
+        .get(line as usize)
+        .is_none_or(|(_, len)| column > *len)
+      {
+        self.issues.push(SourceMapIssue::GeneratedOutOfRange {
+          generated_line,
+          generated_column: column,
+        });
+        continue;
+      }
+      self.segments.push(Segment {
+        line,
+        column,
+        mapped: mapping.original.is_some(),
+      });
+
+      let Some(original) = mapping.original else {
+        continue;
+      };
+      if let Some(name_index) = original.name_index
+        && fields.names().get(name_index as usize).is_none()
+      {
+        self.issues.push(SourceMapIssue::InvalidNameIndex {
+          generated_line,
+          generated_column: column,
+          name_index,
+        });
+      }
+      let source_index = original.source_index as usize;
+      let Some(source) = fields.sources().get(source_index) else {
+        self.issues.push(SourceMapIssue::InvalidSourceIndex {
+          generated_line,
+          generated_column: column,
+          source_index: original.source_index,
+        });
+        continue;
+      };
+      let Some(content) = fields
+        .get_source_content(source_index)
+        .filter(|content| !content.is_empty())
+      else {
+        continue;
+      };
+      let content_lines = original_lines[source_index].get_or_insert_with(|| {
+        content
+          .split('\n')
+          .map(|line| utf16_len(line) as u32)
+          .collect()
+      });
+      let in_range = original
+        .original_line
+        .checked_sub(1)
+        .and_then(|line| content_lines.get(line as usize))
+        .is_some_and(|len| original.original_column <= *len);
+      if !in_range {
+        self.issues.push(SourceMapIssue::OriginalOutOfRange {
+          generated_line,
+          generated_column: column,
+          source: source.to_string(),
+          original_line: original.original_line,
+          original_column: original.original_column,
+        });
+      }
+    }
+  }
+
+  fn finish(mut self) -> Vec<SourceMapIssue> {
+    self
+      .segments
+      .sort_by_key(|segment| (segment.line, segment.column));
+    let mut segments = self.segments.iter().peekable();
+    for (index, (text, len)) in self.lines.iter().enumerate() {
+      let line = index as u32;
+      // A mapping does not span to the next line
+      let mut mapped = false;
+      let mut start = 0;
+      loop {
+        let next = segments.next_if(|segment| segment.line == line);
+        let end = next.map_or(*len, |segment| segment.column);
+        if !mapped && end > start {
+          if let Some(issue) = unmapped_code(text, line, start, end) {
+            self.issues.push(issue);
+          }
+        }
+        let Some(next) = next else {
+          break;
+        };
+        mapped = next.mapped;
+        start = end;
+      }
+    }
+    self.issues
+  }
+}
+
+/// The part of `text` between the UTF-16 columns `start` and `end` which is not whitespace.
+fn unmapped_code(text: &str, line: u32, start: u32, end: u32) -> Option<SourceMapIssue> {
+  let start_index = utf16_column_to_utf8_column(text, start as usize)?;
+  let end_index = utf16_column_to_utf8_column(text, end as usize)?;
+  let code = &text[start_index..end_index];
+  let trimmed = code.trim_start();
+  let start_column = start + utf16_len(&code[..code.len() - trimmed.len()]) as u32;
+  let trimmed = trimmed.trim_end();
+  if trimmed.is_empty() {
+    return None;
+  }
+  Some(SourceMapIssue::UnmappedCode {
+    generated_line: line + 1,
+    start_column,
+    end_column: start_column + utf16_len(trimmed) as u32,
+  })
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::{
+    ConcatSource, MapOptions, ObjectPool, OriginalSource, RawStringSource, Source, SourceExt,
+  };
+
+  #[test]
+  fn should_accept_the_map_of_a_source() {
+    let source = OriginalSource::new("const a = 1;\nconsole.log(a);\n", "a.js");
+    let map = source
+      .map(&ObjectPool::default(), &MapOptions::default())
+      .unwrap();
+    assert_eq!(map.validate(&source.source().into_string_lossy()), vec![]);
+  }
+
+  #[test]
+  fn should_report_out_of_range_positions_and_indices() {
+    // 1:0 -> a.js:1:0, 1:6 -> b.js, 1:10 -> a.js:1:20 with the name 3, 3:0
+    let map = SourceMap::new(
+      "AAAA,MCAA,IDAoBG;;AAAA",
+      vec!["a.js".into()],
+      vec!["let a;".into()],
+      vec![],
+    );
+    assert_eq!(
+      map.validate("let a;\n"),
+      vec![
+        SourceMapIssue::InvalidSourceIndex {
+          generated_line: 1,
+          generated_column: 6,
+          source_index: 1,
+        },
+        SourceMapIssue::GeneratedOutOfRange {
+          generated_line: 1,
+          generated_column: 10,
+        },
+        SourceMapIssue::GeneratedOutOfRange {
+          generated_line: 3,
+          generated_column: 0,
+        },
+      ]
+    );
+    assert_eq!(
+      map.validate("let a; 1234;\n"),
+      vec![
+        SourceMapIssue::InvalidSourceIndex {
+          generated_line: 1,
+          generated_column: 6,
+          source_index: 1,
+        },
+        SourceMapIssue::InvalidNameIndex {
+          generated_line: 1,
+          generated_column: 10,
+          name_index: 3,
+        },
+        SourceMapIssue::OriginalOutOfRange {
+          generated_line: 1,
+          generated_column: 10,
+          source: "a.js".into(),
+          original_line: 1,
+          original_column: 20,
+        },
+        SourceMapIssue::GeneratedOutOfRange {
+          generated_line: 3,
+          generated_column: 0,
+        },
+      ]
+    );
+  }
+
+  #[test]
+  fn should_report_unmapped_code() {
+    let source = ConcatSource::new([
+      RawStringSource::from("(() => {\n").boxed(),
+      OriginalSource::new("console.log(a);", "a.js").boxed(),
+      RawStringSource::from("  /* ü */ \n})();").boxed(),
+    ]);
+    let map = source
+      .map(&ObjectPool::default(), &MapOptions::default())
+      .unwrap();
+    let generated = source.source().into_string_lossy();
+    assert_eq!(
+      map
+        .validate(&generated)
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>(),
+      vec![
+        "1:0-8: generated code is not mapped",
+        "2:17-24: generated code is not mapped",
+        "3:0-5: generated code is not mapped",
+      ]
+    );
+  }
+
+  #[test]
+  fn should_validate_the_sections_of_an_index_map() {
+    let source = ConcatSource::new([
+      OriginalSource::new("let a = 1;\nlet b", "a.js").boxed(),
+      RawStringSource::from(" = 2; ").boxed(),
+      OriginalSource::new("log(a, b);\n", "b.js").boxed(),
+    ]);
+    let generated = source.source().into_string_lossy();
+    let mut index_map = source
+      .index_map(&ObjectPool::default(), &MapOptions::default())
+      .unwrap();
+    assert_eq!(
+      index_map.validate(&generated),
+      vec![SourceMapIssue::UnmappedCode {
+        generated_line: 2,
+        start_column: 6,
+        end_column: 10,
+      }]
+    );
+
+    index_map.sections_mut().swap(1, 2);
+    assert!(
+      index_map
+        .validate(&generated)
+        .contains(&SourceMapIssue::SectionOutOfOrder {
+          section: 2,
+          generated_line: 2,
+          generated_column: 5,
+        })
+    );
+  }
+}
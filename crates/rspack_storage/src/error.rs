@@ -13,6 +13,8 @@ pub enum Error {
   InvalidFormat(String),
   /// Data integrity error (e.g., hash mismatch)
   CorruptedData(String),
+  /// Remote store request error (e.g., connection refused, unexpected status)
+  Remote(String),
 }
 
 impl std::fmt::Display for Error {
@@ -21,6 +23,7 @@ impl std::fmt::Display for Error {
       Error::FS(e) => write!(f, "{e}"),
       Error::InvalidFormat(s) => write!(f, "{s}"),
       Error::CorruptedData(s) => write!(f, "{s}"),
+      Error::Remote(s) => write!(f, "{s}"),
     }
   }
 }
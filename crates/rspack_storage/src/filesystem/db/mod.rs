@@ -10,7 +10,7 @@ use std::{
 };
 
 use rspack_parallel::TryFutureConsumer;
-use rustc_hash::FxHashMap as HashMap;
+use rustc_hash::{FxHashMap as HashMap, FxHashSet as HashSet};
 use tokio::sync::Mutex;
 
 use self::{bucket::Bucket, transaction::Transaction};
@@ -34,6 +34,9 @@ pub struct DB {
   /// no-ops so a broken cache state cannot be made worse.  The current build
   /// continues unaffected; restarting the process clears this flag.
   readonly: Arc<AtomicBool>,
+  /// Files (`bucket/file`) written by `save` since the last
+  /// [`DB::take_written_files`] call
+  written_files: Arc<Mutex<HashSet<String>>>,
 }
 
 impl DB {
@@ -43,6 +46,7 @@ impl DB {
       fs,
       buckets: Default::default(),
       readonly: Arc::new(AtomicBool::new(false)),
+      written_files: Default::default(),
     }
   }
 
@@ -148,6 +152,13 @@ impl DB {
 
       match save_result {
         Ok(()) => {
+          // Recorded before the commit, so a partially committed save is still
+          // reported as written.
+          self
+            .written_files
+            .lock()
+            .await
+            .extend(all_files_to_add.iter().cloned());
           transaction
             .commit(all_files_to_add, all_files_to_remove)
             .await?;
@@ -178,6 +189,11 @@ impl DB {
     true
   }
 
+  /// Returns and clears the files (`bucket/file`) written by `save` so far.
+  pub async fn take_written_files(&self) -> HashSet<String> {
+    std::mem::take(&mut *self.written_files.lock().await)
+  }
+
   /// Clears the specified scope (bucket).
   ///
   /// The caller is responsible for sequencing this with saves.
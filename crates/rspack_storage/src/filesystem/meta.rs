@@ -120,6 +120,37 @@ impl Meta {
 
     Ok((stale_directories, next_check_time))
   }
+
+  /// Removes directories that were not accessed within `expire_seconds`.
+  ///
+  /// Unlike [`Meta::refresh`], no directory is marked as accessed, so this is
+  /// safe to call from tools that only maintain the cache.
+  pub fn prune(&mut self, expire_seconds: u64) -> Vec<CacheDirectory> {
+    let mut stale_directories = vec![];
+    if expire_seconds == 0 {
+      return stale_directories;
+    }
+
+    let now = Self::current_timestamp();
+    self.access_times.retain(|cache_directory, time| {
+      if time.saturating_add(expire_seconds) < now {
+        stale_directories.push(cache_directory.clone());
+        return false;
+      }
+      true
+    });
+
+    stale_directories.sort_unstable();
+    stale_directories
+  }
+
+  /// Returns the recorded last access time of each compiler cache directory.
+  pub fn access_times(&self) -> impl Iterator<Item = (&CacheDirectory, u64)> {
+    self
+      .access_times
+      .iter()
+      .map(|(cache_directory, time)| (cache_directory, *time))
+  }
 }
 
 #[cfg(test)]
@@ -168,6 +199,31 @@ mod test {
     Ok(())
   }
 
+  #[test]
+  fn prune_should_not_touch_fresh_directories() {
+    let now = Meta::current_timestamp();
+    let mut meta = Meta::default();
+    meta.access_times.insert(cache_directory(V1), now - 100);
+    meta.access_times.insert(cache_directory(V2), now);
+
+    assert!(meta.prune(0).is_empty());
+    assert_eq!(meta.access_times.len(), 2);
+
+    assert_eq!(meta.prune(10), vec![cache_directory(V1)]);
+    assert_eq!(meta.access_times.len(), 1);
+    assert!(meta.access_times.contains_key(&cache_directory(V2)));
+  }
+
+  #[test]
+  fn prune_should_not_overflow_on_large_max_age() {
+    let now = Meta::current_timestamp();
+    let mut meta = Meta::default();
+    meta.access_times.insert(cache_directory(V1), now - 100);
+
+    assert!(meta.prune(u64::MAX).is_empty());
+    assert_eq!(meta.access_times.len(), 1);
+  }
+
   #[tokio::test]
   async fn load_should_ignore_invalid_meta_entries() -> Result<()> {
     let fs = ScopeFileSystem::new_memory_fs("/invalid_meta_entries".into());
@@ -7,16 +7,20 @@ mod task_queue;
 
 use std::sync::{Arc, Mutex};
 
+use rspack_fs::IntermediateFileSystem;
+use rspack_paths::Utf8PathBuf;
 use rustc_hash::FxHashMap as HashMap;
 
 pub use self::{cache_directory::CacheDirectory, options::FileSystemOptions};
-use self::{db::DB, meta::Meta, scope_fs::ScopeFileSystem, task_queue::TaskQueue};
+pub(crate) use self::{db::DB, scope_fs::ScopeFileSystem};
+use self::{meta::Meta, task_queue::TaskQueue};
 use crate::{Result, Storage};
 
 /// Type alias for in-memory update changes: key -> optional_value
 type BucketChangesMap = HashMap<Vec<u8>, Option<Vec<u8>>>;
 
-const STALE_DIR_NAME: &str = "_stale";
+/// Directory that holds compiler caches waiting to be deleted.
+pub const STALE_DIR_NAME: &str = "_stale";
 
 async fn cleanup_stale_directories(stale_fs: ScopeFileSystem) -> Result<()> {
   stale_fs.ensure_exist().await?;
@@ -90,6 +94,65 @@ async fn refresh_metadata(
   *next_meta_refresh_time.lock().expect("should get lock") = next_refresh_time;
 }
 
+/// Loads the last access time of every compiler cache directory recorded
+/// under the storage root `directory`.
+///
+/// Returns an empty list when the storage root has no metadata yet.
+pub async fn load_access_times(
+  directory: Utf8PathBuf,
+  fs: Arc<dyn IntermediateFileSystem>,
+) -> Result<Vec<(CacheDirectory, u64)>> {
+  let fs = ScopeFileSystem::new(directory, fs);
+  let meta = match Meta::load(&fs).await {
+    Ok(meta) => meta,
+    Err(error) if error.is_not_found() => return Ok(vec![]),
+    Err(error) => return Err(error),
+  };
+
+  let mut access_times: Vec<_> = meta
+    .access_times()
+    .map(|(cache_directory, time)| (cache_directory.clone(), time))
+    .collect();
+  access_times.sort_unstable();
+  Ok(access_times)
+}
+
+/// Prunes compiler cache directories under the storage root `directory` that
+/// were not accessed within `expire` seconds, then empties the stale directory.
+///
+/// `expire` of 0 only cleans up directories that are already stale. With
+/// `dry_run`, nothing is written and the directories that would be pruned are
+/// returned.
+pub async fn prune_cache_directories(
+  directory: Utf8PathBuf,
+  fs: Arc<dyn IntermediateFileSystem>,
+  expire: u64,
+  dry_run: bool,
+) -> Result<Vec<CacheDirectory>> {
+  let fs = ScopeFileSystem::new(directory, fs);
+  let stale_fs = fs.child_fs(STALE_DIR_NAME);
+
+  let mut meta = match Meta::load(&fs).await {
+    Ok(meta) => meta,
+    Err(error) if error.is_not_found() => Meta::default(),
+    Err(error) => return Err(error),
+  };
+  let stale_directories = meta.prune(expire);
+  if dry_run {
+    return Ok(stale_directories);
+  }
+
+  // Same order as `refresh_metadata`: persist metadata before moving
+  // directories so an interrupted prune never leaves dangling entries.
+  if !stale_directories.is_empty() {
+    meta.save(&fs).await?;
+  }
+  move_stale_directories(&fs, &stale_fs, stale_directories.clone()).await?;
+  cleanup_stale_directories(stale_fs).await?;
+
+  Ok(stale_directories)
+}
+
 /// File system-based persistent storage implementation
 #[derive(Debug)]
 pub struct FileSystemStorage {
@@ -126,6 +189,21 @@ impl FileSystemStorage {
   pub fn stale_fs(&self) -> ScopeFileSystem {
     self.fs.child_fs(STALE_DIR_NAME)
   }
+
+  /// Returns the filesystem of the compiler cache directory owned by this storage.
+  pub(crate) fn cache_directory_fs(&self) -> ScopeFileSystem {
+    self.fs.child_fs(self.options.cache_directory.as_str())
+  }
+
+  /// Returns the database of this storage.
+  pub(crate) fn db(&self) -> DB {
+    self.db.clone()
+  }
+
+  /// Enqueues a task that runs after all previously enqueued writes.
+  pub(crate) fn add_task(&self, task: impl Future<Output = ()> + Send + 'static) {
+    self.task_queue.add_task(task);
+  }
 }
 
 #[async_trait::async_trait]
@@ -210,3 +288,92 @@ impl Storage for FileSystemStorage {
     Ok(names)
   }
 }
+
+#[cfg(test)]
+mod test {
+  use std::sync::Arc;
+
+  use rspack_fs::{IntermediateFileSystem, MemoryFileSystem};
+
+  use super::{CacheDirectory, Meta, STALE_DIR_NAME, ScopeFileSystem, prune_cache_directories};
+  use crate::Result;
+
+  const V1: &str = "rspack_v_0000000000000001";
+  const V2: &str = "rspack_v_0000000000000002";
+  const V3: &str = "rspack_v_0000000000000003";
+
+  fn cache_directory(value: &str) -> CacheDirectory {
+    CacheDirectory::parse(value).expect("valid test cache directory")
+  }
+
+  /// Creates a storage root with an expired `V1`, a fresh `V2` and a stale `V3`.
+  async fn create_storage_root(fs: Arc<dyn IntermediateFileSystem>) -> Result<ScopeFileSystem> {
+    let root = ScopeFileSystem::new("/prune".into(), fs);
+    for directory in [V1, V2] {
+      let bucket = root.child_fs(directory).child_fs("snapshot");
+      bucket.ensure_exist().await?;
+      bucket.write("0.pack", b"pack").await?;
+    }
+    let stale = root.child_fs(STALE_DIR_NAME).child_fs(V3);
+    stale.ensure_exist().await?;
+    stale.write("0.pack", b"pack").await?;
+
+    let now = Meta::current_timestamp();
+    root
+      .write(
+        "_meta",
+        format!("{V1} {}\n{V2} {now}\n", now - 100).as_bytes(),
+      )
+      .await?;
+    Ok(root)
+  }
+
+  #[tokio::test]
+  #[cfg_attr(miri, ignore)]
+  async fn prune_should_move_expired_directories_and_clean_stale() -> Result<()> {
+    let fs: Arc<dyn IntermediateFileSystem> = Arc::new(MemoryFileSystem::default());
+    let root = create_storage_root(fs.clone()).await?;
+
+    let pruned = prune_cache_directories("/prune".into(), fs, 10, false).await?;
+    assert_eq!(pruned, vec![cache_directory(V1)]);
+
+    assert!(root.stat(V1).await.is_err());
+    assert!(root.child_fs(V2).stat("snapshot/0.pack").await.is_ok());
+    assert!(root.child_fs(STALE_DIR_NAME).list_child().await?.is_empty());
+
+    let meta = String::from_utf8(root.read("_meta").await?).expect("valid metadata");
+    assert!(!meta.contains(V1));
+    assert!(meta.contains(V2));
+    Ok(())
+  }
+
+  #[tokio::test]
+  #[cfg_attr(miri, ignore)]
+  async fn prune_should_not_write_in_dry_run() -> Result<()> {
+    let fs: Arc<dyn IntermediateFileSystem> = Arc::new(MemoryFileSystem::default());
+    let root = create_storage_root(fs.clone()).await?;
+    let meta = root.read("_meta").await?;
+
+    let pruned = prune_cache_directories("/prune".into(), fs, 10, true).await?;
+    assert_eq!(pruned, vec![cache_directory(V1)]);
+
+    assert!(root.child_fs(V1).stat("snapshot/0.pack").await.is_ok());
+    assert!(root.child_fs(STALE_DIR_NAME).stat(V3).await.is_ok());
+    assert_eq!(root.read("_meta").await?, meta);
+    Ok(())
+  }
+
+  #[tokio::test]
+  #[cfg_attr(miri, ignore)]
+  async fn prune_should_keep_every_directory_without_max_age() -> Result<()> {
+    let fs: Arc<dyn IntermediateFileSystem> = Arc::new(MemoryFileSystem::default());
+    let root = create_storage_root(fs.clone()).await?;
+
+    let pruned = prune_cache_directories("/prune".into(), fs, 0, false).await?;
+    assert!(pruned.is_empty());
+
+    assert!(root.child_fs(V1).stat("snapshot/0.pack").await.is_ok());
+    assert!(root.child_fs(STALE_DIR_NAME).list_child().await?.is_empty());
+    Ok(())
+  }
+}
@@ -1,17 +1,25 @@
 //! Rspack persistent cache storage layer
 //!
-//! Provides two storage implementations:
+//! Provides three storage implementations:
 //! - `FileSystemStorage`: File system-based persistent storage using pack file format
+//! - `RemoteStorage`: `FileSystemStorage` shared with other machines through a remote store
 //! - `MemoryStorage`: Memory-based temporary storage for testing or non-persistent scenarios
 
 mod error;
 mod filesystem;
 mod memory;
+mod remote;
 
+#[cfg(not(target_family = "wasm"))]
+pub use self::remote::LocalRemoteServer;
 pub use self::{
   error::{Error, Result},
-  filesystem::{CacheDirectory, FileSystemOptions, FileSystemStorage},
+  filesystem::{
+    CacheDirectory, FileSystemOptions, FileSystemStorage, STALE_DIR_NAME, load_access_times,
+    prune_cache_directories,
+  },
   memory::MemoryStorage,
+  remote::{HttpRemoteClient, RemoteClient, RemoteOptions, RemoteReporter, RemoteStorage},
 };
 
 /// Persistent storage abstraction interface
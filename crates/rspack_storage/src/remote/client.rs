@@ -0,0 +1,344 @@
+use std::time::Duration;
+
+#[cfg(not(target_family = "wasm"))]
+use tokio::{
+  io::{AsyncReadExt, AsyncWriteExt},
+  net::TcpStream,
+  time::timeout,
+};
+use url::{Host, Url};
+
+use crate::{Error, Result};
+
+/// Client of a content-addressed remote store
+///
+/// Keys are plain relative paths such as `blobs/<hash>` or `manifests/<cache_directory>`.
+#[async_trait::async_trait]
+pub trait RemoteClient: std::fmt::Debug + Send + Sync {
+  /// Fetches the value of `key`, returns `None` if the remote store doesn't have it
+  async fn get(&self, key: &str) -> Result<Option<Vec<u8>>>;
+
+  /// Uploads `value` under `key`, replacing any existing value
+  async fn put(&self, key: &str, value: Vec<u8>) -> Result<()>;
+}
+
+/// Default time to wait for a connection to the remote store
+const DEFAULT_CONNECT_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Default time to wait for the remote store to accept or send data
+const DEFAULT_READ_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Response of a single HTTP request
+#[derive(Debug)]
+struct HttpResponse {
+  status: u16,
+  body: Vec<u8>,
+}
+
+/// Finds the value of the header `name` in raw HTTP header lines
+#[cfg(not(target_family = "wasm"))]
+fn find_header<'a>(headers: &'a str, name: &str) -> Option<&'a str> {
+  headers.split("\r\n").find_map(|line| {
+    let (key, value) = line.split_once(':')?;
+    key.trim().eq_ignore_ascii_case(name).then(|| value.trim())
+  })
+}
+
+/// Parses `Content-Length` from raw HTTP header lines
+#[cfg(not(target_family = "wasm"))]
+pub(super) fn parse_content_length(headers: &str) -> Result<Option<usize>> {
+  find_header(headers, "content-length")
+    .map(|value| {
+      value
+        .parse::<usize>()
+        .map_err(|e| Error::Remote(format!("Invalid content length '{value}' ({e})")))
+    })
+    .transpose()
+}
+
+/// Returns whether raw HTTP header lines declare a chunked body
+///
+/// Any other transfer coding is rejected, since the body could not be decoded.
+#[cfg(not(target_family = "wasm"))]
+fn is_chunked(headers: &str) -> Result<bool> {
+  let Some(value) = find_header(headers, "transfer-encoding") else {
+    return Ok(false);
+  };
+  if value.eq_ignore_ascii_case("chunked") {
+    Ok(true)
+  } else {
+    Err(Error::Remote(format!(
+      "Unsupported transfer encoding '{value}', only 'chunked' is supported"
+    )))
+  }
+}
+
+/// Decodes a `Transfer-Encoding: chunked` body, trailers are ignored
+#[cfg(not(target_family = "wasm"))]
+fn decode_chunked(mut bytes: &[u8]) -> Result<Vec<u8>> {
+  let truncated = || Error::Remote("Truncated chunked response".into());
+  let mut body = vec![];
+  loop {
+    let line_end = bytes
+      .windows(2)
+      .position(|window| window == b"\r\n")
+      .ok_or_else(truncated)?;
+    let line = String::from_utf8_lossy(&bytes[..line_end]);
+    // chunk extensions follow the size after a `;`
+    let size = line.split(';').next().unwrap_or_default().trim();
+    let size = usize::from_str_radix(size, 16)
+      .map_err(|e| Error::Remote(format!("Invalid chunk size '{size}' ({e})")))?;
+    bytes = &bytes[line_end + 2..];
+    if size == 0 {
+      return Ok(body);
+    }
+    if bytes.len() < size + 2 {
+      return Err(truncated());
+    }
+    body.extend_from_slice(&bytes[..size]);
+    bytes = &bytes[size + 2..];
+  }
+}
+
+/// Finds the end of the HTTP head, i.e. the position after `\r\n\r\n`
+#[cfg(not(target_family = "wasm"))]
+pub(super) fn find_head_end(bytes: &[u8]) -> Option<usize> {
+  bytes
+    .windows(4)
+    .position(|window| window == b"\r\n\r\n")
+    .map(|pos| pos + 4)
+}
+
+/// Minimal HTTP/1.1 client for plain `http://` remote stores
+///
+/// Every request opens a new connection with `Connection: close`, responses
+/// may be `Content-Length` delimited, chunked or connection delimited. IPv6
+/// hosts are written in brackets, e.g. `http://[::1]:8080`. Stores behind TLS or requiring authentication should be reached through a
+/// custom [`RemoteClient`].
+///
+/// A request fails when the connection is not established within the connect
+/// timeout, or when the remote store sends nothing for the read timeout.
+#[derive(Debug, Clone)]
+pub struct HttpRemoteClient {
+  /// Host as written in the url, IPv6 addresses keep their brackets
+  host: String,
+  /// Host passed to the socket, IPv6 addresses without brackets
+  address: String,
+  port: u16,
+  base_path: String,
+  connect_timeout: Duration,
+  read_timeout: Duration,
+}
+
+impl HttpRemoteClient {
+  pub fn new(url: &str) -> Result<Self> {
+    let url =
+      Url::parse(url).map_err(|e| Error::Remote(format!("Invalid remote url '{url}' ({e})")))?;
+    if url.scheme() != "http" {
+      return Err(Error::Remote(format!(
+        "Unsupported remote url scheme '{}', only 'http' is supported",
+        url.scheme()
+      )));
+    }
+    let host = url
+      .host()
+      .ok_or_else(|| Error::Remote(format!("Remote url '{url}' has no host")))?;
+    let address = match &host {
+      Host::Ipv6(ip) => ip.to_string(),
+      host => host.to_string(),
+    };
+    let host = host.to_string();
+    let port = url.port_or_known_default().unwrap_or(80);
+    let base_path = url.path().trim_end_matches('/').to_string();
+
+    Ok(Self {
+      host,
+      address,
+      port,
+      base_path,
+      connect_timeout: DEFAULT_CONNECT_TIMEOUT,
+      read_timeout: DEFAULT_READ_TIMEOUT,
+    })
+  }
+
+  /// Replaces the default connect and read timeouts
+  pub fn with_timeouts(mut self, connect_timeout: Duration, read_timeout: Duration) -> Self {
+    self.connect_timeout = connect_timeout;
+    self.read_timeout = read_timeout;
+    self
+  }
+
+  #[cfg(target_family = "wasm")]
+  async fn request(&self, method: &str, key: &str, _body: Option<&[u8]>) -> Result<HttpResponse> {
+    Err(Error::Remote(format!(
+      "{method} {key} failed: remote cache is not supported on wasm targets"
+    )))
+  }
+
+  #[cfg(not(target_family = "wasm"))]
+  async fn request(&self, method: &str, key: &str, body: Option<&[u8]>) -> Result<HttpResponse> {
+    let to_error = |e: std::io::Error| {
+      Error::Remote(format!(
+        "{method} {}:{}{}/{key} failed ({e})",
+        self.host, self.port, self.base_path
+      ))
+    };
+    let timed_out = |action: &str, duration: Duration| {
+      to_error(std::io::Error::new(
+        std::io::ErrorKind::TimedOut,
+        format!("{action} timed out after {}ms", duration.as_millis()),
+      ))
+    };
+
+    let mut stream = timeout(
+      self.connect_timeout,
+      TcpStream::connect((self.address.as_str(), self.port)),
+    )
+    .await
+    .map_err(|_| timed_out("connect", self.connect_timeout))?
+    .map_err(to_error)?;
+
+    let body = body.unwrap_or_default();
+    let head = format!(
+      "{method} {}/{key} HTTP/1.1\r\nHost: {}:{}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+      self.base_path,
+      self.host,
+      self.port,
+      body.len()
+    );
+    timeout(self.read_timeout, async {
+      stream.write_all(head.as_bytes()).await?;
+      stream.write_all(body).await?;
+      stream.flush().await
+    })
+    .await
+    .map_err(|_| timed_out("write", self.read_timeout))?
+    .map_err(to_error)?;
+
+    // The timeout applies to every read, so large bodies are not cut off.
+    let mut response = vec![];
+    let mut chunk = vec![0; 64 * 1024];
+    loop {
+      let read = timeout(self.read_timeout, stream.read(&mut chunk))
+        .await
+        .map_err(|_| timed_out("read", self.read_timeout))?
+        .map_err(to_error)?;
+      if read == 0 {
+        break;
+      }
+      response.extend_from_slice(&chunk[..read]);
+    }
+
+    let head_end = find_head_end(&response)
+      .ok_or_else(|| Error::Remote(format!("Incomplete response for {method} {key}")))?;
+    let head = String::from_utf8_lossy(&response[..head_end]);
+    let (status_line, headers) = head.split_once("\r\n").unwrap_or((&head, ""));
+    let status = status_line
+      .split(' ')
+      .nth(1)
+      .and_then(|status| status.parse::<u16>().ok())
+      .ok_or_else(|| Error::Remote(format!("Invalid status line '{status_line}'")))?;
+
+    let mut body = response[head_end..].to_vec();
+    if is_chunked(headers)? {
+      body = decode_chunked(&body)
+        .map_err(|e| Error::Remote(format!("Invalid response for {method} {key}: {e}")))?;
+    } else if let Some(length) = parse_content_length(headers)? {
+      if body.len() < length {
+        return Err(Error::Remote(format!(
+          "Truncated response for {method} {key}: expected {length} bytes, got {}",
+          body.len()
+        )));
+      }
+      body.truncate(length);
+    }
+
+    Ok(HttpResponse { status, body })
+  }
+}
+
+#[async_trait::async_trait]
+impl RemoteClient for HttpRemoteClient {
+  async fn get(&self, key: &str) -> Result<Option<Vec<u8>>> {
+    let response = self.request("GET", key, None).await?;
+    match response.status {
+      200 => Ok(Some(response.body)),
+      404 => Ok(None),
+      status => Err(Error::Remote(format!(
+        "GET {key} failed with status {status}"
+      ))),
+    }
+  }
+
+  async fn put(&self, key: &str, value: Vec<u8>) -> Result<()> {
+    let response = self.request("PUT", key, Some(&value)).await?;
+    match response.status {
+      200..300 => Ok(()),
+      status => Err(Error::Remote(format!(
+        "PUT {key} failed with status {status}"
+      ))),
+    }
+  }
+}
+
+#[cfg(all(test, not(target_family = "wasm")))]
+mod test {
+  use tokio::{
+    io::{AsyncReadExt, AsyncWriteExt},
+    net::TcpListener,
+  };
+
+  use super::{HttpRemoteClient, RemoteClient, decode_chunked, is_chunked, parse_content_length};
+  use crate::Result;
+
+  #[test]
+  fn should_parse_headers() -> Result<()> {
+    let headers = "Content-Type: text/plain\r\ncontent-length: 12";
+    assert_eq!(parse_content_length(headers)?, Some(12));
+    assert!(!is_chunked(headers)?);
+    assert!(is_chunked("Transfer-Encoding: chunked")?);
+    assert!(is_chunked("Transfer-Encoding: gzip").is_err());
+    Ok(())
+  }
+
+  #[test]
+  fn should_decode_chunked_body() -> Result<()> {
+    assert_eq!(
+      decode_chunked(b"5\r\nhello\r\n7;ext=1\r\n, world\r\n0\r\nTrailer: x\r\n\r\n")?,
+      b"hello, world"
+    );
+    assert_eq!(decode_chunked(b"0\r\n\r\n")?, b"");
+    assert!(decode_chunked(b"5\r\nhel").is_err());
+    assert!(decode_chunked(b"z\r\n").is_err());
+    Ok(())
+  }
+
+  #[tokio::test]
+  #[cfg_attr(miri, ignore)]
+  async fn should_read_chunked_response_from_ipv6_host() -> Result<()> {
+    let Ok(listener) = TcpListener::bind("[::1]:0").await else {
+      // IPv6 is not available
+      return Ok(());
+    };
+    let port = listener.local_addr().expect("should have address").port();
+    let handle = tokio::spawn(async move {
+      let (mut stream, _) = listener.accept().await.expect("should accept");
+      let mut request = vec![0; 1024];
+      let read = stream.read(&mut request).await.expect("should read");
+      stream
+        .write_all(
+          b"HTTP/1.1 200 OK\r\nTransfer-Encoding: chunked\r\n\r\n3\r\nfoo\r\n3\r\nbar\r\n0\r\n\r\n",
+        )
+        .await
+        .expect("should write");
+      String::from_utf8_lossy(&request[..read]).to_string()
+    });
+
+    let client = HttpRemoteClient::new(&format!("http://[::1]:{port}/cache"))?;
+    assert_eq!(client.get("blobs/hash").await?, Some(b"foobar".to_vec()));
+    let request = handle.await.expect("should join");
+    assert!(request.starts_with("GET /cache/blobs/hash HTTP/1.1\r\n"));
+    assert!(request.contains(&format!("Host: [::1]:{port}\r\n")));
+    Ok(())
+  }
+}
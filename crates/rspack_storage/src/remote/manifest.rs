@@ -0,0 +1,94 @@
+use sha2::{Digest, Sha256};
+
+use crate::{Error, Result};
+
+/// Returns the content address of `data`
+pub fn content_hash(data: &[u8]) -> String {
+  hex::encode(Sha256::digest(data))
+}
+
+/// List of files in a compiler cache directory with their content hashes.
+///
+/// Format:
+/// ```text
+/// bucket_name/_meta content_hash
+/// bucket_name/0.pack content_hash
+/// ```
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct Manifest {
+  files: Vec<(String, String)>,
+}
+
+impl Manifest {
+  /// Adds a file with a path relative to the compiler cache directory
+  pub fn add(&mut self, path: String, hash: String) {
+    self.files.push((path, hash));
+  }
+
+  pub fn files(&self) -> &[(String, String)] {
+    &self.files
+  }
+
+  /// Returns the sorted and deduplicated top level directories (buckets)
+  pub fn buckets(&self) -> Vec<&str> {
+    let mut buckets: Vec<_> = self
+      .files
+      .iter()
+      .filter_map(|(path, _)| path.split_once('/').map(|(bucket, _)| bucket))
+      .collect();
+    buckets.sort_unstable();
+    buckets.dedup();
+    buckets
+  }
+
+  pub fn parse(bytes: &[u8]) -> Result<Self> {
+    let content = std::str::from_utf8(bytes)
+      .map_err(|e| Error::InvalidFormat(format!("Failed to parse remote manifest ({e})")))?;
+
+    let mut manifest = Self::default();
+    for line in content.lines().filter(|line| !line.is_empty()) {
+      let Some((path, hash)) = line.split_once(' ') else {
+        return Err(Error::InvalidFormat(format!(
+          "Failed to parse remote manifest: invalid line '{line}'"
+        )));
+      };
+      // Reject paths that could escape the compiler cache directory.
+      if path.starts_with(['/', '.']) || path.split('/').any(|part| part == "..") {
+        return Err(Error::InvalidFormat(format!(
+          "Failed to parse remote manifest: invalid path '{path}'"
+        )));
+      }
+      manifest.add(path.to_string(), hash.to_string());
+    }
+    Ok(manifest)
+  }
+
+  pub fn to_bytes(&self) -> Vec<u8> {
+    self
+      .files
+      .iter()
+      .map(|(path, hash)| format!("{path} {hash}\n"))
+      .collect::<String>()
+      .into_bytes()
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use super::{Manifest, content_hash};
+
+  #[test]
+  fn test_manifest() {
+    let mut manifest = Manifest::default();
+    manifest.add("scope_a/_meta".into(), content_hash(b"meta"));
+    manifest.add("scope_a/0.pack".into(), content_hash(b"pack"));
+    manifest.add("scope_b/0.pack".into(), content_hash(b"pack"));
+
+    let other = Manifest::parse(&manifest.to_bytes()).expect("should parse manifest");
+    assert_eq!(manifest, other);
+    assert_eq!(other.buckets(), vec!["scope_a", "scope_b"]);
+
+    assert!(Manifest::parse(b"../outside/0.pack hash\n").is_err());
+    assert!(Manifest::parse(b"invalid-line\n").is_err());
+  }
+}
@@ -0,0 +1,452 @@
+mod client;
+mod manifest;
+#[cfg(not(target_family = "wasm"))]
+mod server;
+
+use std::{fmt::Debug, sync::Arc};
+
+use rustc_hash::{FxHashMap as HashMap, FxHashSet as HashSet};
+use tokio::sync::{Mutex, OnceCell};
+
+pub use self::client::{HttpRemoteClient, RemoteClient};
+use self::manifest::{Manifest, content_hash};
+#[cfg(not(target_family = "wasm"))]
+pub use self::server::LocalRemoteServer;
+use crate::{
+  CacheDirectory, Error, FileSystemOptions, FileSystemStorage, Result, Storage,
+  filesystem::{DB, ScopeFileSystem},
+};
+
+/// Temporary directory that downloaded files are written to before being moved in place
+const PULL_DIR_NAME: &str = ".remote";
+
+fn manifest_key(cache_directory: &CacheDirectory) -> String {
+  format!("manifests/{cache_directory}")
+}
+
+fn blob_key(hash: &str) -> String {
+  format!("blobs/{hash}")
+}
+
+/// Receiver of remote store failures, which never fail the build
+pub trait RemoteReporter: Debug + Send + Sync {
+  fn report(&self, message: String);
+}
+
+/// Remote storage configuration options
+#[derive(Debug)]
+pub struct RemoteOptions {
+  /// Options of the local filesystem layer
+  pub local: FileSystemOptions,
+  /// Client of the content-addressed remote store
+  pub client: Arc<dyn RemoteClient>,
+  /// Receiver of pull and push failures
+  pub reporter: Arc<dyn RemoteReporter>,
+}
+
+#[derive(Debug, Default)]
+struct PushState {
+  /// Blob hashes known to exist in the remote store
+  blobs: HashSet<String>,
+  /// Content hashes of the local files (`bucket/file`) as last pulled or pushed
+  files: HashMap<String, String>,
+}
+
+/// Downloads the remote manifest and its blobs into an empty local cache directory.
+async fn pull(
+  fs: &ScopeFileSystem,
+  client: &dyn RemoteClient,
+  cache_directory: &CacheDirectory,
+  state: &Mutex<PushState>,
+) -> Result<()> {
+  fs.ensure_exist().await?;
+  // A warm local cache is always newer than or equal to the shared one.
+  let has_local_data = fs
+    .list_child()
+    .await?
+    .iter()
+    .any(|child| !child.starts_with('.'));
+  if has_local_data {
+    return Ok(());
+  }
+
+  let Some(manifest) = client.get(&manifest_key(cache_directory)).await? else {
+    return Ok(());
+  };
+  let manifest = Manifest::parse(&manifest)?;
+
+  let pull_fs = fs.child_fs(PULL_DIR_NAME);
+  pull_fs.remove().await?;
+  for (path, hash) in manifest.files() {
+    let data = client.get(&blob_key(hash)).await?.ok_or_else(|| {
+      Error::CorruptedData(format!("Remote blob '{hash}' for '{path}' is missing"))
+    })?;
+    let actual_hash = content_hash(&data);
+    if actual_hash != *hash {
+      return Err(Error::CorruptedData(format!(
+        "Remote blob for '{path}' content hash mismatch: expected {hash}, got {actual_hash}"
+      )));
+    }
+    pull_fs.write(path, &data).await?;
+  }
+
+  // Only move buckets in place once every file has been downloaded, so an
+  // interrupted pull never leaves a partial bucket behind.
+  for bucket in manifest.buckets() {
+    ScopeFileSystem::move_to(&pull_fs, fs, bucket).await?;
+  }
+  pull_fs.remove().await?;
+
+  let mut state = state.lock().await;
+  for (path, hash) in manifest.files() {
+    state.blobs.insert(hash.clone());
+    state.files.insert(path.clone(), hash.clone());
+  }
+  Ok(())
+}
+
+/// Uploads changed files of the local cache directory and replaces the remote manifest.
+///
+/// Only files written by the database since the previous push are read and
+/// hashed, the hashes of the other files are reused.
+async fn push(
+  fs: &ScopeFileSystem,
+  db: &DB,
+  client: &dyn RemoteClient,
+  cache_directory: &CacheDirectory,
+  state: &Mutex<PushState>,
+) -> Result<()> {
+  let mut state = state.lock().await;
+  // Pack ids are recycled, so written files drop their known hash before
+  // anything can fail and leave a stale one behind.
+  for path in db.take_written_files().await {
+    state.files.remove(&path);
+  }
+
+  let mut manifest = Manifest::default();
+  let mut files = HashMap::default();
+  let mut buckets = fs.list_child().await?;
+  buckets.sort();
+  for bucket in buckets {
+    if bucket.starts_with('.') || !fs.stat(&bucket).await?.is_directory {
+      continue;
+    }
+    let bucket_fs = fs.child_fs(&bucket);
+    let mut bucket_files = bucket_fs.list_child().await?;
+    bucket_files.sort();
+    for file in bucket_files {
+      let path = format!("{bucket}/{file}");
+      let hash = match state.files.get(&path) {
+        Some(hash) => hash.clone(),
+        None => {
+          let data = bucket_fs.read(&file).await?;
+          let hash = content_hash(&data);
+          if !state.blobs.contains(&hash) {
+            client.put(&blob_key(&hash), data).await?;
+            state.blobs.insert(hash.clone());
+          }
+          hash
+        }
+      };
+      files.insert(path.clone(), hash.clone());
+      manifest.add(path, hash);
+    }
+  }
+  // Removed files are dropped as well.
+  state.files = files;
+
+  client
+    .put(&manifest_key(cache_directory), manifest.to_bytes())
+    .await
+}
+
+/// Storage that shares a compiler cache directory through a remote store
+///
+/// Reads and writes go through a local [`FileSystemStorage`]. Before the first
+/// read an empty local cache directory is filled from the remote store, and
+/// after every save the pack files written by the save are pushed as
+/// content-addressed blobs together with a manifest of the whole cache directory.
+///
+/// Remote failures never fail the build, they are sent to the
+/// [`RemoteReporter`] and the storage keeps working with the local cache only.
+#[derive(Debug)]
+pub struct RemoteStorage {
+  local: FileSystemStorage,
+  client: Arc<dyn RemoteClient>,
+  reporter: Arc<dyn RemoteReporter>,
+  cache_directory: CacheDirectory,
+  pulled: OnceCell<()>,
+  state: Arc<Mutex<PushState>>,
+}
+
+impl RemoteStorage {
+  /// Creates a new remote storage instance
+  pub fn new(options: RemoteOptions) -> Self {
+    let cache_directory = options.local.cache_directory.clone();
+    Self {
+      local: FileSystemStorage::new(options.local),
+      client: options.client,
+      reporter: options.reporter,
+      cache_directory,
+      pulled: OnceCell::new(),
+      state: Default::default(),
+    }
+  }
+
+  async fn ensure_pulled(&self) {
+    self
+      .pulled
+      .get_or_init(|| async {
+        let fs = self.local.cache_directory_fs();
+        if let Err(err) = pull(&fs, &*self.client, &self.cache_directory, &self.state).await {
+          self.reporter.report(format!(
+            "Remote persistent cache pull failed: {err}. \
+             Continuing with the local persistent cache."
+          ));
+        }
+      })
+      .await;
+  }
+}
+
+#[async_trait::async_trait]
+impl Storage for RemoteStorage {
+  fn cleanup_stale(&self) {
+    self.local.cleanup_stale();
+  }
+
+  async fn load(&self, scope: &'static str) -> Result<Vec<(Vec<u8>, Vec<u8>)>> {
+    self.ensure_pulled().await;
+    self.local.load(scope).await
+  }
+
+  fn set(&mut self, scope: &'static str, key: Vec<u8>, value: Vec<u8>) {
+    self.local.set(scope, key, value);
+  }
+
+  fn remove(&mut self, scope: &'static str, key: &[u8]) {
+    self.local.remove(scope, key);
+  }
+
+  fn save(&mut self) {
+    self.local.save();
+
+    // The local task queue is sequential, so the push observes the files
+    // written by the save above.
+    let fs = self.local.cache_directory_fs();
+    let db = self.local.db();
+    let client = self.client.clone();
+    let reporter = self.reporter.clone();
+    let cache_directory = self.cache_directory.clone();
+    let state = self.state.clone();
+    self.local.add_task(async move {
+      if let Err(err) = push(&fs, &db, &*client, &cache_directory, &state).await {
+        reporter.report(format!(
+          "Remote persistent cache push failed: {err}. \
+           The local persistent cache is not affected."
+        ));
+      }
+    });
+  }
+
+  fn reset(&mut self, scope: &'static str) {
+    self.local.reset(scope);
+  }
+
+  fn reset_all(&mut self) {
+    self.local.reset_all();
+  }
+
+  async fn flush(&self) {
+    self.local.flush().await;
+  }
+
+  async fn scopes(&self) -> Result<Vec<String>> {
+    self.ensure_pulled().await;
+    self.local.scopes().await
+  }
+}
+
+#[cfg(all(test, not(target_family = "wasm")))]
+mod test {
+  use std::{
+    sync::{Arc, Mutex},
+    time::Duration,
+  };
+
+  use rspack_fs::MemoryFileSystem;
+
+  use super::{
+    HttpRemoteClient, LocalRemoteServer, RemoteClient, RemoteOptions, RemoteReporter,
+    RemoteStorage, manifest::Manifest,
+  };
+  use crate::{CacheDirectory, FileSystemOptions, Result, Storage};
+
+  #[derive(Debug, Default)]
+  struct TestReporter(Mutex<Vec<String>>);
+
+  impl RemoteReporter for TestReporter {
+    fn report(&self, message: String) {
+      self.0.lock().expect("should lock").push(message);
+    }
+  }
+
+  impl TestReporter {
+    fn messages(&self) -> Vec<String> {
+      self.0.lock().expect("should lock").clone()
+    }
+  }
+
+  fn create_storage(url: &str) -> RemoteStorage {
+    create_storage_with_reporter(url, Default::default())
+  }
+
+  fn create_storage_with_reporter(url: &str, reporter: Arc<TestReporter>) -> RemoteStorage {
+    RemoteStorage::new(RemoteOptions {
+      local: FileSystemOptions {
+        directory: "/cache".into(),
+        cache_directory: CacheDirectory::new("0000000000000001"),
+        max_pack_size: 25,
+        expire: 0,
+        fs: Arc::new(MemoryFileSystem::default()),
+      },
+      client: Arc::new(HttpRemoteClient::new(url).expect("should create client")),
+      reporter,
+    })
+  }
+
+  async fn remote_manifest(url: &str) -> Result<Manifest> {
+    let client = HttpRemoteClient::new(url)?;
+    let manifest = client
+      .get("manifests/rspack_v_0000000000000001")
+      .await?
+      .expect("should have manifest");
+    Manifest::parse(&manifest)
+  }
+
+  #[tokio::test]
+  #[cfg_attr(miri, ignore)]
+  async fn should_share_cache_through_remote() -> Result<()> {
+    let server = LocalRemoteServer::start().await?;
+
+    let mut storage = create_storage(&server.url());
+    assert!(storage.load("scope").await?.is_empty());
+    for num in 0..5 {
+      storage.set(
+        "scope",
+        format!("key{num}").into_bytes(),
+        format!("value{num}").into_bytes(),
+      );
+    }
+    storage.save();
+    storage.flush().await;
+
+    let keys = server.keys();
+    assert!(keys.contains(&"manifests/rspack_v_0000000000000001".to_string()));
+    assert!(keys.iter().any(|key| key.starts_with("blobs/")));
+
+    // a fresh machine with an empty local directory
+    let other_storage = create_storage(&server.url());
+    let mut data = other_storage.load("scope").await?;
+    data.sort();
+    assert_eq!(data.len(), 5);
+    assert_eq!(data[0], (b"key0".to_vec(), b"value0".to_vec()));
+    assert_eq!(other_storage.scopes().await?, vec!["scope".to_string()]);
+
+    Ok(())
+  }
+
+  #[tokio::test]
+  #[cfg_attr(miri, ignore)]
+  async fn should_only_rehash_written_files() -> Result<()> {
+    let server = LocalRemoteServer::start().await?;
+
+    let mut storage = create_storage(&server.url());
+    assert!(storage.load("scope").await?.is_empty());
+    for num in 0..5 {
+      storage.set(
+        "scope",
+        format!("key{num}").into_bytes(),
+        format!("value{num}").into_bytes(),
+      );
+    }
+    storage.save();
+    storage.flush().await;
+
+    // replace every known hash, a push without writes must reuse them
+    let files = remote_manifest(&server.url()).await?.files().len();
+    {
+      let mut state = storage.state.lock().await;
+      for hash in state.files.values_mut() {
+        *hash = "known".into();
+      }
+    }
+    super::push(
+      &storage.local.cache_directory_fs(),
+      &storage.local.db(),
+      &*storage.client,
+      &storage.cache_directory,
+      &storage.state,
+    )
+    .await?;
+    let manifest = remote_manifest(&server.url()).await?;
+    assert_eq!(manifest.files().len(), files);
+    assert!(manifest.files().iter().all(|(_, hash)| hash == "known"));
+
+    // files written by a save are hashed again
+    storage.set("scope", b"key0".to_vec(), b"changed".to_vec());
+    storage.save();
+    storage.flush().await;
+    let manifest = remote_manifest(&server.url()).await?;
+    assert!(manifest.files().iter().any(|(_, hash)| hash != "known"));
+
+    Ok(())
+  }
+
+  #[tokio::test]
+  #[cfg_attr(miri, ignore)]
+  async fn should_work_without_remote() -> Result<()> {
+    let reporter = Arc::new(TestReporter::default());
+    // nothing listens on port 1
+    let mut storage = create_storage_with_reporter("http://127.0.0.1:1", reporter.clone());
+    assert!(storage.load("scope").await?.is_empty());
+    storage.set("scope", b"key".to_vec(), b"value".to_vec());
+    storage.save();
+    storage.flush().await;
+    assert_eq!(storage.load("scope").await?.len(), 1);
+
+    let messages = reporter.messages();
+    assert_eq!(messages.len(), 2);
+    assert!(messages[0].starts_with("Remote persistent cache pull failed"));
+    assert!(messages[1].starts_with("Remote persistent cache push failed"));
+    Ok(())
+  }
+
+  #[tokio::test]
+  #[cfg_attr(miri, ignore)]
+  async fn should_time_out_when_remote_hangs() -> Result<()> {
+    // accepts connections but never answers
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0")
+      .await
+      .expect("should bind");
+    let addr = listener.local_addr().expect("should have address");
+    let _handle = tokio::spawn(async move {
+      let mut streams = vec![];
+      while let Ok((stream, _)) = listener.accept().await {
+        streams.push(stream);
+      }
+    });
+
+    let client = HttpRemoteClient::new(&format!("http://{addr}"))?
+      .with_timeouts(Duration::from_secs(5), Duration::from_millis(50));
+    let err = client
+      .get("manifests/rspack_v_0000000000000001")
+      .await
+      .expect_err("should time out");
+    assert!(
+      err.to_string().contains("read timed out after 50ms"),
+      "{err}"
+    );
+    Ok(())
+  }
+}
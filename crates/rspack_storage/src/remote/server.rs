@@ -0,0 +1,140 @@
+use std::{
+  net::SocketAddr,
+  sync::{Arc, Mutex},
+};
+
+use rustc_hash::FxHashMap as HashMap;
+use tokio::{
+  io::{AsyncReadExt, AsyncWriteExt},
+  net::{TcpListener, TcpStream},
+  task::JoinHandle,
+};
+
+use super::client::{find_head_end, parse_content_length};
+use crate::{Error, Result};
+
+type Objects = Arc<Mutex<HashMap<String, Vec<u8>>>>;
+
+/// In-process HTTP stand-in for a remote store
+///
+/// Keeps every object in memory and answers `GET` and `PUT` requests sent by
+/// [`super::HttpRemoteClient`]. The server stops when it is dropped.
+#[derive(Debug)]
+pub struct LocalRemoteServer {
+  addr: SocketAddr,
+  objects: Objects,
+  handle: JoinHandle<()>,
+}
+
+impl LocalRemoteServer {
+  /// Starts the server on a random local port
+  pub async fn start() -> Result<Self> {
+    let listener = TcpListener::bind("127.0.0.1:0")
+      .await
+      .map_err(|e| Error::Remote(format!("Failed to start local remote server ({e})")))?;
+    let addr = listener
+      .local_addr()
+      .map_err(|e| Error::Remote(format!("Failed to start local remote server ({e})")))?;
+
+    let objects: Objects = Default::default();
+    let server_objects = objects.clone();
+    let handle = tokio::spawn(async move {
+      while let Ok((stream, _)) = listener.accept().await {
+        let objects = server_objects.clone();
+        tokio::spawn(async move {
+          let _ = handle_connection(stream, objects).await;
+        });
+      }
+    });
+
+    Ok(Self {
+      addr,
+      objects,
+      handle,
+    })
+  }
+
+  /// Base url to pass to [`super::HttpRemoteClient::new`]
+  pub fn url(&self) -> String {
+    format!("http://{}", self.addr)
+  }
+
+  /// Returns the sorted keys of all stored objects
+  pub fn keys(&self) -> Vec<String> {
+    let mut keys: Vec<_> = self
+      .objects
+      .lock()
+      .expect("should get lock")
+      .keys()
+      .cloned()
+      .collect();
+    keys.sort();
+    keys
+  }
+}
+
+impl Drop for LocalRemoteServer {
+  fn drop(&mut self) {
+    self.handle.abort();
+  }
+}
+
+async fn handle_connection(mut stream: TcpStream, objects: Objects) -> std::io::Result<()> {
+  let mut request = vec![];
+  let mut buf = [0u8; 8192];
+
+  // read the request head
+  let head_end = loop {
+    let n = stream.read(&mut buf).await?;
+    if n == 0 {
+      return Ok(());
+    }
+    request.extend_from_slice(&buf[..n]);
+    if let Some(head_end) = find_head_end(&request) {
+      break head_end;
+    }
+  };
+
+  let head = String::from_utf8_lossy(&request[..head_end]).into_owned();
+  let (request_line, headers) = head.split_once("\r\n").unwrap_or((&head, ""));
+  let mut parts = request_line.split(' ');
+  let method = parts.next().unwrap_or_default();
+  let key = parts
+    .next()
+    .unwrap_or_default()
+    .trim_start_matches('/')
+    .to_string();
+
+  // read the request body
+  let content_length = parse_content_length(headers).ok().flatten().unwrap_or(0);
+  while request.len() < head_end + content_length {
+    let n = stream.read(&mut buf).await?;
+    if n == 0 {
+      break;
+    }
+    request.extend_from_slice(&buf[..n]);
+  }
+
+  let (status, body) = match method {
+    "GET" => match objects.lock().expect("should get lock").get(&key) {
+      Some(value) => ("200 OK", value.clone()),
+      None => ("404 Not Found", vec![]),
+    },
+    "PUT" => {
+      let end = request.len().min(head_end + content_length);
+      let value = request[head_end..end].to_vec();
+      objects.lock().expect("should get lock").insert(key, value);
+      ("201 Created", vec![])
+    }
+    _ => ("405 Method Not Allowed", vec![]),
+  };
+
+  let head = format!(
+    "HTTP/1.1 {status}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+    body.len()
+  );
+  stream.write_all(head.as_bytes()).await?;
+  stream.write_all(&body).await?;
+  stream.flush().await?;
+  stream.shutdown().await
+}
@@ -1,17 +1,21 @@
-mod occasion;
+pub(crate) mod occasion;
 mod snapshot;
 
 use std::{collections::VecDeque, sync::Arc};
 
-use rspack_core::cache::persistent::storage::{
-  BoxStorage, CacheDirectory, StorageOptions, create_storage,
+use rspack_core::{
+  CompilationLogger,
+  cache::persistent::storage::{BoxStorage, CacheDirectory, StorageOptions, create_storage},
 };
 use rspack_error::{Result, error};
-use rspack_fs::{NativeFileSystem, ReadableFileSystem};
+use rspack_fs::ReadableFileSystem;
 use rspack_paths::Utf8PathBuf;
 use rustc_hash::{FxHashMap as HashMap, FxHashSet as HashSet};
 
-use crate::{debug_info::DebugInfo, utils::ensure_iter_equal};
+use crate::{
+  debug_info::DebugInfo,
+  utils::{CacheFileSystem, ensure_iter_equal, native_fs},
+};
 
 const META_FILE_NAME: &str = "_meta";
 
@@ -19,7 +23,7 @@ fn is_cache_scope(name: &str) -> bool {
   name == occasion::meta::SCOPE || name == occasion::make::SCOPE
 }
 
-fn has_cache_directory(fs: &NativeFileSystem, path: &Utf8PathBuf) -> bool {
+fn has_cache_directory(fs: &dyn CacheFileSystem, path: &Utf8PathBuf) -> bool {
   let Ok(cache_directories) = fs.read_dir_sync(path.as_path()) else {
     return false;
   };
@@ -37,19 +41,21 @@ fn has_cache_directory(fs: &NativeFileSystem, path: &Utf8PathBuf) -> bool {
     })
 }
 
-fn is_storage_root(fs: &NativeFileSystem, path: &Utf8PathBuf) -> bool {
+fn is_storage_root(fs: &dyn CacheFileSystem, path: &Utf8PathBuf) -> bool {
   fs.metadata_sync(&path.join(META_FILE_NAME))
     .is_ok_and(|metadata| metadata.is_file)
     || has_cache_directory(fs, path)
 }
 
-pub fn find_relative_cache_path(root_path: &Utf8PathBuf) -> HashSet<String> {
-  let fs = NativeFileSystem::new(false);
+pub fn find_relative_cache_path(
+  fs: &dyn CacheFileSystem,
+  root_path: &Utf8PathBuf,
+) -> HashSet<String> {
   let mut relative_paths = HashSet::default();
   let mut queue = VecDeque::new();
   queue.push_back(root_path.clone());
   while let Some(path) = queue.pop_front() {
-    if is_storage_root(&fs, &path) {
+    if is_storage_root(fs, &path) {
       relative_paths.insert(
         path
           .strip_prefix(root_path)
@@ -88,8 +94,10 @@ fn join_relative_cache_path(root_path: &Utf8PathBuf, relative_path: &str) -> Utf
 
 /// Load all compiler-path-specific storages from a directory path.
 /// Returns a HashMap where the key is the compiler cache directory name.
-pub fn load_storages_from_path(path: &Utf8PathBuf) -> HashMap<String, BoxStorage> {
-  let fs = Arc::new(NativeFileSystem::new(false));
+pub fn load_storages_from_path(
+  fs: Arc<dyn CacheFileSystem>,
+  path: &Utf8PathBuf,
+) -> HashMap<String, BoxStorage> {
   let mut storages = HashMap::default();
 
   let Ok(cache_directories) = fs.read_dir_sync(path.as_path()) else {
@@ -117,6 +125,8 @@ pub fn load_storages_from_path(path: &Utf8PathBuf) -> HashMap<String, BoxStorage
       cache_directory,
       0,
       fs.clone(),
+      // filesystem storages never report remote failures
+      CompilationLogger::new("rspack_tools".into(), Default::default()),
     );
 
     storages.insert(cache_directory_name, storage);
@@ -127,8 +137,9 @@ pub fn load_storages_from_path(path: &Utf8PathBuf) -> HashMap<String, BoxStorage
 
 /// Compare cache dir from two directories and return whether they are equal
 pub async fn compare_cache_dir(path1: Utf8PathBuf, path2: Utf8PathBuf) -> Result<()> {
-  let cache_paths1 = find_relative_cache_path(&path1);
-  let cache_paths2 = find_relative_cache_path(&path2);
+  let fs = native_fs();
+  let cache_paths1 = find_relative_cache_path(&*fs, &path1);
+  let cache_paths2 = find_relative_cache_path(&*fs, &path2);
   let debug_info = DebugInfo::default()
     .with_field("path1", path1.as_ref())
     .with_field("path2", path2.as_ref());
@@ -149,8 +160,8 @@ pub async fn compare_cache_dir(path1: Utf8PathBuf, path2: Utf8PathBuf) -> Result
       .with_field("path2", cache_path2.as_ref());
 
     // Load storages from both paths
-    let storages1 = load_storages_from_path(&cache_path1);
-    let mut storages2 = load_storages_from_path(&cache_path2);
+    let storages1 = load_storages_from_path(fs.clone(), &cache_path1);
+    let mut storages2 = load_storages_from_path(fs.clone(), &cache_path2);
 
     // Check if compiler cache directories are identical.
     ensure_iter_equal(
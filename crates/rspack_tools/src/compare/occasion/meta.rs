@@ -8,7 +8,7 @@ use crate::debug_info::DebugInfo;
 /// Meta struct that mirrors rspack_core's Meta structure
 #[cacheable]
 #[derive(Debug)]
-struct Meta {
+pub(crate) struct Meta {
   pub version: String,
   pub max_dependencies_id: u32,
 }
@@ -0,0 +1,257 @@
+use std::sync::Arc;
+
+use rspack_core::{
+  cache::persistent::{
+    codec::CacheCodec,
+    occasion::{
+      Occasion, devtool,
+      make::{MakeOccasion, SCOPE as MAKE_SCOPE},
+      minimize,
+    },
+    snapshot::Strategy,
+    storage::Storage,
+  },
+  rspack_sources::Source,
+};
+use rspack_error::Result;
+use rspack_paths::Utf8PathBuf;
+use rustc_hash::FxHashMap as HashMap;
+use serde_json::{Map, Value, json};
+
+use crate::{
+  compare::{find_relative_cache_path, load_storages_from_path, occasion::meta::Meta},
+  scan::{ScopeKind, known_scopes},
+  utils::{CacheFileSystem, native_fs},
+};
+
+fn decode_entry(
+  kind: ScopeKind,
+  codec: &CacheCodec,
+  key: &[u8],
+  value: &[u8],
+) -> Result<Map<String, Value>> {
+  let mut entry = Map::new();
+  match kind {
+    ScopeKind::Meta => {
+      let meta: Meta = codec.decode(value)?;
+      entry.insert("version".into(), meta.version.into());
+      entry.insert("maxDependenciesId".into(), meta.max_dependencies_id.into());
+    }
+    ScopeKind::Snapshot => {
+      let strategy: Strategy = codec.decode(value)?;
+      entry.insert("path".into(), String::from_utf8_lossy(key).into());
+      entry.insert("strategy".into(), format!("{strategy:?}").into());
+    }
+    ScopeKind::Devtool => {
+      let key: devtool::CacheKey = codec.decode(key)?;
+      let value: devtool::Entry = codec.decode(value)?;
+      entry.insert("filename".into(), key.filename.into());
+      entry.insert("version".into(), key.version.into());
+      entry.insert(
+        "appendSize".into(),
+        value.append.iter().map(|s| s.size()).sum::<usize>().into(),
+      );
+      entry.insert(
+        "sourceMap".into(),
+        value.source_map.map_or(Value::Null, |source_map| {
+          json!({
+            "filename": source_map.filename,
+            "size": source_map.source.size(),
+          })
+        }),
+      );
+    }
+    ScopeKind::Minimize => {
+      let value: minimize::Entry = codec.decode(value)?;
+      let hash = <[u8; 8]>::try_from(key)
+        .map(|bytes| format!("{:016x}", u64::from_ne_bytes(bytes)))
+        .unwrap_or_else(|_| String::from_utf8_lossy(key).into_owned());
+      entry.insert("key".into(), hash.into());
+      entry.insert("sourceSize".into(), value.source.size().into());
+      entry.insert(
+        "extractedComments".into(),
+        value.extracted_comments.map_or(Value::Null, |comments| {
+          json!({
+            "filename": comments.comments_file_name,
+            "size": comments.source.size(),
+          })
+        }),
+      );
+    }
+    ScopeKind::Make | ScopeKind::Unknown => {
+      // make scope is exported by `export_make_scope`
+      entry.insert("key".into(), String::from_utf8_lossy(key).into());
+    }
+  }
+  Ok(entry)
+}
+
+/// Export make scope through `MakeOccasion` so that modules are decoded with
+/// their dependencies attached
+async fn export_make_scope(storage: &dyn Storage, codec: Arc<CacheCodec>) -> Result<Vec<Value>> {
+  let stored_sizes: HashMap<_, _> = storage
+    .load(MAKE_SCOPE)
+    .await?
+    .into_iter()
+    .map(|(key, value)| (key, value.len()))
+    .collect();
+
+  let artifact = MakeOccasion::new(codec).recovery(storage).await?;
+  let mut modules: Vec<_> = artifact
+    .module_graph
+    .modules()
+    .map(|(identifier, module)| {
+      json!({
+        "identifier": identifier.as_str(),
+        "moduleType": module.module_type().to_string(),
+        "dependencies": module.get_dependencies().len(),
+        "size": stored_sizes.get(identifier.as_bytes()).copied().unwrap_or_default(),
+      })
+    })
+    .collect();
+  modules.sort_by(|a, b| a["identifier"].as_str().cmp(&b["identifier"].as_str()));
+  Ok(modules)
+}
+
+async fn export_storage(
+  storage: &dyn Storage,
+  codec: Arc<CacheCodec>,
+  scope_filter: Option<&str>,
+) -> Result<Map<String, Value>> {
+  let mut result = Map::new();
+  for scope in storage.scopes().await? {
+    if scope_filter.is_some_and(|filter| filter != scope) {
+      continue;
+    }
+    // `Storage::load` only accepts static scope names, so scopes written by
+    // other versions of rspack can not be exported.
+    let Some((scope_name, kind)) = known_scopes().into_iter().find(|(name, _)| *name == scope)
+    else {
+      result.insert(scope, json!({ "occasion": ScopeKind::Unknown.as_str() }));
+      continue;
+    };
+
+    let entries = if kind == ScopeKind::Make {
+      export_make_scope(storage, codec.clone()).await?
+    } else {
+      storage
+        .load(scope_name)
+        .await?
+        .into_iter()
+        .map(|(key, value)| {
+          let mut entry = decode_entry(kind, &codec, &key, &value).unwrap_or_else(|err| {
+            let mut entry = Map::new();
+            entry.insert("key".into(), String::from_utf8_lossy(&key).into());
+            entry.insert("error".into(), err.to_string().into());
+            entry
+          });
+          entry.insert("storedSize".into(), value.len().into());
+          Value::Object(entry)
+        })
+        .collect()
+    };
+
+    result.insert(
+      scope,
+      json!({
+        "occasion": kind.as_str(),
+        "entries": entries,
+      }),
+    );
+  }
+  Ok(result)
+}
+
+/// Decode every entry of a cache directory through `CacheCodec` and dump it as JSON
+///
+/// `project_path` is used to restore portable paths, `scope` limits the export to one storage scope.
+pub async fn export_cache_dir(
+  path: Utf8PathBuf,
+  project_path: Option<Utf8PathBuf>,
+  scope: Option<String>,
+) -> Result<Value> {
+  export(native_fs(), &path, project_path, scope.as_deref()).await
+}
+
+async fn export(
+  fs: Arc<dyn CacheFileSystem>,
+  path: &Utf8PathBuf,
+  project_path: Option<Utf8PathBuf>,
+  scope: Option<&str>,
+) -> Result<Value> {
+  let codec = Arc::new(CacheCodec::new(Some(
+    project_path.unwrap_or_else(|| Utf8PathBuf::from("/")),
+  )));
+
+  let mut relative_paths: Vec<_> = find_relative_cache_path(&*fs, path).into_iter().collect();
+  relative_paths.sort();
+
+  let mut result = Map::new();
+  for relative_path in relative_paths {
+    let root_path = if relative_path.is_empty() {
+      path.clone()
+    } else {
+      path.join(&relative_path)
+    };
+
+    let mut root = Map::new();
+    let mut storages: Vec<_> = load_storages_from_path(fs.clone(), &root_path)
+      .into_iter()
+      .collect();
+    storages.sort_by(|(a, _), (b, _)| a.cmp(b));
+    for (cache_directory, storage) in storages {
+      let data = export_storage(&*storage, codec.clone(), scope).await?;
+      root.insert(cache_directory, Value::Object(data));
+    }
+
+    let key = if relative_path.is_empty() {
+      ".".to_string()
+    } else {
+      relative_path
+    };
+    result.insert(key, Value::Object(root));
+  }
+
+  Ok(Value::Object(result))
+}
+
+#[cfg(test)]
+mod test {
+  use rspack_core::cache::persistent::occasion::minimize;
+  use rspack_paths::Utf8PathBuf;
+  use serde_json::json;
+
+  use super::export;
+  use crate::scan::test::{V1, memory_fs, write_minimize_cache};
+
+  #[tokio::test]
+  async fn export_should_decode_entries() {
+    let fs = memory_fs();
+    write_minimize_cache(fs.clone(), "/cache", V1, "minimized").await;
+
+    let json = export(fs, &Utf8PathBuf::from("/cache"), None, None)
+      .await
+      .expect("should export cache");
+    let scope = &json["."][V1][minimize::SCOPE];
+    assert_eq!(scope["occasion"], "minimize");
+    assert_eq!(scope["entries"][0]["key"], format!("{:016x}", 1));
+    assert_eq!(scope["entries"][0]["sourceSize"], "minimized".len());
+    assert_eq!(scope["entries"][0]["extractedComments"], json!(null));
+  }
+
+  #[tokio::test]
+  async fn export_should_filter_scope() {
+    let fs = memory_fs();
+    write_minimize_cache(fs.clone(), "/cache", V1, "minimized").await;
+
+    let json = export(
+      fs,
+      &Utf8PathBuf::from("/cache"),
+      None,
+      Some("occasion_make"),
+    )
+    .await
+    .expect("should export cache");
+    assert_eq!(json, json!({ ".": { V1: {} } }));
+  }
+}
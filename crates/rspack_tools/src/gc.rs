@@ -0,0 +1,145 @@
+use std::{fmt, sync::Arc};
+
+use rspack_error::Result;
+use rspack_paths::Utf8PathBuf;
+use rspack_storage::prune_cache_directories;
+
+use crate::{
+  scan::scan_cache_dir,
+  utils::{CacheFileSystem, format_size, native_fs},
+};
+
+/// Result of pruning a cache directory
+#[derive(Debug)]
+pub struct GcReport {
+  dry_run: bool,
+  /// (storage root, compiler cache directory, size)
+  pruned: Vec<(String, String, u64)>,
+  stale_size: u64,
+}
+
+impl fmt::Display for GcReport {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    let action = if self.dry_run {
+      "Would remove"
+    } else {
+      "Removed"
+    };
+    for (root, cache_directory, size) in &self.pruned {
+      writeln!(
+        f,
+        "{action} {root}/{cache_directory} ({})",
+        format_size(*size)
+      )?;
+    }
+    let freed = self.pruned.iter().map(|(_, _, size)| size).sum::<u64>() + self.stale_size;
+    writeln!(
+      f,
+      "{action} {} expired cache directories and {} of stale data, {} in total",
+      self.pruned.len(),
+      format_size(self.stale_size),
+      format_size(freed)
+    )
+  }
+}
+
+/// Remove compiler cache directories that were not accessed in the last
+/// `max_age` seconds, together with everything left in the stale directory.
+///
+/// A `max_age` of 0 keeps every compiler cache directory.
+pub async fn gc_cache_dir(path: Utf8PathBuf, max_age: u64, dry_run: bool) -> Result<GcReport> {
+  gc(native_fs(), &path, max_age, dry_run).await
+}
+
+async fn gc(
+  fs: Arc<dyn CacheFileSystem>,
+  path: &Utf8PathBuf,
+  max_age: u64,
+  dry_run: bool,
+) -> Result<GcReport> {
+  let roots = scan_cache_dir(fs.clone(), path).await?;
+
+  let mut pruned = vec![];
+  let mut stale_size = 0;
+  for root in roots {
+    let directories =
+      prune_cache_directories(root.path.clone(), fs.clone(), max_age, dry_run).await?;
+    for directory in directories {
+      let size = root
+        .cache_directories
+        .iter()
+        .find(|info| info.name == directory)
+        .map(|info| info.size())
+        .unwrap_or_default();
+      pruned.push((root.display_path().to_string(), directory.to_string(), size));
+    }
+    stale_size += root.stale_size;
+  }
+
+  Ok(GcReport {
+    dry_run,
+    pruned,
+    stale_size,
+  })
+}
+
+#[cfg(test)]
+mod test {
+  use rspack_fs::{ReadableFileSystem, WritableFileSystem};
+  use rspack_paths::Utf8PathBuf;
+
+  use super::gc;
+  use crate::{
+    scan::test::{V1, V2, memory_fs, write_minimize_cache},
+    utils::current_timestamp,
+  };
+
+  #[tokio::test]
+  async fn gc_should_remove_expired_cache_directories() {
+    let fs = memory_fs();
+    write_minimize_cache(fs.clone(), "/cache", V1, "a").await;
+    write_minimize_cache(fs.clone(), "/cache", V2, "b").await;
+    let now = current_timestamp();
+    fs.write(
+      &Utf8PathBuf::from("/cache/_meta"),
+      format!("{V1} {}\n{V2} {now}\n", now - 100).as_bytes(),
+    )
+    .await
+    .expect("should write meta");
+    let path = Utf8PathBuf::from("/cache");
+
+    let report = gc(fs.clone(), &path, 10, true)
+      .await
+      .expect("should run gc");
+    assert_eq!(report.pruned.len(), 1);
+    assert_eq!(report.pruned[0].1, V1);
+    assert!(report.pruned[0].2 > 0);
+    assert!(
+      report
+        .to_string()
+        .starts_with(&format!("Would remove ./{V1} ("))
+    );
+    assert!(fs.metadata_sync(&path.join(V1)).is_ok());
+
+    let report = gc(fs.clone(), &path, 10, false)
+      .await
+      .expect("should run gc");
+    assert_eq!(report.pruned.len(), 1);
+    assert!(report.to_string().starts_with(&format!("Removed ./{V1} (")));
+    assert!(fs.metadata_sync(&path.join(V1)).is_err());
+    assert!(fs.metadata_sync(&path.join(V2)).is_ok());
+  }
+
+  #[tokio::test]
+  async fn gc_should_keep_cache_directories_without_max_age() {
+    let fs = memory_fs();
+    write_minimize_cache(fs.clone(), "/cache", V1, "a").await;
+    let path = Utf8PathBuf::from("/cache");
+
+    let report = gc(fs.clone(), &path, 0, false)
+      .await
+      .expect("should run gc");
+    assert!(report.pruned.is_empty());
+    assert!(fs.metadata_sync(&path.join(V1)).is_ok());
+  }
+}
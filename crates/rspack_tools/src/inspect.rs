@@ -0,0 +1,193 @@
+use std::fmt;
+
+use itertools::Itertools;
+use rspack_error::Result;
+use rspack_paths::Utf8PathBuf;
+use rustc_hash::FxHashMap as HashMap;
+
+use crate::{
+  scan::{ScopeKind, StorageRootInfo, scan_cache_dir},
+  utils::{current_timestamp, format_age, format_size, native_fs},
+};
+
+/// Tree view of every storage root, compiler cache directory, bucket and pack
+#[derive(Debug)]
+pub struct InspectReport {
+  roots: Vec<StorageRootInfo>,
+}
+
+impl fmt::Display for InspectReport {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    if self.roots.is_empty() {
+      return writeln!(f, "No persistent cache found");
+    }
+
+    let now = current_timestamp();
+    for root in &self.roots {
+      writeln!(
+        f,
+        "{} ({}, stale {})",
+        root.display_path(),
+        format_size(root.size()),
+        format_size(root.stale_size)
+      )?;
+      for cache_directory in &root.cache_directories {
+        let last_access = cache_directory
+          .last_access
+          .map(|time| format!("last access {}", format_age(now.saturating_sub(time))))
+          .unwrap_or_else(|| "untracked".to_string());
+        writeln!(
+          f,
+          "  {} ({}, {last_access})",
+          cache_directory.name,
+          format_size(cache_directory.size())
+        )?;
+        for bucket in &cache_directory.buckets {
+          writeln!(
+            f,
+            "    {} [{}] ({}, {} files)",
+            bucket.name,
+            bucket.kind.as_str(),
+            format_size(bucket.size()),
+            bucket.packs.len()
+          )?;
+          for pack in &bucket.packs {
+            writeln!(f, "      {} ({})", pack.name, format_size(pack.size))?;
+          }
+        }
+      }
+    }
+    Ok(())
+  }
+}
+
+/// List the content of a cache directory
+pub async fn inspect_cache_dir(path: Utf8PathBuf) -> Result<InspectReport> {
+  let roots = scan_cache_dir(native_fs(), &path).await?;
+  Ok(InspectReport { roots })
+}
+
+/// Space usage summary grouped by occasion
+#[derive(Debug)]
+pub struct StatsReport {
+  cache_directory_count: usize,
+  stale_size: u64,
+  occasions: Vec<(ScopeKind, u64)>,
+}
+
+impl fmt::Display for StatsReport {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    let total = self.occasions.iter().map(|(_, size)| size).sum::<u64>() + self.stale_size;
+    writeln!(
+      f,
+      "Compiler cache directories: {}",
+      self.cache_directory_count
+    )?;
+    writeln!(f, "Total size: {}", format_size(total))?;
+    writeln!(f)?;
+    writeln!(f, "{:<10} {:>12} {:>7}", "Occasion", "Size", "Share")?;
+
+    let rows = self
+      .occasions
+      .iter()
+      .map(|(kind, size)| (kind.as_str(), *size))
+      .chain(std::iter::once(("stale", self.stale_size)));
+    for (name, size) in rows {
+      let share = if total == 0 {
+        0.0
+      } else {
+        size as f64 * 100.0 / total as f64
+      };
+      writeln!(f, "{name:<10} {:>12} {share:>6.1}%", format_size(size))?;
+    }
+    Ok(())
+  }
+}
+
+/// Summarize which occasions take up space in a cache directory
+pub async fn stats_cache_dir(path: Utf8PathBuf) -> Result<StatsReport> {
+  let roots = scan_cache_dir(native_fs(), &path).await?;
+  Ok(StatsReport::new(&roots))
+}
+
+impl StatsReport {
+  fn new(roots: &[StorageRootInfo]) -> Self {
+    let mut occasions: HashMap<ScopeKind, u64> = HashMap::default();
+    for bucket in roots
+      .iter()
+      .flat_map(|root| &root.cache_directories)
+      .flat_map(|cache_directory| &cache_directory.buckets)
+    {
+      *occasions.entry(bucket.kind).or_default() += bucket.size();
+    }
+
+    Self {
+      cache_directory_count: roots.iter().map(|root| root.cache_directories.len()).sum(),
+      stale_size: roots.iter().map(|root| root.stale_size).sum(),
+      occasions: occasions
+        .into_iter()
+        .sorted_by(|(kind_a, size_a), (kind_b, size_b)| size_b.cmp(size_a).then(kind_a.cmp(kind_b)))
+        .collect(),
+    }
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use rspack_core::cache::persistent::occasion::minimize;
+  use rspack_paths::Utf8PathBuf;
+
+  use super::{InspectReport, StatsReport};
+  use crate::scan::{
+    ScopeKind, scan_cache_dir,
+    test::{V1, V2, memory_fs, write_minimize_cache},
+  };
+
+  #[tokio::test]
+  async fn inspect_should_list_cache_directories_and_buckets() {
+    let fs = memory_fs();
+    write_minimize_cache(fs.clone(), "/cache", V1, "a").await;
+    let roots = scan_cache_dir(fs, &Utf8PathBuf::from("/cache"))
+      .await
+      .expect("should scan cache");
+
+    let report = InspectReport { roots }.to_string();
+    let lines = report.lines().collect::<Vec<_>>();
+    assert!(lines[0].starts_with(". ("));
+    assert!(lines[1].starts_with(&format!("  {V1} (")));
+    assert!(lines[1].contains("last access"));
+    assert!(lines[2].starts_with(&format!("    {} [minimize] (", minimize::SCOPE)));
+  }
+
+  #[tokio::test]
+  async fn inspect_should_report_empty_directory() {
+    let roots = scan_cache_dir(memory_fs(), &Utf8PathBuf::from("/cache"))
+      .await
+      .expect("should scan cache");
+    assert_eq!(
+      InspectReport { roots }.to_string(),
+      "No persistent cache found\n"
+    );
+  }
+
+  #[tokio::test]
+  async fn stats_should_group_sizes_by_occasion() {
+    let fs = memory_fs();
+    write_minimize_cache(fs.clone(), "/cache", V1, "a").await;
+    write_minimize_cache(fs.clone(), "/cache", V2, "b").await;
+    let roots = scan_cache_dir(fs, &Utf8PathBuf::from("/cache"))
+      .await
+      .expect("should scan cache");
+    let total = roots.iter().map(|root| root.size()).sum::<u64>();
+
+    let stats = StatsReport::new(&roots);
+    assert_eq!(stats.cache_directory_count, 2);
+    assert_eq!(stats.stale_size, 0);
+    assert_eq!(stats.occasions, [(ScopeKind::Minimize, total)]);
+    assert!(
+      stats
+        .to_string()
+        .starts_with("Compiler cache directories: 2\n")
+    );
+  }
+}
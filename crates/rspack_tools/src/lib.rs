@@ -1,5 +1,14 @@
 mod compare;
 mod debug_info;
+mod export;
+mod gc;
+mod inspect;
+mod scan;
+mod source_map;
 mod utils;
 
 pub use compare::compare_cache_dir;
+pub use export::export_cache_dir;
+pub use gc::gc_cache_dir;
+pub use inspect::{inspect_cache_dir, stats_cache_dir};
+pub use source_map::{SourceMapReport, check_source_map};
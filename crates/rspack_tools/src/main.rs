@@ -1,7 +1,10 @@
 use clap::{Parser, Subcommand};
 use rspack_error::Diagnostic;
-use rspack_paths::Utf8PathBuf;
-use rspack_tools::compare_cache_dir;
+use rspack_paths::{Utf8Path, Utf8PathBuf};
+use rspack_tools::{
+  check_source_map, compare_cache_dir, export_cache_dir, gc_cache_dir, inspect_cache_dir,
+  stats_cache_dir,
+};
 
 /// Toolkit for debugging and testing rspack internals
 #[derive(Parser, Debug)]
@@ -25,6 +28,79 @@ enum Commands {
     #[arg(value_name = "CACHE2")]
     cache2: String,
   },
+
+  /// List storage roots, compiler cache directories, scopes and packs with their sizes
+  Inspect {
+    /// Path to the cache directory
+    #[arg(value_name = "CACHE")]
+    cache: String,
+  },
+
+  /// Show how much space each occasion (make, devtool, minimize, ...) takes up
+  Stats {
+    /// Path to the cache directory
+    #[arg(value_name = "CACHE")]
+    cache: String,
+  },
+
+  /// Remove compiler cache directories that have not been used recently
+  Gc {
+    /// Path to the cache directory
+    #[arg(value_name = "CACHE")]
+    cache: String,
+
+    /// Remove compiler cache directories not accessed in the last N seconds, 0 only cleans stale data
+    #[arg(long, value_name = "SECONDS", default_value_t = 0)]
+    max_age: u64,
+
+    /// Only print what would be removed
+    #[arg(long)]
+    dry_run: bool,
+  },
+
+  /// Decode cache entries and dump them as JSON
+  Export {
+    /// Path to the cache directory
+    #[arg(value_name = "CACHE")]
+    cache: String,
+
+    /// Only export the given storage scope, e.g. `occasion_minimize`
+    #[arg(long)]
+    scope: Option<String>,
+
+    /// Project root used to restore portable paths
+    #[arg(long, value_name = "PATH")]
+    project: Option<String>,
+
+    /// Write the JSON to a file instead of stdout
+    #[arg(long, short, value_name = "FILE")]
+    output: Option<String>,
+  },
+
+  /// Validate the source map of an asset, and show its mappings side by side
+  SourceMap {
+    /// Path to the generated asset
+    #[arg(value_name = "ASSET")]
+    asset: String,
+
+    /// Path to the source map, `<ASSET>.map` by default
+    #[arg(value_name = "MAP")]
+    map: Option<String>,
+
+    /// Print the generated code next to the original code of every mapping
+    #[arg(long)]
+    listing: bool,
+  },
+}
+
+fn exit_with_error(err: rspack_error::Error) -> ! {
+  eprintln!(
+    "{}",
+    Diagnostic::from(err)
+      .render_report(true)
+      .expect("render error failed")
+  );
+  std::process::exit(1);
 }
 
 #[tokio::main]
@@ -42,16 +118,69 @@ async fn main() {
       let path2 = Utf8PathBuf::from(&cache2);
 
       if let Err(err) = compare_cache_dir(path1, path2).await {
-        eprintln!(
-          "{}",
-          Diagnostic::from(err)
-            .render_report(true)
-            .expect("render error failed")
-        );
-        std::process::exit(1);
+        exit_with_error(err);
       }
 
       println!("✓ Cache directories are identical");
     }
+    Commands::Inspect { cache } => match inspect_cache_dir(Utf8PathBuf::from(&cache)).await {
+      Ok(report) => print!("{report}"),
+      Err(err) => exit_with_error(err),
+    },
+    Commands::Stats { cache } => match stats_cache_dir(Utf8PathBuf::from(&cache)).await {
+      Ok(report) => print!("{report}"),
+      Err(err) => exit_with_error(err),
+    },
+    Commands::Gc {
+      cache,
+      max_age,
+      dry_run,
+    } => match gc_cache_dir(Utf8PathBuf::from(&cache), max_age, dry_run).await {
+      Ok(report) => print!("{report}"),
+      Err(err) => exit_with_error(err),
+    },
+    Commands::Export {
+      cache,
+      scope,
+      project,
+      output,
+    } => {
+      let json = match export_cache_dir(
+        Utf8PathBuf::from(&cache),
+        project.map(Utf8PathBuf::from),
+        scope,
+      )
+      .await
+      {
+        Ok(json) => json,
+        Err(err) => exit_with_error(err),
+      };
+      let content = serde_json::to_string_pretty(&json).expect("should serialize json");
+      match output {
+        Some(output) => {
+          if let Err(err) = std::fs::write(&output, content) {
+            exit_with_error(rspack_error::error!("Failed to write {output}: {err}"));
+          }
+        }
+        None => println!("{content}"),
+      }
+    }
+    Commands::SourceMap {
+      asset,
+      map,
+      listing,
+    } => match check_source_map(
+      Utf8Path::new(&asset),
+      map.as_deref().map(Utf8Path::new),
+      listing,
+    ) {
+      Ok(report) => {
+        print!("{report}");
+        if !report.is_valid() {
+          std::process::exit(1);
+        }
+      }
+      Err(err) => exit_with_error(err),
+    },
   }
 }
@@ -0,0 +1,336 @@
+use std::sync::Arc;
+
+use rspack_core::cache::persistent::{
+  occasion::{devtool, make, minimize},
+  snapshot::SnapshotScope,
+  validation,
+};
+use rspack_error::Result;
+use rspack_fs::ReadableFileSystem;
+use rspack_paths::Utf8PathBuf;
+use rspack_storage::{CacheDirectory, STALE_DIR_NAME, load_access_times};
+use rustc_hash::FxHashMap as HashMap;
+
+use crate::{compare::find_relative_cache_path, utils::CacheFileSystem};
+
+/// The occasion a storage scope belongs to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum ScopeKind {
+  Meta,
+  Snapshot,
+  Make,
+  Devtool,
+  Minimize,
+  Unknown,
+}
+
+/// Every scope written by rspack_core's persistent cache
+pub fn known_scopes() -> [(&'static str, ScopeKind); 8] {
+  [
+    (validation::SCOPE, ScopeKind::Meta),
+    (SnapshotScope::FILE.name(), ScopeKind::Snapshot),
+    (SnapshotScope::CONTEXT.name(), ScopeKind::Snapshot),
+    (SnapshotScope::MISSING.name(), ScopeKind::Snapshot),
+    (SnapshotScope::BUILD.name(), ScopeKind::Snapshot),
+    (make::SCOPE, ScopeKind::Make),
+    (devtool::SCOPE, ScopeKind::Devtool),
+    (minimize::SCOPE, ScopeKind::Minimize),
+  ]
+}
+
+impl ScopeKind {
+  pub fn from_scope(scope: &str) -> Self {
+    known_scopes()
+      .into_iter()
+      .find_map(|(name, kind)| (name == scope).then_some(kind))
+      .unwrap_or(Self::Unknown)
+  }
+
+  pub fn as_str(&self) -> &'static str {
+    match self {
+      Self::Meta => "meta",
+      Self::Snapshot => "snapshot",
+      Self::Make => "make",
+      Self::Devtool => "devtool",
+      Self::Minimize => "minimize",
+      Self::Unknown => "unknown",
+    }
+  }
+}
+
+/// A single file inside a bucket, either a pack or the bucket metadata
+#[derive(Debug)]
+pub struct PackInfo {
+  pub name: String,
+  pub size: u64,
+}
+
+/// A bucket holds the data of one storage scope
+#[derive(Debug)]
+pub struct BucketInfo {
+  pub name: String,
+  pub kind: ScopeKind,
+  pub packs: Vec<PackInfo>,
+}
+
+impl BucketInfo {
+  pub fn size(&self) -> u64 {
+    self.packs.iter().map(|pack| pack.size).sum()
+  }
+}
+
+/// A compiler-path-specific cache directory, e.g. `rspack_v_0123456789abcdef`
+#[derive(Debug)]
+pub struct CacheDirectoryInfo {
+  pub name: CacheDirectory,
+  /// Last access timestamp in seconds recorded in the storage `_meta` file
+  pub last_access: Option<u64>,
+  pub buckets: Vec<BucketInfo>,
+}
+
+impl CacheDirectoryInfo {
+  pub fn size(&self) -> u64 {
+    self.buckets.iter().map(BucketInfo::size).sum()
+  }
+}
+
+/// A storage root, the `directory` option of the filesystem storage
+#[derive(Debug)]
+pub struct StorageRootInfo {
+  pub path: Utf8PathBuf,
+  /// Path relative to the scanned directory, empty when it is the scanned directory itself
+  pub relative_path: String,
+  pub cache_directories: Vec<CacheDirectoryInfo>,
+  /// Total size of compiler caches waiting to be deleted
+  pub stale_size: u64,
+}
+
+impl StorageRootInfo {
+  pub fn size(&self) -> u64 {
+    self
+      .cache_directories
+      .iter()
+      .map(CacheDirectoryInfo::size)
+      .sum::<u64>()
+      + self.stale_size
+  }
+
+  pub fn display_path(&self) -> &str {
+    if self.relative_path.is_empty() {
+      "."
+    } else {
+      &self.relative_path
+    }
+  }
+}
+
+fn dir_size(fs: &dyn CacheFileSystem, path: &Utf8PathBuf) -> u64 {
+  let Ok(children) = fs.read_dir_sync(path) else {
+    return 0;
+  };
+  children
+    .into_iter()
+    .map(|child| {
+      let child_path = path.join(child);
+      match fs.metadata_sync(&child_path) {
+        Ok(metadata) if metadata.is_directory => dir_size(fs, &child_path),
+        Ok(metadata) => metadata.size,
+        Err(_) => 0,
+      }
+    })
+    .sum()
+}
+
+fn scan_bucket(fs: &dyn CacheFileSystem, path: &Utf8PathBuf, name: String) -> BucketInfo {
+  let mut packs: Vec<_> = fs
+    .read_dir_sync(path)
+    .unwrap_or_default()
+    .into_iter()
+    .filter_map(|file| {
+      let metadata = fs.metadata_sync(&path.join(&file)).ok()?;
+      metadata.is_file.then_some(PackInfo {
+        name: file,
+        size: metadata.size,
+      })
+    })
+    .collect();
+  packs.sort_by(|a, b| a.name.cmp(&b.name));
+
+  BucketInfo {
+    kind: ScopeKind::from_scope(&name),
+    name,
+    packs,
+  }
+}
+
+fn scan_cache_directory(
+  fs: &dyn CacheFileSystem,
+  path: &Utf8PathBuf,
+  name: CacheDirectory,
+  last_access: Option<u64>,
+) -> CacheDirectoryInfo {
+  let mut buckets: Vec<_> = fs
+    .read_dir_sync(path)
+    .unwrap_or_default()
+    .into_iter()
+    // skip transaction temp directories
+    .filter(|bucket| !bucket.starts_with('.'))
+    .filter_map(|bucket| {
+      let bucket_path = path.join(&bucket);
+      fs.metadata_sync(&bucket_path)
+        .is_ok_and(|metadata| metadata.is_directory)
+        .then(|| scan_bucket(fs, &bucket_path, bucket))
+    })
+    .collect();
+  buckets.sort_by(|a, b| a.name.cmp(&b.name));
+
+  CacheDirectoryInfo {
+    name,
+    last_access,
+    buckets,
+  }
+}
+
+/// Find every storage root under `path` and collect the size of its content
+pub async fn scan_cache_dir(
+  fs: Arc<dyn CacheFileSystem>,
+  path: &Utf8PathBuf,
+) -> Result<Vec<StorageRootInfo>> {
+  let mut relative_paths: Vec<_> = find_relative_cache_path(&*fs, path).into_iter().collect();
+  relative_paths.sort();
+
+  let mut roots = vec![];
+  for relative_path in relative_paths {
+    let root_path = if relative_path.is_empty() {
+      path.clone()
+    } else {
+      path.join(&relative_path)
+    };
+    let access_times: HashMap<_, _> = load_access_times(root_path.clone(), fs.clone())
+      .await?
+      .into_iter()
+      .collect();
+
+    let mut cache_directories: Vec<_> = fs
+      .read_dir_sync(&root_path)
+      .unwrap_or_default()
+      .into_iter()
+      .filter_map(CacheDirectory::parse)
+      .filter(|name| {
+        fs.metadata_sync(&root_path.join(name.as_str()))
+          .is_ok_and(|metadata| metadata.is_directory)
+      })
+      .map(|name| {
+        let last_access = access_times.get(&name).copied();
+        scan_cache_directory(&*fs, &root_path.join(name.as_str()), name, last_access)
+      })
+      .collect();
+    cache_directories.sort_by(|a, b| a.name.cmp(&b.name));
+
+    roots.push(StorageRootInfo {
+      stale_size: dir_size(&*fs, &root_path.join(STALE_DIR_NAME)),
+      path: root_path,
+      relative_path,
+      cache_directories,
+    });
+  }
+
+  Ok(roots)
+}
+
+#[cfg(test)]
+pub(crate) mod test {
+  use std::sync::Arc;
+
+  use rspack_core::{
+    CompilationLogger,
+    cache::persistent::{
+      codec::CacheCodec,
+      occasion::minimize,
+      storage::{CacheDirectory, Storage, StorageOptions, create_storage},
+    },
+    rspack_sources::{RawStringSource, SourceExt},
+  };
+  use rspack_fs::{MemoryFileSystem, WritableFileSystem};
+  use rspack_paths::Utf8PathBuf;
+
+  use super::{ScopeKind, scan_cache_dir};
+  use crate::utils::CacheFileSystem;
+
+  pub const V1: &str = "rspack_v_0000000000000001";
+  pub const V2: &str = "rspack_v_0000000000000002";
+
+  pub fn memory_fs() -> Arc<dyn CacheFileSystem> {
+    Arc::new(MemoryFileSystem::default())
+  }
+
+  /// Writes a storage root at `directory` whose `cache_directory` holds one
+  /// minimize entry of `source`.
+  pub async fn write_minimize_cache(
+    fs: Arc<dyn CacheFileSystem>,
+    directory: &str,
+    cache_directory: &str,
+    source: &str,
+  ) {
+    let mut storage = create_storage(
+      StorageOptions::FileSystem {
+        directory: directory.into(),
+      },
+      CacheDirectory::parse(cache_directory).expect("should be a cache directory"),
+      0,
+      fs,
+      CompilationLogger::new("rspack_tools".into(), Default::default()),
+    );
+    let entry = minimize::Entry {
+      source: RawStringSource::from(source.to_string()).boxed(),
+      extracted_comments: None,
+    };
+    let value = CacheCodec::new(None)
+      .encode(&entry)
+      .expect("should encode entry");
+    storage.set(minimize::SCOPE, 1u64.to_ne_bytes().to_vec(), value);
+    storage.save();
+    storage.flush().await;
+  }
+
+  #[tokio::test]
+  async fn scan_should_find_nested_storage_roots() {
+    let fs = memory_fs();
+    write_minimize_cache(fs.clone(), "/cache/a", V1, "a").await;
+    write_minimize_cache(fs.clone(), "/cache/b/c", V2, "bb").await;
+    let stale_directory = Utf8PathBuf::from(format!("/cache/b/c/_stale/{V1}"));
+    fs.create_dir_all(&stale_directory)
+      .await
+      .expect("should create stale directory");
+    fs.write(&stale_directory.join("0.pack"), b"pack")
+      .await
+      .expect("should write stale pack");
+
+    let roots = scan_cache_dir(fs, &Utf8PathBuf::from("/cache"))
+      .await
+      .expect("should scan cache");
+    assert_eq!(
+      roots
+        .iter()
+        .map(|root| root.display_path())
+        .collect::<Vec<_>>(),
+      ["a", "b/c"]
+    );
+
+    let cache_directory = &roots[0].cache_directories[0];
+    assert_eq!(cache_directory.name.as_str(), V1);
+    assert!(cache_directory.last_access.is_some());
+    assert_eq!(
+      cache_directory
+        .buckets
+        .iter()
+        .map(|bucket| (bucket.name.as_str(), bucket.kind))
+        .collect::<Vec<_>>(),
+      [(minimize::SCOPE, ScopeKind::Minimize)]
+    );
+    assert!(cache_directory.size() > 0);
+
+    assert_eq!(roots[0].stale_size, 0);
+    assert_eq!(roots[1].stale_size, 4);
+  }
+}
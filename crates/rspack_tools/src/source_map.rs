@@ -0,0 +1,146 @@
+use std::fmt;
+
+use rspack_error::{Result, error};
+use rspack_paths::{Utf8Path, Utf8PathBuf};
+use rspack_sources::{
+  IndexSourceMap, SourceMap, SourceMapIssue, SourceMapSource, WithoutOriginalOptions,
+  render_listing,
+};
+
+/// Issues of the source map of an asset, with an optional listing of its mappings
+#[derive(Debug)]
+pub struct SourceMapReport {
+  map: Utf8PathBuf,
+  sections: usize,
+  issues: Vec<SourceMapIssue>,
+  listing: Option<String>,
+}
+
+impl SourceMapReport {
+  pub fn is_valid(&self) -> bool {
+    self.issues.is_empty()
+  }
+}
+
+impl fmt::Display for SourceMapReport {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    if let Some(listing) = &self.listing {
+      writeln!(f, "{listing}")?;
+    }
+    for issue in &self.issues {
+      writeln!(f, "{issue}")?;
+    }
+    let sections = if self.sections > 1 {
+      format!(" ({} sections)", self.sections)
+    } else {
+      String::new()
+    };
+    match self.issues.len() {
+      0 => writeln!(f, "No issue found in {}{sections}", self.map),
+      1 => writeln!(f, "1 issue found in {}{sections}", self.map),
+      count => writeln!(f, "{count} issues found in {}{sections}", self.map),
+    }
+  }
+}
+
+/// Validate the source map of `asset` against its code.
+///
+/// The map is read from `map`, or from `<asset>.map` by default. Both regular and index
+/// source maps are supported, an index map is flattened for the listing.
+pub fn check_source_map(
+  asset: &Utf8Path,
+  map: Option<&Utf8Path>,
+  listing: bool,
+) -> Result<SourceMapReport> {
+  let map = map.map_or_else(
+    || Utf8PathBuf::from(format!("{asset}.map")),
+    Utf8Path::to_path_buf,
+  );
+  let code =
+    std::fs::read_to_string(asset).map_err(|err| error!("Failed to read {asset}: {err}"))?;
+  let json = std::fs::read_to_string(&map).map_err(|err| error!("Failed to read {map}: {err}"))?;
+  let index_map =
+    IndexSourceMap::from_json(json).map_err(|err| error!("Failed to parse {map}: {err}"))?;
+
+  let issues = index_map.validate(&code);
+  let listing = if listing {
+    let source_map = SourceMap::from_json(index_map.flatten().to_json())
+      .map_err(|err| error!("Failed to flatten {map}: {err}"))?;
+    let source = SourceMapSource::new(WithoutOriginalOptions {
+      value: code,
+      name: asset.as_str(),
+      source_map,
+    });
+    Some(render_listing(&source))
+  } else {
+    None
+  };
+
+  Ok(SourceMapReport {
+    map,
+    sections: index_map.sections().len(),
+    issues,
+    listing,
+  })
+}
+
+#[cfg(test)]
+mod test {
+  use rspack_paths::{Utf8Path, Utf8PathBuf};
+
+  use super::check_source_map;
+
+  fn write(dir: &Utf8Path, name: &str, content: &str) -> Utf8PathBuf {
+    let path = dir.join(name);
+    std::fs::write(&path, content).expect("should write file");
+    path
+  }
+
+  #[test]
+  fn should_check_source_map() {
+    let temp_dir = tempfile::tempdir().expect("should create temp dir");
+    let dir =
+      Utf8PathBuf::from_path_buf(temp_dir.path().to_path_buf()).expect("should be UTF-8 path");
+    let asset = write(&dir, "main.js", "a;\nb;");
+    write(
+      &dir,
+      "main.js.map",
+      r#"{"version":3,"sources":["a.ts"],"sourcesContent":["a;\nb;"],"names":[],"mappings":"AAAA;AACA"}"#,
+    );
+
+    let report = check_source_map(&asset, None, false).expect("should check source map");
+    assert!(report.is_valid());
+    assert_eq!(
+      report.to_string(),
+      format!("No issue found in {dir}/main.js.map\n")
+    );
+  }
+
+  #[test]
+  fn should_check_index_source_map() {
+    let temp_dir = tempfile::tempdir().expect("should create temp dir");
+    let dir =
+      Utf8PathBuf::from_path_buf(temp_dir.path().to_path_buf()).expect("should be UTF-8 path");
+    let asset = write(&dir, "main.js", "a;b;");
+    // the second section refers to a source it doesn't have
+    let map = write(
+      &dir,
+      "index.map",
+      r#"{"version":3,"sections":[
+        {"offset":{"line":0,"column":0},"map":{"version":3,"sources":["a.ts"],"sourcesContent":["a;"],"names":[],"mappings":"AAAA"}},
+        {"offset":{"line":0,"column":2},"map":{"version":3,"sources":[],"names":[],"mappings":"AAAA"}}
+      ]}"#,
+    );
+
+    let report = check_source_map(&asset, Some(&map), true).expect("should check source map");
+    assert!(!report.is_valid());
+    let output = report.to_string();
+    assert!(output.contains("| a.ts:1:0 a;"), "{output}");
+    assert!(
+      output.ends_with(&format!(
+        "1:2: mapping refers to the missing source 0\n1 issue found in {map} (2 sections)\n"
+      )),
+      "{output}"
+    );
+  }
+}
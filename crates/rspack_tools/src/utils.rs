@@ -1,4 +1,10 @@
+use std::{
+  sync::Arc,
+  time::{SystemTime, UNIX_EPOCH},
+};
+
 use rspack_error::{Result, error};
+use rspack_fs::{IntermediateFileSystem, NativeFileSystem, ReadableFileSystem};
 use rustc_hash::FxHashSet as HashSet;
 
 use super::debug_info::DebugInfo;
@@ -38,3 +44,46 @@ where
 
   Ok(())
 }
+
+/// Format a byte count for human readers, e.g. `1.5 MiB`
+pub fn format_size(bytes: u64) -> String {
+  const UNITS: [&str; 4] = ["KiB", "MiB", "GiB", "TiB"];
+  if bytes < 1024 {
+    return format!("{bytes} B");
+  }
+  let mut size = bytes as f64 / 1024.0;
+  let mut unit = 0;
+  while size >= 1024.0 && unit < UNITS.len() - 1 {
+    size /= 1024.0;
+    unit += 1;
+  }
+  format!("{size:.1} {}", UNITS[unit])
+}
+
+/// Format a duration in seconds as a coarse age, e.g. `3d ago`
+pub fn format_age(seconds: u64) -> String {
+  match seconds {
+    0..60 => format!("{seconds}s ago"),
+    60..3600 => format!("{}m ago", seconds / 60),
+    3600..86400 => format!("{}h ago", seconds / 3600),
+    _ => format!("{}d ago", seconds / 86400),
+  }
+}
+
+/// Seconds since UNIX_EPOCH
+pub fn current_timestamp() -> u64 {
+  SystemTime::now()
+    .duration_since(UNIX_EPOCH)
+    .expect("Time went backwards")
+    .as_secs()
+}
+
+/// File system the cache directories are read from and pruned in
+pub trait CacheFileSystem: ReadableFileSystem + IntermediateFileSystem {}
+
+impl<T: ReadableFileSystem + IntermediateFileSystem> CacheFileSystem for T {}
+
+/// The file system of the subcommands
+pub fn native_fs() -> Arc<dyn CacheFileSystem> {
+  Arc::new(NativeFileSystem::new(false))
+}
@@ -66,10 +66,14 @@ impl DiskWatcher {
       },
       config,
     )
-    .expect("Failed to create disk watcher");
+    .inspect_err(|e| {
+      // e.g. the inotify instance limit is reached, every watch root is polled instead
+      tracing::warn!(target: "rspack_watcher::fs_event", "failed to create disk watcher: {e:?}");
+    })
+    .ok();
 
     DiskWatcher {
-      inner: Some(inner),
+      inner,
       watch_patterns: HashSet::default(),
     }
   }
@@ -78,11 +82,12 @@ impl DiskWatcher {
   ///
   /// # Returns
   ///
-  /// * `rspack_error::Result<()>` - Ok if successful, otherwise an error.
+  /// * `rspack_error::Result<Vec<WatchPattern>>` - The patterns that could not be
+  ///   registered with the native watcher, otherwise an error if unwatching fails.
   pub fn watch(
     &mut self,
     patterns: impl Iterator<Item = WatchPattern>,
-  ) -> rspack_error::Result<()> {
+  ) -> rspack_error::Result<Vec<WatchPattern>> {
     let new_patterns: HashSet<WatchPattern> = patterns.collect();
 
     let new_paths = new_patterns.iter().map(|p| &p.path).collect::<HashSet<_>>();
@@ -108,21 +113,32 @@ impl DiskWatcher {
       .watch_patterns
       .retain(|p| !stale_paths.contains(&p.path));
 
+    let mut unavailable_patterns = vec![];
     for pattern in new_patterns {
       if self.watch_patterns.contains(&pattern) {
         continue;
       }
 
-      if let Some(watcher) = &mut self.inner {
-        watcher
-          .watch(&pattern.path, pattern.mode)
-          .map_err(|e| rspack_error::error!(e.to_string()))?;
+      let Some(watcher) = &mut self.inner else {
+        unavailable_patterns.push(pattern);
+        continue;
+      };
+      // Registration fails on some network filesystems or when the OS watch
+      // limit is reached. The pattern is retried on the next `watch()` call.
+      if let Err(e) = watcher.watch(&pattern.path, pattern.mode) {
+        tracing::warn!(
+          target: "rspack_watcher::fs_event",
+          path = ?pattern.path,
+          "failed to watch path natively: {e:?}",
+        );
+        unavailable_patterns.push(pattern);
+        continue;
       }
 
       self.watch_patterns.insert(pattern);
     }
 
-    Ok(())
+    Ok(unavailable_patterns)
   }
 
   pub fn close(&mut self) {
@@ -1,6 +1,9 @@
-use std::sync::{
-  Arc,
-  atomic::{AtomicBool, Ordering},
+use std::{
+  sync::{
+    Arc,
+    atomic::{AtomicBool, AtomicU64, Ordering},
+  },
+  time::{Duration, Instant},
 };
 
 use rspack_util::fx_hash::FxHashSet as HashSet;
@@ -9,7 +12,7 @@ use tokio::sync::{
   mpsc::{self, UnboundedReceiver, UnboundedSender},
 };
 
-use super::{EventAggregateHandler, EventHandler, FsEventKind};
+use super::{EventAggregateHandler, EventHandler, FsEventKind, FsWatcherAggregateMode};
 use crate::EventBatch;
 
 type ThreadSafetyReceiver<T> = ThreadSafety<UnboundedReceiver<T>>;
@@ -25,6 +28,34 @@ impl FilesData {
   fn is_empty(&self) -> bool {
     self.changed.is_empty() && self.deleted.is_empty()
   }
+
+  /// Records an event. With `coalesce` the last event of a path wins, so a
+  /// removal followed by a creation of the same path is a single change.
+  fn insert(&mut self, path: String, kind: FsEventKind, coalesce: bool) {
+    match kind {
+      FsEventKind::Change | FsEventKind::Create => {
+        if coalesce {
+          self.deleted.remove(&path);
+        }
+        self.changed.insert(path);
+      }
+      FsEventKind::Remove => {
+        if coalesce {
+          self.changed.remove(&path);
+        }
+        self.deleted.insert(path);
+      }
+    }
+  }
+}
+
+/// When the aggregate task fires after the first event of a batch.
+#[derive(Debug, Clone, Copy)]
+struct AggregatePolicy {
+  timeout: Duration,
+  /// `None` fires after a single `timeout`, otherwise the timeout restarts
+  /// with every new event until this much time passed in total.
+  settle_max_wait: Option<Duration>,
 }
 
 /// `WatcherExecutor` is responsible for managing the execution of file system event handlers,
@@ -32,7 +63,11 @@ impl FilesData {
 /// a configurable aggregate timeout. It receives events from a channel, tracks changed and
 /// deleted files, and coordinates the event handling logic.
 pub struct Executor {
-  aggregate_timeout: u32,
+  aggregate_policy: AggregatePolicy,
+  coalesce_renames: bool,
+  /// Incremented for every received batch, so the aggregate task can tell
+  /// whether the tree is still changing.
+  generation: Arc<AtomicU64>,
   rx: ThreadSafetyReceiver<EventBatch>,
   files_data: ThreadSafety<FilesData>,
   exec_aggregate_tx: UnboundedSender<ExecAggregateEvent>,
@@ -47,6 +82,7 @@ pub struct Executor {
 }
 
 const DEFAULT_AGGREGATE_TIMEOUT: u32 = 50; // Default timeout in milliseconds
+const DEFAULT_SETTLE_MAX_WAIT: u32 = 5000; // Default settle cap in milliseconds
 
 /// `ExecEvent` represents control events for the watcher executor loop.
 /// - `Execute`: Indicates that an event (change or delete) has occurred and the handler should be triggered.
@@ -71,8 +107,17 @@ impl Executor {
   pub fn new(
     rx: UnboundedReceiver<EventBatch>,
     aggregate_timeout: Option<u32>,
+    aggregate_mode: FsWatcherAggregateMode,
+    coalesce_renames: bool,
     paused: Arc<AtomicBool>,
   ) -> Self {
+    let timeout = aggregate_timeout.unwrap_or(DEFAULT_AGGREGATE_TIMEOUT);
+    let settle_max_wait = match aggregate_mode {
+      FsWatcherAggregateMode::Fixed => None,
+      FsWatcherAggregateMode::Settle { max_wait } => Some(Duration::from_millis(
+        max_wait.unwrap_or(DEFAULT_SETTLE_MAX_WAIT).max(timeout) as u64,
+      )),
+    };
     let (exec_aggregate_tx, exec_aggregate_rx) = mpsc::unbounded_channel::<ExecAggregateEvent>();
     let (exec_tx, exec_rx) = mpsc::unbounded_channel::<ExecEvent>();
 
@@ -88,7 +133,12 @@ impl Executor {
       exec_tx,
       execute_aggregate_handle: None,
       execute_handle: None,
-      aggregate_timeout: aggregate_timeout.unwrap_or(DEFAULT_AGGREGATE_TIMEOUT),
+      aggregate_policy: AggregatePolicy {
+        timeout: Duration::from_millis(timeout as u64),
+        settle_max_wait,
+      },
+      coalesce_renames,
+      generation: Default::default(),
     }
   }
 
@@ -132,23 +182,19 @@ impl Executor {
       let exec_tx = self.exec_tx.clone();
       let paused = Arc::clone(&self.paused);
       let aggregate_running = Arc::clone(&self.aggregate_running);
+      let generation = Arc::clone(&self.generation);
+      let coalesce_renames = self.coalesce_renames;
 
       let future = async move {
         while let Some(events) = rx.lock().await.recv().await {
-          for event in &events {
-            let path = event.path.to_string_lossy().to_string();
-            match event.kind {
-              FsEventKind::Change => {
-                files_data.lock().await.changed.insert(path);
-              }
-              FsEventKind::Remove => {
-                files_data.lock().await.deleted.insert(path);
-              }
-              FsEventKind::Create => {
-                files_data.lock().await.changed.insert(path);
-              }
+          {
+            let mut files_data = files_data.lock().await;
+            for event in &events {
+              let path = event.path.to_string_lossy().to_string();
+              files_data.insert(path, event.kind, coalesce_renames);
             }
           }
+          generation.fetch_add(1, Ordering::Relaxed);
 
           if !paused.load(Ordering::Relaxed) && !aggregate_running.load(Ordering::Relaxed) {
             let _ = exec_aggregate_tx.send(ExecAggregateEvent::Execute);
@@ -190,7 +236,8 @@ impl Executor {
       event_aggregate_handler,
       Arc::clone(&self.exec_aggregate_rx),
       Arc::clone(&self.files_data),
-      self.aggregate_timeout as u64,
+      self.aggregate_policy,
+      Arc::clone(&self.generation),
       Arc::clone(&self.aggregate_running),
     ));
 
@@ -239,7 +286,8 @@ fn create_execute_aggregate_task(
   event_handler: Box<dyn EventAggregateHandler + Send>,
   exec_aggregate_rx: ThreadSafetyReceiver<ExecAggregateEvent>,
   files: ThreadSafety<FilesData>,
-  aggregate_timeout: u64,
+  policy: AggregatePolicy,
+  generation: Arc<AtomicU64>,
   running: Arc<AtomicBool>,
 ) -> tokio::task::JoinHandle<()> {
   let future = async move {
@@ -257,7 +305,19 @@ fn create_execute_aggregate_task(
       if let ExecAggregateEvent::Execute = aggregate_rx {
         running.store(true, Ordering::Relaxed);
         // Wait for the aggregate timeout before executing the handler
-        tokio::time::sleep(tokio::time::Duration::from_millis(aggregate_timeout)).await;
+        let started = Instant::now();
+        loop {
+          let seen = generation.load(Ordering::Relaxed);
+          tokio::time::sleep(policy.timeout).await;
+          let Some(max_wait) = policy.settle_max_wait else {
+            break;
+          };
+          // Settle: keep waiting while events arrive, up to `max_wait`.
+          let settled = generation.load(Ordering::Relaxed) == seen;
+          if settled || started.elapsed() >= max_wait {
+            break;
+          }
+        }
 
         // Get the files to process
         let files = {
@@ -278,3 +338,24 @@ fn create_execute_aggregate_task(
 
   tokio::spawn(future)
 }
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn should_coalesce_rename_pairs() {
+    let mut files = FilesData::default();
+    files.insert("a".to_string(), FsEventKind::Remove, true);
+    files.insert("a".to_string(), FsEventKind::Create, true);
+    files.insert("b".to_string(), FsEventKind::Change, true);
+    files.insert("b".to_string(), FsEventKind::Remove, true);
+    assert!(files.changed.contains("a") && !files.deleted.contains("a"));
+    assert!(files.deleted.contains("b") && !files.changed.contains("b"));
+
+    let mut files = FilesData::default();
+    files.insert("a".to_string(), FsEventKind::Remove, false);
+    files.insert("a".to_string(), FsEventKind::Create, false);
+    assert!(files.changed.contains("a") && files.deleted.contains("a"));
+  }
+}
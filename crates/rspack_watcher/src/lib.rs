@@ -3,6 +3,7 @@ mod disk_watcher;
 mod executor;
 mod ignored;
 mod paths;
+mod poller;
 mod scanner;
 mod time_info;
 mod trigger;
@@ -22,6 +23,7 @@ use disk_watcher::DiskWatcher;
 use executor::Executor;
 pub use ignored::FsWatcherIgnored;
 use paths::PathManager;
+use poller::Poller;
 use rspack_error::Result;
 use rspack_paths::ArcPath;
 use rspack_util::fx_hash::FxHashSet as HashSet;
@@ -91,6 +93,80 @@ pub struct FsWatcherOptions {
 
   /// The timeout in milliseconds to aggregate events.
   pub aggregate_timeout: Option<u32>,
+
+  /// How `aggregate_timeout` is applied to a burst of events.
+  pub aggregate_mode: FsWatcherAggregateMode,
+
+  /// Whether a path that is removed and created again within one aggregation
+  /// is reported as a single change. Editors that save through an atomic
+  /// rename and `git checkout` produce such pairs.
+  pub coalesce_renames: bool,
+
+  /// Which watch roots are polled instead of watched natively.
+  pub polling: FsWatcherPolling,
+
+  /// The maximum number of paths to stat per poll interval, unlimited if `None`.
+  pub poll_max_stats: Option<usize>,
+}
+
+/// `FsWatcherAggregateMode` decides when aggregated events are handed to the
+/// [`EventAggregateHandler`].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum FsWatcherAggregateMode {
+  /// Fire `aggregate_timeout` after the first event of a batch.
+  #[default]
+  Fixed,
+  /// Fire once no event arrived for `aggregate_timeout`, so a burst of writes
+  /// results in a single batch. `max_wait` caps the delay in milliseconds
+  /// for trees that never settle, e.g. a log file written continuously.
+  Settle { max_wait: Option<u32> },
+}
+
+/// `FsWatcherPolling` selects the watch roots that are polled by comparing
+/// mtime and size instead of being watched with native file system events.
+///
+/// Watch roots whose native registration fails are always polled.
+#[derive(Debug, Default, Clone)]
+pub enum FsWatcherPolling {
+  /// Only poll the watch roots that can't be watched natively.
+  #[default]
+  Fallback,
+  /// Poll every watch root.
+  All,
+  /// Poll the watch roots inside the given paths, e.g. network or container mounts.
+  Paths(Vec<ArcPath>),
+}
+
+impl FsWatcherPolling {
+  /// Splits `patterns` into natively watched and polled patterns.
+  fn split(&self, patterns: Vec<WatchPattern>) -> (Vec<WatchPattern>, Vec<WatchPattern>) {
+    match self {
+      Self::Fallback => (patterns, vec![]),
+      Self::All => (vec![], patterns),
+      Self::Paths(poll_paths) => {
+        let (mut polled, native): (Vec<_>, Vec<_>) = patterns.into_iter().partition(|pattern| {
+          poll_paths
+            .iter()
+            .any(|poll_path| pattern.path.starts_with(poll_path))
+        });
+        // A recursive native root can contain a poll path, e.g. the common
+        // root on macOS. The poll path is polled in addition, as native events
+        // below it can't be relied on.
+        for poll_path in poll_paths {
+          let is_contained = native.iter().any(|pattern| {
+            pattern.mode == notify::RecursiveMode::Recursive && poll_path.starts_with(&pattern.path)
+          });
+          if is_contained {
+            polled.push(WatchPattern {
+              path: poll_path.clone(),
+              mode: notify::RecursiveMode::Recursive,
+            });
+          }
+        }
+        (native, polled)
+      }
+    }
+  }
 }
 
 const WATCHER_UNAVAILABLE: &str =
@@ -120,6 +196,8 @@ pub struct FsWatcher {
 struct FsWatcherInner {
   path_manager: Arc<PathManager>,
   disk_watcher: DiskWatcher,
+  poller: Poller,
+  polling: FsWatcherPolling,
   executor: Executor,
   scanner: Scanner,
   analyzer: RecommendedAnalyzer,
@@ -138,14 +216,28 @@ impl FsWatcher {
       options.poll_interval,
       trigger.clone(),
     );
+    let poller = Poller::new(
+      Arc::clone(&path_manager),
+      trigger.clone(),
+      options.poll_interval,
+      options.poll_max_stats,
+    );
     let paused = Arc::new(AtomicBool::new(false));
-    let executor = Executor::new(rx, options.aggregate_timeout, Arc::clone(&paused));
+    let executor = Executor::new(
+      rx,
+      options.aggregate_timeout,
+      options.aggregate_mode,
+      options.coalesce_renames,
+      Arc::clone(&paused),
+    );
     let scanner = Scanner::new(tx, Arc::clone(&path_manager));
     let trigger = Arc::new(Mutex::new(Some(trigger)));
 
     let inner = FsWatcherInner {
       path_manager,
       disk_watcher,
+      poller,
+      polling: options.polling,
       executor,
       scanner,
       analyzer: RecommendedAnalyzer::default(),
@@ -321,6 +413,7 @@ impl FsWatcherInner {
 
   async fn close(&mut self) -> Result<()> {
     self.disk_watcher.close();
+    self.poller.close();
     self.scanner.close();
     self.executor.close().await;
     self.trigger.lock().expect("should lock trigger").take();
@@ -357,7 +450,10 @@ impl FsWatcherInner {
     self.record_initial_file_mtimes();
 
     let watch_patterns = self.analyzer.analyze(self.path_manager.access());
-    self.disk_watcher.watch(watch_patterns.into_iter())?;
+    let (native_patterns, mut polled_patterns) = self.polling.split(watch_patterns);
+    // Fall back to polling for the roots the native watcher can't register.
+    polled_patterns.extend(self.disk_watcher.watch(native_patterns.into_iter())?);
+    self.poller.watch(polled_patterns.into_iter());
 
     // Scan AFTER the disk watcher is registered, not before. notify's `watch()`
     // registers the underlying inotify/FSEvents watch synchronously, so once it
@@ -0,0 +1,271 @@
+use std::{
+  sync::{Arc, Mutex},
+  time::Duration,
+};
+
+use rspack_paths::{ArcPath, ArcPathDashMap};
+use tokio::task::JoinHandle;
+
+use crate::{FsEventKind, WatchPattern, paths::PathManager, time_info, trigger::Trigger};
+
+/// Default interval between two poll ticks, in milliseconds.
+const DEFAULT_POLL_INTERVAL: u64 = 1000;
+
+/// The part of a stat result the poller compares between ticks.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct PollStat {
+  mtime: u64,
+  size: u64,
+}
+
+fn poll_stat(path: &ArcPath) -> Option<PollStat> {
+  let metadata = path.metadata().ok()?;
+  let mtime = metadata.modified().or_else(|_| metadata.created()).ok()?;
+  Some(PollStat {
+    mtime: time_info::system_time_to_millis(mtime),
+    size: metadata.len(),
+  })
+}
+
+/// Whether `path` is reported by a watch of `pattern`.
+fn is_covered_by(pattern: &WatchPattern, path: &ArcPath) -> bool {
+  match pattern.mode {
+    notify::RecursiveMode::Recursive => path.starts_with(&pattern.path),
+    notify::RecursiveMode::NonRecursive => {
+      path == &pattern.path || path.parent() == Some(pattern.path.as_ref())
+    }
+  }
+}
+
+/// State shared between the [`Poller`] and its background task.
+struct PollerState {
+  path_manager: Arc<PathManager>,
+  trigger: Arc<Trigger>,
+  /// Watch roots that are polled instead of watched natively.
+  patterns: Mutex<Vec<WatchPattern>>,
+  /// Last observed stat of every polled path, `None` for a path missing on disk.
+  snapshots: ArcPathDashMap<Option<PollStat>>,
+  max_stats_per_tick: usize,
+}
+
+impl PollerState {
+  /// Stats the next `max_stats_per_tick` polled paths, starting at `cursor`,
+  /// and returns the cursor for the next tick.
+  fn tick(&self, cursor: usize) -> usize {
+    let patterns = self.patterns.lock().expect("should lock patterns");
+    if patterns.is_empty() {
+      self.snapshots.clear();
+      return 0;
+    }
+
+    let accessor = self.path_manager.access();
+    // Sorted so the cursor walks the same order across ticks.
+    let mut paths = accessor
+      .all()
+      .filter(|path| patterns.iter().any(|pattern| is_covered_by(pattern, path)))
+      .collect::<Vec<_>>();
+    drop(patterns);
+    paths.sort_unstable_by(|a, b| a.as_ref().cmp(b.as_ref()));
+    paths.dedup();
+    self.snapshots.retain(|path, _| {
+      paths
+        .binary_search_by(|p| p.as_ref().cmp(path.as_ref()))
+        .is_ok()
+    });
+
+    if paths.is_empty() {
+      return 0;
+    }
+
+    let count = self.max_stats_per_tick.min(paths.len());
+    for index in 0..count {
+      let path = &paths[(cursor + index) % paths.len()];
+      let current = poll_stat(path);
+      let previous = self.snapshots.insert(path.clone(), current);
+
+      // The first stat only records a baseline. Changes before that are
+      // reported by the `Scanner`, which compares against the watch start time.
+      let kind = match (previous, current) {
+        (None, _) => continue,
+        (Some(None), Some(_)) => FsEventKind::Create,
+        (Some(Some(_)), None) => FsEventKind::Remove,
+        (Some(Some(previous)), Some(current)) if previous != current => FsEventKind::Change,
+        _ => continue,
+      };
+      self.trigger.on_polled_event(path, kind);
+    }
+    (cursor + count) % paths.len()
+  }
+}
+
+/// `Poller` detects changes below watch roots that can't be watched natively,
+/// such as NFS, SMB or some container bind mounts, by comparing the mtime and
+/// size of the registered paths on an interval.
+///
+/// Only registered files, directories and missing paths are stat'd. A new file
+/// in a registered directory is noticed through the directory mtime, but a
+/// content change of an unregistered file is not.
+pub struct Poller {
+  state: Arc<PollerState>,
+  interval: Duration,
+  handle: Option<JoinHandle<()>>,
+}
+
+impl Poller {
+  /// Creates a new `Poller`. `max_stats_per_tick` caps how many paths are
+  /// stat'd per tick, the remaining paths are checked on the following ticks.
+  ///
+  /// A `poll_interval` of `0` means the default interval, as a zero period
+  /// would make the poll task spin.
+  pub fn new(
+    path_manager: Arc<PathManager>,
+    trigger: Arc<Trigger>,
+    poll_interval: Option<u32>,
+    max_stats_per_tick: Option<usize>,
+  ) -> Self {
+    Self {
+      state: Arc::new(PollerState {
+        path_manager,
+        trigger,
+        patterns: Default::default(),
+        snapshots: Default::default(),
+        max_stats_per_tick: max_stats_per_tick.unwrap_or(usize::MAX).max(1),
+      }),
+      interval: Duration::from_millis(
+        poll_interval
+          .filter(|interval| *interval > 0)
+          .map_or(DEFAULT_POLL_INTERVAL, u64::from),
+      ),
+      handle: None,
+    }
+  }
+
+  /// Replaces the polled watch roots, starting the poll task on first use.
+  pub fn watch(&mut self, patterns: impl Iterator<Item = WatchPattern>) {
+    let patterns = patterns.collect::<Vec<_>>();
+    if !patterns.is_empty() {
+      tracing::debug!(
+        target: "rspack_watcher::poller",
+        roots = ?patterns.iter().map(|pattern| &pattern.path).collect::<Vec<_>>(),
+        "polling watch roots",
+      );
+    }
+    let is_empty = patterns.is_empty();
+    *self.state.patterns.lock().expect("should lock patterns") = patterns;
+
+    if self.handle.is_none() && !is_empty {
+      let state = Arc::clone(&self.state);
+      let interval = self.interval;
+      self.handle = Some(tokio::spawn(async move {
+        let mut ticker = tokio::time::interval(interval);
+        ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+        let mut cursor = 0;
+        loop {
+          ticker.tick().await;
+          cursor = state.tick(cursor);
+        }
+      }));
+    }
+  }
+
+  pub fn close(&mut self) {
+    if let Some(handle) = self.handle.take() {
+      handle.abort();
+    }
+  }
+}
+
+impl Drop for Poller {
+  fn drop(&mut self) {
+    self.close();
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use tokio::sync::mpsc;
+
+  use super::*;
+  use crate::FsEvent;
+
+  fn create_state(
+    path_manager: Arc<PathManager>,
+  ) -> (PollerState, mpsc::UnboundedReceiver<Vec<FsEvent>>) {
+    let (tx, rx) = mpsc::unbounded_channel();
+    let trigger = Arc::new(Trigger::new(Arc::clone(&path_manager), tx));
+    let state = PollerState {
+      path_manager,
+      trigger,
+      patterns: Default::default(),
+      snapshots: Default::default(),
+      max_stats_per_tick: 1,
+    };
+    (state, rx)
+  }
+
+  #[test]
+  fn should_report_changes_with_limited_stats_per_tick() {
+    let dir = tempfile::tempdir().expect("create temp dir");
+    let root = dir.path().canonicalize().expect("canonicalize temp dir");
+    let a = ArcPath::from(root.join("a.js"));
+    let b = ArcPath::from(root.join("b.js"));
+    std::fs::write(a.as_ref(), b"a").expect("write a");
+    std::fs::write(b.as_ref(), b"b").expect("write b");
+
+    let path_manager = Arc::new(PathManager::default());
+    path_manager
+      .update(
+        (vec![a.clone(), b.clone()].into_iter(), std::iter::empty()),
+        (std::iter::empty(), std::iter::empty()),
+        (std::iter::empty(), std::iter::empty()),
+      )
+      .expect("register files");
+    let (state, mut rx) = create_state(path_manager);
+    *state.patterns.lock().expect("should lock patterns") = vec![WatchPattern {
+      path: ArcPath::from(root.clone()),
+      mode: notify::RecursiveMode::Recursive,
+    }];
+
+    // one stat per tick: two ticks record the baselines of a.js and b.js
+    let cursor = state.tick(0);
+    let cursor = state.tick(cursor);
+    assert_eq!(state.snapshots.len(), 2);
+    assert!(rx.try_recv().is_err());
+
+    // a size change is detected even if the coarse mtime is unchanged
+    std::fs::write(b.as_ref(), b"bb").expect("write b");
+    std::fs::remove_file(a.as_ref()).expect("remove a");
+    let cursor = state.tick(cursor);
+    let events = rx.try_recv().expect("should report removed a.js");
+    assert!(events.contains(&FsEvent {
+      path: a.clone(),
+      kind: FsEventKind::Remove,
+    }));
+    state.tick(cursor);
+    let events = rx.try_recv().expect("should report changed b.js");
+    assert!(events.contains(&FsEvent {
+      path: b.clone(),
+      kind: FsEventKind::Change,
+    }));
+  }
+
+  #[test]
+  fn should_use_the_default_interval_for_zero() {
+    let path_manager = Arc::new(PathManager::default());
+    let (tx, _rx) = mpsc::unbounded_channel();
+    let trigger = Arc::new(Trigger::new(Arc::clone(&path_manager), tx));
+
+    let poller = Poller::new(
+      Arc::clone(&path_manager),
+      Arc::clone(&trigger),
+      Some(0),
+      None,
+    );
+    assert_eq!(
+      poller.interval,
+      Duration::from_millis(DEFAULT_POLL_INTERVAL)
+    );
+    let poller = Poller::new(path_manager, trigger, Some(50), None);
+    assert_eq!(poller.interval, Duration::from_millis(50));
+  }
+}
@@ -150,6 +150,25 @@ impl Trigger {
     let associated_event = finder.find_associated_event(path, kind);
     self.trigger_events(associated_event);
   }
+  /// Called when the poller observed a changed stat of a registered path.
+  ///
+  /// Unlike [`Trigger::on_event`] the mtime based stale event filter is not
+  /// applied, the poller already compared mtime and size against its last
+  /// stat, and a size change with an unchanged coarse mtime must go through.
+  pub fn on_polled_event(&self, path: &ArcPath, kind: FsEventKind) {
+    if self.path_manager.is_ignored_path(path.as_ref()) {
+      return;
+    }
+
+    // Advance the baseline so a late native event for the same write is
+    // still recognized as stale.
+    self.path_manager.has_mtime_changed(path);
+
+    let finder = self.finder();
+    let associated_event = finder.find_associated_event(path, kind);
+    self.trigger_events(associated_event);
+  }
+
   /// Helper to construct a `DependencyFinder` for the current path register state.
   fn finder(&self) -> DependencyFinder<'_> {
     let accessor = self.path_manager.access();
@@ -3,7 +3,7 @@
 use std::sync::atomic::AtomicU8;
 
 use rspack_paths::ArcPath;
-use rspack_watcher::{FsWatcher, FsWatcherOptions};
+use rspack_watcher::{FsWatcher, FsWatcherAggregateMode, FsWatcherOptions, FsWatcherPolling};
 
 mod helpers;
 
@@ -173,3 +173,77 @@ fn should_emit_remove_when_a_watched_file_is_deleted() {
     },
   );
 }
+
+#[test]
+fn should_poll_a_single_file() {
+  let mut helper = h!(FsWatcherOptions {
+    aggregate_timeout: Some(100),
+    poll_interval: Some(50),
+    polling: FsWatcherPolling::All,
+    ..Default::default()
+  });
+
+  helper.file("a");
+
+  let rx = watch!(helper, "a");
+
+  // wait for the first poll to record the baseline
+  helper.tick(|| {
+    helper.file("a");
+  });
+
+  let change_events = c!();
+  helper.collect_events(
+    rx,
+    |file, _| {
+      file.assert_path(helper.join("a"));
+      add!(change_events);
+    },
+    |changes, abort| {
+      changes.assert_changed(helper.join("a"));
+      assert!(load!(change_events) > 0);
+      *abort = true;
+    },
+  );
+}
+
+#[test]
+fn should_settle_a_burst_of_writes_into_one_batch() {
+  let mut helper = h!(FsWatcherOptions {
+    aggregate_timeout: Some(100),
+    aggregate_mode: FsWatcherAggregateMode::Settle { max_wait: None },
+    ..Default::default()
+  });
+
+  helper.file("a");
+  helper.file("b");
+
+  let rx = watch!(helper, "a", "b");
+
+  // A burst longer than the aggregate timeout, but with shorter gaps, then a
+  // single write to `b` once the tree settled.
+  helper.tick(|| {
+    for _ in 0..5 {
+      helper.file("a");
+      std::thread::sleep(std::time::Duration::from_millis(40));
+    }
+    std::thread::sleep(std::time::Duration::from_millis(300));
+    helper.file("b");
+  });
+
+  let aggregated_events = c!();
+  helper.collect_events(
+    rx,
+    |_, _| {},
+    |changes, abort| {
+      add!(aggregated_events);
+      if changes.changed_files.contains(helper.join("b").as_str()) {
+        *abort = true;
+      } else {
+        changes.assert_changed(helper.join("a"));
+      }
+    },
+  );
+  // one batch for the burst and one for `b`
+  assert_eq!(load!(aggregated_events), 2);
+}
@@ -78,6 +78,7 @@ self.fetch = async url => {
 			status: 200,
 			ok: true,
 			arrayBuffer() { return buffer; },
+			text: async () => buffer.toString("utf-8"),
 			json: async () => JSON.parse(buffer.toString("utf-8"))
 		};
 	} catch(err) {
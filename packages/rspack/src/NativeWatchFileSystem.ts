@@ -1,6 +1,7 @@
 import { EventEmitter } from 'node:events';
 import binding from '@rspack/binding';
 import type Watchpack from 'watchpack';
+import type { WatchOptions } from './config';
 import type {
   FileSystemInfoEntry,
   InputFileSystem,
@@ -107,7 +108,7 @@ export default class NativeWatchFileSystem implements WatchFileSystem {
       removed?: Iterable<string>;
     },
     startTime: number,
-    options: Watchpack.WatchOptions,
+    options: WatchOptions,
     callback: (
       error: Error | null,
       fileTimeInfoEntries: Map<string, FileSystemInfoEntry | 'ignore'>,
@@ -237,7 +238,7 @@ export default class NativeWatchFileSystem implements WatchFileSystem {
     };
   }
 
-  getNativeWatcher(options: Watchpack.WatchOptions): binding.NativeWatcher {
+  getNativeWatcher(options: WatchOptions): binding.NativeWatcher {
     if (this.#inner) {
       return this.#inner;
     }
@@ -245,7 +246,15 @@ export default class NativeWatchFileSystem implements WatchFileSystem {
     const nativeWatcherOptions: binding.NativeWatcherOptions = {
       followSymlinks: options.followSymlinks,
       aggregateTimeout: options.aggregateTimeout,
-      pollInterval: typeof options.poll === 'boolean' ? 0 : options.poll,
+      aggregateSettle: options.aggregateSettle,
+      coalesceRenames: options.coalesceRenames,
+      // `poll: true` polls with the default interval of the native watcher
+      pollInterval: typeof options.poll === 'number' ? options.poll : undefined,
+      polling:
+        options.poll === true || typeof options.poll === 'number'
+          ? true
+          : options.pollPaths,
+      pollMaxStats: options.pollMaxStats,
       ignored: toJsWatcherIgnored(options.ignored),
     };
     const nativeWatcher = new binding.NativeWatcher(nativeWatcherOptions);
@@ -35,6 +35,25 @@ export type CircularDependencyRspackPluginOptions = {
    * it is tested against the entire identifier.
    */
   ignoredConnections?: [string | RegExp, string | RegExp][];
+  /**
+   * Groups cycles into strongly connected components and reports one
+   * diagnostic per component, with its size and shortest cycle, instead of
+   * every cycle reachable from each entrypoint.
+   */
+  components?: {
+    /**
+     * Path of a baseline written to `filename` by a previous build, resolved
+     * against the context. Components recorded in it are reported as
+     * warnings, only new components and components that grew are reported
+     * as errors with `failOnError`.
+     */
+    baseline?: string;
+    /**
+     * Emits the components found by the build as a JSON asset, which can be
+     * committed and used as the `baseline` of later builds.
+     */
+    filename?: string;
+  };
   /**
    * Called once for every detected cycle. Providing this handler overrides the
    * default behavior of adding diagnostics to the compilation.
@@ -76,12 +95,14 @@ export class CircularDependencyRspackPlugin extends RspackBuiltinPlugin {
   }
 
   raw(compiler: Compiler): BuiltinPlugin {
-    const { failOnError, exclude, ignoredConnections } = this._options;
+    const { failOnError, exclude, ignoredConnections, components } =
+      this._options;
 
     const rawOptions: RawCircularDependencyRspackPluginOptions = {
       failOnError,
       exclude,
       ignoredConnections,
+      components,
       onDetected: this._options.onDetected
         ? (entripoint: Module, modules: string[]) => {
             const compilation: Compilation =
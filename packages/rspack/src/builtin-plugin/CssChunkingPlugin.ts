@@ -6,6 +6,11 @@ export interface CssChunkingPluginOptions {
   strict?: boolean;
   minSize?: number;
   maxSize?: number;
+  /**
+   * Emit a JSON report of the CSS modules which can't be merged because they are imported in
+   * opposite orders. `true` emits it as `css-chunking-report.json`.
+   */
+  report?: boolean | string;
   /**
    * This plugin is intended to be generic, but currently requires some special handling for Next.js.
    * A `next` option has been added to accommodate this.
@@ -19,12 +24,17 @@ export const CssChunkingPlugin = create(
   function (
     options: CssChunkingPluginOptions = {},
   ): binding.RawCssChunkingPluginOptions {
+    const report =
+      options.report === true
+        ? 'css-chunking-report.json'
+        : options.report || undefined;
     if (options.nextjs) {
       return {
         strict: options.strict,
         minSize: options.minSize,
         maxSize: options.maxSize,
         exclude: /^pages\//,
+        report,
       };
     }
     const { splitChunks } = this.options.optimization;
@@ -40,6 +50,11 @@ export const CssChunkingPlugin = create(
         splitChunks.defaultSizeTypes!.splice(cssIndex, 1);
       }
     }
-    return options;
+    return {
+      strict: options.strict,
+      minSize: options.minSize,
+      maxSize: options.maxSize,
+      report,
+    };
   },
 );
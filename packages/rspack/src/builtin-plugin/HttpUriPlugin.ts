@@ -35,10 +35,14 @@ export type HttpUriPluginOptions = {
   //  * Specify the proxy server to use for fetching remote resources
   //  */
   // proxy?: string;
-  // /**
-  //  * Freeze the remote resources and lockfile. Any modification to the lockfile or resource contents will result in an error
-  //  */
-  // frozen?: boolean;
+  /**
+   * Freeze the remote resources and lockfile. Any modification to the lockfile or resource contents will result in an error
+   */
+  frozen?: boolean;
+  /**
+   * Remove the lockfile entries of remote resources that are no longer used by the build
+   */
+  pruneLockfile?: boolean;
   /**
    * Custom http client
    */
@@ -180,7 +184,8 @@ export class HttpUriPlugin extends RspackBuiltinPlugin {
       lockfileLocation,
       cacheLocation,
       upgrade: options.upgrade ?? false,
-      // frozen: options.frozen,
+      frozen: options.frozen ?? false,
+      pruneLockfile: options.pruneLockfile ?? false,
       // proxy: options.proxy,
       httpClient: options.httpClient ?? defaultHttpClient,
     };
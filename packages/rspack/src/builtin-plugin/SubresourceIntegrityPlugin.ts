@@ -66,13 +66,20 @@ export type SubresourceIntegrityPluginOptions = {
   ];
   htmlPlugin?: string | false;
   enabled?: 'auto' | boolean;
+  /**
+   * Emits a manifest mapping every asset to its integrity,
+   * `true` emits it as `sri-manifest.json`.
+   * @default false
+   */
+  manifest?: boolean | string;
 };
 
 export type NativeSubresourceIntegrityPluginOptions = Omit<
   RawSubresourceIntegrityPluginOptions,
-  'htmlPlugin'
+  'htmlPlugin' | 'manifest'
 > & {
   htmlPlugin: string | false;
+  manifest: boolean | string;
 };
 
 /**
@@ -92,10 +99,17 @@ const NativeSubresourceIntegrityPlugin = create(
     } else if (typeof options.htmlPlugin === 'string') {
       htmlPlugin = 'JavaScript';
     }
+    let manifest: string | undefined;
+    if (options.manifest === true) {
+      manifest = 'sri-manifest.json';
+    } else if (typeof options.manifest === 'string') {
+      manifest = options.manifest;
+    }
     return {
       hashFuncNames: options.hashFuncNames,
       htmlPlugin,
       integrityCallback: options.integrityCallback,
+      manifest,
     };
   },
 );
@@ -113,6 +127,7 @@ export class SubresourceIntegrityPlugin extends NativeSubresourceIntegrityPlugin
       hashFuncNames: options.hashFuncNames ?? ['sha384'],
       htmlPlugin: options.htmlPlugin ?? NATIVE_HTML_PLUGIN,
       enabled: options.enabled ?? 'auto',
+      manifest: options.manifest ?? false,
     };
     super({
       ...finalOptions,
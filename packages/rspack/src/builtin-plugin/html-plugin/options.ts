@@ -92,6 +92,20 @@ export type HtmlRspackPluginOptions = {
    */
   hash?: boolean;
 
+  /**
+   * If `true` then undefined variables in the template are reported as errors with their location.
+   * If `false` then they render nothing, like EJS.
+   * @default true
+   */
+  strict?: boolean;
+
+  /**
+   * If `true` then the CSS rules used by the generated HTML are inlined in a `<style>` tag,
+   * and the stylesheets are loaded asynchronously.
+   * @default false
+   */
+  criticalCss?: boolean;
+
   /**
    * Any other options will be passed by hooks.
    */
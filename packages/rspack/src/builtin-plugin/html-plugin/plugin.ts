@@ -212,6 +212,8 @@ const HtmlRspackPluginImpl = create(
       excludeChunks: c.excludeChunks,
       chunksSortMode,
       minify: c.minify,
+      strict: c.strict,
+      criticalCss: c.criticalCss,
       meta,
       scriptLoading,
       inject,
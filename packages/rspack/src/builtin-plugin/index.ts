@@ -42,6 +42,7 @@ export * from './FlagDependencyExportsPlugin';
 export * from './FlagDependencyUsagePlugin';
 export * from './HashedModuleIdsPlugin';
 export * from './HotModuleReplacementPlugin';
+export * from './HtmlModulesPlugin';
 export * from './HttpExternalsRspackPlugin';
 export * from './HttpUriPlugin';
 export * from './html-plugin/index';
@@ -87,5 +88,6 @@ export * from './SplitChunksPlugin';
 export * from './SubresourceIntegrityPlugin';
 export * from './SwcJsMinimizerPlugin';
 export * from './SyncModuleIdsPlugin';
+export * from './SyncWebAssemblyModulesPlugin';
 export * from './URLPlugin';
 export * from './WorkerPlugin';
@@ -143,6 +143,7 @@ function getRawCache(cache: CacheNormalized): RawOptions['cache'] {
       type: cache.storage.type,
       // Raw `directory` expects the final cache path; normalized `directory` is only the base.
       directory: cache.storage.location!,
+      url: cache.storage.url,
     },
     snapshot: {
       immutablePaths: cache.snapshot.immutablePaths!,
@@ -938,7 +939,10 @@ function getRawStats(stats: StatsValue): RawOptions['stats'] {
     statsOptions.colors === undefined
       ? isStatsColorSupported()
       : Boolean(statsOptions.colors);
+  // the traces of the resolve errors are only collected when they are shown
+  const errorDetails = Boolean(statsOptions.errorDetails ?? statsOptions.all);
   return {
     colors,
+    errorDetails,
   };
 }
@@ -132,6 +132,8 @@ export const applyRspackOptionsDefaults = (
 
   applyModuleDefaults(options.module, {
     asyncWebAssembly: options.experiments.asyncWebAssembly!,
+    syncWebAssembly: options.experiments.syncWebAssembly,
+    html: options.experiments.html,
     targetProperties,
     mode: options.mode,
     uniqueName: options.output.uniqueName,
@@ -291,7 +293,8 @@ const applyExperimentsDefaults = (
   D(experiments, 'buildHttp', undefined);
   if (experiments.buildHttp && typeof experiments.buildHttp === 'object') {
     D(experiments.buildHttp, 'upgrade', false);
-    // D(experiments.buildHttp, "frozen", false);
+    D(experiments.buildHttp, 'frozen', false);
+    D(experiments.buildHttp, 'pruneLockfile', false);
   }
 
   // Enable `useInputFileSystem` will introduce much more fs overheads,  So disable by default.
@@ -436,6 +439,8 @@ const applyModuleDefaults = (
   module: ModuleOptions,
   {
     asyncWebAssembly,
+    syncWebAssembly,
+    html,
     targetProperties,
     mode,
     uniqueName,
@@ -446,6 +451,8 @@ const applyModuleDefaults = (
     hashSalt,
   }: {
     asyncWebAssembly: boolean;
+    syncWebAssembly?: boolean;
+    html?: boolean;
     targetProperties: false | TargetProperties;
     mode?: Mode;
     uniqueName?: string;
@@ -598,9 +605,15 @@ const applyModuleDefaults = (
       },
     ];
 
-    if (asyncWebAssembly) {
+    // async modules take precedence
+    const wasmModuleType = asyncWebAssembly
+      ? 'webassembly/async'
+      : syncWebAssembly
+        ? 'webassembly/sync'
+        : undefined;
+    if (wasmModuleType) {
       const wasm = {
-        type: 'webassembly/async',
+        type: wasmModuleType,
         rules: [
           {
             descriptionData: {
@@ -622,6 +635,17 @@ const applyModuleDefaults = (
       });
     }
 
+    if (html) {
+      rules.push({
+        test: /\.html$/,
+        type: 'html',
+      });
+      rules.push({
+        mimetype: 'text/html',
+        type: 'html',
+      });
+    }
+
     rules.push(
       {
         dependency: 'url',
@@ -327,6 +327,7 @@ export const getNormalizedRspackOptions = (
               location: optionalNestedConfig(storage.location, (location) =>
                 path.resolve(context, location),
               ),
+              url: storage.url,
             })),
           };
         }
@@ -650,9 +651,10 @@ export type CacheNormalized =
         managedPaths?: (string | RegExp)[];
       };
       storage: {
-        type: 'filesystem';
+        type: 'filesystem' | 'remote';
         directory?: string;
         location?: string;
+        url?: string;
       };
       portable?: boolean;
       readonly?: boolean;
@@ -660,7 +662,9 @@ export type CacheNormalized =
 
 export interface ExperimentsNormalized {
   asyncWebAssembly?: boolean;
+  syncWebAssembly?: boolean;
   css?: boolean;
+  html?: boolean;
   futureDefaults?: boolean;
   newCache?: false | NewCache;
   buildHttp?: HttpUriPluginOptions;
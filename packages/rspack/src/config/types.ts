@@ -791,6 +791,12 @@ export type ResolveOptions = {
   /** The replacement of [tsconfig-paths-webpack-plugin](https://www.npmjs.com/package/tsconfig-paths-webpack-plugin) in Rspack. */
   tsConfig?: ResolveTsConfig;
 
+  /**
+   * Absolute path to an [import map](https://html.spec.whatwg.org/multipage/webappapis.html#import-maps) file.
+   * The specifiers listed in its `imports` and `scopes` are remapped before being resolved.
+   */
+  importMap?: string;
+
   /**
    * No longer resolve extensions, no longer resolve mainFiles in package.json (but does not affect requests from mainFiles, browser, alias).
    * @default false
@@ -2057,9 +2063,14 @@ export type CacheSnapshotOptions = {
  */
 export type CacheStorageOptions = {
   /**
-   * Storage type, currently only supports 'filesystem'.
+   * Storage type.
+   * - 'filesystem': the cache is stored in `location`.
+   * - 'remote': the cache is stored in `location` and shared with other machines
+   *   through the HTTP store at `url`. The build continues with the local cache
+   *   when the store can't be reached.
+   * @default 'filesystem'
    */
-  type: 'filesystem';
+  type: 'filesystem' | 'remote';
   /**
    * Base directory for the cache.
    * @default 'node_modules/.cache/rspack'
@@ -2070,6 +2081,11 @@ export type CacheStorageOptions = {
    * @default '<directory>/<cache.name>'
    */
   location?: string;
+  /**
+   * Base url of the content-addressed HTTP store, required by the 'remote' type.
+   * Only `http:` urls are supported.
+   */
+  url?: string;
 };
 
 /**
@@ -3103,6 +3119,19 @@ export type Experiments = {
    * @default false
    */
   asyncWebAssembly?: boolean;
+  /**
+   * Enable sync WebAssembly.
+   * Support the old WebAssembly like in webpack 4, a WebAssembly module is instantiated when the async chunk containing it is loaded.
+   * `asyncWebAssembly` takes precedence for `.wasm` files when both are enabled, use `type: "webassembly/sync"` in a rule to opt in.
+   * @default false
+   */
+  syncWebAssembly?: boolean;
+  /**
+   * Enable html modules.
+   * `.html` files can be used as entries, the scripts, stylesheets and images they reference are bundled and the html is emitted with the urls rewritten.
+   * @default false
+   */
+  html?: boolean;
   /**
    * Enable CSS support.
    *
@@ -3188,6 +3217,21 @@ export type WatchOptions = {
    */
   aggregateTimeout?: number;
 
+  /**
+   * Wait until no change happened for `aggregateTimeout` before rebuilding, so a burst of
+   * writes results in a single rebuild. A number caps the total wait in milliseconds.
+   * Only supported by the native watcher.
+   * @default false
+   */
+  aggregateSettle?: boolean | number;
+
+  /**
+   * Report a file that is removed and created again before rebuilding as a single change,
+   * e.g. for editors that save through an atomic rename. Only supported by the native watcher.
+   * @default false
+   */
+  coalesceRenames?: boolean;
+
   /**
    * Follow symlinks while looking for files.
    * This is usually not needed as Rspack already resolves symlinks ('resolve.symlinks' and 'resolve.alias').
@@ -3205,6 +3249,19 @@ export type WatchOptions = {
    */
   poll?: number | boolean;
 
+  /**
+   * Poll the watched paths inside these directories, e.g. network or container mounts,
+   * and watch the others with native file system events. Ignored when `poll` is enabled.
+   * Only supported by the native watcher.
+   */
+  pollPaths?: string[];
+
+  /**
+   * The maximum number of paths to check per poll interval, the remaining paths are
+   * checked on the following intervals. Only supported by the native watcher.
+   */
+  pollMaxStats?: number;
+
   /**
    * Stop watching when stdin stream has ended.
    */
@@ -3296,7 +3353,48 @@ export type Performance =
        * @default 512000 (500 KiB)
        */
       maxEntrypointSize?: number;
+      /**
+       * Size budgets of assets, chunks or entrypoints, checked in addition to
+       * `maxAssetSize` and `maxEntrypointSize`.
+       */
+      budgets?: PerformanceBudget[];
+      /**
+       * Filename of the emitted JSON report of all budgets.
+       */
+      reportFilename?: string;
+      /**
+       * Path of a report of a previous build. A budget that was already exceeded
+       * in it only fails when the size grows.
+       */
+      baseline?: string;
     };
+
+/**
+ * A size budget of `performance.budgets`.
+ */
+export type PerformanceBudget = {
+  /**
+   * What the budget applies to.
+   */
+  type: 'asset' | 'chunk' | 'entrypoint';
+  /**
+   * Glob of asset filenames, chunk names or entrypoint names.
+   */
+  test: string;
+  /**
+   * How the size of a file is measured.
+   * @default 'raw'
+   */
+  measure?: 'raw' | 'gzip' | 'brotli';
+  /**
+   * Size (in bytes) above which a warning is reported.
+   */
+  warning?: number;
+  /**
+   * Size (in bytes) above which an error is reported.
+   */
+  error?: number;
+};
 //#endregion
 
 export type RspackOptions = {
@@ -43,6 +43,7 @@ import {
   FlagDependencyExportsPlugin,
   FlagDependencyUsagePlugin,
   HashedModuleIdsPlugin,
+  HtmlModulesPlugin,
   HttpExternalsRspackPlugin,
   HttpUriPlugin,
   InferAsyncModulesPlugin,
@@ -69,6 +70,7 @@ import {
   SizeLimitsPlugin,
   SourceMapDevToolPlugin,
   SplitChunksPlugin,
+  SyncWebAssemblyModulesPlugin,
   URLPlugin,
   WorkerPlugin,
 } from './builtin-plugin';
@@ -238,6 +240,12 @@ export class RspackOptionsApply {
     if (options.experiments.asyncWebAssembly) {
       new AsyncWebAssemblyModulesPlugin().apply(compiler);
     }
+    if (options.experiments.syncWebAssembly) {
+      new SyncWebAssemblyModulesPlugin().apply(compiler);
+    }
+    if (options.experiments.html) {
+      new HtmlModulesPlugin().apply(compiler);
+    }
     new CssModulesPlugin().apply(compiler);
     new EntryOptionPlugin().apply(compiler);
     assertNotNill(options.context);
@@ -0,0 +1,18 @@
+import * as styles from "./style.module.css";
+
+it("should substitute imported values inside value expressions", () => {
+	expect(styles.padding).toBe("calc(8px * 2)");
+	expect(styles.border).toBe("1px solid #f00");
+	expect(styles.large).toBe("calc(calc(8px * 2) + 8px)");
+
+	const fs = require("fs");
+	const path = require("path");
+	const css = fs.readFileSync(
+		path.join(__dirname, `bundle${__STATS_I__}.css`),
+		"utf-8"
+	);
+	expect(css).toContain("padding: calc(8px * 2);");
+	expect(css).toContain("border: 1px solid #f00;");
+	expect(css).toContain("margin: calc(calc(8px * 2) + 8px);");
+	expect(css).not.toContain("__RSPACK_PLUGIN_CSS_ICSS_IMPORT__");
+});
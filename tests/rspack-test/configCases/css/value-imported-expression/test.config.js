@@ -0,0 +1,7 @@
+"use strict";
+
+module.exports = {
+	findBundle(i) {
+		return [`bundle${i}.css`, `bundle${i}.js`];
+	}
+};
@@ -0,0 +1,27 @@
+const fs = require('fs');
+const path = require('path');
+
+it('should rewrite the urls of the html', () => {
+  const html = fs.readFileSync(path.resolve(__dirname, 'page.html'), 'utf-8');
+  expect(html).not.toContain('./page.js');
+  expect(html).not.toContain('./style.css');
+  expect(html).not.toContain('./image.svg');
+  expect(html).toMatch(/<script src="[^"]+\.js"><\/script>/);
+  expect(html).toMatch(/<link rel="stylesheet" href="[^"]+\.css" \/>/);
+});
+
+it('should keep commas in the data urls of srcset', () => {
+  const html = fs.readFileSync(path.resolve(__dirname, 'page.html'), 'utf-8');
+  expect(html).toMatch(
+    /srcset="data:image\/png;base64,iVBORw0KGgo= 1x, [^"]+\.svg 2x"/,
+  );
+});
+
+it('should resolve unprefixed urls relative to the html', () => {
+  const html = fs.readFileSync(path.resolve(__dirname, 'page.html'), 'utf-8');
+  expect(html).toMatch(
+    /<img src="\/[^"]+\.svg" srcset="\/[^"]+\.svg 1x, \/[^"]+\.svg 2x" \/>/,
+  );
+  expect(html).not.toContain('logo.svg');
+  expect(html).not.toContain('image.svg');
+});
@@ -0,0 +1 @@
+document.body.className = 'page';
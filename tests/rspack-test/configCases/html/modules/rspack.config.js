@@ -0,0 +1,54 @@
+const path = require('path');
+
+class Plugin {
+  apply(compiler) {
+    compiler.hooks.compilation.tap('Test', (compilation) => {
+      compilation.hooks.processAssets.tap('Test', () => {
+        const runtimes = new Set();
+        for (const chunk of compilation.chunks) {
+          const modules = compilation.chunkGraph.getChunkModules(chunk);
+          if (
+            modules.some(
+              (module) =>
+                module.resource === path.join(__dirname, 'page.js') ||
+                module.resource === path.join(__dirname, 'style.css'),
+            )
+          ) {
+            runtimes.add([...chunk.runtime].join());
+          }
+        }
+        // the script and the stylesheet of the page share one runtime
+        expect(runtimes.size).toBe(1);
+      });
+    });
+  }
+}
+
+/** @type {import("@rspack/core").Configuration} */
+module.exports = {
+  target: 'node',
+  entry: {
+    main: './index.js',
+    page: './page.html',
+  },
+  output: {
+    filename: '[name].js',
+    publicPath: '/',
+  },
+  module: {
+    rules: [
+      {
+        test: /\.css$/,
+        type: 'css',
+      },
+      {
+        test: /\.svg$/,
+        type: 'asset/resource',
+      },
+    ],
+  },
+  experiments: {
+    html: true,
+  },
+  plugins: [new Plugin()],
+};
@@ -0,0 +1,33 @@
+const fs = require("node:fs");
+const path = require("node:path");
+
+const readManifest = name =>
+	JSON.parse(fs.readFileSync(path.join(__STATS__.outputPath, name), "utf-8"));
+
+it("should map copied files to their emitted names", () => {
+	const manifest = readManifest("copy-manifest.json");
+	expect(Object.keys(manifest).sort()).toEqual([
+		"logo.txt",
+		"main.txt",
+		"nested/icon.txt"
+	]);
+	expect(manifest["logo.txt"]).toMatch(/^images\/logo\.[0-9a-f]{8}\.txt$/);
+	expect(manifest["nested/icon.txt"]).toMatch(
+		/^images\/nested\/icon\.[0-9a-f]{8}\.txt$/
+	);
+	expect(manifest["main.txt"]).toMatch(/^fonts\/main\.[0-9a-f]{8}\.txt$/);
+
+	const assetNames = __STATS__.assets.map(({ name }) => name);
+	for (const filename of Object.values(manifest)) {
+		expect(assetNames).toContain(filename);
+		expect(
+			fs.readFileSync(path.join(__STATS__.outputPath, filename), "utf-8").trim()
+		).toBe(path.basename(filename).split(".")[0]);
+	}
+});
+
+it("should emit one manifest per name", () => {
+	expect(readManifest("plain-manifest.json")).toEqual({
+		"main.txt": "plain/main.txt"
+	});
+});
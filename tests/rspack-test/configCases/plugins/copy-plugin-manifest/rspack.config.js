@@ -0,0 +1,27 @@
+const { CopyRspackPlugin } = require('@rspack/core');
+
+module.exports = {
+  entry: './index.js',
+  target: 'node',
+  plugins: [
+    new CopyRspackPlugin({
+      patterns: [
+        {
+          from: 'assets/images',
+          to: 'images/[path][name].[contenthash:8][ext]',
+          manifest: 'copy-manifest.json',
+        },
+        {
+          from: 'assets/fonts',
+          to: 'fonts/[name].[contenthash:8][ext]',
+          manifest: 'copy-manifest.json',
+        },
+        {
+          from: 'assets/fonts',
+          to: 'plain',
+          manifest: 'plain-manifest.json',
+        },
+      ],
+    }),
+  ],
+};
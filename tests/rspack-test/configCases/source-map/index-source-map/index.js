@@ -0,0 +1,41 @@
+import { message } from "./App";
+
+it("should emit an index source map with a section for each module", async () => {
+	const path = require("path");
+	const fs = require("fs");
+	const sourceMap = require("source-map");
+
+	const source = fs.readFileSync(__filename + ".map", "utf-8");
+	const generated = fs.readFileSync(__filename, "utf-8");
+	const app = fs.readFileSync(path.resolve(CONTEXT, "./App.js"), "utf-8");
+	const map = JSON.parse(source);
+	expect(message).toBe("Hello Rspack!");
+	expect(map.version).toBe(3);
+	expect(map.file).toBe(path.basename(__filename));
+	expect(map.mappings).toBeUndefined();
+	expect(map.sections.length).toBeGreaterThan(1);
+	expect(
+		map.sections.some(section =>
+			section.map.sources.some(source => source.endsWith("./App.js"))
+		)
+	).toBe(true);
+
+	const consumer = await new sourceMap.SourceMapConsumer(map);
+	const STUB = "Hello Rspack!";
+	const { source: originalSource, line, column } = consumer.originalPositionFor(
+		positionFor(generated, STUB)
+	);
+	const { line: originalLine, column: originalColumn } = positionFor(app, STUB);
+	expect(originalSource).toMatch(/\.\/App\.js$/);
+	expect(line).toBe(originalLine);
+	expect(column).toBe(originalColumn);
+});
+
+const positionFor = (content, text) => {
+	let lines = content.split(/\r?\n/);
+	for (let i = 0; i < lines.length; i++) {
+		const column = lines[i].indexOf(text);
+		if (column >= 0) return { line: i + 1, column };
+	}
+	return null;
+};
@@ -0,0 +1,18 @@
+const { rspack } = require('@rspack/core');
+
+/** @type {import("@rspack/core").Configuration} */
+module.exports = {
+  mode: 'development',
+  devtool: false,
+  externals: ['source-map'],
+  externalsType: 'commonjs',
+  plugins: [
+    new rspack.SourceMapDevToolPlugin({
+      filename: '[file].map',
+      indexMap: true,
+    }),
+    new rspack.DefinePlugin({
+      CONTEXT: JSON.stringify(__dirname),
+    }),
+  ],
+};
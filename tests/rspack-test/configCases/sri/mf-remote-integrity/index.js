@@ -0,0 +1,5 @@
+it('should compile', async () => {});
+
+export function render() {
+  return import('app/render');
+}
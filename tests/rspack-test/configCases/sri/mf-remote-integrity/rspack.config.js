@@ -0,0 +1,53 @@
+const crypto = require('crypto');
+const {
+  Compilation,
+  SubresourceIntegrityPlugin,
+  container,
+} = require('@rspack/core');
+
+module.exports = {
+  target: 'web',
+  entry: {
+    main: './index.js',
+  },
+  output: {
+    crossOriginLoading: 'anonymous',
+  },
+  plugins: [
+    new SubresourceIntegrityPlugin(),
+    new container.ModuleFederationPlugin({
+      name: 'app',
+      filename: 'remoteEntry.js',
+      exposes: {
+        './render': './render.js',
+      },
+      remotes: {
+        app: 'app@http://localhost:3000/remoteEntry.js',
+      },
+    }),
+    {
+      apply(compiler) {
+        compiler.hooks.compilation.tap('TestPlugin', (compilation) => {
+          compilation.hooks.processAssets.tap(
+            {
+              name: 'TestPlugin',
+              stage: Compilation.PROCESS_ASSETS_STAGE_REPORT,
+            },
+            (assets) => {
+              const integrity = `sha384-${crypto
+                .createHash('sha384')
+                .update(assets['remoteEntry.js'].source())
+                .digest('base64')}`;
+              const main = assets['main.js'].source().toString();
+              // the integrity of the remote entry is computed as it is emitted by the compilation
+              expect(main).toContain('sriRemoteHashes');
+              expect(main).toContain(
+                `"http://localhost:3000/remoteEntry.js":"${integrity}"`,
+              );
+            },
+          );
+        });
+      },
+    },
+  ],
+};
@@ -0,0 +1,3 @@
+import('./dyn.js').then(() => {
+	postMessage("ok");
+});
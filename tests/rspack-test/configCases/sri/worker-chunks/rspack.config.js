@@ -0,0 +1,43 @@
+const { Compilation, SubresourceIntegrityPlugin } = require('@rspack/core');
+
+module.exports = {
+  mode: 'production',
+  target: 'web',
+  entry: {
+    main: './index.js',
+  },
+  output: {
+    crossOriginLoading: 'anonymous',
+  },
+  plugins: [
+    new SubresourceIntegrityPlugin(),
+    {
+      apply(compiler) {
+        compiler.hooks.compilation.tap('TestPlugin', (compilation) => {
+          compilation.hooks.processAssets.tap(
+            {
+              name: 'TestPlugin',
+              stage: Compilation.PROCESS_ASSETS_STAGE_REPORT,
+            },
+            (assets) => {
+              const workers = Object.keys(assets).filter(
+                (file) =>
+                  file.endsWith('.js') &&
+                  assets[file].source().toString().includes('importScripts'),
+              );
+              expect(workers).toHaveLength(1);
+              const worker = assets[workers[0]].source().toString();
+              // the chunks imported by the worker are fetched with their integrities
+              // and the verified responses are evaluated
+              expect(worker).toMatch(/sriHashes=\{[^}]*"sha384-/);
+              expect(worker).toContain('integrity:');
+              expect(worker).toMatch(/\.text\(\)/);
+              expect(worker).toContain('eval)(');
+              expect(worker).toMatch(/credentials:"same-origin"/);
+            },
+          );
+        });
+      },
+    },
+  ],
+};
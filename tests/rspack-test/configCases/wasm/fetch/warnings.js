@@ -0,0 +1,6 @@
+module.exports = [
+	/wasm\.wat\?1 has unused exports: add/,
+	/wasm\.wat\?2 has unused exports: add/,
+	/wasm\.wat\?1 has unused exports: add/,
+	/wasm\.wat\?2 has unused exports: add/
+];
@@ -0,0 +1,3 @@
+module.exports = [
+	/export 'sub' \(imported as 'sub'\) was not found in '\.\/wasm\.wat'/
+];
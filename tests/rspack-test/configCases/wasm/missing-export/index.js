@@ -0,0 +1,6 @@
+import { add, sub } from "./wasm.wat";
+
+it("should keep the existing exports working", () => {
+	expect(add(1, 2)).toBe(3);
+	expect(typeof sub).toBe("undefined");
+});
@@ -0,0 +1,22 @@
+/** @type {import("@rspack/core").Configuration} */
+module.exports = {
+  target: 'node',
+  module: {
+    parser: {
+      javascript: {
+        // missing wasm exports are errors whatever the export presence is
+        exportsPresence: false,
+      },
+    },
+    rules: [
+      {
+        test: /\.wat$/,
+        loader: 'wast-loader',
+        type: 'webassembly/async',
+      },
+    ],
+  },
+  experiments: {
+    asyncWebAssembly: true,
+  },
+};
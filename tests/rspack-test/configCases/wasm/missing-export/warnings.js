@@ -0,0 +1 @@
+module.exports = [/wasm\.wat has unused exports: getNumber/];
@@ -0,0 +1,15 @@
+it("should allow to run a sync WebAssembly module importing a WebAssembly module from another chunk", function() {
+	return import("./wasm.wat").then(function(wasm) {
+		const result = wasm.addNumber(20);
+		expect(result).toEqual(42);
+	});
+});
+
+it("should load the imported WebAssembly module when its chunk is loaded first", function() {
+	return import("./wasm2.wat").then(function(wasm2) {
+		expect(wasm2.getNumber()).toEqual(22);
+		return import("./wasm.wat");
+	}).then(function(wasm) {
+		expect(wasm.addNumber(1)).toEqual(23);
+	});
+});
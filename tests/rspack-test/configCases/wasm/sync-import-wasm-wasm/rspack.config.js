@@ -0,0 +1,31 @@
+/** @type {import("@rspack/core").Configuration} */
+module.exports = {
+  entry: './index',
+  target: 'node',
+  module: {
+    rules: [
+      {
+        test: /\.wat$/,
+        loader: 'wast-loader',
+        type: 'webassembly/sync',
+      },
+    ],
+  },
+  optimization: {
+    splitChunks: {
+      cacheGroups: {
+        // the imported wasm module is loaded from another chunk
+        wasm2: {
+          test: /wasm2\.wat$/,
+          chunks: 'async',
+          name: 'wasm2',
+          enforce: true,
+        },
+      },
+    },
+  },
+  experiments: {
+    asyncWebAssembly: false,
+    syncWebAssembly: true,
+  },
+};